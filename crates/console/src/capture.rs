@@ -0,0 +1,180 @@
+use crate::filter::Level;
+use std::cell::RefCell;
+use std::fmt::Write;
+use wasm_bindgen::JsValue;
+
+struct Capture {
+    active: bool,
+    logs: Vec<CapturedLog>,
+}
+
+thread_local! {
+    static CAPTURE: RefCell<Capture> = const {
+        RefCell::new(Capture {
+            active: false,
+            logs: Vec::new(),
+        })
+    };
+}
+
+/// A log captured while [`start_capture`] is active.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedLog {
+    level: Level,
+    target: String,
+    values: Vec<JsValue>,
+}
+
+impl CapturedLog {
+    /// Returns the level of the log, e.g. [`Level::Warn`] for `warn!`.
+    ///
+    /// Both `info!` and `log!` are captured as [`Level::Info`].
+    #[inline]
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Returns the module the log comes from, or the target of the `tracing` event.
+    #[inline]
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Returns the values which were passed to the console, including the object of the
+    /// `key = value` fields, if any.
+    #[inline]
+    pub fn values(&self) -> &[JsValue] {
+        &self.values
+    }
+
+    /// Returns the values rendered as text, roughly like the console, i.e. with the directives
+    /// of the first value substituted, and the other values separated with spaces. Objects are
+    /// rendered as JSON.
+    pub fn message(&self) -> String {
+        let mut message = String::new();
+        let mut values = self.values.iter();
+        if let Some(format) = values.next() {
+            match format.as_string() {
+                Some(format) => substitute(&mut message, &format, &mut values),
+                None => message.push_str(&render(format)),
+            }
+        }
+        for value in values {
+            message.push(' ');
+            message.push_str(&render(value));
+        }
+        message
+    }
+}
+
+/// Writes `format` to `message`, substituting its `%` directives with `values`.
+fn substitute<'a>(
+    message: &mut String,
+    format: &str,
+    values: &mut impl Iterator<Item = &'a JsValue>,
+) {
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            message.push(c);
+            continue;
+        }
+        match chars.clone().next() {
+            Some('%') => {
+                chars.next();
+                message.push('%');
+            }
+            // Styles are not rendered.
+            Some('c') => {
+                chars.next();
+                values.next();
+            }
+            Some(directive @ ('s' | 'd' | 'i' | 'f' | 'o' | 'O')) => {
+                chars.next();
+                match values.next() {
+                    Some(value) => message.push_str(&render(value)),
+                    None => {
+                        let _ = write!(message, "%{}", directive);
+                    }
+                }
+            }
+            _ => message.push('%'),
+        }
+    }
+}
+
+fn render(value: &JsValue) -> String {
+    if let Some(string) = value.as_string() {
+        string
+    } else if let Some(number) = value.as_f64() {
+        number.to_string()
+    } else if let Some(boolean) = value.as_bool() {
+        boolean.to_string()
+    } else if value.is_undefined() {
+        "undefined".to_owned()
+    } else {
+        js_sys::JSON::stringify(value)
+            .ok()
+            .and_then(|m| m.as_string())
+            .unwrap_or_else(|| format!("{:?}", value))
+    }
+}
+
+/// Starts capturing the logs of the logging macros and of the `tracing` layer in memory, e.g. so
+/// tests can assert on the warnings and errors they emit. The logs are still written to the
+/// console.
+///
+/// Previously captured logs are cleared. The logs which don't pass the global
+/// [`filter`](crate::filter) are not captured either.
+///
+/// # Example
+///
+/// ```no_run
+/// use gloo_console::filter::Level;
+/// use gloo_console::{captured_logs, start_capture, stop_capture, warn};
+///
+/// start_capture();
+/// warn!("cache is full", size = 100);
+/// stop_capture();
+///
+/// let logs = captured_logs();
+/// assert_eq!(logs[0].level(), Level::Warn);
+/// assert_eq!(logs[0].message(), r#"cache is full {"size":100}"#);
+/// ```
+pub fn start_capture() {
+    CAPTURE.with(|capture| {
+        let mut capture = capture.borrow_mut();
+        capture.active = true;
+        capture.logs.clear();
+    });
+}
+
+/// Stops capturing logs. The captured logs are kept until the next [`start_capture`].
+pub fn stop_capture() {
+    CAPTURE.with(|capture| capture.borrow_mut().active = false);
+}
+
+/// Returns the logs captured since [`start_capture`] was last called.
+pub fn captured_logs() -> Vec<CapturedLog> {
+    CAPTURE.with(|capture| capture.borrow().logs.clone())
+}
+
+/// Clears the captured logs, without stopping capturing.
+pub fn clear_captured_logs() {
+    CAPTURE.with(|capture| capture.borrow_mut().logs.clear());
+}
+
+/// Writes `values` to the console with `write`, and captures them if logs are captured.
+pub fn write(write: fn(Box<[JsValue]>), level: Level, target: &str, values: Box<[JsValue]>) {
+    CAPTURE.with(|capture| {
+        let mut capture = capture.borrow_mut();
+        if capture.active {
+            capture.logs.push(CapturedLog {
+                level,
+                target: target.to_owned(),
+                values: values.to_vec(),
+            });
+        }
+    });
+    write(values);
+}
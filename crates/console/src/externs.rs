@@ -46,6 +46,9 @@ extern "C" {
     #[wasm_bindgen(js_namespace = console, js_name = table)]
     pub fn table_with_data_and_columns(data: JsValue, columns: Array);
 
+    #[wasm_bindgen(js_namespace = console, js_name = timeLog)]
+    pub fn time_log(label: &str);
+
     #[wasm_bindgen(js_namespace = console, variadic)]
     pub fn trace(items: Box<[JsValue]>);
 
@@ -0,0 +1,298 @@
+//! A global filter of the logs of the logging macros, which keeps logging code in production
+//! builds but silences it.
+//!
+//! The filter is made of a default level, and levels for module path targets, written like
+//! `RUST_LOG`, e.g. `"warn,my_app::net=debug"`. It applies to the `debug!`, `error!`, `info!`,
+//! `log!`, `trace!` and `warn!` macros, whose target is the module they are called from, and to
+//! the events of the `tracing` layer.
+//!
+//! By default, everything is logged, unless a filter was saved with [`save_filter`], which is
+//! restored when the page is loaded again. The filter can also be changed from the URL of the
+//! page, see [`set_filter_from_query`].
+//!
+//! ```no_run
+//! use gloo_console::filter::{set_filter, Filter, LevelFilter};
+//!
+//! set_filter(Filter::new(LevelFilter::Warn).target("my_app::net", LevelFilter::Debug));
+//! ```
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use web_sys::{Storage, UrlSearchParams};
+
+/// The key of the local storage where the filter is saved.
+const STORAGE_KEY: &str = "gloo_console_filter";
+
+/// The level of a log, from the most to the least severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// Logged with `error!`.
+    Error = 1,
+    /// Logged with `warn!`.
+    Warn,
+    /// Logged with `info!` or `log!`.
+    Info,
+    /// Logged with `debug!`.
+    Debug,
+    /// Logged with `trace!`.
+    Trace,
+}
+
+/// The most verbose [`Level`] which is logged, or [`Off`](LevelFilter::Off).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LevelFilter {
+    /// Nothing is logged.
+    Off,
+    /// Only errors are logged.
+    Error,
+    /// Warnings and errors are logged.
+    Warn,
+    /// Infos, warnings and errors are logged.
+    Info,
+    /// Everything but traces is logged.
+    Debug,
+    /// Everything is logged.
+    Trace,
+}
+
+impl LevelFilter {
+    /// Returns whether logs of `level` pass this filter.
+    #[inline]
+    pub fn enables(self, level: Level) -> bool {
+        level as usize <= self as usize
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            LevelFilter::Off => "off",
+            LevelFilter::Error => "error",
+            LevelFilter::Warn => "warn",
+            LevelFilter::Info => "info",
+            LevelFilter::Debug => "debug",
+            LevelFilter::Trace => "trace",
+        }
+    }
+}
+
+impl FromStr for LevelFilter {
+    type Err = ParseFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(LevelFilter::Off),
+            "error" => Ok(LevelFilter::Error),
+            "warn" => Ok(LevelFilter::Warn),
+            "info" => Ok(LevelFilter::Info),
+            "debug" => Ok(LevelFilter::Debug),
+            "trace" => Ok(LevelFilter::Trace),
+            _ => Err(ParseFilterError {
+                directive: s.to_owned(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for LevelFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A filter of logs, by their level and target.
+///
+/// The level of a target also applies to its submodules, and the level of the longest matching
+/// target is used, e.g. with `"my_app=info,my_app::net=off"`, `my_app::net::http` logs nothing,
+/// while `my_app::ui` logs infos.
+///
+/// Filters are parsed from, and displayed as, comma separated directives, each being either the
+/// default level, e.g. `"warn"`, or a target and its level, e.g. `"my_app::net=debug"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    default: LevelFilter,
+    targets: Vec<(String, LevelFilter)>,
+}
+
+impl Filter {
+    /// Creates a filter which applies `default` to all targets.
+    pub fn new(default: LevelFilter) -> Self {
+        Self {
+            default,
+            targets: Vec::new(),
+        }
+    }
+
+    /// Sets the level of `target` and its submodules.
+    pub fn target(mut self, target: impl Into<String>, level: LevelFilter) -> Self {
+        let target = target.into();
+        self.targets.retain(|(m, _)| *m != target);
+        self.targets.push((target, level));
+        self
+    }
+
+    /// Returns the level of `target`.
+    pub fn level(&self, target: &str) -> LevelFilter {
+        self.targets
+            .iter()
+            .filter(|(m, _)| is_within(target, m))
+            .max_by_key(|(m, _)| m.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    /// Returns whether logs of `level` from `target` pass the filter.
+    #[inline]
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        self.level(target).enables(level)
+    }
+}
+
+/// Returns whether `target` is the module `module` or one of its submodules.
+fn is_within(target: &str, module: &str) -> bool {
+    match target.strip_prefix(module) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false,
+    }
+}
+
+impl Default for Filter {
+    /// Returns a filter which logs everything.
+    fn default() -> Self {
+        Self::new(LevelFilter::Trace)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = ParseFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::default();
+        for directive in s.split(',').map(str::trim).filter(|m| !m.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    let target = target.trim();
+                    if target.is_empty() {
+                        return Err(ParseFilterError {
+                            directive: directive.to_owned(),
+                        });
+                    }
+                    filter = filter.target(target, directive_level(directive, level)?);
+                }
+                None => filter.default = directive_level(directive, directive)?,
+            }
+        }
+        Ok(filter)
+    }
+}
+
+fn directive_level(directive: &str, level: &str) -> Result<LevelFilter, ParseFilterError> {
+    level.parse().map_err(|_| ParseFilterError {
+        directive: directive.to_owned(),
+    })
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.default)?;
+        for (target, level) in &self.targets {
+            write!(f, ",{}={}", target, level)?;
+        }
+        Ok(())
+    }
+}
+
+/// An error returned when parsing a [`Filter`] or a [`LevelFilter`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFilterError {
+    directive: String,
+}
+
+impl fmt::Display for ParseFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid log filter directive `{}`", self.directive)
+    }
+}
+
+impl Error for ParseFilterError {}
+
+thread_local! {
+    /// The global filter, or `None` until it is first used, when the saved filter is loaded.
+    static FILTER: RefCell<Option<Filter>> = const { RefCell::new(None) };
+}
+
+/// Returns the global filter.
+pub fn current() -> Filter {
+    with_filter(Filter::clone)
+}
+
+/// Returns whether logs of `level` from `target` pass the global filter.
+pub fn enabled(level: Level, target: &str) -> bool {
+    with_filter(|filter| filter.enabled(level, target))
+}
+
+fn with_filter<T>(f: impl FnOnce(&Filter) -> T) -> T {
+    FILTER.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let filter = cell.get_or_insert_with(|| saved_filter().unwrap_or_default());
+        f(filter)
+    })
+}
+
+/// Sets the global filter, until the page is unloaded.
+pub fn set_filter(filter: Filter) {
+    FILTER.with(|cell| *cell.borrow_mut() = Some(filter));
+}
+
+/// Sets the global filter, and saves it to the local storage, so it is also used when the page
+/// is loaded again, e.g. to silence logs for all visits of a user.
+///
+/// The filter is only set if the local storage is not available.
+pub fn save_filter(filter: Filter) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(STORAGE_KEY, &filter.to_string());
+    }
+    set_filter(filter);
+}
+
+/// Removes the filter saved to the local storage, and logs everything again.
+pub fn clear_saved_filter() {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(STORAGE_KEY);
+    }
+    set_filter(Filter::default());
+}
+
+/// Sets the global filter from the `name` query parameter of the URL of the page, if any, e.g.
+/// from `?log=debug` with `"log"`, so logs can be enabled in production builds without a new
+/// build.
+///
+/// The filter is not saved. Invalid filters are reported with `console.warn()` and ignored.
+pub fn set_filter_from_query(name: &str) {
+    let search = match web_sys::window().and_then(|m| m.location().search().ok()) {
+        Some(search) => search,
+        None => return,
+    };
+    let value = match UrlSearchParams::new_with_str(&search)
+        .ok()
+        .and_then(|m| m.get(name))
+    {
+        Some(value) => value,
+        None => return,
+    };
+
+    match value.parse() {
+        Ok(filter) => set_filter(filter),
+        Err(error) => crate::warn!(format!(
+            "ignoring the `{}` query parameter: {}",
+            name, error
+        )),
+    }
+}
+
+fn saved_filter() -> Option<Filter> {
+    local_storage()?.get_item(STORAGE_KEY).ok()??.parse().ok()
+}
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
@@ -0,0 +1,230 @@
+use crate::{capture, externs, filter};
+use js_sys::{Object, Reflect};
+use std::fmt::{self, Write};
+use tracing_core::field::{Field, Visit};
+use tracing_core::span::{Attributes, Id, Record};
+use tracing_core::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use wasm_bindgen::JsValue;
+
+/// A [`tracing`](https://docs.rs/tracing) layer which writes to the browser's console.
+///
+/// Events are logged with the console method of their level, i.e. `console.error()`,
+/// `console.warn()`, `console.info()`, or `console.debug()` for both `DEBUG` and `TRACE`, so they
+/// can be filtered by the devtools. Their message is prefixed with their level and target, and
+/// their other fields are logged as an object, which can be inspected. Events which don't pass
+/// the global [`filter`](crate::filter) are not logged.
+///
+/// Entering a span opens a `console.group()`, labelled with the name and fields of the span,
+/// which is closed when the span is exited, so the events of a span are nested under it.
+///
+/// # Example
+///
+/// ```no_run
+/// use gloo_console::ConsoleLayer;
+///
+/// ConsoleLayer::new().init();
+///
+/// let span = tracing::info_span!("fetch", url = "/api/items");
+/// let _guard = span.enter();
+/// tracing::info!(status = 200, "request done");
+/// ```
+///
+/// The layer can also be combined with others, e.g. to filter events:
+///
+/// ```no_run
+/// use gloo_console::ConsoleLayer;
+/// use tracing_subscriber::prelude::*;
+///
+/// tracing_subscriber::registry()
+///     .with(ConsoleLayer::new().collapsed(true))
+///     .init();
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleLayer {
+    collapsed: bool,
+}
+
+impl ConsoleLayer {
+    /// Creates a layer which opens expanded groups for spans.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the groups of spans are collapsed, with `console.groupCollapsed()`.
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    /// Sets a subscriber which only consists of this layer as the global default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a global default subscriber was already set.
+    pub fn init(self) {
+        tracing_subscriber::registry().with(self).init();
+    }
+}
+
+impl<S> Layer<S> for ConsoleLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(id) {
+            Some(span) => span,
+            None => return,
+        };
+
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(fields);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let span = match ctx.span(id) {
+            Some(span) => span,
+            None => return,
+        };
+
+        let mut extensions = span.extensions_mut();
+        if let Some(fields) = extensions.get_mut::<Fields>() {
+            values.record(fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = match *metadata.level() {
+            Level::ERROR => filter::Level::Error,
+            Level::WARN => filter::Level::Warn,
+            Level::INFO => filter::Level::Info,
+            Level::DEBUG => filter::Level::Debug,
+            Level::TRACE => filter::Level::Trace,
+        };
+        if !filter::enabled(level, metadata.target()) {
+            return;
+        }
+
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+
+        let mut text = format!("{} {}", metadata.level(), metadata.target());
+        if let Some(message) = &fields.message {
+            let _ = write!(text, ": {}", message);
+        }
+        let items = fields.into_items(text);
+
+        let write = match level {
+            filter::Level::Error => externs::error,
+            filter::Level::Warn => externs::warn,
+            filter::Level::Info => externs::info,
+            // `console.trace()` also prints a stack trace, which is too verbose for every event.
+            filter::Level::Debug | filter::Level::Trace => externs::debug,
+        };
+        capture::write(write, level, metadata.target(), items);
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(id) {
+            Some(span) => span,
+            None => return,
+        };
+
+        let items = match span.extensions().get::<Fields>() {
+            Some(fields) => fields.clone().into_items(span.name().to_owned()),
+            None => Box::from([JsValue::from(span.name())]),
+        };
+
+        if self.collapsed {
+            externs::group_collapsed(items);
+        } else {
+            externs::group(items);
+        }
+    }
+
+    fn on_exit(&self, _id: &Id, _ctx: Context<'_, S>) {
+        externs::group_end();
+    }
+}
+
+/// The fields of a span or an event.
+///
+/// `JsValue`s can't be stored in the extensions of spans, which must be `Send` and `Sync`, so
+/// they are only created when logging.
+#[derive(Debug, Clone, Default)]
+struct Fields {
+    message: Option<String>,
+    values: Vec<(&'static str, Value)>,
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl Fields {
+    fn push(&mut self, field: &Field, value: Value) {
+        self.values.push((field.name(), value));
+    }
+
+    /// Returns the items passed to the console, i.e. `text` followed by an object of the fields
+    /// other than the message, if any.
+    fn into_items(self, text: String) -> Box<[JsValue]> {
+        if self.values.is_empty() {
+            return Box::from([JsValue::from(text)]);
+        }
+
+        let object = Object::new();
+        for (name, value) in self.values {
+            let value = match value {
+                Value::Bool(value) => JsValue::from_bool(value),
+                Value::Number(value) => JsValue::from_f64(value),
+                Value::String(value) => JsValue::from(value),
+            };
+            // Setting a property on a plain object can't fail.
+            let _ = Reflect::set(&object, &JsValue::from_str(name), &value);
+        }
+        Box::from([JsValue::from(text), object.into()])
+    }
+}
+
+impl Visit for Fields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.push(field, Value::Number(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.push(field, Value::Number(value as f64));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.push(field, Value::Number(value as f64));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.push(field, Value::Bool(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_owned());
+        } else {
+            self.push(field, Value::String(value.to_owned()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{:?}", value));
+        } else {
+            self.push(field, Value::String(format!("{:?}", value)));
+        }
+    }
+}
@@ -14,27 +14,121 @@
 //! let object = JsValue::from("any JsValue can be logged");
 //! log!("text", object)
 //! ```
+//!
+//! The logging macros also take `key = value` fields, after or between the values. Their values
+//! are serialized with [`serde`](https://serde.rs) into a single object, which is logged after
+//! the other values and can be inspected in the console:
+//!
+//! ```no_run, rust
+//! use gloo_console::info;
+//! # let url = String::new();
+//!
+//! info!("request done", status = 200, url = url);
+//! ```
+//!
+//! Messages can be styled with [`Styled`], passed as the first value of the logging macros.
+//!
+//! The logs can be silenced at runtime with a [`filter`], and captured in memory for tests with
+//! [`start_capture`].
 
 #![deny(missing_docs, missing_debug_implementations)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod capture;
 mod console_dbg;
 mod counter;
 #[doc(hidden)]
 pub mod externs;
+pub mod filter;
+#[cfg(feature = "tracing")]
+mod layer;
 mod macros;
+mod panic_hook;
+pub mod perf;
+mod styled;
 mod timer;
 
+pub use capture::{captured_logs, clear_captured_logs, start_capture, stop_capture, CapturedLog};
 pub use counter::Counter;
+#[cfg(feature = "tracing")]
+pub use layer::ConsoleLayer;
 pub use macros::*;
-pub use timer::Timer;
+pub use panic_hook::{set_panic_hook, set_panic_hook_with, PanicReport};
+pub use styled::Styled;
+pub use timer::{time_async, time_scoped, Timer};
 
 #[doc(hidden)]
 pub mod __macro {
+    pub use crate::capture::write;
     use gloo_utils::format::JsValueSerdeExt;
-    pub use js_sys::Array;
+    use js_sys::Reflect;
+    pub use js_sys::{Array, Object};
     pub use wasm_bindgen::JsValue;
     use wasm_bindgen::UnwrapThrowExt;
 
+    /// A value passed to the logging macros, which is logged as one or more items.
+    pub trait ConsoleArg {
+        fn push_to(self, items: &mut Vec<JsValue>);
+    }
+
+    impl<T> ConsoleArg for T
+    where
+        T: Into<JsValue>,
+    {
+        #[inline]
+        fn push_to(self, items: &mut Vec<JsValue>) {
+            items.push(self.into());
+        }
+    }
+
+    pub fn set_field<T>(fields: &Object, key: &str, value: &T)
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        let value = <JsValue as JsValueSerdeExt>::from_serde(value).unwrap_throw();
+        Reflect::set(fields, &JsValue::from_str(key), &value).unwrap_throw();
+    }
+
+    /// Data passed to `table!`, either a JavaScript value or a reference to data that is
+    /// serialized with serde.
+    pub trait TableData {
+        fn into_table_data(self) -> JsValue;
+    }
+
+    impl<T> TableData for &T
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        fn into_table_data(self) -> JsValue {
+            <JsValue as JsValueSerdeExt>::from_serde(self).unwrap_throw()
+        }
+    }
+
+    impl TableData for JsValue {
+        #[inline]
+        fn into_table_data(self) -> JsValue {
+            self
+        }
+    }
+
+    impl TableData for Array {
+        #[inline]
+        fn into_table_data(self) -> JsValue {
+            self.into()
+        }
+    }
+
+    impl TableData for Object {
+        #[inline]
+        fn into_table_data(self) -> JsValue {
+            self.into()
+        }
+    }
+
+    pub fn table_with_data(data: impl TableData) {
+        crate::externs::table_with_data(data.into_table_data());
+    }
+
     pub fn table_with_data_and_columns<'a>(
         data: impl serde::Serialize,
         columns: impl IntoIterator<Item = &'a str>,
@@ -9,16 +9,23 @@ macro_rules! clear {
 /// Calls `console.assert()`
 #[macro_export]
 macro_rules! assert {
-    ($assertion:expr, $($arg:expr),+) => {
-       $crate::externs::assert($assertion, ::std::boxed::Box::from([$($crate::__macro::JsValue::from($arg),)+]));
+    ($assertion:expr, $($arg:tt)+) => {
+       $crate::externs::assert($assertion, $crate::__console_items!($($arg)+));
     }
 }
 
 /// Calls `console.debug()`
 #[macro_export]
 macro_rules! debug {
-    ($($arg:expr),+) => {
-       $crate::externs::debug(::std::boxed::Box::from([$($crate::__macro::JsValue::from($arg),)+]));
+    ($($arg:tt)+) => {
+        if $crate::filter::enabled($crate::filter::Level::Debug, ::std::module_path!()) {
+            $crate::__macro::write(
+                $crate::externs::debug,
+                $crate::filter::Level::Debug,
+                ::std::module_path!(),
+                $crate::__console_items!($($arg)+),
+            );
+        }
     }
 }
 
@@ -53,8 +60,15 @@ macro_rules! dirxml {
 /// Calls `console.error()`
 #[macro_export]
 macro_rules! error {
-    ($($arg:expr),+) => {
-       $crate::externs::error(::std::boxed::Box::from([$($crate::__macro::JsValue::from($arg),)+]));
+    ($($arg:tt)+) => {
+        if $crate::filter::enabled($crate::filter::Level::Error, ::std::module_path!()) {
+            $crate::__macro::write(
+                $crate::externs::error,
+                $crate::filter::Level::Error,
+                ::std::module_path!(),
+                $crate::__console_items!($($arg)+),
+            );
+        }
     }
 }
 
@@ -63,11 +77,15 @@ macro_rules! error {
 /// In order to call `console.groupCollapsed`, prefix the arguments with `collapsed`.
 #[macro_export]
 macro_rules! group {
-    ($($arg:expr),+) => {
-       $crate::externs::group(::std::boxed::Box::from([$($crate::__macro::JsValue::from($arg),)+]));
+    // Matched first so a variable named `collapsed` is still a value.
+    ($first:ident, $($arg:tt)+) => {
+       $crate::externs::group($crate::__console_items!($first, $($arg)+));
     };
-    (collapsed $($arg:expr),+) => {
-       $crate::externs::group_collapsed(::std::boxed::Box::from([$($crate::__macro::JsValue::from($arg),)+]));
+    (collapsed $($arg:tt)+) => {
+       $crate::externs::group_collapsed($crate::__console_items!($($arg)+));
+    };
+    ($($arg:tt)+) => {
+       $crate::externs::group($crate::__console_items!($($arg)+));
     };
 }
 
@@ -82,8 +100,15 @@ macro_rules! group_end {
 /// Calls `console.info()`
 #[macro_export]
 macro_rules! info {
-    ($($arg:expr),+) => {
-       $crate::externs::info(::std::boxed::Box::from([$($crate::__macro::JsValue::from($arg),)+]));
+    ($($arg:tt)+) => {
+        if $crate::filter::enabled($crate::filter::Level::Info, ::std::module_path!()) {
+            $crate::__macro::write(
+                $crate::externs::info,
+                $crate::filter::Level::Info,
+                ::std::module_path!(),
+                $crate::__console_items!($($arg)+),
+            );
+        }
     }
 }
 
@@ -91,13 +116,34 @@ macro_rules! info {
 ///
 /// Since in most cases, this takes in an object, instead of, say a string literal/variable,
 /// we use [`serde`](https://serde.rs) to serialize the passed data object into
-/// [`JsValue`][wasm_bindgen::JsValue].
+/// [`JsValue`][wasm_bindgen::JsValue]. A slice of structs is rendered with a row per item and
+/// a column per field. A [`JsValue`][wasm_bindgen::JsValue], [`Array`][js_sys::Array] or
+/// [`Object`][js_sys::Object] is passed to `console.table()` as it is.
 ///
 /// An `IntoIterator<Item = &str>` can be passed to specify the columns.
+///
+/// ## Example
+/// ```no_run
+/// # use gloo_console::table;
+/// #[derive(serde::Serialize)]
+/// struct User {
+///     id: u32,
+///     name: String,
+///     email: String,
+/// }
+///
+/// let users: Vec<User> = Vec::new();
+///
+/// table!(&users);
+/// table!(&users, ["name", "email"]);
+///
+/// let array = js_sys::Array::of2(&"first".into(), &"second".into());
+/// table!(array);
+/// ```
 #[macro_export]
 macro_rules! table {
     ($data:expr) => {
-        $crate::externs::table_with_data($crate::__macro::JsValue::from($data));
+        $crate::__macro::table_with_data($data);
     };
     ($data:expr, $columns:expr) => {
         $crate::__macro::table_with_data_and_columns($data, $columns);
@@ -107,23 +153,75 @@ macro_rules! table {
 /// Calls `console.log()`
 #[macro_export]
 macro_rules! log {
-    ($($arg:expr),+) => {
-       $crate::externs::log(::std::boxed::Box::from([$($crate::__macro::JsValue::from($arg),)+]));
+    ($($arg:tt)+) => {
+        if $crate::filter::enabled($crate::filter::Level::Info, ::std::module_path!()) {
+            $crate::__macro::write(
+                $crate::externs::log,
+                $crate::filter::Level::Info,
+                ::std::module_path!(),
+                $crate::__console_items!($($arg)+),
+            );
+        }
     }
 }
 
 /// Calls `console.trace()`
 #[macro_export]
 macro_rules! trace {
-    ($($arg:expr),+) => {
-       $crate::externs::trace(::std::boxed::Box::from([$($crate::__macro::JsValue::from($arg),)+]));
+    ($($arg:tt)+) => {
+        if $crate::filter::enabled($crate::filter::Level::Trace, ::std::module_path!()) {
+            $crate::__macro::write(
+                $crate::externs::trace,
+                $crate::filter::Level::Trace,
+                ::std::module_path!(),
+                $crate::__console_items!($($arg)+),
+            );
+        }
     }
 }
 
 /// Calls `console.warn()`
 #[macro_export]
 macro_rules! warn {
-    ($($arg:expr),+) => {
-       $crate::externs::warn(::std::boxed::Box::from([$($crate::__macro::JsValue::from($arg),)+]));
+    ($($arg:tt)+) => {
+        if $crate::filter::enabled($crate::filter::Level::Warn, ::std::module_path!()) {
+            $crate::__macro::write(
+                $crate::externs::warn,
+                $crate::filter::Level::Warn,
+                ::std::module_path!(),
+                $crate::__console_items!($($arg)+),
+            );
+        }
     }
 }
+
+/// This is an implementation detail and *should not* be called directly!
+///
+/// Splits the arguments of the logging macros into values and `key = value` fields, and
+/// returns the items passed to the console.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __console_items {
+    (@split [$($arg:expr,)*] [$($field:tt)*] $key:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__console_items!(@split [$($arg,)*] [$($field)* ($key, $value)] $($($rest)*)?)
+    };
+    (@split [$($arg:expr,)*] [$($field:tt)*] $next:expr $(, $($rest:tt)*)?) => {
+        $crate::__console_items!(@split [$($arg,)* $next,] [$($field)*] $($($rest)*)?)
+    };
+    (@split [$($arg:expr,)*] []) => {{
+        let mut items = ::std::vec::Vec::new();
+        $($crate::__macro::ConsoleArg::push_to($arg, &mut items);)*
+        items.into_boxed_slice()
+    }};
+    (@split [$($arg:expr,)*] [$(($key:ident, $value:expr))+]) => {{
+        let mut items = ::std::vec::Vec::new();
+        $($crate::__macro::ConsoleArg::push_to($arg, &mut items);)*
+        let fields = $crate::__macro::Object::new();
+        $($crate::__macro::set_field(&fields, ::std::stringify!($key), &$value);)+
+        items.push($crate::__macro::JsValue::from(fields));
+        items.into_boxed_slice()
+    }};
+    ($($arg:tt)+) => {
+        $crate::__console_items!(@split [] [] $($arg)+)
+    };
+}
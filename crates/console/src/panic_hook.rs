@@ -0,0 +1,88 @@
+use crate::externs;
+use js_sys::{Error, Reflect};
+use serde::Serialize;
+use std::panic;
+use wasm_bindgen::JsValue;
+
+/// A panic, as reported to the callback of [`set_panic_hook_with`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PanicReport {
+    /// The message of the panic, or `"Box<dyn Any>"` if its payload is not a string.
+    pub message: String,
+    /// The file the panic comes from, if known.
+    pub file: Option<String>,
+    /// The line the panic comes from, if known.
+    pub line: Option<u32>,
+    /// The column the panic comes from, if known.
+    pub column: Option<u32>,
+    /// The JavaScript stack trace of the panic, if supported by the browser.
+    pub stack: Option<String>,
+}
+
+/// Sets a panic hook which logs panics with `console.error()`, followed by a collapsed group
+/// with the JavaScript stack trace of the panic.
+///
+/// Without a panic hook, panics only show up as an `unreachable` error in the console, without
+/// their message.
+///
+/// # Example
+///
+/// ```no_run
+/// gloo_console::set_panic_hook();
+/// ```
+pub fn set_panic_hook() {
+    set_panic_hook_with(|_| {});
+}
+
+/// Sets a panic hook which logs panics like [`set_panic_hook`], then passes them to `callback`,
+/// e.g. to report them to an error tracking service.
+///
+/// # Example
+///
+/// ```no_run
+/// gloo_console::set_panic_hook_with(|report| {
+///     // Send the report to the error tracking service...
+/// });
+/// ```
+pub fn set_panic_hook_with<F>(callback: F)
+where
+    F: Fn(&PanicReport) + Send + Sync + 'static,
+{
+    panic::set_hook(Box::new(move |info| {
+        let payload = info.payload();
+        let message = match (
+            payload.downcast_ref::<&str>(),
+            payload.downcast_ref::<String>(),
+        ) {
+            (Some(message), _) => (*message).to_owned(),
+            (_, Some(message)) => message.clone(),
+            (None, None) => "Box<dyn Any>".to_owned(),
+        };
+        let stack = Reflect::get(&Error::new(""), &JsValue::from_str("stack"))
+            .ok()
+            .and_then(|m| m.as_string());
+        let report = PanicReport {
+            message,
+            file: info.location().map(|m| m.file().to_owned()),
+            line: info.location().map(|m| m.line()),
+            column: info.location().map(|m| m.column()),
+            stack,
+        };
+
+        let text = match (&report.file, report.line, report.column) {
+            (Some(file), Some(line), Some(column)) => format!(
+                "panicked at {}:{}:{}:\n{}",
+                file, line, column, report.message
+            ),
+            _ => format!("panicked:\n{}", report.message),
+        };
+        externs::error(Box::from([JsValue::from(text)]));
+        if let Some(stack) = &report.stack {
+            externs::group_collapsed(Box::from([JsValue::from_str("stack trace")]));
+            externs::log(Box::from([JsValue::from_str(stack)]));
+            externs::group_end();
+        }
+
+        callback(&report);
+    }));
+}
@@ -0,0 +1,194 @@
+//! The `performance.mark` and `performance.measure` functions record named
+//! timestamps and durations, which show up in the "Timings" track of the
+//! browser's Performance panel, alongside the work of the browser itself.
+//!
+//! [See MDN for more info](https://developer.mozilla.org/en-US/docs/Web/API/Performance_API/User_timing).
+//!
+//! These functions are available both in windows and in workers.
+//!
+//! ## Scoped Measurement
+//!
+//! Wrap code to be measured in a closure with [`Measure::scope`], or hold a
+//! [`Measure`] until the measurement ends.
+//!
+//! ```no_run
+//! use gloo_console::perf::Measure;
+//!
+//! let value = Measure::scope("render", || {
+//!     // Place code to be measured here
+//!     // Optionally return a value.
+//! });
+//! ```
+//!
+//! ## Marks
+//!
+//! ```no_run
+//! use gloo_console::{perf, table};
+//!
+//! perf::mark("load");
+//! // Load...
+//! perf::mark("loaded");
+//! perf::measure_between("loading", "load", "loaded");
+//!
+//! table!(&perf::measures());
+//! ```
+
+use serde::Serialize;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use web_sys::{Performance, PerformanceEntry};
+
+fn performance() -> Performance {
+    js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("performance"))
+        .ok()
+        .and_then(|m| m.dyn_into().ok())
+        .expect_throw("`performance` is not available")
+}
+
+/// Returns the number of milliseconds elapsed since the page or worker was created, with
+/// `performance.now()`.
+pub fn now() -> f64 {
+    performance().now()
+}
+
+/// Records a mark named `name` at the current time, with `performance.mark()`.
+pub fn mark(name: &str) {
+    performance().mark(name).unwrap_throw();
+}
+
+/// Records a measurement named `name` from the last mark named `start_mark` to the current
+/// time, with `performance.measure()`.
+///
+/// # Panics
+///
+/// Panics if no mark is named `start_mark`.
+pub fn measure(name: &str, start_mark: &str) {
+    performance()
+        .measure_with_start_mark(name, start_mark)
+        .unwrap_throw();
+}
+
+/// Records a measurement named `name` from the last mark named `start_mark` to the last mark
+/// named `end_mark`, with `performance.measure()`.
+///
+/// # Panics
+///
+/// Panics if no mark is named `start_mark` or `end_mark`.
+pub fn measure_between(name: &str, start_mark: &str, end_mark: &str) {
+    performance()
+        .measure_with_start_mark_and_end_mark(name, start_mark, end_mark)
+        .unwrap_throw();
+}
+
+/// A mark recorded with [`mark`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MarkEntry {
+    /// The name of the mark.
+    pub name: String,
+    /// The time of the mark, in milliseconds since the page or worker was created.
+    pub start_time: f64,
+}
+
+/// A measurement recorded with [`measure`], [`measure_between`] or [`Measure`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MeasureEntry {
+    /// The name of the measurement.
+    pub name: String,
+    /// The start of the measurement, in milliseconds since the page or worker was created.
+    pub start_time: f64,
+    /// The duration of the measurement, in milliseconds.
+    pub duration: f64,
+}
+
+fn entries(entries: js_sys::Array) -> impl Iterator<Item = PerformanceEntry> {
+    entries.into_iter().map(JsCast::unchecked_into)
+}
+
+/// Returns the recorded marks, in chronological order.
+pub fn marks() -> Vec<MarkEntry> {
+    entries(performance().get_entries_by_type("mark"))
+        .map(|m| MarkEntry {
+            name: m.name(),
+            start_time: m.start_time(),
+        })
+        .collect()
+}
+
+/// Returns the recorded measurements, in chronological order of their start.
+pub fn measures() -> Vec<MeasureEntry> {
+    entries(performance().get_entries_by_type("measure"))
+        .map(|m| MeasureEntry {
+            name: m.name(),
+            start_time: m.start_time(),
+            duration: m.duration(),
+        })
+        .collect()
+}
+
+/// Returns the recorded measurements named `name`, in chronological order of their start.
+pub fn measures_named(name: &str) -> Vec<MeasureEntry> {
+    measures().into_iter().filter(|m| m.name == name).collect()
+}
+
+/// Removes all the recorded marks.
+pub fn clear_marks() {
+    performance().clear_marks();
+}
+
+/// Removes all the recorded measurements.
+pub fn clear_measures() {
+    performance().clear_measures();
+}
+
+/// A scoped measurement, which is recorded when it is dropped.
+///
+/// A mark named after the measurement, followed by `" start"`, is recorded when the
+/// measurement starts.
+#[derive(Debug)]
+pub struct Measure<'a> {
+    name: &'a str,
+    start_mark: String,
+}
+
+impl<'a> Measure<'a> {
+    /// Starts a measurement named `name`. The measurement ends when the constructed `Measure`
+    /// object is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_console::perf::Measure;
+    ///
+    /// let _measure = Measure::new("render");
+    /// ```
+    pub fn new(name: &'a str) -> Measure<'a> {
+        let start_mark = format!("{} start", name);
+        mark(&start_mark);
+        Measure { name, start_mark }
+    }
+
+    /// Measures the execution of `f`, under `name`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_console::perf::Measure;
+    ///
+    /// let value = Measure::scope("render", || {
+    ///     // Code to measure here
+    /// });
+    /// ```
+    pub fn scope<F, T>(name: &str, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        let _measure = Measure::new(name);
+        f()
+    }
+}
+
+impl<'a> Drop for Measure<'a> {
+    fn drop(&mut self) {
+        // The start mark can only be missing if it was cleared in the meantime.
+        let _ = performance().measure_with_start_mark(self.name, &self.start_mark);
+    }
+}
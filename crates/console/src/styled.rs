@@ -0,0 +1,80 @@
+use crate::__macro::ConsoleArg;
+use wasm_bindgen::JsValue;
+
+/// A builder of a message made of styled parts, logged with the `%c` directive of the console.
+///
+/// The message is passed to the logging macros like other values, but must be the first one,
+/// as only the first value logged can contain directives. Each part is styled with CSS, e.g. to
+/// tell the logs of the subsystems of an app apart with colored badges.
+///
+/// # Example
+///
+/// ```no_run
+/// use gloo_console::{info, Styled};
+///
+/// let prefix = Styled::new().badge("net", "white", "#0a7");
+///
+/// info!(prefix.clone().text(" request ").bold("done"), status = 200);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Styled {
+    parts: Vec<(String, String)>,
+}
+
+impl Styled {
+    /// Creates an empty message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `text`, styled with the CSS declarations of `style`, e.g. `"font-style: italic"`.
+    pub fn styled(mut self, text: &str, style: &str) -> Self {
+        self.parts.push((text.to_owned(), style.to_owned()));
+        self
+    }
+
+    /// Adds unstyled `text`.
+    pub fn text(self, text: &str) -> Self {
+        self.styled(text, "")
+    }
+
+    /// Adds bold `text`.
+    pub fn bold(self, text: &str) -> Self {
+        self.styled(text, "font-weight: bold")
+    }
+
+    /// Adds `text` in `color`, which is any CSS color, e.g. `"#c00"` or `"orange"`.
+    pub fn color(self, text: &str, color: &str) -> Self {
+        self.styled(text, &format!("color: {}", color))
+    }
+
+    /// Adds `text` as a badge, i.e. in `color` on a rounded `background`.
+    pub fn badge(self, text: &str, color: &str, background: &str) -> Self {
+        self.styled(
+            text,
+            &format!(
+                "color: {}; background: {}; border-radius: 3px; padding: 1px 4px",
+                color, background
+            ),
+        )
+    }
+}
+
+impl ConsoleArg for Styled {
+    fn push_to(self, items: &mut Vec<JsValue>) {
+        // The texts are substituted with `%s`, so that they are never parsed as directives.
+        let format = "%c%s".repeat(self.parts.len());
+        items.push(format.into());
+        for (text, style) in self.parts {
+            items.push(style.into());
+            items.push(text.into());
+        }
+    }
+}
+
+impl ConsoleArg for &Styled {
+    #[inline]
+    fn push_to(self, items: &mut Vec<JsValue>) {
+        self.clone().push_to(items);
+    }
+}
@@ -42,7 +42,21 @@
 //!     drop(timer);
 //! });
 //! ```
+//!
+//! ## Asynchronous Measurement
+//!
+//! Awaited operations can be measured with [`time_async`].
+//!
+//! ```no_run
+//! use gloo_console::time_async;
+//! # async fn fetch_items() {}
+//! # async fn example() {
+//!
+//! let items = time_async("fetch items", fetch_items()).await;
+//! # }
+//! ```
 
+use std::future::Future;
 use web_sys::console;
 
 /// A console time measurement.
@@ -88,6 +102,66 @@ impl<'a> Timer<'a> {
         let _timer = Timer::new(label);
         f()
     }
+
+    /// Logs the time elapsed since the measurement started, with `console.timeLog`, without
+    /// ending it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_console::Timer;
+    ///
+    /// let timer = Timer::new("foo");
+    /// // Load...
+    /// timer.log();
+    /// // Render...
+    /// ```
+    pub fn log(&self) {
+        crate::externs::time_log(self.label);
+    }
+}
+
+/// Starts a console time measurement, which ends when the returned [`Timer`] is dropped, e.g. at
+/// the end of the current block.
+///
+/// # Example
+///
+/// ```no_run
+/// use gloo_console::time_scoped;
+///
+/// {
+///     let _timer = time_scoped("foo");
+///     // Code to measure here
+/// }
+/// ```
+#[must_use = "the measurement ends when the timer is dropped"]
+pub fn time_scoped(label: &str) -> Timer<'_> {
+    Timer::new(label)
+}
+
+/// Measures the time until `future` completes, including the time it spends waiting.
+///
+/// The measurement starts when the returned future is first polled, and ends once `future`
+/// completes, or when the returned future is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use gloo_console::time_async;
+/// # async fn example() {
+///
+/// let value = time_async("foo", async {
+///     // Code to measure here
+/// })
+/// .await;
+/// # }
+/// ```
+pub async fn time_async<F>(label: &str, future: F) -> F::Output
+where
+    F: Future,
+{
+    let _timer = Timer::new(label);
+    future.await
 }
 
 impl<'a> Drop for Timer<'a> {
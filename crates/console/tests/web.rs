@@ -13,3 +13,175 @@ fn scoped_timer_returns_value() {
 
     assert!(value);
 }
+
+#[wasm_bindgen_test]
+fn scoped_timer_logs() {
+    let timer = gloo_console::time_scoped("foo");
+    timer.log();
+}
+
+#[wasm_bindgen_test]
+async fn async_timer_returns_value() {
+    let value = gloo_console::time_async("foo", async { true }).await;
+
+    assert!(value);
+}
+
+#[wasm_bindgen_test]
+fn key_value_fields() {
+    let url = String::from("/api/items");
+    let collapsed = "not a keyword";
+
+    gloo_console::info!("request done", status = 200, url = url);
+    gloo_console::log!(status = 200, "interleaved", tags = ["a", "b"], 1 + 1);
+    gloo_console::warn!("no fields", url.len() == 10);
+    gloo_console::group!(collapsed, "group");
+    gloo_console::group_end!();
+    gloo_console::group!(collapsed "collapsed group", id = 1);
+    gloo_console::group_end!();
+    gloo_console::assert!(true, "assertion", value = Some(1));
+}
+
+#[wasm_bindgen_test]
+fn table_of_structs() {
+    #[derive(serde::Serialize)]
+    struct Row {
+        id: u32,
+        name: &'static str,
+        tags: Vec<&'static str>,
+    }
+
+    let rows = vec![
+        Row {
+            id: 1,
+            name: "first",
+            tags: vec!["a"],
+        },
+        Row {
+            id: 2,
+            name: "second",
+            tags: Vec::new(),
+        },
+    ];
+
+    gloo_console::table!(&rows);
+    gloo_console::table!(&rows, ["id", "name"]);
+
+    let array = js_sys::Array::of2(&"first".into(), &"second".into());
+    gloo_console::table!(array);
+    gloo_console::table!(wasm_bindgen::JsValue::from_str("not a table"));
+}
+
+#[wasm_bindgen_test]
+fn filter() {
+    use gloo_console::filter::{self, Filter, Level, LevelFilter};
+
+    let parsed: Filter = "warn, my_app=info,my_app::net=off".parse().unwrap();
+    assert_eq!(
+        parsed,
+        Filter::new(LevelFilter::Warn)
+            .target("my_app", LevelFilter::Info)
+            .target("my_app::net", LevelFilter::Off)
+    );
+    assert_eq!(parsed.to_string(), "warn,my_app=info,my_app::net=off");
+    assert_eq!(parsed.level("my_app::net::http"), LevelFilter::Off);
+    assert_eq!(parsed.level("my_app::ui"), LevelFilter::Info);
+    assert_eq!(parsed.level("my_application"), LevelFilter::Warn);
+    assert!(parsed.enabled(Level::Error, "other"));
+    assert!(!parsed.enabled(Level::Info, "other"));
+    assert!("loud".parse::<Filter>().is_err());
+    assert!("=info".parse::<Filter>().is_err());
+
+    filter::save_filter(Filter::new(LevelFilter::Error));
+    assert!(!filter::enabled(Level::Warn, module_path!()));
+    gloo_console::warn!("silenced");
+    filter::clear_saved_filter();
+    assert_eq!(filter::current(), Filter::default());
+    assert!(filter::enabled(Level::Trace, module_path!()));
+}
+
+#[wasm_bindgen_test]
+fn styled() {
+    use gloo_console::Styled;
+
+    let prefix = Styled::new().badge("net", "white", "#0a7");
+    gloo_console::info!(&prefix, "plain value");
+    gloo_console::warn!(
+        prefix.text(" 100% ").bold("done").color(" %s", "red"),
+        status = 200
+    );
+}
+
+#[wasm_bindgen_test]
+fn capture() {
+    use gloo_console::filter::Level;
+    use gloo_console::{captured_logs, clear_captured_logs, start_capture, stop_capture, Styled};
+
+    gloo_console::info!("before");
+    start_capture();
+    gloo_console::warn!("cache is full", size = 100);
+    gloo_console::error!(Styled::new().bold("failed").text(" 100%"), 404);
+    let logs = captured_logs();
+    assert_eq!(logs.len(), 2);
+    assert_eq!(logs[0].level(), Level::Warn);
+    assert_eq!(logs[0].target(), module_path!());
+    assert_eq!(logs[0].message(), r#"cache is full {"size":100}"#);
+    assert_eq!(logs[1].level(), Level::Error);
+    assert_eq!(logs[1].message(), "failed 100% 404");
+
+    clear_captured_logs();
+    gloo_console::log!("%s and %c%%", "this", "color: red");
+    assert_eq!(captured_logs()[0].message(), "this and %");
+
+    stop_capture();
+    gloo_console::debug!("after");
+    assert_eq!(captured_logs().len(), 1);
+}
+
+#[wasm_bindgen_test]
+fn perf_marks_and_measures() {
+    use gloo_console::perf::{self, Measure};
+
+    perf::clear_marks();
+    perf::clear_measures();
+
+    perf::mark("load");
+    perf::mark("loaded");
+    perf::measure_between("loading", "load", "loaded");
+    let value = Measure::scope("scoped", || true);
+    assert!(value);
+
+    let marks = perf::marks();
+    assert_eq!(
+        marks.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(),
+        ["load", "loaded", "scoped start"]
+    );
+    let loading = &perf::measures_named("loading")[0];
+    assert_eq!(loading.start_time, marks[0].start_time);
+    assert_eq!(loading.duration, marks[1].start_time - marks[0].start_time);
+    assert_eq!(perf::measures().len(), 2);
+    assert!(perf::now() >= marks[2].start_time);
+}
+
+// Panics abort on wasm32, so the hook can only be set.
+#[wasm_bindgen_test]
+fn panic_hook() {
+    gloo_console::set_panic_hook();
+    gloo_console::set_panic_hook_with(|report| drop(report.clone()));
+    drop(std::panic::take_hook());
+}
+
+#[cfg(feature = "tracing")]
+#[wasm_bindgen_test]
+fn console_layer() {
+    use gloo_console::ConsoleLayer;
+    use tracing_subscriber::prelude::*;
+
+    let subscriber = tracing_subscriber::registry().with(ConsoleLayer::new());
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("request", url = "/api/items", attempt = 1);
+        let _guard = span.enter();
+        tracing::info!(status = 200, cached = false, "request done");
+        tracing::debug!(elapsed = ?std::time::Duration::from_millis(20));
+    });
+}
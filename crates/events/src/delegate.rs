@@ -0,0 +1,188 @@
+use crate::{EventListener, EventListenerOptions};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{Element, Event, Node};
+
+struct Handler {
+    selector: String,
+    callback: RefCell<Box<dyn FnMut(&Event, &Element)>>,
+}
+
+/// A single event listener on a container, which dispatches its events to handlers registered
+/// per CSS selector, for the descendants of the container the selectors match.
+///
+/// This is cheaper than an [`EventListener`] per element for large lists, and keeps working for
+/// elements added to the container later.
+///
+/// The handlers are called with the event and the matching element, which is what
+/// `currentTarget` would be with a listener on that element, as `event.current_target()` is
+/// the container. They are called from the [`target`](https://developer.mozilla.org/en-US/docs/Web/API/Event/target)
+/// of the event up to the container, which is not matched itself, like bubbling events.
+/// Calling [`event.stop_propagation()`](https://rustwasm.github.io/wasm-bindgen/api/web_sys/struct.Event.html#method.stop_propagation)
+/// in a handler skips the handlers of the ancestors of its element.
+///
+/// Events which don't bubble, like `"focus"` and `"blur"`, are only delegated in the capture
+/// phase, see [`DelegatedListener::new_with_options`](#method.new_with_options). Their bubbling
+/// counterparts, like `"focusin"` and `"focusout"`, work either way.
+///
+/// When the `DelegatedListener` is dropped, its event listener is removed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use gloo_events::DelegatedListener;
+/// # let list: web_sys::Element = unimplemented!();
+/// #
+/// let mut listener = DelegatedListener::new(&list, "click");
+///
+/// listener.on("li > .remove", move |_event, button| {
+///     // `button` is the `.remove` element that was clicked, or contains the clicked element.
+///     let item = button.parent_element();
+///
+///     // ...
+/// });
+/// ```
+#[must_use = "handlers will never be called after being dropped"]
+pub struct DelegatedListener {
+    container: Element,
+    handlers: Rc<RefCell<Vec<Rc<Handler>>>>,
+    listener: EventListener,
+}
+
+impl DelegatedListener {
+    /// Listens to events of `event_type` on `container`, in the bubble phase.
+    ///
+    /// As with [`EventListener::new`](struct.EventListener.html#method.new), the listener is
+    /// passive, so the handlers can't use `event.prevent_default()`.
+    #[inline]
+    pub fn new<S>(container: &Element, event_type: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self::new_with_options(container, event_type, EventListenerOptions::default())
+    }
+
+    /// Listens to events of `event_type` on `container` with `options`.
+    ///
+    /// See the documentation for [`EventListenerOptions`](struct.EventListenerOptions.html)
+    /// for more details.
+    pub fn new_with_options<S>(
+        container: &Element,
+        event_type: S,
+        options: EventListenerOptions,
+    ) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        let handlers: Rc<RefCell<Vec<Rc<Handler>>>> = Rc::default();
+
+        let listener = EventListener::new_with_options(container, event_type, options, {
+            let container = container.clone();
+            let handlers = handlers.clone();
+            move |event| dispatch(&container, &handlers, event)
+        });
+
+        Self {
+            container: container.clone(),
+            handlers,
+            listener,
+        }
+    }
+
+    /// Registers `callback` to be called for the events of the descendants of the container
+    /// matching `selector`, with the event and the matching element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `selector` is not a valid CSS selector.
+    pub fn on<S, F>(&mut self, selector: S, callback: F)
+    where
+        S: Into<String>,
+        F: FnMut(&Event, &Element) + 'static,
+    {
+        let selector = selector.into();
+        // Fail at registration rather than on every event.
+        self.container.matches(&selector).unwrap_throw();
+
+        self.handlers.borrow_mut().push(Rc::new(Handler {
+            selector,
+            callback: RefCell::new(Box::new(callback)),
+        }));
+    }
+
+    /// Removes the handlers registered for `selector`.
+    pub fn off(&mut self, selector: &str) {
+        self.handlers
+            .borrow_mut()
+            .retain(|handler| handler.selector != selector);
+    }
+
+    /// Returns the container.
+    #[inline]
+    pub fn container(&self) -> &Element {
+        &self.container
+    }
+
+    /// Returns the event type.
+    #[inline]
+    pub fn event_type(&self) -> &str {
+        self.listener.event_type()
+    }
+}
+
+impl fmt::Debug for DelegatedListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let handlers = self.handlers.borrow();
+        f.debug_struct("DelegatedListener")
+            .field("container", &self.container)
+            .field(
+                "selectors",
+                &handlers.iter().map(|m| &m.selector).collect::<Vec<_>>(),
+            )
+            .field("listener", &self.listener)
+            .finish()
+    }
+}
+
+fn dispatch(container: &Element, handlers: &RefCell<Vec<Rc<Handler>>>, event: &Event) {
+    // Handlers may register other handlers or dispatch events themselves.
+    let handlers = handlers.borrow().clone();
+    if handlers.is_empty() {
+        return;
+    }
+
+    let mut element = match event.target() {
+        Some(target) => match target.dyn_into::<Element>() {
+            Ok(element) => Some(element),
+            // e.g. text nodes
+            Err(target) => target
+                .dyn_into::<Node>()
+                .ok()
+                .and_then(|m| m.parent_element()),
+        },
+        None => None,
+    };
+
+    while let Some(current) = element {
+        if &current == container {
+            break;
+        }
+
+        for handler in &handlers {
+            if current.matches(&handler.selector).unwrap_or(false) {
+                // A handler dispatching the same event synchronously is not called again.
+                if let Ok(mut callback) = handler.callback.try_borrow_mut() {
+                    callback(event, &current);
+                }
+            }
+        }
+
+        if event.cancel_bubble() {
+            break;
+        }
+        element = current.parent_element();
+    }
+}
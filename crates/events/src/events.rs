@@ -0,0 +1,129 @@
+//! Types of DOM events, associated with the `web_sys` type of their events, for use with
+//! [`on`](crate::on) and its variants.
+//!
+//! Each type is named after its event, e.g. [`KeyDown`] is the `"keydown"` event, whose events
+//! are [`KeyboardEvent`](web_sys::KeyboardEvent)s.
+//!
+//! Other events can be supported by implementing [`StaticEvent`]:
+//!
+//! ```rust
+//! use gloo_events::events::StaticEvent;
+//!
+//! #[derive(Debug)]
+//! struct ColorChange;
+//!
+//! impl StaticEvent for ColorChange {
+//!     const TYPE: &'static str = "colorchange";
+//!     type Event = web_sys::CustomEvent;
+//! }
+//! ```
+
+use wasm_bindgen::JsCast;
+
+/// A DOM event whose type is known statically.
+pub trait StaticEvent {
+    /// The event type, e.g. `"click"`.
+    const TYPE: &'static str;
+
+    /// The type of the events, e.g. [`MouseEvent`](web_sys::MouseEvent).
+    type Event: JsCast;
+}
+
+macro_rules! static_events {
+    ($($name:ident => $type:literal: $event:ident,)*) => {
+        $(
+            #[doc = concat!("The `\"", $type, "\"` event, of type [`", stringify!($event), "`](web_sys::", stringify!($event), ").")]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct $name;
+
+            impl StaticEvent for $name {
+                const TYPE: &'static str = $type;
+                type Event = web_sys::$event;
+            }
+        )*
+    };
+}
+
+static_events! {
+    // Mouse events
+    Click => "click": MouseEvent,
+    DblClick => "dblclick": MouseEvent,
+    AuxClick => "auxclick": MouseEvent,
+    ContextMenu => "contextmenu": MouseEvent,
+    MouseDown => "mousedown": MouseEvent,
+    MouseUp => "mouseup": MouseEvent,
+    MouseMove => "mousemove": MouseEvent,
+    MouseEnter => "mouseenter": MouseEvent,
+    MouseLeave => "mouseleave": MouseEvent,
+    MouseOver => "mouseover": MouseEvent,
+    MouseOut => "mouseout": MouseEvent,
+    Wheel => "wheel": WheelEvent,
+
+    // Pointer events
+    PointerDown => "pointerdown": PointerEvent,
+    PointerUp => "pointerup": PointerEvent,
+    PointerMove => "pointermove": PointerEvent,
+    PointerEnter => "pointerenter": PointerEvent,
+    PointerLeave => "pointerleave": PointerEvent,
+    PointerOver => "pointerover": PointerEvent,
+    PointerOut => "pointerout": PointerEvent,
+    PointerCancel => "pointercancel": PointerEvent,
+    GotPointerCapture => "gotpointercapture": PointerEvent,
+    LostPointerCapture => "lostpointercapture": PointerEvent,
+
+    // Keyboard events
+    KeyDown => "keydown": KeyboardEvent,
+    KeyUp => "keyup": KeyboardEvent,
+
+    // Focus events
+    Focus => "focus": FocusEvent,
+    Blur => "blur": FocusEvent,
+    FocusIn => "focusin": FocusEvent,
+    FocusOut => "focusout": FocusEvent,
+
+    // Form events
+    // `input` is a plain `Event` on some elements, and often dispatched as one by scripts.
+    Input => "input": Event,
+    BeforeInput => "beforeinput": InputEvent,
+    Change => "change": Event,
+    Submit => "submit": SubmitEvent,
+    Reset => "reset": Event,
+
+    // Drag and drop events
+    DragStart => "dragstart": DragEvent,
+    Drag => "drag": DragEvent,
+    DragEnd => "dragend": DragEvent,
+    DragEnter => "dragenter": DragEvent,
+    DragLeave => "dragleave": DragEvent,
+    DragOver => "dragover": DragEvent,
+    Drop => "drop": DragEvent,
+
+    // Clipboard events
+    Copy => "copy": ClipboardEvent,
+    Cut => "cut": ClipboardEvent,
+    Paste => "paste": ClipboardEvent,
+
+    // Animation events
+    AnimationStart => "animationstart": AnimationEvent,
+    AnimationEnd => "animationend": AnimationEvent,
+    AnimationIteration => "animationiteration": AnimationEvent,
+    TransitionEnd => "transitionend": TransitionEvent,
+
+    // Window and document events
+    Load => "load": Event,
+    Scroll => "scroll": Event,
+    Resize => "resize": Event,
+    HashChange => "hashchange": HashChangeEvent,
+    PopState => "popstate": PopStateEvent,
+    Storage => "storage": StorageEvent,
+    Message => "message": MessageEvent,
+    Online => "online": Event,
+    Offline => "offline": Event,
+    DomContentLoaded => "DOMContentLoaded": Event,
+    VisibilityChange => "visibilitychange": Event,
+    PageShow => "pageshow": PageTransitionEvent,
+    PageHide => "pagehide": PageTransitionEvent,
+    Freeze => "freeze": Event,
+    Resume => "resume": Event,
+    BeforeUnload => "beforeunload": BeforeUnloadEvent,
+}
@@ -0,0 +1,493 @@
+use crate::{events, on, EventListener};
+use gloo_timers::callback::Timeout;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+use web_sys::{Element, PointerEvent};
+
+/// A position, in CSS pixels relative to the viewport, like `event.client_x()` and
+/// `event.client_y()`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    #[allow(missing_docs)]
+    pub x: f64,
+    #[allow(missing_docs)]
+    pub y: f64,
+}
+
+impl Point {
+    fn of(event: &PointerEvent) -> Self {
+        Self {
+            x: event.client_x() as f64,
+            y: event.client_y() as f64,
+        }
+    }
+
+    fn distance(self, other: Point) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+
+    fn midpoint(self, other: Point) -> Point {
+        Point {
+            x: (self.x + other.x) / 2.0,
+            y: (self.y + other.y) / 2.0,
+        }
+    }
+}
+
+/// A gesture recognized by a [`GestureListener`](struct.GestureListener.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// A pointer was pressed and released without moving.
+    Tap(Point),
+    /// A second tap close to the previous one, which is reported after the [`Tap`](#variant.Tap)
+    /// of the second tap.
+    DoubleTap(Point),
+    /// A pointer was pressed for a while without moving. It is not followed by a tap when
+    /// released.
+    LongPress(Point),
+    /// A pointer started moving while pressed, from the given position.
+    DragStart(Point),
+    /// A pointer moved while pressed.
+    Drag {
+        /// The position of the pointer.
+        position: Point,
+        /// The distance the pointer moved since the drag started.
+        delta: Point,
+    },
+    /// The pointer of a drag was released, at the given position.
+    DragEnd(Point),
+    /// A second pointer was pressed, e.g. a second finger on a touch screen.
+    PinchStart {
+        /// The middle of the two pointers.
+        center: Point,
+    },
+    /// One of the two pointers of a pinch moved.
+    Pinch {
+        /// The middle of the two pointers.
+        center: Point,
+        /// The distance between the pointers, relative to when the pinch started.
+        scale: f64,
+    },
+    /// One of the two pointers of a pinch was released.
+    PinchEnd {
+        /// The final scale of the pinch.
+        scale: f64,
+    },
+}
+
+/// Specifies the thresholds of gesture recognition, see [`GestureListener::new_with_options`](struct.GestureListener.html#method.new_with_options).
+///
+/// # Default
+///
+/// ```rust
+/// # use gloo_events::GestureOptions;
+/// #
+/// GestureOptions {
+///     slop: 10.0,
+///     double_tap_delay: 300,
+///     long_press_delay: 500,
+/// }
+/// # ;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureOptions {
+    /// How far a pointer can move, in CSS pixels, before a press becomes a drag, and how far
+    /// apart the taps of a double tap can be.
+    pub slop: f64,
+
+    /// The maximum delay between the taps of a double tap, in milliseconds.
+    pub double_tap_delay: u32,
+
+    /// How long a pointer must be pressed without moving to be a long press, in milliseconds.
+    pub long_press_delay: u32,
+}
+
+impl Default for GestureOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            slop: 10.0,
+            double_tap_delay: 300,
+            long_press_delay: 500,
+        }
+    }
+}
+
+/// RAII type which recognizes taps, double taps, long presses, drags and pinches on an element
+/// from its pointer events, and reports them to a callback.
+///
+/// The pointers pressed on the element are captured, so they keep being tracked when they
+/// leave it. Only the primary button of mice is considered, and only the first two pointers
+/// pressed at once.
+///
+/// Browsers scroll and zoom on touch screens rather than firing pointer events, unless the
+/// element has the [`touch-action: none`](https://developer.mozilla.org/en-US/docs/Web/CSS/touch-action)
+/// CSS property.
+///
+/// When the `GestureListener` is dropped, its event listeners are removed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use gloo_events::{Gesture, GestureListener};
+/// # let element = unimplemented!();
+/// #
+/// let listener = GestureListener::new(&element, move |gesture| match gesture {
+///     Gesture::DoubleTap(_) => {
+///         // Zoom in...
+///     }
+///     Gesture::Pinch { scale, .. } => {
+///         // Zoom by `scale`...
+///     }
+///     _ => {}
+/// });
+/// ```
+#[must_use = "gestures are no longer recognized after the listener is dropped"]
+pub struct GestureListener {
+    element: Element,
+    _listeners: [EventListener; 4],
+}
+
+impl GestureListener {
+    /// Recognizes the gestures on `element` with the default [`GestureOptions`](struct.GestureOptions.html).
+    #[inline]
+    pub fn new<F>(element: &Element, callback: F) -> Self
+    where
+        F: FnMut(Gesture) + 'static,
+    {
+        Self::new_with_options(element, GestureOptions::default(), callback)
+    }
+
+    /// Recognizes the gestures on `element` with `options`.
+    pub fn new_with_options<F>(element: &Element, options: GestureOptions, callback: F) -> Self
+    where
+        F: FnMut(Gesture) + 'static,
+    {
+        let recognizer = Rc::new(Recognizer {
+            options,
+            state: RefCell::new(State::default()),
+            callback: RefCell::new(Box::new(callback)),
+        });
+
+        let listeners = [
+            on::<events::PointerDown>(element, {
+                let recognizer = recognizer.clone();
+                let element = element.clone();
+                move |event| {
+                    if event.button() == 0 {
+                        // Fails for pointers which were released since.
+                        let _ = element.set_pointer_capture(event.pointer_id());
+                        recognizer.down(&event);
+                    }
+                }
+            }),
+            on::<events::PointerMove>(element, {
+                let recognizer = recognizer.clone();
+                move |event| recognizer.moved(&event)
+            }),
+            on::<events::PointerUp>(element, {
+                let recognizer = recognizer.clone();
+                move |event| recognizer.up(&event, false)
+            }),
+            on::<events::PointerCancel>(element, move |event| recognizer.up(&event, true)),
+        ];
+
+        Self {
+            element: element.clone(),
+            _listeners: listeners,
+        }
+    }
+
+    /// Returns the element whose gestures are recognized.
+    #[inline]
+    pub fn element(&self) -> &Element {
+        &self.element
+    }
+}
+
+impl fmt::Debug for GestureListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GestureListener")
+            .field("element", &self.element)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Recognizer {
+    options: GestureOptions,
+    state: RefCell<State>,
+    callback: RefCell<Box<dyn FnMut(Gesture)>>,
+}
+
+#[derive(Default)]
+struct State {
+    phase: Phase,
+    pointers: Vec<Pointer>,
+    long_press: Option<Timeout>,
+    last_tap: Option<(Point, f64)>,
+}
+
+#[derive(Default, Clone, Copy, PartialEq)]
+enum Phase {
+    #[default]
+    Idle,
+    /// A pointer is pressed and didn't move yet.
+    Pressed,
+    LongPressed,
+    Dragging,
+    Pinching {
+        distance: f64,
+        scale: f64,
+    },
+    /// The pointers left after a pinch, which are ignored until released.
+    Ignoring,
+}
+
+#[derive(Clone, Copy)]
+struct Pointer {
+    id: i32,
+    start: Point,
+    position: Point,
+}
+
+impl Recognizer {
+    fn down(self: &Rc<Self>, event: &PointerEvent) {
+        let position = Point::of(event);
+        let pointer = Pointer {
+            id: event.pointer_id(),
+            start: position,
+            position,
+        };
+
+        let mut gestures = Vec::new();
+        {
+            let mut state = self.state.borrow_mut();
+            match state.phase {
+                Phase::Idle => {
+                    state.pointers = vec![pointer];
+                    state.phase = Phase::Pressed;
+                    state.long_press = Some(self.long_press_timeout());
+                }
+                Phase::Pressed | Phase::LongPressed | Phase::Dragging
+                    if state.pointers.len() == 1 =>
+                {
+                    state.long_press = None;
+                    if state.phase == Phase::Dragging {
+                        gestures.push(Gesture::DragEnd(state.pointers[0].position));
+                    }
+                    let first = state.pointers[0].position;
+                    state.pointers.push(pointer);
+                    state.phase = Phase::Pinching {
+                        distance: first.distance(position),
+                        scale: 1.0,
+                    };
+                    gestures.push(Gesture::PinchStart {
+                        center: first.midpoint(position),
+                    });
+                }
+                _ => state.pointers.push(pointer),
+            }
+        }
+        self.emit(gestures);
+    }
+
+    fn moved(&self, event: &PointerEvent) {
+        let mut gestures = Vec::new();
+        {
+            let mut state = self.state.borrow_mut();
+            let id = event.pointer_id();
+            let index = match state.pointers.iter().position(|m| m.id == id) {
+                Some(index) => index,
+                None => return,
+            };
+            let position = Point::of(event);
+            state.pointers[index].position = position;
+            let start = state.pointers[index].start;
+
+            match state.phase {
+                Phase::Pressed if start.distance(position) > self.options.slop => {
+                    state.long_press = None;
+                    state.phase = Phase::Dragging;
+                    gestures.push(Gesture::DragStart(start));
+                    gestures.push(drag(start, position));
+                }
+                Phase::Dragging => gestures.push(drag(start, position)),
+                Phase::Pinching { distance, .. } if index < 2 => {
+                    let (first, second) = (state.pointers[0].position, state.pointers[1].position);
+                    let scale = if distance > 0.0 {
+                        first.distance(second) / distance
+                    } else {
+                        1.0
+                    };
+                    state.phase = Phase::Pinching { distance, scale };
+                    gestures.push(Gesture::Pinch {
+                        center: first.midpoint(second),
+                        scale,
+                    });
+                }
+                _ => {}
+            }
+        }
+        self.emit(gestures);
+    }
+
+    fn up(&self, event: &PointerEvent, cancelled: bool) {
+        let mut gestures = Vec::new();
+        {
+            let mut state = self.state.borrow_mut();
+            let id = event.pointer_id();
+            let index = match state.pointers.iter().position(|m| m.id == id) {
+                Some(index) => index,
+                None => return,
+            };
+            let pointer = state.pointers.remove(index);
+            state.long_press = None;
+
+            match state.phase {
+                Phase::Pressed if !cancelled => {
+                    let position = pointer.position;
+                    let time = event.time_stamp();
+                    gestures.push(Gesture::Tap(position));
+                    match state.last_tap {
+                        Some((last, last_time))
+                            if time - last_time <= self.options.double_tap_delay as f64
+                                && last.distance(position) <= self.options.slop =>
+                        {
+                            gestures.push(Gesture::DoubleTap(position));
+                            state.last_tap = None;
+                        }
+                        _ => state.last_tap = Some((position, time)),
+                    }
+                }
+                Phase::Dragging => gestures.push(Gesture::DragEnd(pointer.position)),
+                Phase::Pinching { scale, .. } if index < 2 => {
+                    gestures.push(Gesture::PinchEnd { scale });
+                    state.phase = Phase::Ignoring;
+                }
+                _ => {}
+            }
+
+            if state.pointers.is_empty() {
+                state.phase = Phase::Idle;
+            }
+        }
+        self.emit(gestures);
+    }
+
+    fn long_press_timeout(self: &Rc<Self>) -> Timeout {
+        // The timeout is owned by the state, so it must not keep the recognizer alive.
+        let recognizer = Rc::downgrade(self);
+        Timeout::new(self.options.long_press_delay, move || {
+            if let Some(recognizer) = Weak::upgrade(&recognizer) {
+                recognizer.long_pressed();
+            }
+        })
+    }
+
+    fn long_pressed(&self) {
+        let gesture = {
+            let mut state = self.state.borrow_mut();
+            state.long_press = None;
+            if state.phase != Phase::Pressed {
+                return;
+            }
+            state.phase = Phase::LongPressed;
+            Gesture::LongPress(state.pointers[0].start)
+        };
+        self.emit(vec![gesture]);
+    }
+
+    fn emit(&self, gestures: Vec<Gesture>) {
+        let mut callback = self.callback.borrow_mut();
+        for gesture in gestures {
+            callback(gesture);
+        }
+    }
+}
+
+fn drag(start: Point, position: Point) -> Gesture {
+    Gesture::Drag {
+        position,
+        delta: Point {
+            x: position.x - start.x,
+            y: position.y - start.y,
+        },
+    }
+}
+
+#[cfg(feature = "futures")]
+pub use stream::GestureStream;
+
+#[cfg(feature = "futures")]
+mod stream {
+    use super::{Gesture, GestureListener, GestureOptions};
+    use futures_channel::mpsc;
+    use futures_core::stream::Stream;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use web_sys::Element;
+
+    /// A [`Stream`] of the gestures recognized on an element, see
+    /// [`GestureListener`](struct.GestureListener.html).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use gloo_events::{Gesture, GestureStream};
+    /// use futures::StreamExt;
+    /// # async fn example() {
+    /// # let element = unimplemented!();
+    ///
+    /// let mut gestures = GestureStream::new(&element);
+    ///
+    /// while let Some(gesture) = gestures.next().await {
+    ///     if let Gesture::LongPress(position) = gesture {
+    ///         // Open a context menu at `position`...
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled"]
+    pub struct GestureStream {
+        receiver: mpsc::UnboundedReceiver<Gesture>,
+        listener: GestureListener,
+    }
+
+    impl GestureStream {
+        /// Recognizes the gestures on `element` with the default
+        /// [`GestureOptions`](struct.GestureOptions.html).
+        #[inline]
+        pub fn new(element: &Element) -> Self {
+            Self::new_with_options(element, GestureOptions::default())
+        }
+
+        /// Recognizes the gestures on `element` with `options`.
+        pub fn new_with_options(element: &Element, options: GestureOptions) -> Self {
+            let (sender, receiver) = mpsc::unbounded();
+            let listener = GestureListener::new_with_options(element, options, move |gesture| {
+                // This only fails while the stream is being dropped.
+                let _ = sender.unbounded_send(gesture);
+            });
+
+            Self { receiver, listener }
+        }
+
+        /// Returns the element whose gestures are recognized.
+        #[inline]
+        pub fn element(&self) -> &Element {
+            self.listener.element()
+        }
+    }
+
+    impl Stream for GestureStream {
+        type Item = Gesture;
+
+        #[inline]
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Pin::new(&mut self.receiver).poll_next(cx)
+        }
+    }
+}
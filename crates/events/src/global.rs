@@ -0,0 +1,287 @@
+use crate::events::{self, StaticEvent};
+use crate::{on, EventListener};
+use wasm_bindgen::UnwrapThrowExt;
+use web_sys::{Event, HashChangeEvent, KeyboardEvent, MouseEvent, PopStateEvent, StorageEvent};
+
+/// Returns the events of the global `window`.
+///
+/// # Panics
+///
+/// Panics outside of windows, e.g. in workers.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// let listener = gloo_events::window().on_resize(move |_event| {
+///     // Update the layout...
+/// });
+/// ```
+#[inline]
+pub fn window() -> WindowEvents {
+    WindowEvents {
+        window: web_sys::window().expect_throw("no global `window` exists"),
+    }
+}
+
+/// Returns the events of the `document` of the global `window`.
+///
+/// # Panics
+///
+/// Panics outside of windows, e.g. in workers.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// let listener = gloo_events::document().on_keydown(move |event| {
+///     if event.key() == "Escape" {
+///         // Close the dialog...
+///     }
+/// });
+/// ```
+#[inline]
+pub fn document() -> DocumentEvents {
+    DocumentEvents {
+        document: window()
+            .window
+            .document()
+            .expect_throw("the window has no document"),
+    }
+}
+
+/// The events of the global `window`, see [`window`](fn.window.html).
+///
+/// The `on_*` methods register event listeners like [`on`](fn.on.html), which are removed when
+/// the returned `EventListener`s are dropped.
+#[derive(Debug, Clone)]
+pub struct WindowEvents {
+    window: web_sys::Window,
+}
+
+impl WindowEvents {
+    /// Registers an event listener for events of type `E` on the window.
+    #[inline]
+    pub fn on<E>(&self, callback: impl FnMut(E::Event) + 'static) -> EventListener
+    where
+        E: StaticEvent,
+    {
+        on::<E>(&self.window, callback)
+    }
+
+    /// Registers an event listener for when the window is resized.
+    #[inline]
+    pub fn on_resize<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(Event) + 'static,
+    {
+        self.on::<events::Resize>(callback)
+    }
+
+    /// Registers an event listener for when the document is scrolled.
+    #[inline]
+    pub fn on_scroll<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(Event) + 'static,
+    {
+        self.on::<events::Scroll>(callback)
+    }
+
+    /// Registers an event listener for when the browser goes online.
+    #[inline]
+    pub fn on_online<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(Event) + 'static,
+    {
+        self.on::<events::Online>(callback)
+    }
+
+    /// Registers an event listener for when the browser goes offline.
+    #[inline]
+    pub fn on_offline<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(Event) + 'static,
+    {
+        self.on::<events::Offline>(callback)
+    }
+
+    /// Registers an event listener for when the fragment of the URL changes.
+    #[inline]
+    pub fn on_hashchange<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(HashChangeEvent) + 'static,
+    {
+        self.on::<events::HashChange>(callback)
+    }
+
+    /// Registers an event listener for when the user navigates the session history.
+    #[inline]
+    pub fn on_popstate<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(PopStateEvent) + 'static,
+    {
+        self.on::<events::PopState>(callback)
+    }
+
+    /// Registers an event listener for when the storage is modified by another document, e.g.
+    /// in another tab.
+    #[inline]
+    pub fn on_storage<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(StorageEvent) + 'static,
+    {
+        self.on::<events::Storage>(callback)
+    }
+
+    /// Returns the window.
+    #[inline]
+    pub fn target(&self) -> &web_sys::Window {
+        &self.window
+    }
+}
+
+/// The events of the `document` of the global `window`, see [`document`](fn.document.html).
+///
+/// The `on_*` methods register event listeners like [`on`](fn.on.html), which are removed when
+/// the returned `EventListener`s are dropped.
+#[derive(Debug, Clone)]
+pub struct DocumentEvents {
+    document: web_sys::Document,
+}
+
+impl DocumentEvents {
+    /// Registers an event listener for events of type `E` on the document.
+    #[inline]
+    pub fn on<E>(&self, callback: impl FnMut(E::Event) + 'static) -> EventListener
+    where
+        E: StaticEvent,
+    {
+        on::<E>(&self.document, callback)
+    }
+
+    /// Registers an event listener for when the document is shown or hidden, e.g. when the tab
+    /// is switched.
+    #[inline]
+    pub fn on_visibilitychange<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(Event) + 'static,
+    {
+        self.on::<events::VisibilityChange>(callback)
+    }
+
+    /// Registers an event listener for when the document is parsed, before its images and
+    /// stylesheets are loaded.
+    #[inline]
+    pub fn on_dom_content_loaded<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(Event) + 'static,
+    {
+        self.on::<events::DomContentLoaded>(callback)
+    }
+
+    /// Registers an event listener for when a key is pressed anywhere in the document.
+    #[inline]
+    pub fn on_keydown<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(KeyboardEvent) + 'static,
+    {
+        self.on::<events::KeyDown>(callback)
+    }
+
+    /// Registers an event listener for when a key is released anywhere in the document.
+    #[inline]
+    pub fn on_keyup<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(KeyboardEvent) + 'static,
+    {
+        self.on::<events::KeyUp>(callback)
+    }
+
+    /// Registers an event listener for clicks anywhere in the document, e.g. to close a menu
+    /// when clicking outside of it.
+    #[inline]
+    pub fn on_click<F>(&self, callback: F) -> EventListener
+    where
+        F: FnMut(MouseEvent) + 'static,
+    {
+        self.on::<events::Click>(callback)
+    }
+
+    /// Returns the document.
+    #[inline]
+    pub fn target(&self) -> &web_sys::Document {
+        &self.document
+    }
+}
+
+#[cfg(feature = "futures")]
+mod streams {
+    use super::{DocumentEvents, WindowEvents};
+    use crate::events::{self, StaticEvent};
+    use crate::TypedEventStream;
+
+    impl WindowEvents {
+        /// Returns a stream of the events of type `E` on the window.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// use futures::StreamExt;
+        /// use gloo_events::events;
+        /// # async fn example() {
+        ///
+        /// let mut online = gloo_events::window().stream::<events::Online>();
+        ///
+        /// while online.next().await.is_some() {
+        ///     // Retry the failed requests...
+        /// }
+        /// # }
+        /// ```
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        #[inline]
+        pub fn stream<E>(&self) -> TypedEventStream<E>
+        where
+            E: StaticEvent,
+        {
+            TypedEventStream::new(&self.window)
+        }
+
+        /// Returns a stream of the resizes of the window.
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        #[inline]
+        pub fn resizes(&self) -> TypedEventStream<events::Resize> {
+            self.stream()
+        }
+
+        /// Returns a stream of the scrolls of the document.
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        #[inline]
+        pub fn scrolls(&self) -> TypedEventStream<events::Scroll> {
+            self.stream()
+        }
+
+        /// Returns a stream of the changes of the fragment of the URL.
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        #[inline]
+        pub fn hashchanges(&self) -> TypedEventStream<events::HashChange> {
+            self.stream()
+        }
+    }
+
+    impl DocumentEvents {
+        /// Returns a stream of the events of type `E` on the document.
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        #[inline]
+        pub fn stream<E>(&self) -> TypedEventStream<E>
+        where
+            E: StaticEvent,
+        {
+            TypedEventStream::new(&self.document)
+        }
+
+        /// Returns a stream of the changes of the visibility of the document.
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        #[inline]
+        pub fn visibilitychanges(&self) -> TypedEventStream<events::VisibilityChange> {
+            self.stream()
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::EventListenerOptions;
+use std::borrow::Cow;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{AbortController, AbortSignal, Event, EventTarget};
+
+/// A group of event listeners, possibly on different targets, which are all removed at once.
+///
+/// The listeners are registered with the [`AbortSignal`](https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal)
+/// of a single `AbortController`, so removing them is a single call to `abort()`, rather than
+/// one call to `removeEventListener` per listener.
+///
+/// When the `ListenerGroup` is dropped, its event listeners are removed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use gloo_events::ListenerGroup;
+/// # let (window, document) = unimplemented!();
+/// #
+/// let mut group = ListenerGroup::new();
+///
+/// group.add(&window, "resize", move |_event| {
+///     // ...
+/// });
+/// group.add(&document, "keydown", move |_event| {
+///     // ...
+/// });
+///
+/// // Later...
+/// group.abort();
+/// ```
+#[derive(Debug)]
+#[must_use = "event listeners are removed when the group is dropped"]
+pub struct ListenerGroup {
+    controller: AbortController,
+    callbacks: Vec<Closure<dyn FnMut(&Event)>>,
+}
+
+impl ListenerGroup {
+    /// Creates an empty group.
+    pub fn new() -> Self {
+        Self {
+            controller: AbortController::new().unwrap_throw(),
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Registers an event listener on `target` in the group.
+    ///
+    /// This is the same as [`EventListener::new`](struct.EventListener.html#method.new),
+    /// except the listener is removed with the group.
+    #[inline]
+    pub fn add<S, F>(&mut self, target: &EventTarget, event_type: S, callback: F)
+    where
+        S: Into<Cow<'static, str>>,
+        F: FnMut(&Event) + 'static,
+    {
+        self.add_with_options(
+            target,
+            event_type,
+            EventListenerOptions::default(),
+            callback,
+        )
+    }
+
+    /// Registers an event listener on `target` in the group, with `options`.
+    ///
+    /// The [`signal`](struct.EventListenerOptions.html#structfield.signal) of `options` is
+    /// ignored, as the listener is registered with the signal of the group.
+    pub fn add_with_options<S, F>(
+        &mut self,
+        target: &EventTarget,
+        event_type: S,
+        options: EventListenerOptions,
+        callback: F,
+    ) where
+        S: Into<Cow<'static, str>>,
+        F: FnMut(&Event) + 'static,
+    {
+        let callback = Closure::wrap(Box::new(callback) as Box<dyn FnMut(&Event)>);
+        self.raw_add(target, &event_type.into(), options, false, callback);
+    }
+
+    /// Registers an event listener on `target` in the group, which will only fire once.
+    ///
+    /// This is the same as [`EventListener::once`](struct.EventListener.html#method.once),
+    /// except the listener is removed with the group if it didn't fire yet.
+    #[inline]
+    pub fn once<S, F>(&mut self, target: &EventTarget, event_type: S, callback: F)
+    where
+        S: Into<Cow<'static, str>>,
+        F: FnOnce(&Event) + 'static,
+    {
+        let callback = Closure::once(callback);
+        self.raw_add(
+            target,
+            &event_type.into(),
+            EventListenerOptions::default(),
+            true,
+            callback,
+        );
+    }
+
+    fn raw_add(
+        &mut self,
+        target: &EventTarget,
+        event_type: &str,
+        options: EventListenerOptions,
+        once: bool,
+        callback: Closure<dyn FnMut(&Event)>,
+    ) {
+        let options = EventListenerOptions {
+            signal: Some(self.signal()),
+            ..options
+        };
+
+        target
+            .add_event_listener_with_callback_and_add_event_listener_options(
+                event_type,
+                callback.as_ref().unchecked_ref(),
+                &options.as_js(once),
+            )
+            .unwrap_throw();
+
+        self.callbacks.push(callback);
+    }
+
+    /// Returns the signal the listeners of the group are registered with, e.g. to also abort
+    /// a `fetch` with the group.
+    #[inline]
+    pub fn signal(&self) -> AbortSignal {
+        self.controller.signal()
+    }
+
+    /// Returns the number of event listeners registered in the group since it was created or
+    /// last aborted, including the `once` listeners which already fired.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    /// Returns whether no event listener was registered in the group since it was created or
+    /// last aborted.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+
+    /// Removes all the event listeners of the group, and frees their callbacks.
+    ///
+    /// The group can still be used afterwards, with a new signal.
+    pub fn abort(&mut self) {
+        self.controller.abort();
+        self.callbacks.clear();
+        self.controller = AbortController::new().unwrap_throw();
+    }
+}
+
+impl Default for ListenerGroup {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for ListenerGroup {
+    #[inline]
+    fn drop(&mut self) {
+        self.controller.abort();
+    }
+}
@@ -0,0 +1,369 @@
+#[cfg(feature = "futures")]
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use web_sys::{Element, IntersectionObserverEntry};
+
+type Callback = Box<dyn FnMut(&[IntersectionObserverEntry])>;
+
+/// Builder of an [`IntersectionObserver`], see [`IntersectionObserver::builder`].
+#[must_use = "the observer is only created by `build`"]
+pub struct IntersectionObserverBuilder {
+    root: Option<Element>,
+    root_margin: Option<String>,
+    thresholds: Vec<f64>,
+    callback: Option<Callback>,
+}
+
+impl IntersectionObserverBuilder {
+    /// Sets the element whose bounds the targets intersect with, which must be an ancestor of
+    /// the targets. Defaults to the viewport.
+    pub fn root(mut self, root: &Element) -> Self {
+        self.root = Some(root.clone());
+        self
+    }
+
+    /// Sets the margins which grow or shrink the bounds of the root, with the syntax of the CSS
+    /// `margin` property, e.g. `"200px 0px"` to find targets before they are scrolled into view.
+    pub fn root_margin(mut self, margin: &str) -> Self {
+        self.root_margin = Some(margin.to_owned());
+        self
+    }
+
+    /// Adds a ratio of the area of the targets, between `0.0` and `1.0`, at which they are
+    /// reported when crossed in either direction. Defaults to `0.0` only, i.e. any visibility.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.thresholds.push(threshold);
+        self
+    }
+
+    /// Adds several thresholds, see [`threshold`](#method.threshold).
+    pub fn thresholds(mut self, thresholds: &[f64]) -> Self {
+        self.thresholds.extend_from_slice(thresholds);
+        self
+    }
+
+    /// Sets a callback which is called with the entries of all the targets each time some of
+    /// them cross a threshold.
+    pub fn callback<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(&[IntersectionObserverEntry]) + 'static,
+    {
+        self.callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Creates the observer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the root margin can't be parsed, or a threshold is not between `0.0` and
+    /// `1.0`.
+    pub fn build(self) -> IntersectionObserver {
+        let mut init = web_sys::IntersectionObserverInit::new();
+        init.root(self.root.as_ref());
+        if let Some(margin) = &self.root_margin {
+            init.root_margin(margin);
+        }
+        if !self.thresholds.is_empty() {
+            let thresholds = self
+                .thresholds
+                .iter()
+                .map(|m| JsValue::from_f64(*m))
+                .collect::<js_sys::Array>();
+            init.threshold(&thresholds);
+        }
+
+        let shared = Rc::new(Shared::default());
+        let closure = Closure::wrap(Box::new({
+            let shared = shared.clone();
+            let mut callback = self.callback;
+            move |entries: js_sys::Array| {
+                let entries = entries
+                    .iter()
+                    .map(JsCast::unchecked_into)
+                    .collect::<Vec<IntersectionObserverEntry>>();
+                shared.dispatch(&entries);
+                if let Some(callback) = &mut callback {
+                    callback(&entries);
+                }
+            }
+        }) as Box<dyn FnMut(js_sys::Array)>);
+
+        let inner = web_sys::IntersectionObserver::new_with_options(
+            closure.as_ref().unchecked_ref(),
+            &init,
+        )
+        .unwrap_throw();
+
+        IntersectionObserver {
+            inner,
+            shared,
+            _closure: closure,
+        }
+    }
+}
+
+impl fmt::Debug for IntersectionObserverBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntersectionObserverBuilder")
+            .field("root", &self.root)
+            .field("root_margin", &self.root_margin)
+            .field("thresholds", &self.thresholds)
+            .finish_non_exhaustive()
+    }
+}
+
+/// RAII type which wraps an [`IntersectionObserver`](https://developer.mozilla.org/en-US/docs/Web/API/IntersectionObserver),
+/// which reports when elements enter or leave the viewport or another element, e.g. to lazily
+/// load images, or load more items at the end of a list.
+///
+/// The targets are reported either to a callback set with
+/// [`IntersectionObserverBuilder::callback`](struct.IntersectionObserverBuilder.html#method.callback),
+/// or, with the `futures` feature enabled, to a [`Visibility`](struct.Visibility.html) stream
+/// per target.
+///
+/// When the `IntersectionObserver` is dropped, it stops observing its targets, and their
+/// streams end.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use gloo_events::IntersectionObserver;
+/// # let (list, sentinel) = unimplemented!();
+/// #
+/// let observer = IntersectionObserver::builder()
+///     .root(&list)
+///     .root_margin("0px 0px 200px 0px")
+///     .callback(move |entries| {
+///         if entries.iter().any(|entry| entry.is_intersecting()) {
+///             // Load more items...
+///         }
+///     })
+///     .build();
+///
+/// observer.observe(&sentinel);
+/// ```
+pub struct IntersectionObserver {
+    inner: web_sys::IntersectionObserver,
+    shared: Rc<Shared>,
+    _closure: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl IntersectionObserver {
+    /// Returns a builder of an observer, which observes the visibility of targets in the
+    /// viewport by default.
+    pub fn builder() -> IntersectionObserverBuilder {
+        IntersectionObserverBuilder {
+            root: None,
+            root_margin: None,
+            thresholds: Vec::new(),
+            callback: None,
+        }
+    }
+
+    /// Starts observing `target`, which is reported once observing starts, then each time it
+    /// crosses a threshold.
+    #[inline]
+    pub fn observe(&self, target: &Element) {
+        self.inner.observe(target);
+    }
+
+    /// Stops observing `target`, which ends its streams.
+    pub fn unobserve(&self, target: &Element) {
+        self.inner.unobserve(target);
+        self.shared.remove_target(target);
+    }
+
+    /// Stops observing all targets, which ends their streams.
+    pub fn disconnect(&self) {
+        self.inner.disconnect();
+        self.shared.clear();
+    }
+
+    /// Returns the underlying `web_sys::IntersectionObserver`.
+    #[inline]
+    pub fn as_raw(&self) -> &web_sys::IntersectionObserver {
+        &self.inner
+    }
+}
+
+impl fmt::Debug for IntersectionObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntersectionObserver")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for IntersectionObserver {
+    #[inline]
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+/// The state shared by the observer, its callback and its streams.
+#[derive(Default)]
+struct Shared {
+    #[cfg(feature = "futures")]
+    streams: RefCell<Vec<stream::Registration>>,
+    #[cfg(feature = "futures")]
+    next_id: Cell<usize>,
+}
+
+#[cfg(not(feature = "futures"))]
+impl Shared {
+    #[inline]
+    fn dispatch(&self, _entries: &[IntersectionObserverEntry]) {}
+
+    #[inline]
+    fn remove_target(&self, _target: &Element) {}
+
+    #[inline]
+    fn clear(&self) {}
+}
+
+#[cfg(feature = "futures")]
+pub use stream::Visibility;
+
+#[cfg(feature = "futures")]
+mod stream {
+    use super::{IntersectionObserver, Shared};
+    use futures_channel::mpsc;
+    use futures_core::stream::Stream;
+    use std::pin::Pin;
+    use std::rc::{Rc, Weak};
+    use std::task::{Context, Poll};
+    use web_sys::{Element, IntersectionObserverEntry};
+
+    pub(super) struct Registration {
+        id: usize,
+        target: Element,
+        sender: mpsc::UnboundedSender<IntersectionObserverEntry>,
+    }
+
+    impl Shared {
+        pub(super) fn dispatch(&self, entries: &[IntersectionObserverEntry]) {
+            let streams = self.streams.borrow();
+            for entry in entries {
+                let target = entry.target();
+                for registration in streams.iter().filter(|m| m.target == target) {
+                    let _ = registration.sender.unbounded_send(entry.clone());
+                }
+            }
+        }
+
+        pub(super) fn remove_target(&self, target: &Element) {
+            self.streams
+                .borrow_mut()
+                .retain(|registration| &registration.target != target);
+        }
+
+        pub(super) fn clear(&self) {
+            self.streams.borrow_mut().clear();
+        }
+    }
+
+    impl IntersectionObserver {
+        /// Starts observing `target`, and returns a stream of its entries, which yields one once
+        /// observing starts, then one each time it crosses a threshold.
+        ///
+        /// The target is also reported to the callback of the observer, if any. When the stream
+        /// is dropped, the target is no longer observed, unless other streams of it remain, so
+        /// targets reported to the callback should be observed with
+        /// [`observe`](#method.observe) only.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # use gloo_events::IntersectionObserver;
+        /// use futures::StreamExt;
+        /// # async fn example() {
+        /// # let image: web_sys::Element = unimplemented!();
+        ///
+        /// let observer = IntersectionObserver::builder().root_margin("200px").build();
+        /// let mut visibility = observer.visibility(&image);
+        ///
+        /// while let Some(entry) = visibility.next().await {
+        ///     if entry.is_intersecting() {
+        ///         // Load the image...
+        ///         break;
+        ///     }
+        /// }
+        /// # }
+        /// ```
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        pub fn visibility(&self, target: &Element) -> Visibility {
+            let (sender, receiver) = mpsc::unbounded();
+            let id = self.shared.next_id.get();
+            self.shared.next_id.set(id + 1);
+            self.shared.streams.borrow_mut().push(Registration {
+                id,
+                target: target.clone(),
+                sender,
+            });
+            self.inner.observe(target);
+
+            Visibility {
+                receiver,
+                id,
+                target: target.clone(),
+                observer: self.inner.clone(),
+                shared: Rc::downgrade(&self.shared),
+            }
+        }
+    }
+
+    /// A stream of the [`IntersectionObserverEntry`](https://rustwasm.github.io/wasm-bindgen/api/web_sys/struct.IntersectionObserverEntry.html)s
+    /// of a target, see [`IntersectionObserver::visibility`](struct.IntersectionObserver.html#method.visibility).
+    ///
+    /// The stream ends when the target is no longer observed, or the observer is dropped.
+    #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled"]
+    pub struct Visibility {
+        receiver: mpsc::UnboundedReceiver<IntersectionObserverEntry>,
+        id: usize,
+        target: Element,
+        observer: web_sys::IntersectionObserver,
+        shared: Weak<Shared>,
+    }
+
+    impl Visibility {
+        /// Returns the observed target.
+        #[inline]
+        pub fn target(&self) -> &Element {
+            &self.target
+        }
+    }
+
+    impl Stream for Visibility {
+        type Item = IntersectionObserverEntry;
+
+        #[inline]
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Pin::new(&mut self.receiver).poll_next(cx)
+        }
+    }
+
+    impl Drop for Visibility {
+        fn drop(&mut self) {
+            let shared = match self.shared.upgrade() {
+                Some(shared) => shared,
+                None => return,
+            };
+
+            let mut streams = shared.streams.borrow_mut();
+            streams.retain(|registration| registration.id != self.id);
+            if !streams
+                .iter()
+                .any(|registration| registration.target == self.target)
+            {
+                self.observer.unobserve(&self.target);
+            }
+        }
+    }
+}
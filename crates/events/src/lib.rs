@@ -4,16 +4,68 @@ provides an [`EventListener`] type which makes it easy!
 
 See the documentation for [`EventListener`] for more information.
 
+For the common events, [`on`] passes events already cast to their `web_sys` type:
+
+```rust,no_run
+use gloo_events::{events, on};
+# let target = unimplemented!();
+
+let listener = on::<events::Click>(&target, move |event| {
+    let (x, y) = (event.client_x(), event.client_y());
+
+    // ...
+});
+```
+
+With the `futures` feature enabled, events can also be awaited as a `Stream`, see
+[`EventStream`].
+
 [`EventListener`]: struct.EventListener.html
+[`on`]: fn.on.html
+[`EventStream`]: https://docs.rs/gloo-events/latest/gloo_events/struct.EventStream.html
 */
 #![deny(missing_docs, missing_debug_implementations)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 // Clippy doesn't like the callback types passed to raw web-sys
 #![allow(clippy::type_complexity)]
 
+mod delegate;
+pub mod events;
+mod gesture;
+mod global;
+mod group;
+mod intersection;
+mod lifecycle;
+mod media_query;
+mod rate_limit;
+#[cfg(feature = "futures")]
+mod stream;
+mod unload;
+
+pub use delegate::DelegatedListener;
+#[cfg(feature = "futures")]
+pub use gesture::GestureStream;
+pub use gesture::{Gesture, GestureListener, GestureOptions, Point};
+pub use global::{document, window, DocumentEvents, WindowEvents};
+pub use group::ListenerGroup;
+#[cfg(feature = "futures")]
+pub use intersection::Visibility;
+pub use intersection::{IntersectionObserver, IntersectionObserverBuilder};
+#[cfg(feature = "futures")]
+pub use lifecycle::PageLifecycleStream;
+pub use lifecycle::{PageLifecycle, PageState};
+pub use media_query::MediaQuery;
+#[cfg(feature = "futures")]
+pub use media_query::MediaQueryChanges;
+#[cfg(feature = "futures")]
+pub use stream::{EventStream, TypedEventStream};
+pub use unload::UnloadGuard;
+
+use events::StaticEvent;
 use std::borrow::Cow;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
-use web_sys::{AddEventListenerOptions, Event, EventTarget};
+use web_sys::{AbortSignal, AddEventListenerOptions, Event, EventTarget};
 
 /// Specifies whether the event listener is run during the capture or bubble phase.
 ///
@@ -59,6 +111,7 @@ impl EventListenerPhase {
 /// EventListenerOptions {
 ///     phase: EventListenerPhase::Bubble,
 ///     passive: true,
+///     signal: None,
 /// }
 /// # ;
 /// ```
@@ -83,15 +136,17 @@ impl EventListenerPhase {
 ///
 /// Specifies all options:
 ///
-/// ```rust
+/// ```rust,no_run
 /// # use gloo_events::{EventListenerOptions, EventListenerPhase};
 /// #
+/// # let signal = unimplemented!();
 /// let options = EventListenerOptions {
 ///     phase: EventListenerPhase::Capture,
 ///     passive: false,
+///     signal: Some(signal),
 /// };
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EventListenerOptions {
     /// The phase that the event listener should be run in.
     pub phase: EventListenerPhase,
@@ -105,6 +160,14 @@ pub struct EventListenerOptions {
     /// You can read more about the performance costs
     /// [here](https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener#Improving_scrolling_performance_with_passive_listeners).
     pub passive: bool,
+
+    /// An [`AbortSignal`](https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal) which
+    /// removes the event listener when aborted, e.g. by JavaScript code owning its
+    /// `AbortController`.
+    ///
+    /// The callback is only freed when the `EventListener` is dropped. To remove many event
+    /// listeners at once from Rust, see [`ListenerGroup`](struct.ListenerGroup.html).
+    pub signal: Option<AbortSignal>,
 }
 
 impl EventListenerOptions {
@@ -157,6 +220,10 @@ impl EventListenerOptions {
         options.capture(self.phase.is_capture());
         options.once(once);
         options.passive(self.passive);
+        if let Some(signal) = &self.signal {
+            // `AddEventListenerOptions.signal` is missing from `web-sys`.
+            js_sys::Reflect::set(&options, &"signal".into(), signal).unwrap_throw();
+        }
 
         options
     }
@@ -168,6 +235,7 @@ impl Default for EventListenerOptions {
         Self {
             phase: Default::default(),
             passive: true,
+            signal: None,
         }
     }
 }
@@ -581,3 +649,94 @@ impl Drop for EventListener {
         }
     }
 }
+
+/// Registers an event listener for events of type `E`, whose callback receives the events as
+/// [`E::Event`](events::StaticEvent::Event), without downcasting them.
+///
+/// This is the same as [`EventListener::new`](struct.EventListener.html#method.new), with the
+/// event type taken from `E`.
+///
+/// The events are cast to `E::Event` without checking their type. Events dispatched by scripts
+/// with another interface than the one the browser uses, e.g. a plain `Event` named `"click"`,
+/// must be listened to with [`EventListener`] instead.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use gloo_events::{events, on};
+/// # let target = unimplemented!();
+///
+/// let listener = on::<events::KeyDown>(&target, move |event| {
+///     if event.key() == "Escape" {
+///         // ...
+///     }
+/// });
+/// ```
+#[inline]
+pub fn on<E>(target: &EventTarget, mut callback: impl FnMut(E::Event) + 'static) -> EventListener
+where
+    E: StaticEvent,
+{
+    EventListener::new(target, E::TYPE, move |event| {
+        callback(event.clone().unchecked_into())
+    })
+}
+
+/// This is exactly the same as [`on`], except the event will only fire once, and it accepts
+/// `FnOnce` instead of `FnMut`.
+#[inline]
+pub fn once<E>(target: &EventTarget, callback: impl FnOnce(E::Event) + 'static) -> EventListener
+where
+    E: StaticEvent,
+{
+    EventListener::once(target, E::TYPE, move |event| {
+        callback(event.clone().unchecked_into())
+    })
+}
+
+/// Same as [`on`], with `options`, like
+/// [`EventListener::new_with_options`](struct.EventListener.html#method.new_with_options).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use gloo_events::{events, on_with_options, EventListenerOptions};
+/// # let target = unimplemented!();
+///
+/// let options = EventListenerOptions::enable_prevent_default();
+///
+/// let listener = on_with_options::<events::Submit>(&target, options, move |event| {
+///     event.prevent_default();
+///
+///     // ...
+/// });
+/// ```
+#[inline]
+pub fn on_with_options<E>(
+    target: &EventTarget,
+    options: EventListenerOptions,
+    mut callback: impl FnMut(E::Event) + 'static,
+) -> EventListener
+where
+    E: StaticEvent,
+{
+    EventListener::new_with_options(target, E::TYPE, options, move |event| {
+        callback(event.clone().unchecked_into())
+    })
+}
+
+/// Same as [`once`], with `options`, like
+/// [`EventListener::once_with_options`](struct.EventListener.html#method.once_with_options).
+#[inline]
+pub fn once_with_options<E>(
+    target: &EventTarget,
+    options: EventListenerOptions,
+    callback: impl FnOnce(E::Event) + 'static,
+) -> EventListener
+where
+    E: StaticEvent,
+{
+    EventListener::once_with_options(target, E::TYPE, options, move |event| {
+        callback(event.clone().unchecked_into())
+    })
+}
@@ -0,0 +1,257 @@
+use crate::{events, on, EventListener};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use wasm_bindgen::UnwrapThrowExt;
+use web_sys::{Document, Window};
+
+/// A state of the page, reported by [`PageLifecycle`](struct.PageLifecycle.html).
+///
+/// See the [Page Lifecycle API](https://developer.chrome.com/docs/web-platform/page-lifecycle-api)
+/// for when browsers change the states of pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageState {
+    /// The page became visible, e.g. its tab was switched to.
+    Visible,
+    /// The page was hidden, e.g. its tab was switched from, or the browser was minimized. It
+    /// may be discarded without further notice, so state should be saved.
+    Hidden,
+    /// The page was frozen, e.g. put in the back/forward cache, so its timers and tasks are
+    /// suspended until it is visible again.
+    Frozen,
+    /// The page gained the focus.
+    Focused,
+    /// The page lost the focus, while possibly still being visible.
+    Blurred,
+}
+
+impl PageState {
+    /// Returns whether the page is not seen by the user in this state, so work like animations
+    /// or polling should be paused.
+    #[inline]
+    pub fn is_background(self) -> bool {
+        matches!(self, PageState::Hidden | PageState::Frozen)
+    }
+}
+
+/// RAII type which reports the changes of the [`PageState`](enum.PageState.html) of the page to
+/// a callback, from its `visibilitychange`, `focus`, `blur`, `pagehide`, `pageshow`, `freeze`
+/// and `resume` events.
+///
+/// The same state is not reported twice in a row, e.g. when a page is both hidden and put in
+/// the back/forward cache on navigation.
+///
+/// When the `PageLifecycle` is dropped, its event listeners are removed.
+///
+/// # Panics
+///
+/// Panics outside of windows, e.g. in workers.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use gloo_events::PageLifecycle;
+///
+/// let lifecycle = PageLifecycle::new(move |state| {
+///     if state.is_background() {
+///         // Pause polling...
+///     } else {
+///         // Resume polling...
+///     }
+/// });
+/// ```
+#[must_use = "states are no longer reported after the lifecycle is dropped"]
+pub struct PageLifecycle {
+    _listeners: [EventListener; 7],
+}
+
+impl PageLifecycle {
+    /// Returns the current state of the page, which is either
+    /// [`Focused`](enum.PageState.html#variant.Focused),
+    /// [`Visible`](enum.PageState.html#variant.Visible), or
+    /// [`Hidden`](enum.PageState.html#variant.Hidden).
+    ///
+    /// # Panics
+    ///
+    /// Panics outside of windows, e.g. in workers.
+    pub fn current() -> PageState {
+        current_state(&document())
+    }
+
+    /// Reports the changes of the state of the page to `callback`.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: FnMut(PageState) + 'static,
+    {
+        let window = window();
+        let document = document();
+
+        let reporter = Rc::new(Reporter {
+            last: RefCell::new(current_state(&document)),
+            callback: RefCell::new(Box::new(callback)),
+        });
+        let visibility = {
+            let document = document.clone();
+            move || match document.hidden() {
+                true => PageState::Hidden,
+                false => PageState::Visible,
+            }
+        };
+
+        let listeners = [
+            on::<events::VisibilityChange>(&document, {
+                let reporter = reporter.clone();
+                let visibility = visibility.clone();
+                move |_| reporter.report(visibility())
+            }),
+            on::<events::Focus>(&window, {
+                let reporter = reporter.clone();
+                move |_| reporter.report(PageState::Focused)
+            }),
+            on::<events::Blur>(&window, {
+                let reporter = reporter.clone();
+                move |_| reporter.report(PageState::Blurred)
+            }),
+            on::<events::PageHide>(&window, {
+                let reporter = reporter.clone();
+                move |event| {
+                    reporter.report(match event.persisted() {
+                        // The page is put in the back/forward cache.
+                        true => PageState::Frozen,
+                        false => PageState::Hidden,
+                    })
+                }
+            }),
+            on::<events::PageShow>(&window, {
+                let reporter = reporter.clone();
+                let visibility = visibility.clone();
+                move |event| {
+                    // Pages which are not restored from the back/forward cache were never
+                    // reported as hidden.
+                    if event.persisted() {
+                        reporter.report(visibility())
+                    }
+                }
+            }),
+            on::<events::Freeze>(&document, {
+                let reporter = reporter.clone();
+                move |_| reporter.report(PageState::Frozen)
+            }),
+            on::<events::Resume>(&document, move |_| reporter.report(visibility())),
+        ];
+
+        Self {
+            _listeners: listeners,
+        }
+    }
+}
+
+impl fmt::Debug for PageLifecycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PageLifecycle").finish_non_exhaustive()
+    }
+}
+
+struct Reporter {
+    last: RefCell<PageState>,
+    callback: RefCell<Box<dyn FnMut(PageState)>>,
+}
+
+impl Reporter {
+    fn report(&self, state: PageState) {
+        if self.last.replace(state) != state {
+            (self.callback.borrow_mut())(state);
+        }
+    }
+}
+
+fn window() -> Window {
+    web_sys::window().expect_throw("no global `window` exists")
+}
+
+fn document() -> Document {
+    window()
+        .document()
+        .expect_throw("the window has no document")
+}
+
+fn current_state(document: &Document) -> PageState {
+    if document.hidden() {
+        PageState::Hidden
+    } else if document.has_focus().unwrap_or(false) {
+        PageState::Focused
+    } else {
+        PageState::Visible
+    }
+}
+
+#[cfg(feature = "futures")]
+pub use stream::PageLifecycleStream;
+
+#[cfg(feature = "futures")]
+mod stream {
+    use super::{PageLifecycle, PageState};
+    use futures_channel::mpsc;
+    use futures_core::stream::Stream;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    impl PageLifecycle {
+        /// Returns a stream of the changes of the state of the page.
+        ///
+        /// The stream doesn't yield the current state, see [`current`](#method.current).
+        ///
+        /// # Panics
+        ///
+        /// Panics outside of windows, e.g. in workers.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// use futures::StreamExt;
+        /// use gloo_events::{PageLifecycle, PageState};
+        /// # async fn example() {
+        ///
+        /// let mut states = PageLifecycle::stream();
+        ///
+        /// while let Some(state) = states.next().await {
+        ///     if state == PageState::Hidden {
+        ///         // Save the draft...
+        ///     }
+        /// }
+        /// # }
+        /// ```
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        pub fn stream() -> PageLifecycleStream {
+            let (sender, receiver) = mpsc::unbounded();
+            let lifecycle = PageLifecycle::new(move |state| {
+                // This only fails while the stream is being dropped.
+                let _ = sender.unbounded_send(state);
+            });
+
+            PageLifecycleStream {
+                receiver,
+                _lifecycle: lifecycle,
+            }
+        }
+    }
+
+    /// A [`Stream`] of the changes of the state of the page, see
+    /// [`PageLifecycle::stream`](struct.PageLifecycle.html#method.stream).
+    #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled"]
+    pub struct PageLifecycleStream {
+        receiver: mpsc::UnboundedReceiver<PageState>,
+        _lifecycle: PageLifecycle,
+    }
+
+    impl Stream for PageLifecycleStream {
+        type Item = PageState;
+
+        #[inline]
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Pin::new(&mut self.receiver).poll_next(cx)
+        }
+    }
+}
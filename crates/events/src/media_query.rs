@@ -0,0 +1,156 @@
+use crate::EventListener;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{MediaQueryList, MediaQueryListEvent};
+
+/// A [media query](https://developer.mozilla.org/en-US/docs/Web/CSS/Media_Queries/Using_media_queries)
+/// of the global `window`, whose matching can be checked and followed, e.g. to adapt a layout
+/// to the width of the viewport, or to react to the preferences of the user.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use gloo_events::MediaQuery;
+///
+/// let dark = MediaQuery::prefers_dark_color_scheme();
+/// // Pick the initial theme...
+/// let is_dark = dark.matches();
+///
+/// let listener = dark.on_change(move |is_dark| {
+///     // Switch the theme...
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct MediaQuery {
+    inner: MediaQueryList,
+}
+
+impl MediaQuery {
+    /// Parses `query`, e.g. `"(min-width: 800px)"`.
+    ///
+    /// Invalid queries never match.
+    ///
+    /// # Panics
+    ///
+    /// Panics outside of windows, e.g. in workers.
+    pub fn new(query: &str) -> Self {
+        let inner = web_sys::window()
+            .expect_throw("no global `window` exists")
+            .match_media(query)
+            .unwrap_throw()
+            .unwrap_throw();
+        Self { inner }
+    }
+
+    /// The `(prefers-color-scheme: dark)` query, which matches when the user prefers dark
+    /// themes.
+    #[inline]
+    pub fn prefers_dark_color_scheme() -> Self {
+        Self::new("(prefers-color-scheme: dark)")
+    }
+
+    /// The `(prefers-reduced-motion: reduce)` query, which matches when the user prefers less
+    /// animations.
+    #[inline]
+    pub fn prefers_reduced_motion() -> Self {
+        Self::new("(prefers-reduced-motion: reduce)")
+    }
+
+    /// Returns the query, as serialized by the browser, e.g. `"not all"` for invalid queries.
+    #[inline]
+    pub fn query(&self) -> String {
+        self.inner.media()
+    }
+
+    /// Returns whether the query currently matches.
+    #[inline]
+    pub fn matches(&self) -> bool {
+        self.inner.matches()
+    }
+
+    /// Registers an event listener which is called with whether the query matches each time
+    /// it changes.
+    pub fn on_change<F>(&self, mut callback: F) -> EventListener
+    where
+        F: FnMut(bool) + 'static,
+    {
+        EventListener::new(&self.inner, "change", move |event| {
+            callback(event.unchecked_ref::<MediaQueryListEvent>().matches())
+        })
+    }
+
+    /// Returns the underlying `web_sys::MediaQueryList`.
+    #[inline]
+    pub fn as_raw(&self) -> &MediaQueryList {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "futures")]
+pub use stream::MediaQueryChanges;
+
+#[cfg(feature = "futures")]
+mod stream {
+    use super::MediaQuery;
+    use crate::EventStream;
+    use futures_core::stream::{FusedStream, Stream};
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use wasm_bindgen::JsCast;
+    use web_sys::MediaQueryListEvent;
+
+    impl MediaQuery {
+        /// Returns a stream of whether the query matches, each time it changes.
+        ///
+        /// The stream doesn't yield the current state, see [`matches`](#method.matches).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// use futures::StreamExt;
+        /// use gloo_events::MediaQuery;
+        /// # async fn example() {
+        ///
+        /// let query = MediaQuery::new("(min-width: 800px)");
+        /// let mut changes = query.changes();
+        ///
+        /// while let Some(is_wide) = changes.next().await {
+        ///     // Show or hide the sidebar...
+        /// }
+        /// # }
+        /// ```
+        #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+        #[inline]
+        pub fn changes(&self) -> MediaQueryChanges {
+            MediaQueryChanges {
+                inner: EventStream::new(&self.inner, "change"),
+            }
+        }
+    }
+
+    /// A [`Stream`] of whether a [`MediaQuery`](struct.MediaQuery.html) matches, see
+    /// [`MediaQuery::changes`](struct.MediaQuery.html#method.changes).
+    #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled"]
+    pub struct MediaQueryChanges {
+        inner: EventStream,
+    }
+
+    impl Stream for MediaQueryChanges {
+        type Item = bool;
+
+        #[inline]
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Pin::new(&mut self.inner)
+                .poll_next(cx)
+                .map(|event| event.map(|m| m.unchecked_into::<MediaQueryListEvent>().matches()))
+        }
+    }
+
+    impl FusedStream for MediaQueryChanges {
+        #[inline]
+        fn is_terminated(&self) -> bool {
+            false
+        }
+    }
+}
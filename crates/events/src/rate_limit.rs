@@ -0,0 +1,138 @@
+use crate::EventListener;
+use gloo_timers::callback::Timeout;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use web_sys::{Event, EventTarget};
+
+impl EventListener {
+    /// Registers an event listener which is only called once events stop firing for `millis`
+    /// milliseconds, with the last of them, e.g. to validate a text field once the user
+    /// stopped typing.
+    ///
+    /// Pending calls are cancelled when the `EventListener` is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use gloo_events::EventListener;
+    /// # let target = unimplemented!();
+    /// #
+    /// let listener = EventListener::new_debounced(&target, "input", 300, move |event| {
+    ///     // Search for the text of the input...
+    /// });
+    /// ```
+    pub fn new_debounced<S, F>(
+        target: &EventTarget,
+        event_type: S,
+        millis: u32,
+        callback: F,
+    ) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+        F: FnMut(&Event) + 'static,
+    {
+        let callback = Rc::new(RefCell::new(callback));
+        let mut timeout: Option<Timeout> = None;
+
+        Self::new(target, event_type, move |event| {
+            let callback = callback.clone();
+            let event = event.clone();
+            let next = Timeout::new(millis, move || {
+                (callback.borrow_mut())(&event);
+            });
+            // Dropping the previous timeout cancels it.
+            drop(timeout.replace(next));
+        })
+    }
+
+    /// Registers an event listener which is called at most once every `millis` milliseconds,
+    /// e.g. to update a layout on `"scroll"` or `"resize"`.
+    ///
+    /// The first event is handled right away. The events which fire within `millis` of a call
+    /// are dropped, except the last one, which is handled once `millis` elapsed, so the last
+    /// state is never missed.
+    ///
+    /// Pending calls are cancelled when the `EventListener` is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use gloo_events::EventListener;
+    /// # let target = unimplemented!();
+    /// #
+    /// let listener = EventListener::new_throttled(&target, "scroll", 100, move |event| {
+    ///     // Show or hide a "Back to top" button...
+    /// });
+    /// ```
+    pub fn new_throttled<S, F>(
+        target: &EventTarget,
+        event_type: S,
+        millis: u32,
+        callback: F,
+    ) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+        F: FnMut(&Event) + 'static,
+    {
+        let throttle = Rc::new(Throttle {
+            millis,
+            state: RefCell::new(ThrottleState {
+                timeout: None,
+                pending: None,
+            }),
+            callback: RefCell::new(Box::new(callback)),
+        });
+
+        Self::new(target, event_type, move |event| throttle.fire(event))
+    }
+}
+
+struct Throttle {
+    millis: u32,
+    state: RefCell<ThrottleState>,
+    callback: RefCell<Box<dyn FnMut(&Event)>>,
+}
+
+struct ThrottleState {
+    /// Set while calls are throttled.
+    timeout: Option<Timeout>,
+    /// The last event which fired while calls were throttled.
+    pending: Option<Event>,
+}
+
+impl Throttle {
+    fn fire(self: &Rc<Self>, event: &Event) {
+        {
+            let mut state = self.state.borrow_mut();
+            if state.timeout.is_some() {
+                state.pending = Some(event.clone());
+                return;
+            }
+            state.timeout = Some(self.timeout());
+        }
+        (self.callback.borrow_mut())(event);
+    }
+
+    fn timeout(self: &Rc<Self>) -> Timeout {
+        // The timeout is owned by the state, so it must not keep the throttle alive.
+        let throttle = Rc::downgrade(self);
+        Timeout::new(self.millis, move || {
+            if let Some(throttle) = Weak::upgrade(&throttle) {
+                throttle.elapsed();
+            }
+        })
+    }
+
+    fn elapsed(self: &Rc<Self>) {
+        let event = {
+            let mut state = self.state.borrow_mut();
+            let event = state.pending.take();
+            state.timeout = event.as_ref().map(|_| self.timeout());
+            event
+        };
+        if let Some(event) = event {
+            (self.callback.borrow_mut())(&event);
+        }
+    }
+}
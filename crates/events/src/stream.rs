@@ -0,0 +1,193 @@
+use crate::events::StaticEvent;
+use crate::{EventListener, EventListenerOptions};
+use futures_channel::mpsc;
+use futures_core::stream::{FusedStream, Stream};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use wasm_bindgen::JsCast;
+use web_sys::{Event, EventTarget};
+
+/// A [`Stream`] of the events of an [`EventTarget`](https://rustwasm.github.io/wasm-bindgen/api/web_sys/struct.EventTarget.html).
+///
+/// The events are queued until they are polled, so the stream never misses events, and never
+/// ends. When the `EventStream` is dropped, its event listener is removed.
+///
+/// As the events are only polled after they were dispatched, it is too late to call
+/// `event.prevent_default()` or `event.stop_propagation()` on them; these need an
+/// [`EventListener`](struct.EventListener.html) instead.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use gloo_events::EventStream;
+/// use futures::StreamExt;
+/// # async fn example() {
+/// # let target = unimplemented!();
+///
+/// let mut clicks = EventStream::new(&target, "click");
+///
+/// while let Some(event) = clicks.next().await {
+///     // ...
+/// }
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct EventStream {
+    receiver: mpsc::UnboundedReceiver<Event>,
+    listener: EventListener,
+}
+
+impl EventStream {
+    /// Listens to events of `event_type` on `target`.
+    ///
+    /// The event type can be either a `&'static str` like `"click"`, or it can be a
+    /// dynamically constructed `String`.
+    #[inline]
+    pub fn new<S>(target: &EventTarget, event_type: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self::new_with_options(target, event_type, EventListenerOptions::default())
+    }
+
+    /// Listens to events of `event_type` on `target` with `options`, e.g. in the capture phase.
+    ///
+    /// See the documentation for [`EventListenerOptions`](struct.EventListenerOptions.html)
+    /// for more details.
+    pub fn new_with_options<S>(
+        target: &EventTarget,
+        event_type: S,
+        options: EventListenerOptions,
+    ) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        let (sender, receiver) = mpsc::unbounded();
+        let listener = EventListener::new_with_options(target, event_type, options, move |event| {
+            // This only fails while the stream is being dropped.
+            let _ = sender.unbounded_send(event.clone());
+        });
+
+        Self { receiver, listener }
+    }
+
+    /// Returns the `EventTarget`.
+    #[inline]
+    pub fn target(&self) -> &EventTarget {
+        self.listener.target()
+    }
+
+    /// Returns the event type.
+    #[inline]
+    pub fn event_type(&self) -> &str {
+        self.listener.event_type()
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Event;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl FusedStream for EventStream {
+    #[inline]
+    fn is_terminated(&self) -> bool {
+        false
+    }
+}
+
+/// A [`Stream`] of the events of type `E` of an [`EventTarget`](https://rustwasm.github.io/wasm-bindgen/api/web_sys/struct.EventTarget.html),
+/// as [`E::Event`](crate::events::StaticEvent::Event).
+///
+/// This is the same as [`EventStream`](struct.EventStream.html), with the event type taken from
+/// `E`, and the events cast like with [`on`](fn.on.html).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use gloo_events::{events, TypedEventStream};
+/// use futures::StreamExt;
+/// # async fn example() {
+/// # let target = unimplemented!();
+///
+/// let mut keys = TypedEventStream::<events::KeyDown>::new(&target);
+///
+/// while let Some(event) = keys.next().await {
+///     if event.key() == "Enter" {
+///         // ...
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+#[must_use = "streams do nothing unless polled"]
+pub struct TypedEventStream<E> {
+    inner: EventStream,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<E> TypedEventStream<E>
+where
+    E: StaticEvent,
+{
+    /// Listens to events of type `E` on `target`.
+    #[inline]
+    pub fn new(target: &EventTarget) -> Self {
+        Self::new_with_options(target, EventListenerOptions::default())
+    }
+
+    /// Listens to events of type `E` on `target` with `options`.
+    #[inline]
+    pub fn new_with_options(target: &EventTarget, options: EventListenerOptions) -> Self {
+        Self {
+            inner: EventStream::new_with_options(target, E::TYPE, options),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the `EventTarget`.
+    #[inline]
+    pub fn target(&self) -> &EventTarget {
+        self.inner.target()
+    }
+}
+
+impl<E> std::fmt::Debug for TypedEventStream<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedEventStream")
+            .field(&self.inner)
+            .finish()
+    }
+}
+
+impl<E> Stream for TypedEventStream<E>
+where
+    E: StaticEvent,
+{
+    type Item = E::Event;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner)
+            .poll_next(cx)
+            .map(|event| event.map(JsCast::unchecked_into))
+    }
+}
+
+impl<E> FusedStream for TypedEventStream<E>
+where
+    E: StaticEvent,
+{
+    #[inline]
+    fn is_terminated(&self) -> bool {
+        false
+    }
+}
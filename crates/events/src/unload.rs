@@ -0,0 +1,84 @@
+use crate::{events, on_with_options, EventListener, EventListenerOptions};
+use std::fmt;
+use wasm_bindgen::UnwrapThrowExt;
+
+/// RAII type which asks the user to confirm leaving the page while it is held, e.g. while a
+/// form has unsaved changes.
+///
+/// The confirmation is requested from the `beforeunload` event of the window, so browsers show
+/// their own dialog, whose message can't be customized, and only if the user interacted with
+/// the page. Navigations within single-page apps don't unload the page, see the blockers of
+/// `gloo-history` for those.
+///
+/// Some browsers don't put pages with `beforeunload` listeners in the back/forward cache, so the
+/// guard should only be held while it is needed, rather than for the lifetime of the app.
+///
+/// When the `UnloadGuard` is dropped, leaving the page is no longer confirmed.
+///
+/// # Panics
+///
+/// Panics outside of windows, e.g. in workers.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use gloo_events::UnloadGuard;
+///
+/// let mut guard = None;
+///
+/// // When the form is edited...
+/// guard = Some(UnloadGuard::new());
+///
+/// // When the form is saved...
+/// guard = None;
+/// ```
+#[must_use = "leaving the page is no longer confirmed after the guard is dropped"]
+pub struct UnloadGuard {
+    _listener: EventListener,
+}
+
+impl UnloadGuard {
+    /// Asks the user to confirm leaving the page until the guard is dropped.
+    #[inline]
+    pub fn new() -> Self {
+        Self::new_when(|| true)
+    }
+
+    /// Asks the user to confirm leaving the page if `condition` returns `true` when the page is
+    /// about to be unloaded, until the guard is dropped.
+    ///
+    /// This is how `gloo-history` asks its blockers before the page is unloaded. `condition`
+    /// must not prompt the user itself, as browsers don't allow dialogs at that point.
+    pub fn new_when<F>(condition: F) -> Self
+    where
+        F: Fn() -> bool + 'static,
+    {
+        let window = web_sys::window().expect_throw("no global `window` exists");
+        // The listener can't be passive, or `prevent_default` would be ignored.
+        let options = EventListenerOptions::enable_prevent_default();
+        let listener = on_with_options::<events::BeforeUnload>(&window, options, move |event| {
+            if condition() {
+                event.prevent_default();
+                // Older browsers only show the dialog when a non-empty return value is set.
+                event.set_return_value("unsaved changes");
+            }
+        });
+
+        Self {
+            _listener: listener,
+        }
+    }
+}
+
+impl Default for UnloadGuard {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for UnloadGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnloadGuard").finish_non_exhaustive()
+    }
+}
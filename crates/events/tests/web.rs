@@ -4,7 +4,10 @@
 
 use futures::channel::mpsc;
 use futures::prelude::*;
-use gloo_events::{EventListener, EventListenerOptions, EventListenerPhase};
+use gloo_events::{
+    events, DelegatedListener, EventListener, EventListenerOptions, EventListenerPhase,
+    ListenerGroup,
+};
 use js_sys::Error;
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 use wasm_bindgen_test::*;
@@ -74,6 +77,7 @@ async fn new_with_options() {
             EventListenerOptions {
                 phase: EventListenerPhase::Capture,
                 passive: false,
+                signal: None,
             },
             move |e| {
                 sender.send(|| {
@@ -102,6 +106,7 @@ async fn once_with_options() {
             EventListenerOptions {
                 phase: EventListenerPhase::Capture,
                 passive: false,
+                signal: None,
             },
             move |e| {
                 sender.send(|| {
@@ -159,6 +164,369 @@ async fn once() {
     assert_eq!(results, Ok(vec![()]));
 }
 
+#[wasm_bindgen_test]
+async fn on() {
+    let results = mpsc(|sender| {
+        let body = body();
+
+        let _click = gloo_events::on::<events::Click>(&body, {
+            let sender = sender.clone();
+            move |e| sender.send(|| Ok(e.button().to_string()))
+        });
+
+        let _keydown =
+            gloo_events::once::<events::KeyDown>(&body, move |e| sender.send(|| Ok(e.key())));
+
+        body.click();
+
+        let mut init = web_sys::KeyboardEventInit::new();
+        init.key("Escape");
+        let event = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init)
+            .unwrap_throw();
+        body.dispatch_event(&event).unwrap_throw();
+        body.dispatch_event(&event).unwrap_throw();
+    })
+    .await;
+    assert_eq!(results, Ok(vec!["0".to_string(), "Escape".to_string()]));
+}
+
+#[wasm_bindgen_test]
+async fn delegated() {
+    let results = mpsc(|sender| {
+        let document = window().unwrap_throw().document().unwrap_throw();
+        let list = document.create_element("ul").unwrap_throw();
+        list.set_inner_html(
+            r#"<li id="a"><span>a</span></li><li id="b" class="stop"><span>b</span></li>"#,
+        );
+        body().append_child(&list).unwrap_throw();
+
+        let mut listener = DelegatedListener::new(&list, "click");
+        listener.on("li", {
+            let sender = sender.clone();
+            let list = list.clone();
+            move |e, li| {
+                sender.send(|| {
+                    is(e.current_target(), Some(list.clone().into()))?;
+                    Ok(li.id())
+                })
+            }
+        });
+        listener.on("span", move |e, span| {
+            if span.parent_element().unwrap_throw().class_name() == "stop" {
+                e.stop_propagation();
+            }
+            sender.send(|| Ok(span.text_content().unwrap_throw()))
+        });
+
+        for id in ["a", "b"] {
+            document
+                .query_selector(&format!("#{} span", id))
+                .unwrap_throw()
+                .unwrap_throw()
+                .unchecked_into::<HtmlElement>()
+                .click();
+        }
+        list.unchecked_ref::<HtmlElement>().click();
+        list.remove();
+    })
+    .await;
+    assert_eq!(
+        results,
+        Ok(vec!["a".to_string(), "a".to_string(), "b".to_string()])
+    );
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn event_stream() {
+    use gloo_events::{EventStream, TypedEventStream};
+
+    let body = body();
+    let mut clicks = EventStream::new(&body, "click");
+    let mut mouse_clicks = TypedEventStream::<events::Click>::new(&body);
+
+    body.click();
+    body.click();
+
+    for _ in 0..2 {
+        let event = clicks.next().await.unwrap_throw();
+        assert!(event.dyn_ref::<web_sys::MouseEvent>().is_some());
+        assert_eq!(mouse_clicks.next().await.unwrap_throw().button(), 0);
+    }
+}
+
+#[wasm_bindgen_test]
+async fn listener_group() {
+    let results = mpsc(|sender| {
+        let body = body();
+        let document: web_sys::EventTarget =
+            window().unwrap_throw().document().unwrap_throw().into();
+
+        let mut group = ListenerGroup::new();
+        group.add(&body, "click", {
+            let sender = sender.clone();
+            move |_| sender.send(|| Ok(1))
+        });
+        group.once(&document, "click", {
+            let sender = sender.clone();
+            move |_| sender.send(|| Ok(2))
+        });
+        assert_eq!(group.len(), 2);
+
+        body.click();
+        body.click();
+        group.abort();
+        assert!(group.is_empty());
+        body.click();
+
+        group.add(&body, "click", move |_| sender.send(|| Ok(3)));
+        body.click();
+    })
+    .await;
+    assert_eq!(results, Ok(vec![1, 2, 1, 3]));
+}
+
+#[wasm_bindgen_test]
+async fn abort_signal() {
+    let results = mpsc(|sender| {
+        let body = body();
+        let controller = web_sys::AbortController::new().unwrap_throw();
+
+        let _handler = EventListener::new_with_options(
+            &body,
+            "click",
+            EventListenerOptions {
+                signal: Some(controller.signal()),
+                ..Default::default()
+            },
+            move |_| sender.send(|| Ok(())),
+        );
+
+        body.click();
+        controller.abort();
+        body.click();
+    })
+    .await;
+    assert_eq!(results, Ok(vec![()]));
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn intersection_observer() {
+    use gloo_events::IntersectionObserver;
+
+    let document = window().unwrap_throw().document().unwrap_throw();
+    let element = document.create_element("div").unwrap_throw();
+    element
+        .set_attribute("style", "width: 10px; height: 10px")
+        .unwrap_throw();
+    body().prepend_with_node_1(&element).unwrap_throw();
+
+    let (sender, mut receiver) = mpsc::unbounded();
+    let observer = IntersectionObserver::builder()
+        .thresholds(&[0.0, 1.0])
+        .callback(move |entries| sender.unbounded_send(entries.len()).unwrap_throw())
+        .build();
+    let mut visibility = observer.visibility(&element);
+
+    let entry = visibility.next().await.unwrap_throw();
+    assert_eq!(&entry.target(), &element);
+    assert!(entry.is_intersecting());
+    assert_eq!(receiver.next().await, Some(1));
+
+    observer.unobserve(&element);
+    assert!(visibility.next().await.is_none());
+    element.remove();
+}
+
+#[wasm_bindgen_test]
+fn gestures() {
+    use gloo_events::{Gesture, GestureListener, Point};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let element = window()
+        .unwrap_throw()
+        .document()
+        .unwrap_throw()
+        .create_element("div")
+        .unwrap_throw();
+
+    let gestures = Rc::new(RefCell::new(Vec::new()));
+    let _listener = GestureListener::new(&element, {
+        let gestures = gestures.clone();
+        move |gesture| gestures.borrow_mut().push(gesture)
+    });
+
+    let pointer = |event_type: &str, x: i32| {
+        let mut init = web_sys::PointerEventInit::new();
+        init.pointer_id(1).client_x(x).client_y(0);
+        let event =
+            web_sys::PointerEvent::new_with_event_init_dict(event_type, &init).unwrap_throw();
+        element.dispatch_event(&event).unwrap_throw();
+    };
+
+    for _ in 0..2 {
+        pointer("pointerdown", 0);
+        pointer("pointerup", 0);
+    }
+    pointer("pointerdown", 0);
+    pointer("pointermove", 20);
+    pointer("pointerup", 20);
+
+    let at = |x| Point { x, y: 0.0 };
+    assert_eq!(
+        *gestures.borrow(),
+        vec![
+            Gesture::Tap(at(0.0)),
+            Gesture::Tap(at(0.0)),
+            Gesture::DoubleTap(at(0.0)),
+            Gesture::DragStart(at(0.0)),
+            Gesture::Drag {
+                position: at(20.0),
+                delta: at(20.0),
+            },
+            Gesture::DragEnd(at(20.0)),
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn debounced_and_throttled() {
+    use gloo_timers::future::TimeoutFuture;
+
+    let body = body();
+    let (sender, receiver) = mpsc::unbounded();
+
+    let debounced = EventListener::new_debounced(&body, "click", 50, {
+        let sender = sender.clone();
+        move |_| sender.unbounded_send("debounced").unwrap_throw()
+    });
+    let throttled = EventListener::new_throttled(&body, "click", 50, move |_| {
+        sender.unbounded_send("throttled").unwrap_throw()
+    });
+
+    for _ in 0..3 {
+        body.click();
+    }
+    TimeoutFuture::new(200).await;
+    drop((debounced, throttled));
+
+    let calls = receiver.collect::<Vec<_>>().await;
+    assert_eq!(calls, vec!["throttled", "throttled", "debounced"]);
+}
+
+#[wasm_bindgen_test]
+async fn global_events() {
+    let results = mpsc(|sender| {
+        let _resize = gloo_events::window().on_resize({
+            let sender = sender.clone();
+            move |e| sender.send(|| Ok(e.type_()))
+        });
+        let _keydown = gloo_events::document().on_keydown(move |e| sender.send(|| Ok(e.key())));
+
+        let event = web_sys::Event::new("resize").unwrap_throw();
+        window()
+            .unwrap_throw()
+            .dispatch_event(&event)
+            .unwrap_throw();
+
+        let mut init = web_sys::KeyboardEventInit::new();
+        init.key("a").bubbles(true);
+        let event = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init)
+            .unwrap_throw();
+        body().dispatch_event(&event).unwrap_throw();
+    })
+    .await;
+    assert_eq!(results, Ok(vec!["resize".to_string(), "a".to_string()]));
+}
+
+#[wasm_bindgen_test]
+async fn media_query() {
+    use gloo_events::MediaQuery;
+
+    assert!(MediaQuery::new("all").matches());
+    assert!(!MediaQuery::new("not all").matches());
+    assert_eq!(MediaQuery::new("(min-width: ").query(), "not all");
+
+    let query = MediaQuery::new("(min-width: 1px)");
+    let results = mpsc(|sender| {
+        let _listener = query.on_change(move |matches| sender.send(|| Ok(matches)));
+
+        let mut init = web_sys::MediaQueryListEventInit::new();
+        init.matches(false);
+        let event =
+            web_sys::MediaQueryListEvent::new_with_event_init_dict("change", &init).unwrap_throw();
+        query.as_raw().dispatch_event(&event).unwrap_throw();
+    })
+    .await;
+    assert_eq!(results, Ok(vec![false]));
+}
+
+#[wasm_bindgen_test]
+async fn page_lifecycle() {
+    use gloo_events::{PageLifecycle, PageState};
+
+    let results = mpsc(|sender| {
+        let _lifecycle = PageLifecycle::new(move |state| sender.send(|| Ok(state)));
+
+        let window: web_sys::EventTarget = window().unwrap_throw().into();
+        let document: web_sys::EventTarget = body().owner_document().unwrap_throw().into();
+        for (target, event_type) in [
+            (&window, "blur"),
+            (&window, "focus"),
+            (&window, "focus"),
+            (&document, "freeze"),
+            (&window, "blur"),
+        ] {
+            let event = web_sys::Event::new(event_type).unwrap_throw();
+            target.dispatch_event(&event).unwrap_throw();
+        }
+    })
+    .await;
+    assert_eq!(
+        results,
+        Ok(vec![
+            PageState::Blurred,
+            PageState::Focused,
+            PageState::Frozen,
+            PageState::Blurred,
+        ])
+    );
+    assert!(!PageState::Focused.is_background());
+    assert!(PageState::Frozen.is_background());
+}
+
+#[wasm_bindgen_test]
+fn unload_guard() {
+    use gloo_events::UnloadGuard;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let window = window().unwrap_throw();
+    let unload = || {
+        let mut init = web_sys::EventInit::new();
+        init.cancelable(true);
+        let event = web_sys::Event::new_with_event_init_dict("beforeunload", &init).unwrap_throw();
+        window.dispatch_event(&event).unwrap_throw();
+        event.default_prevented()
+    };
+
+    let guard = UnloadGuard::new();
+    assert!(unload());
+    drop(guard);
+    assert!(!unload());
+
+    let dirty = Rc::new(Cell::new(false));
+    let _guard = UnloadGuard::new_when({
+        let dirty = dirty.clone();
+        move || dirty.get()
+    });
+    assert!(!unload());
+    dirty.set(true);
+    assert!(unload());
+}
+
 // TODO is it possible to somehow cleanup the closure after a timeout?
 #[wasm_bindgen_test]
 fn forget() {
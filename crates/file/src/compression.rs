@@ -0,0 +1,188 @@
+use crate::Blob;
+use js_sys::{Array, Reflect, Uint8Array};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    // `CompressionStream` and `DecompressionStream` are unstable in `web-sys`.
+    type CompressionStream;
+
+    #[wasm_bindgen(constructor, catch)]
+    fn new(format: &str) -> Result<CompressionStream, JsValue>;
+
+    type DecompressionStream;
+
+    #[wasm_bindgen(constructor, catch)]
+    fn new(format: &str) -> Result<DecompressionStream, JsValue>;
+}
+
+/// A compression format of [`compress`] and [`decompress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+    /// The gzip format, as in `.gz` files.
+    Gzip,
+    /// The zlib format.
+    Deflate,
+    /// Raw DEFLATE data, without header nor checksum, as in zip archives.
+    DeflateRaw,
+}
+
+impl CompressionFormat {
+    fn as_str(self) -> &'static str {
+        match self {
+            CompressionFormat::Gzip => "gzip",
+            CompressionFormat::Deflate => "deflate",
+            CompressionFormat::DeflateRaw => "deflate-raw",
+        }
+    }
+}
+
+/// Compresses the contents of `blob` in `format` with a [`CompressionStream`], e.g. to export
+/// data without bundling a compression library.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use gloo_file::{Blob, CompressionFormat};
+///
+/// # async fn example() {
+/// let blob = Blob::new("hello world");
+/// let compressed = gloo_file::compress(&blob, CompressionFormat::Gzip).await.unwrap();
+/// gloo_file::save(&compressed, "hello.txt.gz");
+/// # }
+/// ```
+///
+/// [`CompressionStream`]: https://developer.mozilla.org/en-US/docs/Web/API/CompressionStream
+pub async fn compress(blob: &Blob, format: CompressionFormat) -> Result<Blob, CompressionError> {
+    compress_with_progress(blob, format, |_| {}).await
+}
+
+/// Same as [`compress`], but calls `progress` with the number of compressed bytes produced so
+/// far, as they are produced.
+pub async fn compress_with_progress<F>(
+    blob: &Blob,
+    format: CompressionFormat,
+    progress: F,
+) -> Result<Blob, CompressionError>
+where
+    F: FnMut(u64),
+{
+    let transform: JsValue = CompressionStream::new(format.as_str())
+        .map_err(|_| CompressionError::Unsupported)?
+        .into();
+    let mime_type = match format {
+        CompressionFormat::Gzip => Some("application/gzip"),
+        _ => None,
+    };
+    transform_blob(blob, &transform, mime_type, progress).await
+}
+
+/// Decompresses the contents of `blob` in `format` with a [`DecompressionStream`].
+///
+/// [`DecompressionStream`]: https://developer.mozilla.org/en-US/docs/Web/API/DecompressionStream
+pub async fn decompress(blob: &Blob, format: CompressionFormat) -> Result<Blob, CompressionError> {
+    decompress_with_progress(blob, format, |_| {}).await
+}
+
+/// Same as [`decompress`], but calls `progress` with the number of decompressed bytes produced
+/// so far, as they are produced.
+pub async fn decompress_with_progress<F>(
+    blob: &Blob,
+    format: CompressionFormat,
+    progress: F,
+) -> Result<Blob, CompressionError>
+where
+    F: FnMut(u64),
+{
+    let transform: JsValue = DecompressionStream::new(format.as_str())
+        .map_err(|_| CompressionError::Unsupported)?
+        .into();
+    transform_blob(blob, &transform, None, progress).await
+}
+
+/// Pipes the contents of `blob` through `transform`, and gathers the output in a blob.
+async fn transform_blob<F>(
+    blob: &Blob,
+    transform: &JsValue,
+    mime_type: Option<&str>,
+    mut progress: F,
+) -> Result<Blob, CompressionError>
+where
+    F: FnMut(u64),
+{
+    let input: &web_sys::Blob = blob.as_ref();
+    let output = input
+        .stream()
+        .pipe_through(transform.unchecked_ref::<web_sys::ReadableWritablePair>());
+    let reader = output
+        .get_reader()
+        .unchecked_into::<web_sys::ReadableStreamDefaultReader>();
+
+    let parts = Array::new();
+    let mut size = 0;
+    loop {
+        let result = JsFuture::from(reader.read())
+            .await
+            .map_err(CompressionError::from_js)?;
+        let done = Reflect::get(&result, &JsValue::from_str("done")).unwrap_throw();
+        if done.is_truthy() {
+            break;
+        }
+
+        let chunk = Reflect::get(&result, &JsValue::from_str("value"))
+            .unwrap_throw()
+            .unchecked_into::<Uint8Array>();
+        size += chunk.length() as u64;
+        parts.push(&chunk);
+        progress(size);
+    }
+
+    let mut options = web_sys::BlobPropertyBag::new();
+    if let Some(mime_type) = mime_type {
+        options.type_(mime_type);
+    }
+    let blob =
+        web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options).unwrap_throw();
+    Ok(Blob::from(blob))
+}
+
+#[derive(Debug)]
+pub enum CompressionError {
+    /// The format is not supported by the browser, or the API isn't at all.
+    Unsupported,
+    /// The data to decompress is corrupt, or not in the given format.
+    InvalidData(String),
+    Other(String),
+}
+
+impl CompressionError {
+    fn from_js(value: JsValue) -> Self {
+        match value.dyn_into::<js_sys::Error>() {
+            Ok(m) if m.is_instance_of::<js_sys::TypeError>() => {
+                CompressionError::InvalidData(m.message().into())
+            }
+            Ok(m) => CompressionError::Other(m.message().into()),
+            Err(value) => CompressionError::Other(
+                value
+                    .dyn_into::<web_sys::DomException>()
+                    .map(|m| m.message())
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for CompressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CompressionError::Unsupported => {
+                write!(f, "compression format is not supported by the browser")
+            }
+            CompressionError::InvalidData(msg) => write!(f, "cannot decompress data: {msg}"),
+            CompressionError::Other(msg) => write!(f, "compression failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for CompressionError {}
@@ -0,0 +1,157 @@
+use crate::{from_input, DirectoryHandle, File, FileList, FileSystemError, Handle, RelativeFile};
+use futures_core::Stream;
+use js_sys::Reflect;
+use std::{
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use wasm_bindgen::JsValue;
+
+type Entries = Pin<Box<dyn Stream<Item = Result<Handle, FileSystemError>>>>;
+type PendingFile = Pin<Box<dyn Future<Output = Result<File, FileSystemError>>>>;
+
+/// A directory the user picked, whose files can be listed recursively, e.g. for uploads of
+/// whole folders.
+///
+/// It is created either from the files of an `<input type="file" webkitdirectory>`, or from a
+/// [`DirectoryHandle`] of the File System Access API.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use futures_rs as futures;
+/// use futures::TryStreamExt;
+/// use gloo_file::Directory;
+///
+/// # async fn example(input: web_sys::HtmlInputElement) {
+/// let directory = Directory::from_input(&input);
+/// let files = directory.files().try_collect::<Vec<_>>().await.unwrap();
+/// for file in files {
+///     // Upload `file.file()` to `file.path()`...
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directory {
+    source: Source,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Source {
+    Files(Vec<File>),
+    Handle(DirectoryHandle),
+}
+
+impl Directory {
+    /// Creates a directory of the files selected with an `<input type="file" webkitdirectory>`.
+    pub fn from_input(input: &web_sys::HtmlInputElement) -> Self {
+        Self {
+            source: Source::Files(from_input(input).to_vec()),
+        }
+    }
+
+    /// Returns a stream of the files of this directory and of its subdirectories, with their
+    /// paths starting with the name of this directory, in no particular order.
+    pub fn files(&self) -> impl Stream<Item = Result<RelativeFile, FileSystemError>> {
+        match &self.source {
+            Source::Files(files) => DirectoryFiles {
+                ready: files
+                    .iter()
+                    .cloned()
+                    .map(input_file)
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+                stack: Vec::new(),
+                pending: None,
+            },
+            Source::Handle(handle) => DirectoryFiles {
+                ready: Vec::new().into_iter(),
+                stack: vec![(handle.name(), Box::pin(handle.entries()))],
+                pending: None,
+            },
+        }
+    }
+}
+
+impl From<FileList> for Directory {
+    fn from(files: FileList) -> Self {
+        Self {
+            source: Source::Files(files.to_vec()),
+        }
+    }
+}
+
+impl From<DirectoryHandle> for Directory {
+    fn from(handle: DirectoryHandle) -> Self {
+        Self {
+            source: Source::Handle(handle),
+        }
+    }
+}
+
+/// Pairs a file of an `<input webkitdirectory>` with its relative path.
+fn input_file(file: File) -> RelativeFile {
+    // `webkitRelativePath` is missing from `web-sys`, and empty for files picked on their own.
+    let raw: &web_sys::File = file.as_ref();
+    let path = Reflect::get(raw, &JsValue::from_str("webkitRelativePath"))
+        .ok()
+        .and_then(|m| m.as_string())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| file.name());
+    RelativeFile::new(path, file)
+}
+
+struct DirectoryFiles {
+    /// Files listed upfront, for directories from an input.
+    ready: std::vec::IntoIter<RelativeFile>,
+    /// The entries of the directories being listed, with their paths.
+    stack: Vec<(String, Entries)>,
+    /// The file being read, with its path.
+    pending: Option<(String, PendingFile)>,
+}
+
+impl Stream for DirectoryFiles {
+    type Item = Result<RelativeFile, FileSystemError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if let Some(file) = this.ready.next() {
+            return Poll::Ready(Some(Ok(file)));
+        }
+
+        loop {
+            if let Some((path, file)) = this.pending.as_mut() {
+                let result = match file.as_mut().poll(cx) {
+                    Poll::Ready(m) => m,
+                    Poll::Pending => return Poll::Pending,
+                };
+                let path = mem::take(path);
+                this.pending = None;
+
+                return Poll::Ready(Some(result.map(|file| RelativeFile::new(path, file))));
+            }
+
+            let (prefix, entries) = match this.stack.last_mut() {
+                Some(m) => m,
+                None => return Poll::Ready(None),
+            };
+            match entries.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(Handle::File(handle)))) => {
+                    let path = format!("{}/{}", prefix, handle.name());
+                    this.pending = Some((path, Box::pin(async move { handle.get_file().await })));
+                }
+                Poll::Ready(Some(Ok(Handle::Directory(handle)))) => {
+                    let path = format!("{}/{}", prefix, handle.name());
+                    this.stack.push((path, Box::pin(handle.entries())));
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => {
+                    this.stack.pop();
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{file_system::resolve, File, FileSystemError};
+use futures_channel::mpsc;
+use futures_core::Stream;
+use gloo_events::{EventListener, EventListenerOptions};
+use js_sys::{Array, Function, Promise};
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::spawn_local;
+
+/// A [`File`] with its path relative to the directory it was picked or dropped with, e.g.
+/// `photos/2023/beach.jpg`, or just its name if it was picked on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelativeFile {
+    path: String,
+    file: File,
+}
+
+impl RelativeFile {
+    pub(crate) fn new(path: String, file: File) -> Self {
+        Self { path, file }
+    }
+
+    /// The path of the file, with `/` separating its directories.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The file itself.
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns the file, dropping its path.
+    pub fn into_file(self) -> File {
+        self.file
+    }
+}
+
+/// Extracts the files dropped with a `drop` event, including the contents of dropped
+/// directories.
+///
+/// Browsers only give access to the dropped items during the `drop` event, so this must be
+/// called from its listener. The returned future can then be awaited at any time.
+///
+/// Directories are read with [`webkitGetAsEntry`], which is supported by all major browsers.
+///
+/// [`webkitGetAsEntry`]: https://developer.mozilla.org/en-US/docs/Web/API/DataTransferItem/webkitGetAsEntry
+pub fn dropped_files(
+    event: &web_sys::DragEvent,
+) -> impl Future<Output = Result<Vec<RelativeFile>, FileSystemError>> {
+    let mut entries = VecDeque::new();
+    let mut files = Vec::new();
+
+    if let Some(data) = event.data_transfer() {
+        let items = data.items();
+        for i in 0..items.length() {
+            let item = match items.get(i) {
+                Some(m) if m.kind() == "file" => m,
+                _ => continue,
+            };
+
+            match item.webkit_get_as_entry() {
+                Ok(Some(entry)) => entries.push_back(entry),
+                // Without entries, only the files themselves are available.
+                _ => {
+                    if let Ok(Some(file)) = item.get_as_file() {
+                        let file = File::from(file);
+                        files.push(RelativeFile::new(file.name(), file));
+                    }
+                }
+            }
+        }
+    }
+
+    async move {
+        while let Some(entry) = entries.pop_front() {
+            if entry.is_directory() {
+                let reader = entry
+                    .unchecked_into::<web_sys::FileSystemDirectoryEntry>()
+                    .create_reader();
+                // Entries are read in batches, until an empty one.
+                loop {
+                    let batch = read_entries(&reader).await?;
+                    if batch.length() == 0 {
+                        break;
+                    }
+                    entries.extend(batch.iter().map(JsCast::unchecked_into));
+                }
+            } else if entry.is_file() {
+                let path = entry.full_path();
+                let file = entry_file(entry.unchecked_ref()).await?;
+                files.push(RelativeFile::new(
+                    path.trim_start_matches('/').to_owned(),
+                    file,
+                ));
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+async fn read_entries(
+    reader: &web_sys::FileSystemDirectoryReader,
+) -> Result<Array, FileSystemError> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        if let Err(e) = reader.read_entries_with_callback_and_callback(&resolve, &reject) {
+            reject_with(&reject, &e);
+        }
+    });
+    Ok(resolve(promise).await?.unchecked_into())
+}
+
+async fn entry_file(entry: &web_sys::FileSystemFileEntry) -> Result<File, FileSystemError> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        entry.file_with_callback_and_callback(&resolve, &reject);
+    });
+    let file = resolve(promise).await?;
+    Ok(File::from(file.unchecked_into::<web_sys::File>()))
+}
+
+fn reject_with(reject: &Function, error: &JsValue) {
+    reject.call1(&JsValue::UNDEFINED, error).unwrap_throw();
+}
+
+/// An element files can be dropped on, which yields the files of each drop as a [`Stream`].
+///
+/// The browser's default handling of drops, which would open the dropped file in place of
+/// the page, is prevented on the element. The listeners are removed when the `DropZone` is
+/// dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use futures_rs as futures;
+/// use futures::StreamExt;
+/// use gloo_file::DropZone;
+///
+/// # async fn example(element: web_sys::Element) {
+/// let mut drops = DropZone::new(&element);
+/// while let Some(files) = drops.next().await {
+///     for file in files.unwrap() {
+///         // Upload `file.file()` to `file.path()`...
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DropZone {
+    receiver: mpsc::UnboundedReceiver<Result<Vec<RelativeFile>, FileSystemError>>,
+    _dragover_listener: EventListener,
+    _drop_listener: EventListener,
+}
+
+impl DropZone {
+    /// Starts accepting drops of files on `target`.
+    pub fn new(target: &web_sys::EventTarget) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+
+        // Elements only accept drops if `dragover` is cancelled.
+        let dragover_listener = EventListener::new_with_options(
+            target,
+            "dragover",
+            EventListenerOptions::enable_prevent_default(),
+            |event| event.prevent_default(),
+        );
+
+        let drop_listener = EventListener::new_with_options(
+            target,
+            "drop",
+            EventListenerOptions::enable_prevent_default(),
+            move |event| {
+                event.prevent_default();
+
+                let files = dropped_files(event.unchecked_ref());
+                let sender = sender.clone();
+                spawn_local(async move {
+                    let _ = sender.unbounded_send(files.await);
+                });
+            },
+        );
+
+        Self {
+            receiver,
+            _dragover_listener: dragover_listener,
+            _drop_listener: drop_listener,
+        }
+    }
+}
+
+impl Stream for DropZone {
+    type Item = Result<Vec<RelativeFile>, FileSystemError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
@@ -7,6 +7,15 @@ pub struct FileList {
     inner: Vec<File>,
 }
 
+/// Returns the files selected with an `<input type="file">`, which are empty if none were
+/// selected or `input` is not a file input.
+pub fn from_input(input: &web_sys::HtmlInputElement) -> FileList {
+    input
+        .files()
+        .map(FileList::from)
+        .unwrap_or(FileList { inner: Vec::new() })
+}
+
 impl From<web_sys::FileList> for FileList {
     fn from(raw: web_sys::FileList) -> Self {
         let length = raw.length();
@@ -1,9 +1,9 @@
-use wasm_bindgen::throw_str;
+use wasm_bindgen::{throw_str, UnwrapThrowExt};
 
 pub mod callbacks {
     use crate::{
         blob::Blob,
-        file_reader::{FileReadError, ReadyState},
+        file_reader::{decode, BomHandling, FileReadError, ReadyState},
     };
     use gloo_events::EventListener;
     use std::{cell::RefCell, rc::Rc};
@@ -42,6 +42,30 @@ pub mod callbacks {
         )
     }
 
+    /// Asynchronously converts `blob` into a text string decoded with `encoding`, e.g.
+    /// `"shift_jis"` or `"windows-1252"`, and then passes it to the `callback`.
+    ///
+    /// See the [Encoding Standard](https://encoding.spec.whatwg.org/#names-and-labels) for the
+    /// supported labels, and [`BomHandling`] for how a byte order mark is handled. An unknown
+    /// label is reported as [`FileReadError::UnknownEncoding`].
+    ///
+    /// If the returned `FileReader` is dropped before the callback is called, the read will be
+    /// cancelled.
+    pub fn read_as_text_with_encoding<F>(
+        blob: &Blob,
+        encoding: &str,
+        bom: BomHandling,
+        callback: F,
+    ) -> FileReader
+    where
+        F: FnOnce(Result<String, FileReadError>) + 'static,
+    {
+        let encoding = encoding.to_owned();
+        read_as_array_buffer(blob, move |result| {
+            callback(result.and_then(|buffer| decode(&buffer, &encoding, bom)));
+        })
+    }
+
     /// Asynchronously converts the `blob` into a base64 encoded `data:` URL and then passes it to
     /// the `callback`.
     ///
@@ -143,8 +167,13 @@ pub mod callbacks {
 
 #[cfg(feature = "futures")]
 pub mod futures {
-    use crate::{Blob, FileReadError};
-    use std::future::Future;
+    use crate::{Blob, BomHandling, FileReadError};
+    use futures_core::Stream;
+    use std::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
     use wasm_bindgen::UnwrapThrowExt;
 
     /// Returns the contents of `blob` as a text string.
@@ -164,6 +193,28 @@ pub mod futures {
         }
     }
 
+    /// Returns the contents of `blob` as a text string decoded with `encoding`, e.g.
+    /// `"shift_jis"` or `"windows-1252"`.
+    ///
+    /// See [`callbacks::read_as_text_with_encoding`](super::callbacks::read_as_text_with_encoding)
+    /// for details.
+    pub fn read_as_text_with_encoding(
+        blob: &Blob,
+        encoding: &str,
+        bom: BomHandling,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        let (sender, receiver) = futures_channel::oneshot::channel();
+        let reader = super::callbacks::read_as_text_with_encoding(blob, encoding, bom, |result| {
+            sender.send(result).unwrap_throw();
+        });
+
+        async move {
+            let output = receiver.await.unwrap_throw();
+            drop(reader);
+            output
+        }
+    }
+
     /// Returns the contents of `blob` as a base64 encoded `data:` URL.
     ///
     /// Equivalent to `async fn read_as_data_url(blob: &Blob) -> Result<String, FileReadError>` but
@@ -218,6 +269,116 @@ pub mod futures {
             output
         }
     }
+
+    /// Returns the contents of `blob` as a stream of `Vec<u8>` chunks of `chunk_size` bytes, the
+    /// last one being possibly shorter.
+    ///
+    /// Unlike [`read_as_bytes`], only one chunk is held in memory at a time, so large files can
+    /// be hashed or parsed incrementally. Chunks are read one after another as the stream is
+    /// polled, and the stream ends after the first error.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `chunk_size` is 0.
+    pub fn read_as_stream(
+        blob: &Blob,
+        chunk_size: u64,
+    ) -> impl Stream<Item = Result<Vec<u8>, FileReadError>> {
+        assert!(chunk_size > 0, "chunk_size must be greater than 0");
+
+        ReadStream {
+            blob: blob.clone(),
+            chunk_size,
+            offset: 0,
+            pending: None,
+        }
+    }
+
+    type ReadFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>, FileReadError>>>>;
+
+    struct ReadStream {
+        blob: Blob,
+        chunk_size: u64,
+        /// The start of the next chunk to read.
+        offset: u64,
+        pending: Option<ReadFuture>,
+    }
+
+    impl Stream for ReadStream {
+        type Item = Result<Vec<u8>, FileReadError>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let size = self.blob.size();
+
+            if self.pending.is_none() {
+                if self.offset >= size {
+                    return Poll::Ready(None);
+                }
+
+                let end = size.min(self.offset + self.chunk_size);
+                let chunk = self.blob.slice(self.offset, end);
+                self.pending = Some(Box::pin(read_as_bytes(&chunk)));
+                self.offset = end;
+            }
+
+            let result = match self.pending.as_mut().map(|m| m.as_mut().poll(cx)) {
+                Some(Poll::Ready(result)) => result,
+                _ => return Poll::Pending,
+            };
+            self.pending = None;
+
+            if result.is_err() {
+                self.offset = size;
+            }
+
+            Poll::Ready(Some(result))
+        }
+    }
+}
+
+/// How a byte order mark (BOM) at the start of a blob is handled when it is decoded as text
+/// with a given encoding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BomHandling {
+    /// A BOM of UTF-8 or UTF-16 takes precedence over the given encoding, like with
+    /// `FileReader.readAsText()`. This suits files of unknown origin, which may have been saved
+    /// as Unicode.
+    #[default]
+    Sniff,
+    /// The given encoding is always used, and only a BOM of that encoding is removed.
+    Strip,
+}
+
+/// Decodes the contents of `buffer` with the encoding of `label`, or the one of its BOM.
+fn decode(
+    buffer: &js_sys::ArrayBuffer,
+    label: &str,
+    bom: BomHandling,
+) -> Result<String, FileReadError> {
+    let bytes = js_sys::Uint8Array::new(buffer);
+    let label = match bom {
+        BomHandling::Sniff => sniff_bom(&bytes).unwrap_or(label),
+        BomHandling::Strip => label,
+    };
+
+    let decoder = web_sys::TextDecoder::new_with_label(label)
+        .map_err(|_| FileReadError::UnknownEncoding(label.to_owned()))?;
+    // Decoding doesn't fail unless the decoder is fatal.
+    Ok(decoder.decode_with_buffer_source(&bytes).unwrap_throw())
+}
+
+/// Returns the encoding of the byte order mark `bytes` start with, if any.
+fn sniff_bom(bytes: &js_sys::Uint8Array) -> Option<&'static str> {
+    let mut head = [0; 3];
+    let len = bytes.length().min(3);
+    bytes.subarray(0, len).copy_to(&mut head[..len as usize]);
+
+    match head {
+        [0xEF, 0xBB, 0xBF] => Some("utf-8"),
+        [0xFE, 0xFF, _] => Some("utf-16be"),
+        [0xFF, 0xFE, _] => Some("utf-16le"),
+        _ => None,
+    }
 }
 
 enum ReadyState {
@@ -249,6 +410,8 @@ pub enum FileReadError {
     NotFound(String),
     NotReadable(String),
     Security(String),
+    /// The encoding label to decode text with is not known.
+    UnknownEncoding(String),
 }
 
 impl std::fmt::Display for FileReadError {
@@ -262,6 +425,12 @@ impl std::fmt::Display for FileReadError {
             FileReadError::Security(msg) => {
                 write!(f, "FileReader encountered a security exception: {msg}")
             }
+            FileReadError::UnknownEncoding(label) => {
+                write!(
+                    f,
+                    "FileReader cannot decode text with unknown encoding: {label}"
+                )
+            }
         }
     }
 }
@@ -0,0 +1,617 @@
+use crate::{Blob, BlobContents, File, SyncAccessHandle};
+use futures_core::Stream;
+use js_sys::{Array, AsyncIterator, Object, Promise, Reflect};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch, js_name = showOpenFilePicker)]
+    fn show_open_file_picker_raw(options: &Object) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(catch, js_name = showSaveFilePicker)]
+    fn show_save_file_picker_raw(options: &Object) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(catch, js_name = showDirectoryPicker)]
+    fn show_directory_picker_raw(options: &Object) -> Result<Promise, JsValue>;
+
+    // The permission methods of `FileSystemHandle` are unstable in `web-sys`.
+    type PermissionHandle;
+
+    #[wasm_bindgen(method, catch, js_name = queryPermission)]
+    fn query_permission(this: &PermissionHandle, descriptor: &Object) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = requestPermission)]
+    fn request_permission(this: &PermissionHandle, descriptor: &Object)
+        -> Result<Promise, JsValue>;
+
+    // `FileSystemDirectoryHandle` is async iterable, which `web-sys` doesn't expose.
+    type IterableDirectory;
+
+    #[wasm_bindgen(method)]
+    fn values(this: &IterableDirectory) -> AsyncIterator;
+}
+
+/// A file type the file pickers offer to choose from, see [`FilePickerOptions::accept`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AcceptType {
+    description: String,
+    mime_type: String,
+    extensions: Vec<String>,
+}
+
+/// Options of [`show_open_file_picker`] and [`show_save_file_picker`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use gloo_file::{show_open_file_picker, FilePickerOptions};
+///
+/// # async fn example() {
+/// let options = FilePickerOptions::new()
+///     .multiple(true)
+///     .accept("Images", "image/*", &[".png", ".jpg"]);
+/// let handles = show_open_file_picker(&options).await.unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilePickerOptions {
+    multiple: bool,
+    exclude_accept_all_option: bool,
+    suggested_name: Option<String>,
+    types: Vec<AcceptType>,
+}
+
+impl FilePickerOptions {
+    /// Creates options that let the user pick a single file of any type.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the user can pick several files, only used by [`show_open_file_picker`].
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Adds a type of files the user can filter by, e.g. `accept("Images", "image/*", &[".png"])`.
+    pub fn accept(mut self, description: &str, mime_type: &str, extensions: &[&str]) -> Self {
+        self.types.push(AcceptType {
+            description: description.to_owned(),
+            mime_type: mime_type.to_owned(),
+            extensions: extensions.iter().map(|m| (*m).to_owned()).collect(),
+        });
+        self
+    }
+
+    /// Sets whether the user is prevented from picking files of other types than the
+    /// [`accept`](Self::accept)ed ones.
+    pub fn exclude_accept_all_option(mut self, exclude: bool) -> Self {
+        self.exclude_accept_all_option = exclude;
+        self
+    }
+
+    /// Sets the file name the picker suggests, only used by [`show_save_file_picker`].
+    pub fn suggested_name(mut self, name: &str) -> Self {
+        self.suggested_name = Some(name.to_owned());
+        self
+    }
+
+    fn to_js(&self) -> Object {
+        let options = Object::new();
+        set(&options, "multiple", &self.multiple.into());
+        set(
+            &options,
+            "excludeAcceptAllOption",
+            &self.exclude_accept_all_option.into(),
+        );
+        if let Some(name) = &self.suggested_name {
+            set(&options, "suggestedName", &name.into());
+        }
+
+        let types = Array::new();
+        for ty in &self.types {
+            let extensions = ty
+                .extensions
+                .iter()
+                .map(|m| JsValue::from_str(m))
+                .collect::<Array>();
+            let accept = Object::new();
+            set(&accept, &ty.mime_type, &extensions);
+
+            let js_ty = Object::new();
+            set(&js_ty, "description", &ty.description.as_str().into());
+            set(&js_ty, "accept", &accept);
+            types.push(&js_ty);
+        }
+        set(&options, "types", &types);
+
+        options
+    }
+}
+
+/// Lets the user pick files to open with [`showOpenFilePicker`], and returns their handles.
+///
+/// Must be called in response to a user action, e.g. a click.
+///
+/// [`showOpenFilePicker`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/showOpenFilePicker
+pub async fn show_open_file_picker(
+    options: &FilePickerOptions,
+) -> Result<Vec<FileHandle>, FileSystemError> {
+    ensure_supported("showOpenFilePicker")?;
+    let promise = show_open_file_picker_raw(&options.to_js()).map_err(FileSystemError::from)?;
+    let handles = resolve(promise).await?.unchecked_into::<Array>();
+
+    Ok(handles
+        .iter()
+        .map(|m| FileHandle::from(m.unchecked_into::<web_sys::FileSystemFileHandle>()))
+        .collect())
+}
+
+/// Lets the user pick a file to save to with [`showSaveFilePicker`], and returns its handle.
+///
+/// Must be called in response to a user action, e.g. a click.
+///
+/// [`showSaveFilePicker`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/showSaveFilePicker
+pub async fn show_save_file_picker(
+    options: &FilePickerOptions,
+) -> Result<FileHandle, FileSystemError> {
+    ensure_supported("showSaveFilePicker")?;
+    let promise = show_save_file_picker_raw(&options.to_js()).map_err(FileSystemError::from)?;
+    Ok(FileHandle::from(
+        resolve(promise)
+            .await?
+            .unchecked_into::<web_sys::FileSystemFileHandle>(),
+    ))
+}
+
+/// Lets the user pick a directory with [`showDirectoryPicker`], and returns its handle.
+///
+/// Must be called in response to a user action, e.g. a click.
+///
+/// [`showDirectoryPicker`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/showDirectoryPicker
+pub async fn show_directory_picker(
+    mode: PermissionMode,
+) -> Result<DirectoryHandle, FileSystemError> {
+    ensure_supported("showDirectoryPicker")?;
+    let options = Object::new();
+    set(&options, "mode", &mode.as_str().into());
+
+    let promise = show_directory_picker_raw(&options).map_err(FileSystemError::from)?;
+    Ok(DirectoryHandle::from(
+        resolve(promise)
+            .await?
+            .unchecked_into::<web_sys::FileSystemDirectoryHandle>(),
+    ))
+}
+
+/// The access a handle is granted to its file or directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionMode {
+    /// Reading only.
+    Read,
+    /// Reading and writing.
+    ReadWrite,
+}
+
+impl PermissionMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            PermissionMode::Read => "read",
+            PermissionMode::ReadWrite => "readwrite",
+        }
+    }
+}
+
+/// The state of a permission of a handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionState {
+    Granted,
+    Denied,
+    /// The user will be asked on [`request_permission`](FileHandle::request_permission).
+    Prompt,
+}
+
+impl PermissionState {
+    fn from_js(value: JsValue) -> Self {
+        match value.as_string().as_deref() {
+            Some("granted") => PermissionState::Granted,
+            Some("denied") => PermissionState::Denied,
+            _ => PermissionState::Prompt,
+        }
+    }
+}
+
+/// A handle to a file of the file system of the user, or of the origin private file system.
+///
+/// See [`FileSystemFileHandle`](https://developer.mozilla.org/en-US/docs/Web/API/FileSystemFileHandle).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHandle {
+    inner: web_sys::FileSystemFileHandle,
+}
+
+impl FileHandle {
+    /// Returns the name of the file.
+    pub fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    /// Returns a snapshot of the contents of the file, which can be read with the functions of
+    /// [`futures`](crate::futures).
+    pub async fn get_file(&self) -> Result<File, FileSystemError> {
+        let file = resolve(self.inner.get_file()).await?;
+        Ok(File::from(file.unchecked_into::<web_sys::File>()))
+    }
+
+    /// Replaces the contents of the file with `contents`.
+    ///
+    /// The file is only changed once all of `contents` is written.
+    pub async fn write_all<T>(&self, contents: T) -> Result<(), FileSystemError>
+    where
+        T: BlobContents,
+    {
+        let blob = web_sys::Blob::from(Blob::new(contents));
+
+        let stream = resolve(self.inner.create_writable())
+            .await?
+            .unchecked_into::<web_sys::FileSystemWritableFileStream>();
+        resolve(stream.write_with_blob(&blob)?).await?;
+        resolve(stream.close()).await?;
+
+        Ok(())
+    }
+
+    /// Opens the file for synchronous reads and writes, see [`SyncAccessHandle`].
+    ///
+    /// Only available for files of the origin private file system, in a dedicated worker.
+    pub async fn create_sync_access_handle(&self) -> Result<SyncAccessHandle, FileSystemError> {
+        let handle = resolve(self.inner.create_sync_access_handle()).await?;
+        Ok(SyncAccessHandle::from(
+            handle.unchecked_into::<web_sys::FileSystemSyncAccessHandle>(),
+        ))
+    }
+
+    /// Returns the state of the `mode` permission of this handle.
+    pub async fn query_permission(
+        &self,
+        mode: PermissionMode,
+    ) -> Result<PermissionState, FileSystemError> {
+        query_permission(&self.inner, mode).await
+    }
+
+    /// Asks the user for the `mode` permission of this handle, if it is not granted yet.
+    ///
+    /// Must be called in response to a user action, e.g. a click.
+    pub async fn request_permission(
+        &self,
+        mode: PermissionMode,
+    ) -> Result<PermissionState, FileSystemError> {
+        request_permission(&self.inner, mode).await
+    }
+}
+
+impl From<web_sys::FileSystemFileHandle> for FileHandle {
+    fn from(inner: web_sys::FileSystemFileHandle) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<FileHandle> for web_sys::FileSystemFileHandle {
+    fn from(handle: FileHandle) -> Self {
+        handle.inner
+    }
+}
+
+impl AsRef<web_sys::FileSystemFileHandle> for FileHandle {
+    fn as_ref(&self) -> &web_sys::FileSystemFileHandle {
+        &self.inner
+    }
+}
+
+/// A handle to a directory of the file system of the user, or of the origin private file
+/// system.
+///
+/// See [`FileSystemDirectoryHandle`](https://developer.mozilla.org/en-US/docs/Web/API/FileSystemDirectoryHandle).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryHandle {
+    inner: web_sys::FileSystemDirectoryHandle,
+}
+
+impl DirectoryHandle {
+    /// Returns the name of the directory.
+    pub fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    /// Returns the handle of the file `name` in this directory, creating it first if `create`
+    /// is true.
+    pub async fn get_file_handle(
+        &self,
+        name: &str,
+        create: bool,
+    ) -> Result<FileHandle, FileSystemError> {
+        let mut options = web_sys::FileSystemGetFileOptions::new();
+        options.create(create);
+
+        let handle = resolve(self.inner.get_file_handle_with_options(name, &options)).await?;
+        Ok(FileHandle::from(
+            handle.unchecked_into::<web_sys::FileSystemFileHandle>(),
+        ))
+    }
+
+    /// Returns the handle of the directory `name` in this directory, creating it first if
+    /// `create` is true.
+    pub async fn get_directory_handle(
+        &self,
+        name: &str,
+        create: bool,
+    ) -> Result<DirectoryHandle, FileSystemError> {
+        let mut options = web_sys::FileSystemGetDirectoryOptions::new();
+        options.create(create);
+
+        let handle = resolve(self.inner.get_directory_handle_with_options(name, &options)).await?;
+        Ok(DirectoryHandle::from(
+            handle.unchecked_into::<web_sys::FileSystemDirectoryHandle>(),
+        ))
+    }
+
+    /// Removes the file or directory `name` from this directory, along with its contents if
+    /// `recursive` is true.
+    pub async fn remove_entry(&self, name: &str, recursive: bool) -> Result<(), FileSystemError> {
+        let mut options = web_sys::FileSystemRemoveOptions::new();
+        options.recursive(recursive);
+
+        resolve(self.inner.remove_entry_with_options(name, &options)).await?;
+        Ok(())
+    }
+
+    /// Returns a stream of the files and directories in this directory, in no particular order.
+    pub fn entries(&self) -> impl Stream<Item = Result<Handle, FileSystemError>> {
+        DirectoryEntries {
+            iter: self.inner.unchecked_ref::<IterableDirectory>().values(),
+            pending: None,
+            done: false,
+        }
+    }
+
+    /// Returns the state of the `mode` permission of this handle.
+    pub async fn query_permission(
+        &self,
+        mode: PermissionMode,
+    ) -> Result<PermissionState, FileSystemError> {
+        query_permission(&self.inner, mode).await
+    }
+
+    /// Asks the user for the `mode` permission of this handle, if it is not granted yet.
+    ///
+    /// Must be called in response to a user action, e.g. a click.
+    pub async fn request_permission(
+        &self,
+        mode: PermissionMode,
+    ) -> Result<PermissionState, FileSystemError> {
+        request_permission(&self.inner, mode).await
+    }
+}
+
+impl From<web_sys::FileSystemDirectoryHandle> for DirectoryHandle {
+    fn from(inner: web_sys::FileSystemDirectoryHandle) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<DirectoryHandle> for web_sys::FileSystemDirectoryHandle {
+    fn from(handle: DirectoryHandle) -> Self {
+        handle.inner
+    }
+}
+
+impl AsRef<web_sys::FileSystemDirectoryHandle> for DirectoryHandle {
+    fn as_ref(&self) -> &web_sys::FileSystemDirectoryHandle {
+        &self.inner
+    }
+}
+
+/// A handle to an entry of a directory, see [`DirectoryHandle::entries`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Handle {
+    File(FileHandle),
+    Directory(DirectoryHandle),
+}
+
+impl Handle {
+    /// Returns the name of the file or directory.
+    pub fn name(&self) -> String {
+        match self {
+            Handle::File(m) => m.name(),
+            Handle::Directory(m) => m.name(),
+        }
+    }
+}
+
+impl From<web_sys::FileSystemHandle> for Handle {
+    fn from(handle: web_sys::FileSystemHandle) -> Self {
+        match handle.kind() {
+            web_sys::FileSystemHandleKind::Directory => Handle::Directory(DirectoryHandle::from(
+                handle.unchecked_into::<web_sys::FileSystemDirectoryHandle>(),
+            )),
+            _ => Handle::File(FileHandle::from(
+                handle.unchecked_into::<web_sys::FileSystemFileHandle>(),
+            )),
+        }
+    }
+}
+
+struct DirectoryEntries {
+    iter: AsyncIterator,
+    pending: Option<JsFuture>,
+    done: bool,
+}
+
+impl Stream for DirectoryEntries {
+    type Item = Result<Handle, FileSystemError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        if self.pending.is_none() {
+            match self.iter.next() {
+                Ok(promise) => self.pending = Some(JsFuture::from(promise)),
+                Err(e) => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(e.into())));
+                }
+            }
+        }
+
+        let result = match self.pending.as_mut().map(|m| Pin::new(m).poll(cx)) {
+            Some(Poll::Ready(result)) => result,
+            _ => return Poll::Pending,
+        };
+        self.pending = None;
+
+        let result = match result {
+            Ok(m) => m,
+            Err(e) => {
+                self.done = true;
+                return Poll::Ready(Some(Err(e.into())));
+            }
+        };
+
+        let done = Reflect::get(&result, &JsValue::from_str("done")).unwrap_throw();
+        if done.as_bool().unwrap_or(false) {
+            self.done = true;
+            return Poll::Ready(None);
+        }
+
+        let value = Reflect::get(&result, &JsValue::from_str("value")).unwrap_throw();
+        Poll::Ready(Some(Ok(Handle::from(
+            value.unchecked_into::<web_sys::FileSystemHandle>(),
+        ))))
+    }
+}
+
+async fn query_permission(
+    handle: &web_sys::FileSystemHandle,
+    mode: PermissionMode,
+) -> Result<PermissionState, FileSystemError> {
+    let handle = handle.unchecked_ref::<PermissionHandle>();
+    let promise = handle.query_permission(&permission_descriptor(mode))?;
+    Ok(PermissionState::from_js(resolve(promise).await?))
+}
+
+async fn request_permission(
+    handle: &web_sys::FileSystemHandle,
+    mode: PermissionMode,
+) -> Result<PermissionState, FileSystemError> {
+    let handle = handle.unchecked_ref::<PermissionHandle>();
+    let promise = handle.request_permission(&permission_descriptor(mode))?;
+    Ok(PermissionState::from_js(resolve(promise).await?))
+}
+
+fn permission_descriptor(mode: PermissionMode) -> Object {
+    let descriptor = Object::new();
+    set(&descriptor, "mode", &mode.as_str().into());
+    descriptor
+}
+
+/// Returns [`FileSystemError::Unsupported`] if the global function `name` is missing.
+fn ensure_supported(name: &str) -> Result<(), FileSystemError> {
+    match Reflect::has(&js_sys::global(), &JsValue::from_str(name)) {
+        Ok(true) => Ok(()),
+        _ => Err(FileSystemError::Unsupported),
+    }
+}
+
+fn set(target: &Object, key: &str, value: &JsValue) {
+    Reflect::set(target, &JsValue::from_str(key), value).unwrap_throw();
+}
+
+pub(crate) async fn resolve(promise: Promise) -> Result<JsValue, FileSystemError> {
+    JsFuture::from(promise).await.map_err(FileSystemError::from)
+}
+
+#[derive(Debug)]
+pub enum FileSystemError {
+    /// The user dismissed a picker, or the operation was aborted.
+    Aborted,
+    /// The API is not supported by the browser.
+    Unsupported,
+    NotAllowed(String),
+    NotFound(String),
+    TypeMismatch(String),
+    NoModificationAllowed(String),
+    InvalidModification(String),
+    QuotaExceeded(String),
+    Security(String),
+    Other(String),
+}
+
+impl From<JsValue> for FileSystemError {
+    fn from(value: JsValue) -> Self {
+        let exception = match value.dyn_into::<web_sys::DomException>() {
+            Ok(m) => m,
+            Err(value) => {
+                return FileSystemError::Other(
+                    value
+                        .dyn_into::<js_sys::Error>()
+                        .map(|m| String::from(m.message()))
+                        .unwrap_or_default(),
+                )
+            }
+        };
+
+        let message = exception.message();
+        match exception.name().as_str() {
+            "AbortError" => FileSystemError::Aborted,
+            "NotAllowedError" => FileSystemError::NotAllowed(message),
+            "NotFoundError" => FileSystemError::NotFound(message),
+            "TypeMismatchError" => FileSystemError::TypeMismatch(message),
+            "NoModificationAllowedError" => FileSystemError::NoModificationAllowed(message),
+            "InvalidModificationError" => FileSystemError::InvalidModification(message),
+            "QuotaExceededError" => FileSystemError::QuotaExceeded(message),
+            "SecurityError" => FileSystemError::Security(message),
+            _ => FileSystemError::Other(message),
+        }
+    }
+}
+
+impl std::fmt::Display for FileSystemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FileSystemError::Aborted => write!(f, "file system operation aborted"),
+            FileSystemError::Unsupported => {
+                write!(f, "file system API is not supported by the browser")
+            }
+            FileSystemError::NotAllowed(msg) => write!(f, "file system access not allowed: {msg}"),
+            FileSystemError::NotFound(msg) => write!(f, "file system entry not found: {msg}"),
+            FileSystemError::TypeMismatch(msg) => {
+                write!(f, "file system entry is of the wrong kind: {msg}")
+            }
+            FileSystemError::NoModificationAllowed(msg) => {
+                write!(f, "file system entry can't be modified: {msg}")
+            }
+            FileSystemError::InvalidModification(msg) => {
+                write!(f, "invalid file system modification: {msg}")
+            }
+            FileSystemError::QuotaExceeded(msg) => {
+                write!(f, "file system quota exceeded: {msg}")
+            }
+            FileSystemError::Security(msg) => {
+                write!(
+                    f,
+                    "file system operation encountered a security exception: {msg}"
+                )
+            }
+            FileSystemError::Other(msg) => write!(f, "file system operation failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for FileSystemError {}
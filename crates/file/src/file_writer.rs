@@ -0,0 +1,169 @@
+use crate::{FileHandle, FileSystemError};
+use futures_sink::Sink;
+use js_sys::{Promise, Uint8Array};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+/// A writer of the contents of a file of the file system, created with
+/// [`FileHandle::create_writer`].
+///
+/// Chunks of bytes are written one after another, either with [`write`](Self::write) or
+/// through the [`Sink`] implementation, so large outputs don't need to be held in memory as a
+/// whole. The changes are only applied to the file once the writer is
+/// [`close`](Self::close)d: dropping it before discards them.
+///
+/// See [`FileSystemWritableFileStream`](https://developer.mozilla.org/en-US/docs/Web/API/FileSystemWritableFileStream).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use gloo_file::FileHandle;
+///
+/// # async fn example(handle: FileHandle) {
+/// let mut writer = handle.create_writer(false).await.unwrap();
+/// for i in 0..1000 {
+///     writer.write(format!("line {i}\n").as_bytes()).await.unwrap();
+/// }
+/// writer.close().await.unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FileWriter {
+    inner: web_sys::FileSystemWritableFileStream,
+    /// The last operation, which must complete before the next one starts.
+    pending: Option<JsFuture>,
+    closing: bool,
+    closed: bool,
+}
+
+impl FileHandle {
+    /// Opens the file for writing with a [`FileWriter`].
+    ///
+    /// The writer starts at the beginning of the file, which is empty unless
+    /// `keep_existing_data` is true.
+    pub async fn create_writer(
+        &self,
+        keep_existing_data: bool,
+    ) -> Result<FileWriter, FileSystemError> {
+        let mut options = web_sys::FileSystemCreateWritableOptions::new();
+        options.keep_existing_data(keep_existing_data);
+
+        let inner = JsFuture::from(self.as_ref().create_writable_with_options(&options))
+            .await?
+            .unchecked_into::<web_sys::FileSystemWritableFileStream>();
+
+        Ok(FileWriter {
+            inner,
+            pending: None,
+            closing: false,
+            closed: false,
+        })
+    }
+}
+
+impl FileWriter {
+    /// Writes `data` at the current position, and moves the position past it.
+    pub async fn write(&mut self, data: &[u8]) -> Result<(), FileSystemError> {
+        // The data is copied, as the write completes asynchronously.
+        let data = Uint8Array::from(data);
+        self.run(self.inner.write_with_buffer_source(&data)?).await
+    }
+
+    /// Moves the current position to `position` bytes from the beginning of the file.
+    pub async fn seek(&mut self, position: u64) -> Result<(), FileSystemError> {
+        self.run(self.inner.seek_with_f64(position as f64)?).await
+    }
+
+    /// Resizes the file to `size` bytes, padding it with zeros or cutting its end.
+    pub async fn truncate(&mut self, size: u64) -> Result<(), FileSystemError> {
+        self.run(self.inner.truncate_with_f64(size as f64)?).await
+    }
+
+    /// Applies the changes to the file, and closes the writer.
+    pub async fn close(mut self) -> Result<(), FileSystemError> {
+        self.closing = true;
+        let result = self.run(self.inner.close()).await;
+        self.closed = true;
+        result
+    }
+
+    /// Waits for the pending operation, then for `promise`.
+    async fn run(&mut self, promise: Promise) -> Result<(), FileSystemError> {
+        if let Some(pending) = self.pending.take() {
+            pending.await?;
+        }
+        JsFuture::from(promise).await?;
+        Ok(())
+    }
+
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), FileSystemError>> {
+        let result = match self.pending.as_mut() {
+            Some(pending) => match Pin::new(pending).poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => return Poll::Pending,
+            },
+            None => return Poll::Ready(Ok(())),
+        };
+        self.pending = None;
+
+        Poll::Ready(result.map(|_| ()).map_err(FileSystemError::from))
+    }
+}
+
+impl Sink<Vec<u8>> for FileWriter {
+    type Error = FileSystemError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().poll_pending(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Vec<u8>) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        let data = Uint8Array::from(&item[..]);
+        let promise = this.inner.write_with_buffer_source(&data)?;
+        this.pending = Some(JsFuture::from(promise));
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().poll_pending(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        if this.closed {
+            return Poll::Ready(Ok(()));
+        }
+
+        if !this.closing {
+            match this.poll_pending(cx) {
+                Poll::Ready(Ok(())) => {}
+                other => return other,
+            }
+            this.closing = true;
+            this.pending = Some(JsFuture::from(this.inner.close()));
+        }
+
+        let result = match this.poll_pending(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        this.closed = true;
+
+        Poll::Ready(result)
+    }
+}
+
+impl Drop for FileWriter {
+    fn drop(&mut self) {
+        // Aborting discards the changes and unlocks the file.
+        if !self.closing {
+            let _ = self.inner.abort();
+        }
+    }
+}
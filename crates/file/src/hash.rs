@@ -0,0 +1,114 @@
+use crate::{futures::read_as_stream, Blob, FileReadError};
+use futures_core::Stream;
+use sha2::digest::DynDigest;
+use std::{future::Future, pin::Pin};
+
+/// The size of the chunks blobs are read and hashed in, between reports of progress.
+const CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// A hash algorithm of [`hash`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// SHA-1, which is not secure against collisions, but still common for deduplication.
+    Sha1,
+    /// SHA-256, with a 32-byte digest.
+    Sha256,
+    /// SHA-384, with a 48-byte digest.
+    Sha384,
+    /// SHA-512, with a 64-byte digest.
+    Sha512,
+}
+
+impl HashAlgorithm {
+    fn hasher(self) -> Box<dyn DynDigest> {
+        match self {
+            HashAlgorithm::Sha1 => Box::new(sha1::Sha1::default()),
+            HashAlgorithm::Sha256 => Box::new(sha2::Sha256::default()),
+            HashAlgorithm::Sha384 => Box::new(sha2::Sha384::default()),
+            HashAlgorithm::Sha512 => Box::new(sha2::Sha512::default()),
+        }
+    }
+}
+
+/// Returns the digest of the contents of `blob` with `algorithm`, e.g. to check its integrity
+/// or skip uploading duplicates.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use gloo_file::{Blob, HashAlgorithm};
+///
+/// # async fn example() {
+/// let blob = Blob::new("hello world");
+/// let digest = gloo_file::hash(&blob, HashAlgorithm::Sha256).await.unwrap();
+/// assert_eq!(digest.len(), 32);
+/// # }
+/// ```
+pub async fn hash(blob: &Blob, algorithm: HashAlgorithm) -> Result<Vec<u8>, HashError> {
+    hash_with_progress(blob, algorithm, |_, _| {}).await
+}
+
+/// Same as [`hash`], but calls `progress` with the number of bytes read so far and the size of
+/// the blob after each chunk is read, e.g. to show a progress bar for large files.
+///
+/// The blob is read and hashed in chunks of 1 MiB, so only one chunk is held in memory at a
+/// time, whatever the size of the blob. `SubtleCrypto` can't hash data incrementally, so the
+/// hashing is done in Rust.
+pub async fn hash_with_progress<F>(
+    blob: &Blob,
+    algorithm: HashAlgorithm,
+    mut progress: F,
+) -> Result<Vec<u8>, HashError>
+where
+    F: FnMut(u64, u64),
+{
+    let size = blob.size();
+    let mut hasher = algorithm.hasher();
+    let mut read = 0;
+    let mut chunks = Box::pin(read_as_stream(blob, CHUNK_SIZE));
+    while let Some(chunk) = next(&mut chunks).await {
+        let chunk = chunk?;
+        hasher.update(&chunk);
+        read += chunk.len() as u64;
+        progress(read, size);
+    }
+    Ok(hasher.finalize().into_vec())
+}
+
+/// Polls the next item of `stream`, without depending on `futures-util`.
+fn next<S>(stream: &mut Pin<Box<S>>) -> impl Future<Output = Option<S::Item>> + '_
+where
+    S: Stream + ?Sized,
+{
+    std::future::poll_fn(move |cx| stream.as_mut().poll_next(cx))
+}
+
+/// An error returned by [`hash`] and [`hash_with_progress`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum HashError {
+    /// The blob could not be read.
+    Read(FileReadError),
+}
+
+impl From<FileReadError> for HashError {
+    fn from(error: FileReadError) -> Self {
+        HashError::Read(error)
+    }
+}
+
+impl std::fmt::Display for HashError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HashError::Read(e) => write!(f, "cannot read blob to hash: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for HashError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HashError::Read(e) => Some(e),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Decoding of images, e.g. to make thumbnails of picked files without `<img>` elements.
+//!
+//! Images are decoded with [`createImageBitmap`], which works in windows and workers alike.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use gloo_file::{image, Blob};
+//!
+//! # async fn example(blob: Blob) {
+//! let options = image::DecodeOptions::new().width(128);
+//! let thumbnail = image::decode_with_options(&blob, &options).await.unwrap();
+//! let pixels = image::to_rgba(&thumbnail).unwrap();
+//! # }
+//! ```
+//!
+//! [`createImageBitmap`]: https://developer.mozilla.org/en-US/docs/Web/API/createImageBitmap
+
+use crate::Blob;
+use js_sys::Promise;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+pub use web_sys::ImageBitmap;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch, js_name = createImageBitmap)]
+    fn create_image_bitmap(
+        image: &web_sys::Blob,
+        options: &web_sys::ImageBitmapOptions,
+    ) -> Result<Promise, JsValue>;
+
+    // `OffscreenCanvasRenderingContext2D` is unstable in `web-sys`.
+    type Context2d;
+
+    #[wasm_bindgen(method, catch, js_name = drawImage)]
+    fn draw_image(this: &Context2d, image: &ImageBitmap, dx: f64, dy: f64) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = getImageData)]
+    fn get_image_data(
+        this: &Context2d,
+        sx: f64,
+        sy: f64,
+        sw: f64,
+        sh: f64,
+    ) -> Result<web_sys::ImageData, JsValue>;
+}
+
+/// Options of [`decode_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    width: Option<u32>,
+    height: Option<u32>,
+    quality: Option<ResizeQuality>,
+    orientation: Option<Orientation>,
+}
+
+impl DecodeOptions {
+    /// Creates options that decode images at their own size.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resizes the image to `width` pixels wide, keeping its aspect ratio unless a
+    /// [`height`](Self::height) is set too.
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Resizes the image to `height` pixels high, keeping its aspect ratio unless a
+    /// [`width`](Self::width) is set too.
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Sets the quality of the algorithm used for resizing.
+    pub fn quality(mut self, quality: ResizeQuality) -> Self {
+        self.quality = Some(quality);
+        self
+    }
+
+    /// Sets how the image is oriented, which follows its EXIF metadata by default.
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = Some(orientation);
+        self
+    }
+
+    fn to_js(&self) -> web_sys::ImageBitmapOptions {
+        let mut options = web_sys::ImageBitmapOptions::new();
+        if let Some(width) = self.width {
+            options.resize_width(width);
+        }
+        if let Some(height) = self.height {
+            options.resize_height(height);
+        }
+        if let Some(quality) = self.quality {
+            options.resize_quality(quality.to_js());
+        }
+        if let Some(orientation) = self.orientation {
+            options.image_orientation(orientation.to_js());
+        }
+        options
+    }
+}
+
+/// The quality of resizing, see [`DecodeOptions::quality`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeQuality {
+    /// Nearest neighbour, which keeps the edges of pixel art sharp.
+    Pixelated,
+    Low,
+    Medium,
+    High,
+}
+
+impl ResizeQuality {
+    fn to_js(self) -> web_sys::ResizeQuality {
+        match self {
+            ResizeQuality::Pixelated => web_sys::ResizeQuality::Pixelated,
+            ResizeQuality::Low => web_sys::ResizeQuality::Low,
+            ResizeQuality::Medium => web_sys::ResizeQuality::Medium,
+            ResizeQuality::High => web_sys::ResizeQuality::High,
+        }
+    }
+}
+
+/// The orientation of decoded images, see [`DecodeOptions::orientation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Follows the EXIF metadata of the image, e.g. to rotate photos upright.
+    FromImage,
+    /// Flips the image vertically.
+    FlipY,
+}
+
+impl Orientation {
+    fn to_js(self) -> web_sys::ImageOrientation {
+        match self {
+            Orientation::FromImage => web_sys::ImageOrientation::FromImage,
+            Orientation::FlipY => web_sys::ImageOrientation::FlipY,
+        }
+    }
+}
+
+/// Decodes the image in `blob`, e.g. a PNG or JPEG file.
+pub async fn decode(blob: &Blob) -> Result<ImageBitmap, ImageError> {
+    decode_with_options(blob, &DecodeOptions::new()).await
+}
+
+/// Decodes the image in `blob`, resized and oriented according to `options`.
+pub async fn decode_with_options(
+    blob: &Blob,
+    options: &DecodeOptions,
+) -> Result<ImageBitmap, ImageError> {
+    let promise = create_image_bitmap(blob.as_ref(), &options.to_js())
+        .map_err(|_| ImageError::Unsupported)?;
+    let bitmap = JsFuture::from(promise).await?;
+    Ok(bitmap.unchecked_into())
+}
+
+/// Returns the pixels of `bitmap` as RGBA bytes, row after row from the top left.
+///
+/// The pixels are drawn on an [`OffscreenCanvas`], so this fails with
+/// [`ImageError::Unsupported`] in browsers without it.
+///
+/// [`OffscreenCanvas`]: https://developer.mozilla.org/en-US/docs/Web/API/OffscreenCanvas
+pub fn to_rgba(bitmap: &ImageBitmap) -> Result<Vec<u8>, ImageError> {
+    let (width, height) = (bitmap.width(), bitmap.height());
+    if width == 0 || height == 0 {
+        return Ok(Vec::new());
+    }
+
+    let canvas =
+        web_sys::OffscreenCanvas::new(width, height).map_err(|_| ImageError::Unsupported)?;
+    let context = canvas
+        .get_context("2d")?
+        .ok_or(ImageError::Unsupported)?
+        .unchecked_into::<Context2d>();
+    context.draw_image(bitmap, 0.0, 0.0)?;
+
+    let data = context.get_image_data(0.0, 0.0, width as f64, height as f64)?;
+    Ok(data.data().0)
+}
+
+#[derive(Debug)]
+pub enum ImageError {
+    /// The data is not an image, or of a format the browser can't decode.
+    Decode(String),
+    /// The API is not supported by the browser.
+    Unsupported,
+    Other(String),
+}
+
+impl From<JsValue> for ImageError {
+    fn from(value: JsValue) -> Self {
+        match value.dyn_into::<web_sys::DomException>() {
+            Ok(m) if m.name() == "InvalidStateError" => ImageError::Decode(m.message()),
+            Ok(m) => ImageError::Other(m.message()),
+            Err(value) => ImageError::Other(
+                value
+                    .dyn_into::<js_sys::Error>()
+                    .map(|m| String::from(m.message()))
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ImageError::Decode(msg) => write!(f, "image cannot be decoded: {msg}"),
+            ImageError::Unsupported => write!(f, "image API is not supported by the browser"),
+            ImageError::Other(msg) => write!(f, "image error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
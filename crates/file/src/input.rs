@@ -0,0 +1,164 @@
+use crate::{from_input, FileList};
+use wasm_bindgen::{prelude::*, JsCast};
+
+/// An `<input type="file">`, to let the user pick files and follow their selection.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use gloo_file::FileInput;
+///
+/// # fn example(input: web_sys::HtmlInputElement) {
+/// let input = FileInput::from(input).multiple(true).accept(&["image/*", ".pdf"]);
+/// for file in input.files().iter() {
+///     // Upload `file`...
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileInput {
+    inner: web_sys::HtmlInputElement,
+}
+
+impl FileInput {
+    /// Creates a detached `<input type="file">`, e.g. for a picker opened by a button.
+    pub fn create() -> Self {
+        let input = web_sys::window()
+            .unwrap_throw()
+            .document()
+            .unwrap_throw()
+            .create_element("input")
+            .unwrap_throw()
+            .unchecked_into::<web_sys::HtmlInputElement>();
+        Self::from(input)
+    }
+
+    /// Sets whether several files can be selected at once.
+    pub fn multiple(self, multiple: bool) -> Self {
+        self.inner.set_multiple(multiple);
+        self
+    }
+
+    /// Restricts the files that can be selected to `types`, which are MIME types like
+    /// `"image/png"`, wildcards like `"image/*"`, or extensions like `".pdf"`.
+    ///
+    /// Browsers only use it as a hint for their pickers, so it should not be relied upon.
+    pub fn accept(self, types: &[&str]) -> Self {
+        self.inner.set_accept(&types.join(","));
+        self
+    }
+
+    /// Sets whether whole directories are selected instead of files, whose paths are kept in
+    /// the `webkitRelativePath` of the selected files.
+    pub fn directory(self, directory: bool) -> Self {
+        self.inner.set_webkitdirectory(directory);
+        self
+    }
+
+    /// Returns the selected files.
+    pub fn files(&self) -> FileList {
+        from_input(&self.inner)
+    }
+
+    /// Opens the picker of the browser.
+    ///
+    /// Browsers only open pickers in response to a user action, e.g. a click.
+    pub fn open(&self) {
+        self.inner.click();
+    }
+
+    /// Clears the selection, so selecting the same files again is a change.
+    pub fn clear(&self) {
+        self.inner.set_value("");
+    }
+
+    /// Returns a stream of the selected files, each time the user changes the selection.
+    #[cfg(feature = "futures")]
+    pub fn changes(&self) -> FileInputChanges {
+        FileInputChanges::new(&self.inner)
+    }
+}
+
+impl From<web_sys::HtmlInputElement> for FileInput {
+    fn from(inner: web_sys::HtmlInputElement) -> Self {
+        inner.set_type("file");
+        Self { inner }
+    }
+}
+
+impl AsRef<web_sys::HtmlInputElement> for FileInput {
+    fn as_ref(&self) -> &web_sys::HtmlInputElement {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "futures")]
+pub use changes::FileInputChanges;
+
+#[cfg(feature = "futures")]
+mod changes {
+    use crate::{from_input, FileList};
+    use futures_channel::mpsc;
+    use futures_core::Stream;
+    use gloo_events::EventListener;
+    use std::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    use wasm_bindgen::JsCast;
+
+    /// A stream of the files selected with an `<input type="file">`, see
+    /// [`FileInput::changes`](crate::FileInput::changes).
+    ///
+    /// The stream never ends, and stops listening to the input when dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use futures_rs as futures;
+    /// use futures::StreamExt;
+    /// use gloo_file::FileInput;
+    ///
+    /// # async fn example() {
+    /// let input = FileInput::create().accept(&["image/*"]);
+    /// let mut changes = input.changes();
+    /// // In response to a user action, e.g. a click...
+    /// input.open();
+    /// while let Some(files) = changes.next().await {
+    ///     // Show previews of `files`...
+    /// }
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct FileInputChanges {
+        receiver: mpsc::UnboundedReceiver<FileList>,
+        _listener: EventListener,
+    }
+
+    impl FileInputChanges {
+        /// Listens to the changes of the selection of `input`.
+        pub fn new(input: &web_sys::HtmlInputElement) -> Self {
+            let (sender, receiver) = mpsc::unbounded();
+            let listener = EventListener::new(input, "change", move |event| {
+                if let Some(input) = event
+                    .target()
+                    .and_then(|m| m.dyn_into::<web_sys::HtmlInputElement>().ok())
+                {
+                    let _ = sender.unbounded_send(from_input(&input));
+                }
+            });
+            Self {
+                receiver,
+                _listener: listener,
+            }
+        }
+    }
+
+    impl Stream for FileInputChanges {
+        type Item = FileList;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Pin::new(&mut self.receiver).poll_next(cx)
+        }
+    }
+}
@@ -7,14 +7,52 @@
 //! 2. a `Future` API.
 
 mod blob;
+#[cfg(feature = "futures")]
+mod compression;
+#[cfg(feature = "futures")]
+mod directory;
+#[cfg(feature = "futures")]
+mod drag_drop;
 mod file_list;
 mod file_reader;
+#[cfg(feature = "futures")]
+mod file_system;
+#[cfg(feature = "futures")]
+mod file_writer;
+#[cfg(feature = "futures")]
+mod hash;
+#[cfg(feature = "futures")]
+pub mod image;
+mod input;
 mod object_url;
+#[cfg(feature = "futures")]
+mod opfs;
+#[cfg(feature = "futures")]
+mod recorder;
+mod save;
 
 pub use blob::*;
+#[cfg(feature = "futures")]
+pub use compression::*;
+#[cfg(feature = "futures")]
+pub use directory::*;
+#[cfg(feature = "futures")]
+pub use drag_drop::*;
 pub use file_list::*;
 pub use file_reader::*;
+#[cfg(feature = "futures")]
+pub use file_system::*;
+#[cfg(feature = "futures")]
+pub use file_writer::*;
+#[cfg(feature = "futures")]
+pub use hash::*;
+pub use input::*;
 pub use object_url::*;
+#[cfg(feature = "futures")]
+pub use opfs::*;
+#[cfg(feature = "futures")]
+pub use recorder::*;
+pub use save::*;
 
 mod sealed {
     pub trait Sealed {}
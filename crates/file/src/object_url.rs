@@ -1,16 +1,26 @@
 use crate::{Blob, File};
-use std::{ops::Deref, rc::Rc};
+use gloo_events::EventListener;
+use std::{cell::Cell, cell::RefCell, ops::Deref, rc::Rc};
 
 use wasm_bindgen::UnwrapThrowExt;
 use web_sys::Url;
 
 struct ObjectUrlAllocation {
     url: String,
+    revoked: Cell<bool>,
+}
+
+impl ObjectUrlAllocation {
+    fn revoke(&self) {
+        if !self.revoked.replace(true) {
+            web_sys::Url::revoke_object_url(&self.url).unwrap_throw();
+        }
+    }
 }
 
 impl Drop for ObjectUrlAllocation {
     fn drop(&mut self) {
-        web_sys::Url::revoke_object_url(&self.url).unwrap_throw();
+        self.revoke();
     }
 }
 
@@ -53,14 +63,139 @@ impl From<Blob> for ObjectUrl {
 impl From<web_sys::Blob> for ObjectUrl {
     fn from(blob: web_sys::Blob) -> Self {
         let url = Url::create_object_url_with_blob(&blob).unwrap_throw();
-        let inner = Rc::new(ObjectUrlAllocation { url });
+        let inner = Rc::new(ObjectUrlAllocation {
+            url,
+            revoked: Cell::new(false),
+        });
         Self { inner }
     }
 }
 
+impl ObjectUrl {
+    /// Revokes the URL right away, even if other clones of this `ObjectUrl` are still alive.
+    ///
+    /// Elements that already loaded the URL keep their data, but the URL can't be loaded
+    /// anymore.
+    pub fn revoke(&self) {
+        self.inner.revoke();
+    }
+
+    /// Returns true if the URL was [`revoke`](Self::revoke)d.
+    pub fn is_revoked(&self) -> bool {
+        self.inner.revoked.get()
+    }
+
+    /// Revokes the URL once `target` fires one of the `event_types`, e.g. `load` or `error`.
+    ///
+    /// The URL is kept alive until then, even if all other clones are dropped.
+    pub fn revoke_after(self, target: &web_sys::EventTarget, event_types: &[&'static str]) {
+        // The listeners remove each other once the first one fires.
+        let listeners = Rc::new(RefCell::new(Vec::new()));
+        for event_type in event_types {
+            let url = self.clone();
+            let pending = listeners.clone();
+            let listener = EventListener::new(target, *event_type, move |_| {
+                url.revoke();
+                pending.borrow_mut().clear();
+            });
+            listeners.borrow_mut().push(listener);
+        }
+    }
+}
+
+/// Shows `blob` in `image`, and revokes its [`ObjectUrl`] once the image is loaded.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use gloo_file::Blob;
+///
+/// # fn example(image: web_sys::HtmlImageElement, blob: Blob) {
+/// gloo_file::load_image(&image, blob);
+/// # }
+/// ```
+pub fn load_image(image: &web_sys::HtmlImageElement, blob: impl Into<ObjectUrl>) {
+    let url = blob.into();
+    image.set_src(&url);
+    url.revoke_after(image, &["load", "error"]);
+}
+
+/// Plays `blob` in `media`, a `<video>` or `<audio>` element, and revokes its [`ObjectUrl`]
+/// once the media data is loaded.
+pub fn load_media(media: &web_sys::HtmlMediaElement, blob: impl Into<ObjectUrl>) {
+    let url = blob.into();
+    media.set_src(&url);
+    url.revoke_after(media, &["loadeddata", "error"]);
+}
+
+/// A scope of many [`ObjectUrl`]s, which are all revoked when it is dropped or
+/// [`flush`](Self::flush)ed, even if clones of them are still alive.
+///
+/// This prevents URLs from leaking when they are handed out to many places, e.g. the thumbnails
+/// of a gallery, which can all be revoked when the gallery is closed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use gloo_file::{Blob, ObjectUrlScope};
+///
+/// let mut scope = ObjectUrlScope::new();
+/// let first = scope.create(Blob::new("first"));
+/// let second = scope.create(Blob::new("second"));
+/// // Both URLs are revoked.
+/// scope.flush();
+/// ```
+#[derive(Debug, Default)]
+pub struct ObjectUrlScope {
+    urls: Vec<ObjectUrl>,
+}
+
+impl ObjectUrlScope {
+    /// Creates an empty scope.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an [`ObjectUrl`] of `blob` that is revoked with this scope.
+    pub fn create(&mut self, blob: impl Into<ObjectUrl>) -> ObjectUrl {
+        let url = blob.into();
+        self.urls.push(url.clone());
+        url
+    }
+
+    /// Returns the number of URLs in this scope.
+    pub fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    /// Returns true if this scope has no URLs.
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+
+    /// Revokes all URLs of this scope, which can be reused afterwards.
+    pub fn flush(&mut self) {
+        for url in self.urls.drain(..) {
+            url.revoke();
+        }
+    }
+}
+
+impl Drop for ObjectUrlScope {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
 // Note: some browsers support Url::create_object_url_with_source but this is deprecated!
 // https://developer.mozilla.org/en-US/docs/Web/API/URL/createObjectURL#using_object_urls_for_media_streams
 
+impl std::fmt::Debug for ObjectUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ObjectUrl").field(&self.inner.url).finish()
+    }
+}
+
 impl Deref for ObjectUrl {
     type Target = str;
 
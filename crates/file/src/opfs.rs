@@ -0,0 +1,104 @@
+use crate::{file_system::resolve, DirectoryHandle, FileSystemError};
+use js_sys::Reflect;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Returns the root directory of the [origin private file system], a file system private to
+/// the origin of the page, which needs no permission of the user.
+///
+/// Unlike the file pickers, it is also available in workers, where its files can be opened
+/// with [`FileHandle::create_sync_access_handle`](crate::FileHandle::create_sync_access_handle)
+/// for fast synchronous I/O.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use gloo_file::origin_private_directory;
+///
+/// # async fn example() {
+/// let root = origin_private_directory().await.unwrap();
+/// let file = root.get_file_handle("notes.txt", true).await.unwrap();
+/// file.write_all("hello world").await.unwrap();
+/// # }
+/// ```
+///
+/// [origin private file system]: https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system
+pub async fn origin_private_directory() -> Result<DirectoryHandle, FileSystemError> {
+    // Works in windows and workers alike.
+    let navigator = Reflect::get(&js_sys::global(), &JsValue::from_str("navigator"))?;
+    let storage = Reflect::get(&navigator, &JsValue::from_str("storage"))?;
+    if storage.is_undefined() {
+        return Err(FileSystemError::Unsupported);
+    }
+
+    let storage = storage.unchecked_into::<web_sys::StorageManager>();
+    let handle = resolve(storage.get_directory()).await?;
+    Ok(DirectoryHandle::from(
+        handle.unchecked_into::<web_sys::FileSystemDirectoryHandle>(),
+    ))
+}
+
+/// A handle for synchronous reads and writes of a file of the origin private file system,
+/// created with [`FileHandle::create_sync_access_handle`](crate::FileHandle::create_sync_access_handle).
+///
+/// The file is locked until the handle is dropped, which closes it.
+///
+/// See [`FileSystemSyncAccessHandle`](https://developer.mozilla.org/en-US/docs/Web/API/FileSystemSyncAccessHandle).
+#[derive(Debug)]
+pub struct SyncAccessHandle {
+    inner: web_sys::FileSystemSyncAccessHandle,
+}
+
+impl SyncAccessHandle {
+    /// Reads the file from offset `at` into `buf`, and returns the number of bytes read.
+    pub fn read_at(&self, buf: &mut [u8], at: u64) -> Result<usize, FileSystemError> {
+        let mut options = web_sys::FileSystemReadWriteOptions::new();
+        options.at(at as f64);
+
+        let read = self.inner.read_with_u8_array_and_options(buf, &options)?;
+        Ok(read as usize)
+    }
+
+    /// Writes `buf` into the file at offset `at`, and returns the number of bytes written.
+    pub fn write_at(&self, buf: &[u8], at: u64) -> Result<usize, FileSystemError> {
+        let mut options = web_sys::FileSystemReadWriteOptions::new();
+        options.at(at as f64);
+
+        let written = self.inner.write_with_u8_array_and_options(buf, &options)?;
+        Ok(written as usize)
+    }
+
+    /// Returns the size of the file in bytes.
+    pub fn size(&self) -> Result<u64, FileSystemError> {
+        Ok(self.inner.get_size()? as u64)
+    }
+
+    /// Resizes the file to `size` bytes, padding it with zeros or cutting its end.
+    pub fn truncate(&self, size: u64) -> Result<(), FileSystemError> {
+        self.inner.truncate_with_f64(size as f64)?;
+        Ok(())
+    }
+
+    /// Persists the changes made to the file.
+    pub fn flush(&self) -> Result<(), FileSystemError> {
+        self.inner.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for SyncAccessHandle {
+    fn drop(&mut self) {
+        self.inner.close();
+    }
+}
+
+impl From<web_sys::FileSystemSyncAccessHandle> for SyncAccessHandle {
+    fn from(inner: web_sys::FileSystemSyncAccessHandle) -> Self {
+        Self { inner }
+    }
+}
+
+impl AsRef<web_sys::FileSystemSyncAccessHandle> for SyncAccessHandle {
+    fn as_ref(&self) -> &web_sys::FileSystemSyncAccessHandle {
+        &self.inner
+    }
+}
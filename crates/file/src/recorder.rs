@@ -0,0 +1,300 @@
+use crate::{Blob, File};
+use futures_channel::mpsc;
+use futures_core::Stream;
+use gloo_events::EventListener;
+use js_sys::{Array, Reflect};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use wasm_bindgen::{prelude::*, JsCast};
+
+/// Options of [`Recorder::start`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecorderOptions {
+    mime_types: Vec<String>,
+    bits_per_second: Option<u32>,
+    audio_bits_per_second: Option<u32>,
+    video_bits_per_second: Option<u32>,
+    time_slice: Option<u32>,
+}
+
+impl RecorderOptions {
+    /// Creates options that record in the default format of the browser.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the MIME types to record in, by order of preference, e.g.
+    /// `&["video/webm;codecs=vp9", "video/webm", "video/mp4"]`.
+    ///
+    /// The first type the browser supports is used, see [`supported_mime_type`].
+    pub fn mime_types(mut self, mime_types: &[&str]) -> Self {
+        self.mime_types = mime_types.iter().map(|m| (*m).to_owned()).collect();
+        self
+    }
+
+    /// Sets the bitrate of the audio and video tracks together.
+    pub fn bits_per_second(mut self, bits: u32) -> Self {
+        self.bits_per_second = Some(bits);
+        self
+    }
+
+    /// Sets the bitrate of the audio track.
+    pub fn audio_bits_per_second(mut self, bits: u32) -> Self {
+        self.audio_bits_per_second = Some(bits);
+        self
+    }
+
+    /// Sets the bitrate of the video track.
+    pub fn video_bits_per_second(mut self, bits: u32) -> Self {
+        self.video_bits_per_second = Some(bits);
+        self
+    }
+
+    /// Sets the duration of the recorded chunks, in milliseconds.
+    ///
+    /// Without it, the whole recording is a single chunk, unless
+    /// [`Recorder::request_data`] is called.
+    pub fn time_slice(mut self, millis: u32) -> Self {
+        self.time_slice = Some(millis);
+        self
+    }
+}
+
+/// Returns the first of `mime_types` that the browser can record in, if any.
+pub fn supported_mime_type<'a>(mime_types: &[&'a str]) -> Option<&'a str> {
+    if !is_supported() {
+        return None;
+    }
+    mime_types
+        .iter()
+        .copied()
+        .find(|m| web_sys::MediaRecorder::is_type_supported(m))
+}
+
+fn is_supported() -> bool {
+    Reflect::has(&js_sys::global(), &JsValue::from_str("MediaRecorder")).unwrap_or(false)
+}
+
+/// A recorder of the audio and video of a [`MediaStream`], e.g. of a camera, a microphone or
+/// `canvas.captureStream()`, based on [`MediaRecorder`].
+///
+/// The recorded chunks are yielded as [`Blob`]s through the [`Stream`] implementation, which
+/// ends once the recording is stopped. Alternatively, [`into_file`](Self::into_file) stops the
+/// recording and gathers the chunks in a single [`File`].
+///
+/// The recording is stopped when the `Recorder` is dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use gloo_file::{Recorder, RecorderOptions};
+///
+/// # async fn example(stream: web_sys::MediaStream) {
+/// let options = RecorderOptions::new().mime_types(&["video/webm", "video/mp4"]);
+/// let recorder = Recorder::start(&stream, &options).unwrap();
+/// // Later...
+/// let file = recorder.into_file("recording").await.unwrap();
+/// # }
+/// ```
+///
+/// [`MediaStream`]: web_sys::MediaStream
+/// [`MediaRecorder`]: https://developer.mozilla.org/en-US/docs/Web/API/MediaRecorder
+#[derive(Debug)]
+pub struct Recorder {
+    inner: web_sys::MediaRecorder,
+    receiver: mpsc::UnboundedReceiver<Result<Blob, RecorderError>>,
+    _data_listener: EventListener,
+    _stop_listener: EventListener,
+    _error_listener: EventListener,
+}
+
+impl Recorder {
+    /// Starts recording `stream`.
+    ///
+    /// Returns [`RecorderError::Unsupported`] if the browser can't record in any of the
+    /// [`mime_types`](RecorderOptions::mime_types) of `options`.
+    pub fn start(
+        stream: &web_sys::MediaStream,
+        options: &RecorderOptions,
+    ) -> Result<Self, RecorderError> {
+        if !is_supported() {
+            return Err(RecorderError::Unsupported);
+        }
+
+        let mut js_options = web_sys::MediaRecorderOptions::new();
+        if !options.mime_types.is_empty() {
+            let mime_types = options
+                .mime_types
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            let mime_type = supported_mime_type(&mime_types).ok_or(RecorderError::Unsupported)?;
+            js_options.mime_type(mime_type);
+        }
+        if let Some(bits) = options.bits_per_second {
+            js_options.bits_per_second(bits);
+        }
+        if let Some(bits) = options.audio_bits_per_second {
+            js_options.audio_bits_per_second(bits);
+        }
+        if let Some(bits) = options.video_bits_per_second {
+            js_options.video_bits_per_second(bits);
+        }
+
+        let inner = web_sys::MediaRecorder::new_with_media_stream_and_media_recorder_options(
+            stream,
+            &js_options,
+        )
+        .map_err(RecorderError::from_js)?;
+
+        let (sender, receiver) = mpsc::unbounded();
+
+        let data_sender = sender.clone();
+        let data_listener = EventListener::new(&inner, "dataavailable", move |event| {
+            let event = event.unchecked_ref::<web_sys::BlobEvent>();
+            if let Some(data) = event.data() {
+                let _ = data_sender.unbounded_send(Ok(Blob::from(data)));
+            }
+        });
+
+        // `dataavailable` of the last chunk is fired before `stop`.
+        let stop_sender = sender.clone();
+        let stop_listener = EventListener::new(&inner, "stop", move |_| {
+            stop_sender.close_channel();
+        });
+
+        let error_listener = EventListener::new(&inner, "error", move |event| {
+            let error = Reflect::get(event, &JsValue::from_str("error")).unwrap_or(JsValue::NULL);
+            let _ = sender.unbounded_send(Err(RecorderError::from_js(error)));
+        });
+
+        match options.time_slice {
+            Some(millis) => inner.start_with_time_slice(millis as i32),
+            None => inner.start(),
+        }
+        .map_err(RecorderError::from_js)?;
+
+        Ok(Self {
+            inner,
+            receiver,
+            _data_listener: data_listener,
+            _stop_listener: stop_listener,
+            _error_listener: error_listener,
+        })
+    }
+
+    /// Returns the MIME type the recorder records in.
+    pub fn mime_type(&self) -> String {
+        self.inner.mime_type()
+    }
+
+    /// Pauses the recording.
+    pub fn pause(&self) {
+        if self.inner.state() == web_sys::RecordingState::Recording {
+            self.inner.pause().unwrap_throw();
+        }
+    }
+
+    /// Resumes the recording after a [`pause`](Self::pause).
+    pub fn resume(&self) {
+        if self.inner.state() == web_sys::RecordingState::Paused {
+            self.inner.resume().unwrap_throw();
+        }
+    }
+
+    /// Ends the current chunk, so it is yielded by the stream right away.
+    pub fn request_data(&self) {
+        if self.inner.state() != web_sys::RecordingState::Inactive {
+            self.inner.request_data().unwrap_throw();
+        }
+    }
+
+    /// Stops the recording, after which the stream yields the last chunk and ends.
+    pub fn stop(&self) {
+        if self.inner.state() != web_sys::RecordingState::Inactive {
+            self.inner.stop().unwrap_throw();
+        }
+    }
+
+    /// Stops the recording, and returns a [`File`] named `name` of the chunks that were not
+    /// taken from the stream yet.
+    pub async fn into_file(mut self, name: &str) -> Result<File, RecorderError> {
+        self.stop();
+
+        let parts = Array::new();
+        while let Some(chunk) = std::future::poll_fn(|cx| self.receiver_poll(cx)).await {
+            parts.push(chunk?.as_ref());
+        }
+
+        let mut options = web_sys::FilePropertyBag::new();
+        options.type_(&self.mime_type());
+        let file = web_sys::File::new_with_blob_sequence_and_options(&parts, name, &options)
+            .unwrap_throw();
+        Ok(File::from(file))
+    }
+
+    fn receiver_poll(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Blob, RecorderError>>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl Stream for Recorder {
+    type Item = Result<Blob, RecorderError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver_poll(cx)
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[derive(Debug)]
+pub enum RecorderError {
+    /// The browser can't record in any of the requested MIME types, or at all.
+    Unsupported,
+    /// The stream can't be recorded, e.g. because it has no tracks.
+    InvalidState(String),
+    /// Recording is not allowed, e.g. for streams of other origins.
+    Security(String),
+    Other(String),
+}
+
+impl RecorderError {
+    fn from_js(value: JsValue) -> Self {
+        match value.dyn_into::<web_sys::DomException>() {
+            Ok(m) => match m.name().as_str() {
+                "NotSupportedError" => RecorderError::Unsupported,
+                "InvalidStateError" => RecorderError::InvalidState(m.message()),
+                "SecurityError" => RecorderError::Security(m.message()),
+                _ => RecorderError::Other(m.message()),
+            },
+            Err(value) => RecorderError::Other(
+                value
+                    .dyn_into::<js_sys::Error>()
+                    .map(|m| String::from(m.message()))
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for RecorderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RecorderError::Unsupported => write!(f, "recording is not supported by the browser"),
+            RecorderError::InvalidState(msg) => write!(f, "cannot record stream: {msg}"),
+            RecorderError::Security(msg) => {
+                write!(f, "recording encountered a security exception: {msg}")
+            }
+            RecorderError::Other(msg) => write!(f, "recording failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for RecorderError {}
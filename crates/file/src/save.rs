@@ -0,0 +1,69 @@
+use crate::{Blob, ObjectUrl};
+use wasm_bindgen::{prelude::*, JsCast};
+
+/// How long the object URL of a saved blob is kept, as browsers may start the download after
+/// the click returns.
+const REVOKE_DELAY_MS: i32 = 40_000;
+
+/// Saves `blob` as a download named `filename`.
+///
+/// This clicks a detached `<a download>` linking to an [`ObjectUrl`] of the blob, which is
+/// revoked once the browser had time to start the download. Browsers may ask the user where to
+/// save the file, or rename it.
+///
+/// Some browsers only allow downloads in response to a user action, e.g. a click.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use gloo_file::Blob;
+///
+/// let blob = Blob::new_with_options("a,b\n1,2\n", Some("text/csv"));
+/// gloo_file::save(&blob, "export.csv");
+/// ```
+pub fn save(blob: &Blob, filename: &str) {
+    let window = web_sys::window().unwrap_throw();
+    let document = window.document().unwrap_throw();
+
+    let object_url = ObjectUrl::from(blob.clone());
+    let anchor = document
+        .create_element("a")
+        .unwrap_throw()
+        .unchecked_into::<web_sys::HtmlAnchorElement>();
+    anchor.set_href(&object_url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    // Revoking the URL right away may cancel the download.
+    let revoke = Closure::once_into_js(move || drop(object_url));
+    window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            revoke.unchecked_ref(),
+            REVOKE_DELAY_MS,
+        )
+        .unwrap_throw();
+}
+
+/// Lets the user pick where to save `blob` with [`show_save_file_picker`], suggesting
+/// `filename`, or saves it as a download with [`save`] if the File System Access API is not
+/// supported.
+///
+/// Must be called in response to a user action, e.g. a click. Returns
+/// [`FileSystemError::Aborted`] if the user dismissed the picker.
+///
+/// [`show_save_file_picker`]: crate::show_save_file_picker
+/// [`FileSystemError::Aborted`]: crate::FileSystemError::Aborted
+#[cfg(feature = "futures")]
+pub async fn save_as(blob: &Blob, filename: &str) -> Result<(), crate::FileSystemError> {
+    use crate::{show_save_file_picker, FilePickerOptions, FileSystemError};
+
+    let options = FilePickerOptions::new().suggested_name(filename);
+    match show_save_file_picker(&options).await {
+        Ok(handle) => handle.write_all(blob.clone()).await,
+        Err(FileSystemError::Unsupported) => {
+            save(blob, filename);
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
@@ -124,3 +124,321 @@ async fn blob_to_url() {
         .unwrap();
     assert_eq!(&body, "hello world");
 }
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn bytes_stream() {
+    use futures_rs::TryStreamExt;
+
+    let blob = Blob::new(PNG_FILE);
+    let chunks = gloo_file::futures::read_as_stream(&blob, 16)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    assert_eq!(chunks.len(), (PNG_FILE.len() + 15) / 16);
+    assert!(chunks.iter().all(|chunk| chunk.len() <= 16));
+    assert_eq!(chunks.concat(), PNG_FILE);
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn origin_private_file_system() {
+    use futures_rs::TryStreamExt;
+    use gloo_file::{FileSystemError, Handle};
+
+    let root = match gloo_file::origin_private_directory().await {
+        Ok(m) => m,
+        Err(FileSystemError::Unsupported) => return,
+        Err(e) => panic!("{e}"),
+    };
+
+    let dir = root.get_directory_handle("gloo-test", true).await.unwrap();
+    let file = dir.get_file_handle("hello.txt", true).await.unwrap();
+    file.write_all("hello world").await.unwrap();
+
+    let contents = file.get_file().await.unwrap();
+    assert_eq!(
+        gloo_file::futures::read_as_text(&contents).await.unwrap(),
+        "hello world"
+    );
+
+    let entries = dir.entries().try_collect::<Vec<_>>().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert!(matches!(&entries[0], Handle::File(m) if m.name() == "hello.txt"));
+
+    root.remove_entry("gloo-test", true).await.unwrap();
+    assert!(matches!(
+        root.get_directory_handle("gloo-test", false).await,
+        Err(FileSystemError::NotFound(_))
+    ));
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn file_writer() {
+    use futures_rs::SinkExt;
+    use gloo_file::FileSystemError;
+
+    let root = match gloo_file::origin_private_directory().await {
+        Ok(m) => m,
+        Err(FileSystemError::Unsupported) => return,
+        Err(e) => panic!("{e}"),
+    };
+    let file = root.get_file_handle("gloo-writer.txt", true).await.unwrap();
+
+    let mut writer = file.create_writer(false).await.unwrap();
+    writer.write(b"hello world").await.unwrap();
+    writer.seek(6).await.unwrap();
+    writer.send(b"gloo!".to_vec()).await.unwrap();
+    writer.truncate(10).await.unwrap();
+    writer.close().await.unwrap();
+
+    let contents = file.get_file().await.unwrap();
+    assert_eq!(
+        gloo_file::futures::read_as_text(&contents).await.unwrap(),
+        "hello gloo"
+    );
+
+    root.remove_entry("gloo-writer.txt", false).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+fn save_blob() {
+    gloo_file::save(&Blob::new("hello"), "hello.txt");
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn drop_zone() {
+    use gloo_file::DropZone;
+    use web_sys::{DataTransfer, DragEvent, DragEventInit};
+
+    let document = window().unwrap().document().unwrap();
+    let element = document.create_element("div").unwrap();
+    let mut drops = DropZone::new(&element);
+
+    let data = DataTransfer::new().unwrap();
+    let file = File::new("hello.txt", "hello");
+    data.items().add_with_file(file.as_ref()).unwrap();
+    let mut init = DragEventInit::new();
+    init.data_transfer(Some(&data)).cancelable(true);
+    let event = DragEvent::new_with_event_init_dict("drop", &init).unwrap();
+    assert!(!element.dispatch_event(&event).unwrap());
+
+    let files = drops.next().await.unwrap().unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path(), "hello.txt");
+    assert_eq!(files[0].file(), &file);
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn file_input_changes() {
+    use gloo_file::FileInput;
+
+    let input = FileInput::create()
+        .multiple(true)
+        .accept(&["text/plain", ".md"]);
+    assert!(input.as_ref().multiple());
+    assert_eq!(input.as_ref().accept(), "text/plain,.md");
+    assert!(input.files().is_empty());
+    let mut changes = input.changes();
+
+    let data = web_sys::DataTransfer::new().unwrap();
+    let file = File::new("hello.txt", "hello");
+    data.items().add_with_file(file.as_ref()).unwrap();
+    input.as_ref().set_files(data.files().as_ref());
+    let event = web_sys::Event::new("change").unwrap();
+    input.as_ref().dispatch_event(&event).unwrap();
+
+    let files = changes.next().await.unwrap();
+    assert_eq!(&*files, &[file]);
+    assert_eq!(input.files(), files);
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn directory_files() {
+    use gloo_file::{Directory, FileList, FileSystemError};
+
+    let data = web_sys::DataTransfer::new().unwrap();
+    data.items()
+        .add_with_file(File::new("hello.txt", "hello").as_ref())
+        .unwrap();
+    let directory = Directory::from(FileList::from(data.files().unwrap()));
+    let files = directory.files().try_collect::<Vec<_>>().await.unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path(), "hello.txt");
+
+    let root = match gloo_file::origin_private_directory().await {
+        Ok(m) => m,
+        Err(FileSystemError::Unsupported) => return,
+        Err(e) => panic!("{e}"),
+    };
+    let dir = root.get_directory_handle("gloo-dir", true).await.unwrap();
+    dir.get_file_handle("a.txt", true).await.unwrap();
+    let sub = dir.get_directory_handle("sub", true).await.unwrap();
+    sub.get_file_handle("b.txt", true).await.unwrap();
+
+    let mut paths = Directory::from(dir)
+        .files()
+        .map_ok(|m| m.path().to_owned())
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    paths.sort();
+    assert_eq!(paths, ["gloo-dir/a.txt", "gloo-dir/sub/b.txt"]);
+
+    root.remove_entry("gloo-dir", true).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+fn object_url_scope() {
+    use gloo_file::ObjectUrlScope;
+
+    let mut scope = ObjectUrlScope::new();
+    let first = scope.create(Blob::new("first"));
+    let second = scope.create(Blob::new("second"));
+    assert_eq!(scope.len(), 2);
+    assert!(!first.is_revoked());
+
+    scope.flush();
+    assert!(scope.is_empty());
+    assert!(first.is_revoked());
+    assert!(second.is_revoked());
+
+    let third = scope.create(Blob::new("third"));
+    drop(scope);
+    assert!(third.is_revoked());
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn image_decode() {
+    use gloo_file::image::{self, DecodeOptions, ImageError, ResizeQuality};
+
+    // A 2x1 PNG with a red and a blue pixel.
+    const PNG: &[u8] = &[
+        137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 2, 0, 0, 0, 1, 8, 6,
+        0, 0, 0, 244, 34, 127, 138, 0, 0, 0, 14, 73, 68, 65, 84, 120, 156, 99, 248, 207, 192, 0,
+        66, 255, 1, 15, 249, 3, 253, 133, 17, 153, 118, 0, 0, 0, 0, 73, 69, 78, 68, 174, 66, 96,
+        130,
+    ];
+    let blob = Blob::new_with_options(PNG, Some("image/png"));
+
+    let bitmap = image::decode(&blob).await.unwrap();
+    assert_eq!((bitmap.width(), bitmap.height()), (2, 1));
+    assert_eq!(
+        image::to_rgba(&bitmap).unwrap(),
+        [255, 0, 0, 255, 0, 0, 255, 255]
+    );
+
+    let options = DecodeOptions::new()
+        .width(4)
+        .height(2)
+        .quality(ResizeQuality::Pixelated);
+    let bitmap = image::decode_with_options(&blob, &options).await.unwrap();
+    assert_eq!((bitmap.width(), bitmap.height()), (4, 2));
+
+    assert!(matches!(
+        image::decode(&Blob::new("not an image")).await,
+        Err(ImageError::Decode(_))
+    ));
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn hash_blob() {
+    use gloo_file::HashAlgorithm;
+
+    let blob = Blob::new("hello world");
+    let mut reports = Vec::new();
+    let digest = gloo_file::hash_with_progress(&blob, HashAlgorithm::Sha256, |read, size| {
+        reports.push((read, size))
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(
+        digest,
+        [
+            0xb9, 0x4d, 0x27, 0xb9, 0x93, 0x4d, 0x3e, 0x08, 0xa5, 0x2e, 0x52, 0xd7, 0xda, 0x7d,
+            0xab, 0xfa, 0xc4, 0x84, 0xef, 0xe3, 0x7a, 0x53, 0x80, 0xee, 0x90, 0x88, 0xf7, 0xac,
+            0xe2, 0xef, 0xcd, 0xe9,
+        ]
+    );
+    assert_eq!(reports, [(11, 11)]);
+
+    let digest = gloo_file::hash(&blob, HashAlgorithm::Sha1).await.unwrap();
+    assert_eq!(digest.len(), 20);
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn text_with_encoding() {
+    use gloo_file::{futures::read_as_text_with_encoding, BomHandling, FileReadError};
+
+    let blob = Blob::new(&[0x93, 0xFA, 0x96, 0x7B][..]);
+    let text = read_as_text_with_encoding(&blob, "shift_jis", BomHandling::Sniff)
+        .await
+        .unwrap();
+    assert_eq!(text, "日本");
+
+    let blob = Blob::new(&[0xEF, 0xBB, 0xBF, b'h', b'i'][..]);
+    let text = read_as_text_with_encoding(&blob, "windows-1252", BomHandling::Sniff)
+        .await
+        .unwrap();
+    assert_eq!(text, "hi");
+    let text = read_as_text_with_encoding(&blob, "windows-1252", BomHandling::Strip)
+        .await
+        .unwrap();
+    assert_eq!(text, "ï»¿hi");
+
+    assert!(matches!(
+        read_as_text_with_encoding(&blob, "klingon", BomHandling::Sniff).await,
+        Err(FileReadError::UnknownEncoding(_))
+    ));
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+fn recorder_mime_types() {
+    use gloo_file::{Recorder, RecorderError, RecorderOptions};
+
+    assert_eq!(gloo_file::supported_mime_type(&["not/a-type"]), None);
+
+    let stream = web_sys::MediaStream::new().unwrap();
+    let options = RecorderOptions::new().mime_types(&["not/a-type"]);
+    assert!(matches!(
+        Recorder::start(&stream, &options),
+        Err(RecorderError::Unsupported)
+    ));
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn compression() {
+    use gloo_file::{futures::read_as_text, CompressionError, CompressionFormat};
+
+    let text = "hello world, hello world, hello world";
+    let mut produced = 0;
+    let compressed =
+        gloo_file::compress_with_progress(&Blob::new(text), CompressionFormat::Gzip, |size| {
+            produced = size
+        })
+        .await
+        .unwrap();
+    assert_eq!(produced, compressed.size());
+    assert_eq!(compressed.raw_mime_type(), "application/gzip");
+
+    let decompressed = gloo_file::decompress(&compressed, CompressionFormat::Gzip)
+        .await
+        .unwrap();
+    assert_eq!(read_as_text(&decompressed).await.unwrap(), text);
+
+    assert!(matches!(
+        gloo_file::decompress(&Blob::new(text), CompressionFormat::Gzip).await,
+        Err(CompressionError::InvalidData(_))
+    ));
+}
@@ -1,11 +1,18 @@
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
 
+use crate::blocker::{BlockerHandle, Transition, TransitionKind};
 use crate::browser::BrowserHistory;
 use crate::hash::HashHistory;
 use crate::history::History;
-use crate::listener::HistoryListener;
-use crate::location::Location;
+use crate::hook::{Hook, HookHandle};
+use crate::listener::{HistoryListener, NavigationEvent};
+use crate::location::{Location, LocationBuilder};
 use crate::memory::MemoryHistory;
+use crate::navigation::NavigationHistory;
+use crate::storage::EntryStorage;
+use crate::utils::{apply_hooks, WeakHook};
 #[cfg(feature = "query")]
 use crate::{error::HistoryResult, query::ToQuery};
 
@@ -18,6 +25,75 @@ pub enum AnyHistory {
     Hash(HashHistory),
     /// A Memory History
     Memory(MemoryHistory),
+    /// A Navigation History
+    Navigation(NavigationHistory),
+}
+
+impl AnyHistory {
+    /// Creates a [`NavigationHistory`] if the Navigation API is supported, or a
+    /// [`BrowserHistory`] otherwise.
+    pub fn navigation_or_browser() -> Self {
+        if NavigationHistory::is_supported() {
+            NavigationHistory::new().into()
+        } else {
+            BrowserHistory::new().into()
+        }
+    }
+
+    /// Registers a hook that can rewrite or veto the target of every navigation made through
+    /// this history before it happens, e.g. to prefix routes with the current locale, normalize
+    /// trailing slashes or redirect to a login page.
+    ///
+    /// The hook receives the [`Transition`] about to happen, whose target is the route passed to
+    /// `.push()`, `.replace()` or one of their variants, without the queries of the `_query`
+    /// variants, or the path of a [`LocationBuilder`]. It returns the route to navigate to
+    /// instead, or [`None`] to cancel the navigation. Hooks run in the order they were
+    /// registered, each receiving the route returned by the previous one.
+    ///
+    /// Hooks apply to every backend alike, but only to navigations made through an
+    /// [`AnyHistory`]: using the underlying history directly, or going back and forth in the
+    /// history, bypasses them.
+    ///
+    /// This method returns a [`HookHandle`] that will automatically unregister the hook when
+    /// dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gloo_history::{AnyHistory, History, MemoryHistory};
+    ///
+    /// let history = AnyHistory::from(MemoryHistory::new());
+    /// let _hook = history.hook(|transition| {
+    ///     let to = transition.to()?;
+    ///     Some(to.strip_suffix('/').filter(|m| !m.is_empty()).unwrap_or(to).to_owned())
+    /// });
+    ///
+    /// history.push("/posts/");
+    /// assert_eq!(history.location().path(), "/posts");
+    /// ```
+    pub fn hook<CB>(&self, callback: CB) -> HookHandle
+    where
+        CB: Fn(&Transition) -> Option<String> + 'static,
+    {
+        let hook = Rc::new(callback) as Rc<Hook>;
+
+        self.hooks().borrow_mut().push(Rc::downgrade(&hook));
+
+        HookHandle { _hook: hook }
+    }
+
+    fn hooks(&self) -> &RefCell<Vec<WeakHook>> {
+        match self {
+            Self::Browser(m) => &m.hooks,
+            Self::Hash(m) => &m.hooks,
+            Self::Memory(m) => &m.hooks,
+            Self::Navigation(m) => &m.hooks,
+        }
+    }
+
+    fn apply_hooks(&self, kind: TransitionKind, route: impl Into<String>) -> Option<String> {
+        apply_hooks(self.hooks(), Transition::new(kind, route))
+    }
 }
 
 impl History for AnyHistory {
@@ -26,6 +102,34 @@ impl History for AnyHistory {
             Self::Browser(m) => m.len(),
             Self::Hash(m) => m.len(),
             Self::Memory(m) => m.len(),
+            Self::Navigation(m) => m.len(),
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Self::Browser(m) => m.index(),
+            Self::Hash(m) => m.index(),
+            Self::Memory(m) => m.index(),
+            Self::Navigation(m) => m.index(),
+        }
+    }
+
+    fn can_go_back(&self) -> bool {
+        match self {
+            Self::Browser(m) => m.can_go_back(),
+            Self::Hash(m) => m.can_go_back(),
+            Self::Memory(m) => m.can_go_back(),
+            Self::Navigation(m) => m.can_go_back(),
+        }
+    }
+
+    fn can_go_forward(&self) -> bool {
+        match self {
+            Self::Browser(m) => m.can_go_forward(),
+            Self::Hash(m) => m.can_go_forward(),
+            Self::Memory(m) => m.can_go_forward(),
+            Self::Navigation(m) => m.can_go_forward(),
         }
     }
 
@@ -34,22 +138,35 @@ impl History for AnyHistory {
             Self::Browser(m) => m.go(delta),
             Self::Hash(m) => m.go(delta),
             Self::Memory(m) => m.go(delta),
+            Self::Navigation(m) => m.go(delta),
         }
     }
 
     fn push<'a>(&self, route: impl Into<Cow<'a, str>>) {
+        let route = match self.apply_hooks(TransitionKind::Push, route.into()) {
+            Some(m) => m,
+            None => return,
+        };
+
         match self {
             Self::Browser(m) => m.push(route),
             Self::Hash(m) => m.push(route),
             Self::Memory(m) => m.push(route),
+            Self::Navigation(m) => m.push(route),
         }
     }
 
     fn replace<'a>(&self, route: impl Into<Cow<'a, str>>) {
+        let route = match self.apply_hooks(TransitionKind::Replace, route.into()) {
+            Some(m) => m,
+            None => return,
+        };
+
         match self {
             Self::Browser(m) => m.replace(route),
             Self::Hash(m) => m.replace(route),
             Self::Memory(m) => m.replace(route),
+            Self::Navigation(m) => m.replace(route),
         }
     }
 
@@ -57,10 +174,16 @@ impl History for AnyHistory {
     where
         T: 'static,
     {
+        let route = match self.apply_hooks(TransitionKind::Push, route.into()) {
+            Some(m) => m,
+            None => return,
+        };
+
         match self {
             Self::Browser(m) => m.push_with_state(route, state),
             Self::Hash(m) => m.push_with_state(route, state),
             Self::Memory(m) => m.push_with_state(route, state),
+            Self::Navigation(m) => m.push_with_state(route, state),
         }
     }
 
@@ -68,10 +191,16 @@ impl History for AnyHistory {
     where
         T: 'static,
     {
+        let route = match self.apply_hooks(TransitionKind::Replace, route.into()) {
+            Some(m) => m,
+            None => return,
+        };
+
         match self {
             Self::Browser(m) => m.replace_with_state(route, state),
             Self::Hash(m) => m.replace_with_state(route, state),
             Self::Memory(m) => m.replace_with_state(route, state),
+            Self::Navigation(m) => m.replace_with_state(route, state),
         }
     }
 
@@ -84,10 +213,16 @@ impl History for AnyHistory {
     where
         Q: ToQuery,
     {
+        let route = match self.apply_hooks(TransitionKind::Push, route.into()) {
+            Some(m) => m,
+            None => return Ok(()),
+        };
+
         match self {
             Self::Browser(m) => m.push_with_query(route, query),
             Self::Hash(m) => m.push_with_query(route, query),
             Self::Memory(m) => m.push_with_query(route, query),
+            Self::Navigation(m) => m.push_with_query(route, query),
         }
     }
     #[cfg(feature = "query")]
@@ -99,10 +234,16 @@ impl History for AnyHistory {
     where
         Q: ToQuery,
     {
+        let route = match self.apply_hooks(TransitionKind::Replace, route.into()) {
+            Some(m) => m,
+            None => return Ok(()),
+        };
+
         match self {
             Self::Browser(m) => m.replace_with_query(route, query),
             Self::Hash(m) => m.replace_with_query(route, query),
             Self::Memory(m) => m.replace_with_query(route, query),
+            Self::Navigation(m) => m.replace_with_query(route, query),
         }
     }
 
@@ -117,10 +258,16 @@ impl History for AnyHistory {
         Q: ToQuery,
         T: 'static,
     {
+        let route = match self.apply_hooks(TransitionKind::Push, route.into()) {
+            Some(m) => m,
+            None => return Ok(()),
+        };
+
         match self {
             Self::Browser(m) => m.push_with_query_and_state(route, query, state),
             Self::Hash(m) => m.push_with_query_and_state(route, query, state),
             Self::Memory(m) => m.push_with_query_and_state(route, query, state),
+            Self::Navigation(m) => m.push_with_query_and_state(route, query, state),
         }
     }
 
@@ -135,21 +282,79 @@ impl History for AnyHistory {
         Q: ToQuery,
         T: 'static,
     {
+        let route = match self.apply_hooks(TransitionKind::Replace, route.into()) {
+            Some(m) => m,
+            None => return Ok(()),
+        };
+
         match self {
             Self::Browser(m) => m.replace_with_query_and_state(route, query, state),
             Self::Hash(m) => m.replace_with_query_and_state(route, query, state),
             Self::Memory(m) => m.replace_with_query_and_state(route, query, state),
+            Self::Navigation(m) => m.replace_with_query_and_state(route, query, state),
         }
     }
 
-    fn listen<CB>(&self, callback: CB) -> HistoryListener
+    fn push_location(&self, location: LocationBuilder) {
+        let mut location = location;
+        location.path = match self.apply_hooks(TransitionKind::Push, location.path) {
+            Some(m) => m,
+            None => return,
+        };
+
+        match self {
+            Self::Browser(m) => m.push_location(location),
+            Self::Hash(m) => m.push_location(location),
+            Self::Memory(m) => m.push_location(location),
+            Self::Navigation(m) => m.push_location(location),
+        }
+    }
+
+    fn replace_location(&self, location: LocationBuilder) {
+        let mut location = location;
+        location.path = match self.apply_hooks(TransitionKind::Replace, location.path) {
+            Some(m) => m,
+            None => return,
+        };
+
+        match self {
+            Self::Browser(m) => m.replace_location(location),
+            Self::Hash(m) => m.replace_location(location),
+            Self::Memory(m) => m.replace_location(location),
+            Self::Navigation(m) => m.replace_location(location),
+        }
+    }
+
+    fn listen_with_event<CB>(&self, callback: CB) -> HistoryListener
+    where
+        CB: Fn(&NavigationEvent) + 'static,
+    {
+        match self {
+            Self::Browser(m) => m.listen_with_event(callback),
+            Self::Hash(m) => m.listen_with_event(callback),
+            Self::Memory(m) => m.listen_with_event(callback),
+            Self::Navigation(m) => m.listen_with_event(callback),
+        }
+    }
+
+    fn block<CB>(&self, callback: CB) -> BlockerHandle
     where
-        CB: Fn() + 'static,
+        CB: Fn(&Transition) -> bool + 'static,
     {
         match self {
-            Self::Browser(m) => m.listen(callback),
-            Self::Hash(m) => m.listen(callback),
-            Self::Memory(m) => m.listen(callback),
+            Self::Browser(m) => m.block(callback),
+            Self::Hash(m) => m.block(callback),
+            Self::Memory(m) => m.block(callback),
+            Self::Navigation(m) => m.block(callback),
+        }
+    }
+
+    fn entry_storage(&self) -> EntryStorage {
+        match self {
+            Self::Browser(m) => m.entry_storage(),
+            Self::Hash(m) => m.entry_storage(),
+            Self::Memory(m) => m.entry_storage(),
+            Self::Navigation(m) => m.entry_storage(),
         }
     }
 
@@ -158,6 +363,7 @@ impl History for AnyHistory {
             Self::Browser(m) => m.location(),
             Self::Hash(m) => m.location(),
             Self::Memory(m) => m.location(),
+            Self::Navigation(m) => m.location(),
         }
     }
 }
@@ -179,3 +385,9 @@ impl From<MemoryHistory> for AnyHistory {
         AnyHistory::Memory(m)
     }
 }
+
+impl From<NavigationHistory> for AnyHistory {
+    fn from(m: NavigationHistory) -> AnyHistory {
+        AnyHistory::Navigation(m)
+    }
+}
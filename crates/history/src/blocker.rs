@@ -0,0 +1,86 @@
+use std::fmt;
+use std::rc::Rc;
+
+use gloo_events::UnloadGuard;
+
+/// The kind of navigation described by a [`Transition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    /// A new entry is pushed, with `.push()` or one of its variants.
+    Push,
+    /// The current entry is replaced, with `.replace()` or one of its variants.
+    Replace,
+    /// An existing entry is visited, with `.go()`, `.back()`, `.forward()` or the buttons of the
+    /// browser.
+    Pop,
+    /// The page is about to be unloaded, e.g. the tab is closed or a link to another site is
+    /// followed.
+    Unload,
+}
+
+/// A navigation that is about to happen, passed to the callbacks of [`History::block`].
+///
+/// [`History::block`]: crate::History::block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transition {
+    kind: TransitionKind,
+    to: Option<String>,
+}
+
+impl Transition {
+    pub(crate) fn new(kind: TransitionKind, to: impl Into<String>) -> Self {
+        Self {
+            kind,
+            to: Some(to.into()),
+        }
+    }
+
+    pub(crate) fn unload() -> Self {
+        Self {
+            kind: TransitionKind::Unload,
+            to: None,
+        }
+    }
+
+    /// Returns the kind of this navigation.
+    pub fn kind(&self) -> TransitionKind {
+        self.kind
+    }
+
+    /// Returns the path, query and fragment of the target of this navigation, or [`None`] for
+    /// [`TransitionKind::Unload`], where it is not known.
+    pub fn to(&self) -> Option<&str> {
+        self.to.as_deref()
+    }
+
+    pub(crate) fn map_to(self, f: impl FnOnce(String) -> String) -> Self {
+        Self {
+            kind: self.kind,
+            to: self.to.map(f),
+        }
+    }
+}
+
+/// A handle to a navigation blocker registered with [`History::block`].
+///
+/// Like [`HistoryListener`][crate::HistoryListener], the blocker is removed when the handle is
+/// dropped.
+///
+/// [`History::block`]: crate::History::block
+#[must_use = "the blocker is removed when `BlockerHandle` is dropped"]
+pub struct BlockerHandle {
+    pub(crate) _blocker: Rc<dyn Fn(&Transition) -> bool>,
+    pub(crate) _unload_guard: Option<UnloadGuard>,
+}
+
+impl fmt::Debug for BlockerHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockerHandle").finish()
+    }
+}
+
+/// Asks `blocker` before the page is unloaded, as hard navigations can only be cancelled by the
+/// browser itself.
+pub(crate) fn unload_guard(blocker: Rc<dyn Fn(&Transition) -> bool>) -> UnloadGuard {
+    UnloadGuard::new_when(move || !blocker(&Transition::unload()))
+}
@@ -1,18 +1,30 @@
-use std::{any::Any, borrow::Cow, cell::RefCell, fmt, rc::Rc};
+use std::{
+    any::Any,
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    fmt,
+    rc::Rc,
+};
 
 use gloo_events::EventListener;
 use gloo_utils::window;
+use js_sys::Reflect;
 use wasm_bindgen::{JsValue, UnwrapThrowExt};
 use web_sys::Url;
 
+use crate::blocker::{unload_guard, BlockerHandle, Transition, TransitionKind};
 use crate::history::History;
-use crate::listener::HistoryListener;
-use crate::location::Location;
+use crate::listener::{HistoryListener, NavigationEvent};
+use crate::location::{Location, LocationBuilder};
 use crate::state::{HistoryState, StateMap};
-use crate::utils::WeakCallback;
+use crate::storage::EntryStorage;
+use crate::utils::{get_key, is_blocked, WeakBlocker, WeakCallback, WeakHook};
 #[cfg(feature = "query")]
 use crate::{error::HistoryResult, query::ToQuery};
 
+/// The property of the history state holding the states of `push_with_js_state`.
+const JS_STATE_KEY: &str = "value";
+
 /// A [`History`] that is implemented with [`web_sys::History`] that provides native browser
 /// history and state access.
 #[derive(Clone)]
@@ -20,6 +32,17 @@ pub struct BrowserHistory {
     inner: web_sys::History,
     states: Rc<RefCell<StateMap>>,
     callbacks: Rc<RefCell<Vec<WeakCallback>>>,
+    blockers: Rc<RefCell<Vec<WeakBlocker>>>,
+    /// The hooks of the [`AnyHistory`][crate::AnyHistory]s wrapping this history.
+    pub(crate) hooks: Rc<RefCell<Vec<WeakHook>>>,
+    entry_storage: EntryStorage,
+    /// The position of the current entry in the session history.
+    index: Rc<Cell<isize>>,
+    /// The number of entries created by this history that are in the session history.
+    length: Rc<Cell<isize>>,
+    reverting: Rc<Cell<bool>>,
+    /// The path the application is served under, without a trailing slash.
+    basename: Option<Rc<str>>,
 }
 
 impl fmt::Debug for BrowserHistory {
@@ -29,9 +52,9 @@ impl fmt::Debug for BrowserHistory {
 }
 
 impl PartialEq for BrowserHistory {
-    fn eq(&self, _rhs: &Self) -> bool {
-        // All browser histories are created equal.
-        true
+    fn eq(&self, rhs: &Self) -> bool {
+        // All browser histories are created equal, apart from their basename.
+        self.basename == rhs.basename
     }
 }
 
@@ -40,6 +63,17 @@ impl History for BrowserHistory {
         self.inner.length().expect_throw("failed to get length.") as usize
     }
 
+    /// Returns the position of the current entry, counted from the first entry created by this
+    /// history, as the entries of other pages are not known.
+    fn index(&self) -> usize {
+        self.index.get().max(0) as usize
+    }
+
+    fn can_go_forward(&self) -> bool {
+        // The length of the session history includes the entries of other pages.
+        self.index.get() + 1 < self.length.get()
+    }
+
     fn go(&self, delta: isize) {
         self.inner
             .go_with_delta(delta as i32)
@@ -47,59 +81,33 @@ impl History for BrowserHistory {
     }
 
     fn push<'a>(&self, route: impl Into<Cow<'a, str>>) {
-        let url = route.into();
-        self.inner
-            .push_state_with_url(&Self::create_history_state().1, "", Some(&url))
-            .expect_throw("failed to push state.");
+        let url = self.prefix_basename(route.into());
 
-        self.notify_callbacks();
+        self.push_entry(&url, None, None);
     }
 
     fn replace<'a>(&self, route: impl Into<Cow<'a, str>>) {
-        let url = route.into();
-        self.inner
-            .replace_state_with_url(&Self::create_history_state().1, "", Some(&url))
-            .expect_throw("failed to replace history.");
+        let url = self.prefix_basename(route.into());
 
-        self.notify_callbacks();
+        self.replace_entry(&url, None, None);
     }
 
     fn push_with_state<'a, T>(&self, route: impl Into<Cow<'a, str>>, state: T)
     where
         T: 'static,
     {
-        let url = route.into();
+        let url = self.prefix_basename(route.into());
 
-        let (id, history_state) = Self::create_history_state();
-
-        let mut states = self.states.borrow_mut();
-        states.insert(id, Rc::new(state) as Rc<dyn Any>);
-
-        self.inner
-            .push_state_with_url(&history_state, "", Some(&url))
-            .expect_throw("failed to push state.");
-
-        drop(states);
-        self.notify_callbacks();
+        self.push_entry(&url, Some(Rc::new(state) as Rc<dyn Any>), None);
     }
 
     fn replace_with_state<'a, T>(&self, route: impl Into<Cow<'a, str>>, state: T)
     where
         T: 'static,
     {
-        let url = route.into();
-
-        let (id, history_state) = Self::create_history_state();
-
-        let mut states = self.states.borrow_mut();
-        states.insert(id, Rc::new(state) as Rc<dyn Any>);
+        let url = self.prefix_basename(route.into());
 
-        self.inner
-            .replace_state_with_url(&history_state, "", Some(&url))
-            .expect_throw("failed to replace state.");
-
-        drop(states);
-        self.notify_callbacks();
+        self.replace_entry(&url, Some(Rc::new(state) as Rc<dyn Any>), None);
     }
 
     #[cfg(feature = "query")]
@@ -111,16 +119,12 @@ impl History for BrowserHistory {
     where
         Q: ToQuery,
     {
-        let route = route.into();
+        let route = self.prefix_basename(route.into());
         let query = query.to_query()?;
 
         let url = Self::combine_url(&route, &query);
 
-        self.inner
-            .push_state_with_url(&Self::create_history_state().1, "", Some(&url))
-            .expect_throw("failed to push history.");
-
-        self.notify_callbacks();
+        self.push_entry(&url, None, None);
         Ok(())
     }
 
@@ -133,16 +137,12 @@ impl History for BrowserHistory {
     where
         Q: ToQuery,
     {
-        let route = route.into();
+        let route = self.prefix_basename(route.into());
         let query = query.to_query()?;
 
         let url = Self::combine_url(&route, &query);
 
-        self.inner
-            .replace_state_with_url(&Self::create_history_state().1, "", Some(&url))
-            .expect_throw("failed to replace history.");
-
-        self.notify_callbacks();
+        self.replace_entry(&url, None, None);
         Ok(())
     }
 
@@ -157,22 +157,12 @@ impl History for BrowserHistory {
         Q: ToQuery,
         T: 'static,
     {
-        let (id, history_state) = Self::create_history_state();
-
-        let mut states = self.states.borrow_mut();
-        states.insert(id, Rc::new(state) as Rc<dyn Any>);
-
-        let route = route.into();
+        let route = self.prefix_basename(route.into());
         let query = query.to_query()?;
 
         let url = Self::combine_url(&route, &query);
 
-        self.inner
-            .push_state_with_url(&history_state, "", Some(&url))
-            .expect_throw("failed to push history.");
-
-        drop(states);
-        self.notify_callbacks();
+        self.push_entry(&url, Some(Rc::new(state) as Rc<dyn Any>), None);
         Ok(())
     }
 
@@ -187,49 +177,77 @@ impl History for BrowserHistory {
         Q: ToQuery,
         T: 'static,
     {
-        let (id, history_state) = Self::create_history_state();
-
-        let mut states = self.states.borrow_mut();
-        states.insert(id, Rc::new(state) as Rc<dyn Any>);
-
-        let route = route.into();
+        let route = self.prefix_basename(route.into());
         let query = query.to_query()?;
 
         let url = Self::combine_url(&route, &query);
 
-        self.inner
-            .replace_state_with_url(&history_state, "", Some(&url))
-            .expect_throw("failed to replace history.");
-
-        drop(states);
-        self.notify_callbacks();
+        self.replace_entry(&url, Some(Rc::new(state) as Rc<dyn Any>), None);
         Ok(())
     }
 
-    fn listen<CB>(&self, callback: CB) -> HistoryListener
+    fn push_location(&self, location: LocationBuilder) {
+        let url = self.prefix_basename(location.href().into());
+
+        self.push_entry(&url, location.state, None);
+    }
+
+    fn replace_location(&self, location: LocationBuilder) {
+        let url = self.prefix_basename(location.href().into());
+
+        self.replace_entry(&url, location.state, None);
+    }
+
+    fn listen_with_event<CB>(&self, callback: CB) -> HistoryListener
     where
-        CB: Fn() + 'static,
+        CB: Fn(&NavigationEvent) + 'static,
     {
         // Callbacks do not receive a copy of [`History`] to prevent reference cycle.
-        let cb = Rc::new(callback) as Rc<dyn Fn()>;
+        let cb = Rc::new(callback) as Rc<dyn Fn(&NavigationEvent)>;
 
         self.callbacks.borrow_mut().push(Rc::downgrade(&cb));
 
         HistoryListener { _listener: cb }
     }
 
+    fn block<CB>(&self, callback: CB) -> BlockerHandle
+    where
+        CB: Fn(&Transition) -> bool + 'static,
+    {
+        let blocker = match self.basename.clone() {
+            Some(basename) => Rc::new(move |transition: &Transition| {
+                let transition = transition
+                    .clone()
+                    .map_to(|to| strip_basename(&basename, to));
+                callback(&transition)
+            }) as Rc<dyn Fn(&Transition) -> bool>,
+            None => Rc::new(callback) as Rc<dyn Fn(&Transition) -> bool>,
+        };
+
+        self.blockers.borrow_mut().push(Rc::downgrade(&blocker));
+
+        BlockerHandle {
+            _unload_guard: Some(unload_guard(blocker.clone())),
+            _blocker: blocker,
+        }
+    }
+
+    fn entry_storage(&self) -> EntryStorage {
+        self.entry_storage.clone()
+    }
+
     fn location(&self) -> Location {
         let loc = window().location();
 
-        let history_state = self.inner.state().expect_throw("failed to get state");
-        let history_state = serde_wasm_bindgen::from_value::<HistoryState>(history_state).ok();
-
-        let id = history_state.map(|m| m.id());
+        let history_state = self.history_state();
+        let id = history_state.as_ref().map(|m| m.id());
 
         let states = self.states.borrow();
 
         Location {
-            path: loc.pathname().expect_throw("failed to get pathname").into(),
+            path: self
+                .strip_basename(loc.pathname().expect_throw("failed to get pathname"))
+                .into(),
             query_str: loc
                 .search()
                 .expect_throw("failed to get location query.")
@@ -239,7 +257,9 @@ impl History for BrowserHistory {
                 .expect_throw("failed to get location hash.")
                 .into(),
             state: id.and_then(|m| states.get(&m).cloned()),
+            js_state: id.and_then(|_| self.js_state()),
             id,
+            key: history_state.and_then(|m| m.key().map(Rc::from)),
         }
     }
 }
@@ -260,14 +280,42 @@ impl Default for BrowserHistory {
                     inner,
                     callbacks,
                     states: Rc::default(),
+                    blockers: Rc::default(),
+                    hooks: Rc::default(),
+                    entry_storage: EntryStorage::default(),
+                    index: Rc::default(),
+                    length: Rc::new(Cell::new(1)),
+                    reverting: Rc::default(),
+                    basename: None,
                 };
 
+                // The position of the current entry is needed to revert blocked navigations.
+                let history_state = history.history_state();
+                match history_state.as_ref().and_then(|m| m.index()) {
+                    Some(index) => {
+                        let length = history_state.and_then(|m| m.length()).unwrap_or(0);
+
+                        history.index.set(index);
+                        history.length.set(length.max(index + 1));
+                    }
+                    None => {
+                        let state = history.inner.state().expect_throw("failed to get state");
+                        // Leaves the states of other libraries alone.
+                        if state.is_null() || state.is_undefined() {
+                            history
+                                .inner
+                                .replace_state(&BrowserHistory::create_history_state(0, 1, &get_key()).1, "")
+                                .expect_throw("failed to replace state.");
+                        }
+                    }
+                }
+
                 let listener = {
                     let history = history.clone();
 
                     // Listens to popstate.
                     EventListener::new(&window, "popstate", move |_| {
-                        history.notify_callbacks();
+                        history.handle_popstate();
                     })
                 };
 
@@ -285,12 +333,236 @@ impl BrowserHistory {
         Self::default()
     }
 
-    fn notify_callbacks(&self) {
-        crate::utils::notify_callbacks(self.callbacks.clone());
+    /// Creates a new [`BrowserHistory`] for an application served under `basename`, e.g.
+    /// `/app`.
+    ///
+    /// Absolute routes are prefixed with the basename when pushed or replaced, so
+    /// `.push("/about")` goes to `/app/about`, and the basename is stripped from the path of
+    /// [`location()`](History::location). Relative routes are resolved against the current
+    /// URL as usual.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_history::{BrowserHistory, History};
+    ///
+    /// let history = BrowserHistory::with_basename("/app");
+    /// history.push("/about");
+    /// assert_eq!(history.location().path(), "/about");
+    /// ```
+    pub fn with_basename(basename: impl AsRef<str>) -> Self {
+        let basename = basename.as_ref().trim_end_matches('/');
+        let basename = match basename.strip_prefix('/') {
+            Some(_) => basename.to_string(),
+            None => format!("/{basename}"),
+        };
+
+        Self {
+            basename: Some(basename)
+                .filter(|basename| basename != "/")
+                .map(Rc::from),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the basename this history was created with, see
+    /// [`with_basename`](Self::with_basename).
+    pub fn basename(&self) -> Option<&str> {
+        self.basename.as_deref()
+    }
+
+    fn prefix_basename<'a>(&self, route: Cow<'a, str>) -> Cow<'a, str> {
+        match &self.basename {
+            Some(basename) if route.starts_with('/') => format!("{basename}{route}").into(),
+            _ => route,
+        }
+    }
+
+    fn strip_basename(&self, path: String) -> String {
+        match &self.basename {
+            Some(basename) => strip_basename(basename, path),
+            None => path,
+        }
+    }
+
+    fn notify_callbacks(&self, event: NavigationEvent) {
+        crate::utils::notify_callbacks(self.callbacks.clone(), &event);
     }
 
-    fn create_history_state() -> (u32, JsValue) {
-        let history_state = HistoryState::new();
+    pub(crate) fn push_entry(
+        &self,
+        url: &str,
+        state: Option<Rc<dyn Any>>,
+        js_state: Option<&JsValue>,
+    ) {
+        if self.is_blocked(TransitionKind::Push, url) {
+            return;
+        }
+
+        let index = self.index.get() + 1;
+        // Pushing discards the entries after the current one.
+        let (id, history_state) = Self::create_history_state(index, index + 1, &get_key());
+
+        if let Some(js_state) = js_state {
+            Reflect::set(&history_state, &JsValue::from_str(JS_STATE_KEY), js_state)
+                .expect_throw("failed to set state.");
+        }
+
+        if let Some(state) = state {
+            self.states.borrow_mut().insert(id, state);
+        }
+
+        self.inner
+            .push_state_with_url(&history_state, "", Some(url))
+            .expect_throw("failed to push state.");
+
+        self.index.set(index);
+        self.length.set(index + 1);
+        self.notify_callbacks(NavigationEvent::push());
+    }
+
+    pub(crate) fn replace_entry(
+        &self,
+        url: &str,
+        state: Option<Rc<dyn Any>>,
+        js_state: Option<&JsValue>,
+    ) {
+        if self.is_blocked(TransitionKind::Replace, url) {
+            return;
+        }
+
+        // The entry keeps its key.
+        let key = self
+            .history_state()
+            .and_then(|m| m.key().map(Rc::from))
+            .unwrap_or_else(get_key);
+        let (id, history_state) =
+            Self::create_history_state(self.index.get(), self.length.get(), &key);
+
+        if let Some(js_state) = js_state {
+            Reflect::set(&history_state, &JsValue::from_str(JS_STATE_KEY), js_state)
+                .expect_throw("failed to set state.");
+        }
+
+        if let Some(state) = state {
+            self.states.borrow_mut().insert(id, state);
+        }
+
+        self.inner
+            .replace_state_with_url(&history_state, "", Some(url))
+            .expect_throw("failed to replace state.");
+
+        self.notify_callbacks(NavigationEvent::replace());
+    }
+
+    fn is_blocked(&self, kind: TransitionKind, url: &str) -> bool {
+        let href = window()
+            .location()
+            .href()
+            .expect_throw("Failed to read location href");
+
+        let url = Url::new_with_base(url, &href).expect_throw("current url is not valid.");
+        let to = format!("{}{}{}", url.pathname(), url.search(), url.hash());
+
+        is_blocked(&self.blockers, &Transition::new(kind, to))
+    }
+
+    fn handle_popstate(&self) {
+        // The popstate of a reverted navigation.
+        if self.reverting.replace(false) {
+            return;
+        }
+
+        // Entries not created by this history can't be returned to, so they are never blocked.
+        let delta = match self.history_state().and_then(|m| m.index()) {
+            Some(index) => {
+                let delta = index - self.index.get();
+                // Like pushes and replaces, blockers are given the full path, as the basename is
+                // stripped by the wrappers added in `block`.
+                let loc = window().location();
+                let to = format!(
+                    "{}{}{}",
+                    loc.pathname().expect_throw("failed to get pathname"),
+                    loc.search().expect_throw("failed to get location query."),
+                    loc.hash().expect_throw("failed to get location hash."),
+                );
+                let transition = Transition::new(TransitionKind::Pop, to);
+
+                if delta != 0 && is_blocked(&self.blockers, &transition) {
+                    self.reverting.set(true);
+                    self.go(-delta);
+                    return;
+                }
+
+                self.index.set(index);
+                self.length.set(self.length.get().max(index + 1));
+                self.record_length();
+                Some(delta)
+            }
+            None => None,
+        };
+
+        self.notify_callbacks(NavigationEvent::pop(delta));
+    }
+
+    /// Pushes a route entry with a state that is stored in the session history of the browser.
+    ///
+    /// Unlike the states of [`push_with_state`](History::push_with_state), which are kept in
+    /// memory, `state` is copied with the [structured clone algorithm], so it can be any value
+    /// the browser can clone, such as a `Blob` or a typed array, without implementing `serde`
+    /// traits, and it survives reloads of the page. It is read back with
+    /// [`Location::js_state`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `state` can't be cloned, e.g. if it is a function or a DOM node.
+    ///
+    /// [structured clone algorithm]: https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API/Structured_clone_algorithm
+    pub fn push_with_js_state<'a>(&self, route: impl Into<Cow<'a, str>>, state: &JsValue) {
+        let url = self.prefix_basename(route.into());
+
+        self.push_entry(&url, None, Some(state));
+    }
+
+    /// Replaces the current history entry with provided route and a state that is stored in the
+    /// session history of the browser.
+    ///
+    /// See [`push_with_js_state`](Self::push_with_js_state) for details.
+    pub fn replace_with_js_state<'a>(&self, route: impl Into<Cow<'a, str>>, state: &JsValue) {
+        let url = self.prefix_basename(route.into());
+
+        self.replace_entry(&url, None, Some(state));
+    }
+
+    fn js_state(&self) -> Option<JsValue> {
+        let history_state = self.inner.state().expect_throw("failed to get state");
+        Reflect::get(&history_state, &JsValue::from_str(JS_STATE_KEY))
+            .ok()
+            .filter(|m| !m.is_undefined())
+    }
+
+    fn history_state(&self) -> Option<HistoryState> {
+        let history_state = self.inner.state().expect_throw("failed to get state");
+        serde_wasm_bindgen::from_value::<HistoryState>(history_state).ok()
+    }
+
+    /// Stores the known length in the current entry, so it can be restored after a reload.
+    fn record_length(&self) {
+        let history_state = self.inner.state().expect_throw("failed to get state");
+        Reflect::set(
+            &history_state,
+            &JsValue::from_str("length"),
+            &JsValue::from_f64(self.length.get() as f64),
+        )
+        .expect_throw("failed to set state.");
+
+        self.inner
+            .replace_state(&history_state, "")
+            .expect_throw("failed to replace state.");
+    }
+
+    fn create_history_state(index: isize, length: isize, key: &str) -> (u32, JsValue) {
+        let history_state = HistoryState::new(index, length, key.to_owned());
 
         (
             history_state.id(),
@@ -312,3 +584,13 @@ impl BrowserHistory {
         url.href()
     }
 }
+
+/// Strips `basename` from `path`, leaving paths outside of it untouched.
+fn strip_basename(basename: &str, path: String) -> String {
+    match path.strip_prefix(basename) {
+        Some(rest) if rest.starts_with('/') => rest.to_string(),
+        // The basename itself, possibly followed by a query or a hash.
+        Some(rest) if rest.is_empty() || rest.starts_with(['?', '#']) => format!("/{rest}"),
+        _ => path,
+    }
+}
@@ -11,6 +11,10 @@ pub enum HistoryError {
     #[cfg(feature = "query")]
     #[error("failed to deserialize query.")]
     QueryDe(#[from] serde_urlencoded::de::Error),
+    /// Failed to deserialize route parameters.
+    #[cfg(feature = "query")]
+    #[error("failed to deserialize route parameters.")]
+    RouteParams(#[source] serde_urlencoded::de::Error),
 }
 
 /// The Result type for History.
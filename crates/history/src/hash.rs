@@ -1,25 +1,44 @@
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::{borrow::Cow, fmt};
 
 use gloo_utils::window;
-use wasm_bindgen::UnwrapThrowExt;
+use wasm_bindgen::{JsValue, UnwrapThrowExt};
 use web_sys::Url;
 
+use crate::blocker::{BlockerHandle, Transition};
 use crate::browser::BrowserHistory;
 use crate::history::History;
-use crate::listener::HistoryListener;
-use crate::location::Location;
-use crate::utils::{assert_absolute_path, assert_no_query};
+use crate::listener::{HistoryListener, NavigationEvent};
+use crate::location::{Location, LocationBuilder};
+use crate::storage::EntryStorage;
+#[cfg(feature = "query")]
+use crate::utils::assert_no_query;
+use crate::utils::{assert_absolute_path, WeakHook};
 #[cfg(feature = "query")]
 use crate::{error::HistoryResult, query::ToQuery};
 
 /// A [`History`] that is implemented with [`web_sys::History`] and stores path in `#`(fragment).
 ///
+/// The query of a route is stored in the fragment as well, e.g. `/#/search?q=rust`, so it can be
+/// pushed inline with `.push("/search?q=rust")` or with `.push_with_query()`, and read with
+/// `Location::query` like with other histories. The query of the page itself, before the
+/// `#`, is left alone.
+///
 /// # Panics
 ///
 /// HashHistory does not support relative paths and will panic if routes are not starting with `/`.
-#[derive(Clone, PartialEq)]
+#[derive(Clone)]
 pub struct HashHistory {
     inner: BrowserHistory,
+    /// The hooks of the [`AnyHistory`][crate::AnyHistory]s wrapping this history.
+    pub(crate) hooks: Rc<RefCell<Vec<WeakHook>>>,
+}
+
+impl PartialEq for HashHistory {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.inner == rhs.inner
+    }
 }
 
 impl fmt::Debug for HashHistory {
@@ -33,6 +52,18 @@ impl History for HashHistory {
         self.inner.len()
     }
 
+    fn index(&self) -> usize {
+        self.inner.index()
+    }
+
+    fn can_go_back(&self) -> bool {
+        self.inner.can_go_back()
+    }
+
+    fn can_go_forward(&self) -> bool {
+        self.inner.can_go_forward()
+    }
+
     fn go(&self, delta: isize) {
         self.inner.go(delta)
     }
@@ -41,24 +72,16 @@ impl History for HashHistory {
         let route = route.into();
 
         assert_absolute_path(&route);
-        assert_no_query(&route);
-
-        let url = Self::get_url();
-        url.set_hash(&route);
 
-        self.inner.push(url.href());
+        self.inner.push(Self::hash_url(&route, ""));
     }
 
     fn replace<'a>(&self, route: impl Into<Cow<'a, str>>) {
         let route = route.into();
 
         assert_absolute_path(&route);
-        assert_no_query(&route);
-
-        let url = Self::get_url();
-        url.set_hash(&route);
 
-        self.inner.replace(url.href());
+        self.inner.replace(Self::hash_url(&route, ""));
     }
 
     fn push_with_state<'a, T>(&self, route: impl Into<Cow<'a, str>>, state: T)
@@ -68,12 +91,9 @@ impl History for HashHistory {
         let route = route.into();
 
         assert_absolute_path(&route);
-        assert_no_query(&route);
-
-        let url = Self::get_url();
-        url.set_hash(&route);
 
-        self.inner.push_with_state(url.href(), state)
+        self.inner
+            .push_with_state(Self::hash_url(&route, ""), state)
     }
 
     fn replace_with_state<'a, T>(&self, route: impl Into<Cow<'a, str>>, state: T)
@@ -83,12 +103,9 @@ impl History for HashHistory {
         let route = route.into();
 
         assert_absolute_path(&route);
-        assert_no_query(&route);
 
-        let url = Self::get_url();
-        url.set_hash(&route);
-
-        self.inner.replace_with_state(url.href(), state)
+        self.inner
+            .replace_with_state(Self::hash_url(&route, ""), state)
     }
 
     #[cfg(feature = "query")]
@@ -106,10 +123,7 @@ impl History for HashHistory {
         assert_absolute_path(&route);
         assert_no_query(&route);
 
-        let url = Self::get_url();
-        url.set_hash(&format!("{route}?{query}"));
-
-        self.inner.push(url.href());
+        self.inner.push(Self::hash_url(&route, &query));
         Ok(())
     }
     #[cfg(feature = "query")]
@@ -127,10 +141,7 @@ impl History for HashHistory {
         assert_absolute_path(&route);
         assert_no_query(&route);
 
-        let url = Self::get_url();
-        url.set_hash(&format!("{route}?{query}"));
-
-        self.inner.replace(url.href());
+        self.inner.replace(Self::hash_url(&route, &query));
         Ok(())
     }
 
@@ -150,12 +161,9 @@ impl History for HashHistory {
         assert_absolute_path(&route);
         assert_no_query(&route);
 
-        let url = Self::get_url();
-
         let query = query.to_query()?;
-        url.set_hash(&format!("{route}?{query}"));
-
-        self.inner.push_with_state(url.href(), state);
+        self.inner
+            .push_with_state(Self::hash_url(&route, &query), state);
 
         Ok(())
     }
@@ -176,21 +184,55 @@ impl History for HashHistory {
         assert_absolute_path(&route);
         assert_no_query(&route);
 
-        let url = Self::get_url();
-
         let query = query.to_query()?;
-        url.set_hash(&format!("{route}?{query}"));
-
-        self.inner.replace_with_state(url.href(), state);
+        self.inner
+            .replace_with_state(Self::hash_url(&route, &query), state);
 
         Ok(())
     }
 
-    fn listen<CB>(&self, callback: CB) -> HistoryListener
+    fn push_location(&self, location: LocationBuilder) {
+        let route = location.href();
+
+        assert_absolute_path(&route);
+
+        self.inner
+            .push_entry(&Self::hash_url(&route, ""), location.state, None);
+    }
+
+    fn replace_location(&self, location: LocationBuilder) {
+        let route = location.href();
+
+        assert_absolute_path(&route);
+
+        self.inner
+            .replace_entry(&Self::hash_url(&route, ""), location.state, None);
+    }
+
+    fn listen_with_event<CB>(&self, callback: CB) -> HistoryListener
+    where
+        CB: Fn(&NavigationEvent) + 'static,
+    {
+        self.inner.listen_with_event(callback)
+    }
+
+    fn block<CB>(&self, callback: CB) -> BlockerHandle
     where
-        CB: Fn() + 'static,
+        CB: Fn(&Transition) -> bool + 'static,
     {
-        self.inner.listen(callback)
+        self.inner.block(move |transition| {
+            // The route of a hash history is stored in the fragment of the browser url.
+            let transition = transition.clone().map_to(|to| match to.split_once('#') {
+                Some((_, route)) => route.to_string(),
+                None => "/".to_string(),
+            });
+
+            callback(&transition)
+        })
+    }
+
+    fn entry_storage(&self) -> EntryStorage {
+        self.inner.entry_storage()
     }
 
     fn location(&self) -> Location {
@@ -215,6 +257,8 @@ impl History for HashHistory {
             hash: hash_url.hash().into(),
             id: inner_loc.id,
             state: inner_loc.state,
+            js_state: inner_loc.js_state,
+            key: inner_loc.key,
         }
     }
 }
@@ -225,6 +269,44 @@ impl HashHistory {
         Self::default()
     }
 
+    /// Pushes a route entry with a state that is stored in the session history of the browser.
+    ///
+    /// See [`BrowserHistory::push_with_js_state`] for details.
+    pub fn push_with_js_state<'a>(&self, route: impl Into<Cow<'a, str>>, state: &JsValue) {
+        let route = route.into();
+
+        assert_absolute_path(&route);
+
+        self.inner
+            .push_with_js_state(Self::hash_url(&route, ""), state)
+    }
+
+    /// Replaces the current history entry with provided route and a state that is stored in the
+    /// session history of the browser.
+    ///
+    /// See [`BrowserHistory::push_with_js_state`] for details.
+    pub fn replace_with_js_state<'a>(&self, route: impl Into<Cow<'a, str>>, state: &JsValue) {
+        let route = route.into();
+
+        assert_absolute_path(&route);
+
+        self.inner
+            .replace_with_js_state(Self::hash_url(&route, ""), state)
+    }
+
+    /// Returns the current url with `route`, followed by `query` if it is not empty, as its
+    /// fragment.
+    fn hash_url(route: &str, query: &str) -> String {
+        let url = Self::get_url();
+        if query.is_empty() {
+            url.set_hash(route);
+        } else {
+            url.set_hash(&format!("{route}?{query}"));
+        }
+
+        url.href()
+    }
+
     fn get_url() -> Url {
         let href = window()
             .location()
@@ -254,6 +336,7 @@ impl Default for HashHistory {
 
                 HashHistory {
                     inner: browser_history,
+                    hooks: Rc::default(),
                 }
             };
         }
@@ -1,7 +1,11 @@
 use std::borrow::Cow;
 
-use crate::listener::HistoryListener;
-use crate::location::Location;
+use crate::blocker::{BlockerHandle, Transition};
+use crate::listener::{HistoryListener, NavigationEvent};
+use crate::location::{Location, LocationBuilder};
+use crate::storage::EntryStorage;
+#[cfg(feature = "futures")]
+use crate::stream::HistoryStream;
 #[cfg(feature = "query")]
 use crate::{error::HistoryResult, query::ToQuery};
 
@@ -20,6 +24,19 @@ pub trait History: Clone + PartialEq {
         self.len() == 0
     }
 
+    /// Returns the position of the current entry in [`History`], starting from 0.
+    fn index(&self) -> usize;
+
+    /// Returns true if there is an entry before the current one to go back to.
+    fn can_go_back(&self) -> bool {
+        self.index() > 0
+    }
+
+    /// Returns true if there is an entry after the current one to go forward to.
+    fn can_go_forward(&self) -> bool {
+        self.index() + 1 < self.len()
+    }
+
     /// Moves back 1 page in [`History`].
     fn back(&self) {
         self.go(-1);
@@ -95,13 +112,112 @@ pub trait History: Clone + PartialEq {
         Q: ToQuery,
         T: 'static;
 
+    /// Pushes a location built with a [`LocationBuilder`].
+    fn push_location(&self, location: LocationBuilder);
+
+    /// Replaces the current history entry with a location built with a [`LocationBuilder`].
+    fn replace_location(&self, location: LocationBuilder);
+
+    /// Pushes an entry at `path` with the state of the current location.
+    fn push_preserving_state(&self, path: impl Into<String>) {
+        let location = self.location().to_builder();
+        self.push_location(location.path(path).query_str("").hash(""));
+    }
+
+    /// Replaces the queries of the current location with `query`, keeping its path, hash and
+    /// state.
+    #[cfg(feature = "query")]
+    fn replace_query<Q>(&self, query: Q) -> HistoryResult<(), Q::Error>
+    where
+        Q: ToQuery,
+    {
+        let location = self.location().to_builder().query(query)?;
+        self.replace_location(location);
+        Ok(())
+    }
+
+    /// Replaces the hash fragment of the current location with `hash`, keeping its path, queries
+    /// and state.
+    fn replace_hash(&self, hash: impl AsRef<str>) {
+        let location = self.location().to_builder().hash(hash);
+        self.replace_location(location);
+    }
+
     /// Creates a Listener that will be notified when current state changes.
     ///
     /// This method returns a [`HistoryListener`] that will automatically unregister the callback
     /// when dropped.
     fn listen<CB>(&self, callback: CB) -> HistoryListener
     where
-        CB: Fn() + 'static;
+        CB: Fn() + 'static,
+    {
+        self.listen_with_event(move |_| callback())
+    }
+
+    /// Same as `.listen()` but the callback receives the [`NavigationEvent`] that happened, to
+    /// tell pushes from pops and going back from going forward, e.g. to animate page
+    /// transitions accordingly.
+    fn listen_with_event<CB>(&self, callback: CB) -> HistoryListener
+    where
+        CB: Fn(&NavigationEvent) + 'static;
+
+    /// Returns a [`Stream`](futures_core::Stream) of the [`Location`]s this history navigates
+    /// to, as an alternative to [`listen`](Self::listen) for async code.
+    ///
+    /// Like the callbacks of `listen`, the stream yields a location after each change, not the
+    /// current one. The stream unregisters itself when dropped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use gloo_history::{BrowserHistory, History};
+    /// use wasm_bindgen_futures::spawn_local;
+    ///
+    /// let history = BrowserHistory::new();
+    /// spawn_local(async move {
+    ///     let mut locations = history.stream();
+    ///     while let Some(location) = locations.next().await {
+    ///         // Render the page at `location.path()`...
+    ///     }
+    /// });
+    /// ```
+    #[cfg(feature = "futures")]
+    fn stream(&self) -> HistoryStream
+    where
+        Self: 'static,
+    {
+        HistoryStream::new(self)
+    }
+
+    /// Registers a callback that is asked to confirm every navigation before it happens, e.g.
+    /// to protect unsaved changes.
+    ///
+    /// The callback receives the [`Transition`] about to happen and returns `true` to let it
+    /// through or `false` to cancel it. A cancelled `.push()` or `.replace()` does nothing, and
+    /// a cancelled `.go()` or press of the back button is reverted. With several blockers, the
+    /// navigation is cancelled as soon as one of them returns `false`.
+    ///
+    /// Browser and hash histories also ask the callback before the page is unloaded, with a
+    /// [`TransitionKind::Unload`] transition. Browsers don't allow dialogs at that point, so the
+    /// callback must not prompt the user itself: returning `false` makes the browser show its
+    /// own confirmation dialog instead. This is done with a [`gloo_events::UnloadGuard`], which
+    /// can be held on its own when only unloads need to be confirmed.
+    ///
+    /// Entries that were not created by this history, e.g. before the page was loaded, can't be
+    /// returned to in a controlled way, and navigating to them is never blocked.
+    ///
+    /// This method returns a [`BlockerHandle`] that will automatically unregister the callback
+    /// when dropped.
+    ///
+    /// [`TransitionKind::Unload`]: crate::TransitionKind::Unload
+    fn block<CB>(&self, callback: CB) -> BlockerHandle
+    where
+        CB: Fn(&Transition) -> bool + 'static;
+
+    /// Returns the [`EntryStorage`] of this history, where UI state can be stored for each
+    /// entry by its [key](Location::key).
+    fn entry_storage(&self) -> EntryStorage;
 
     /// Returns current [`Location`].
     fn location(&self) -> Location;
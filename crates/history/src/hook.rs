@@ -0,0 +1,26 @@
+use std::fmt;
+use std::rc::Rc;
+
+use crate::blocker::Transition;
+
+/// A hook of an [`AnyHistory`][crate::AnyHistory], see [`AnyHistory::hook`].
+///
+/// [`AnyHistory::hook`]: crate::AnyHistory::hook
+pub(crate) type Hook = dyn Fn(&Transition) -> Option<String>;
+
+/// A handle to a navigation hook registered with [`AnyHistory::hook`].
+///
+/// Like [`HistoryListener`][crate::HistoryListener], the hook is removed when the handle is
+/// dropped.
+///
+/// [`AnyHistory::hook`]: crate::AnyHistory::hook
+#[must_use = "the hook is removed when `HookHandle` is dropped"]
+pub struct HookHandle {
+    pub(crate) _hook: Rc<Hook>,
+}
+
+impl fmt::Debug for HookHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HookHandle").finish()
+    }
+}
@@ -4,26 +4,39 @@
 #![deny(missing_docs, missing_debug_implementations)]
 
 mod any;
+mod blocker;
 mod browser;
 #[cfg(feature = "query")]
 mod error;
 mod hash;
 mod history;
+mod hook;
 mod listener;
 mod location;
 mod memory;
+mod navigation;
 #[cfg(feature = "query")]
 pub mod query;
+pub mod route;
 mod state;
+mod storage;
+#[cfg(feature = "futures")]
+mod stream;
 mod utils;
 
 pub use any::AnyHistory;
+pub use blocker::{BlockerHandle, Transition, TransitionKind};
 pub use browser::BrowserHistory;
 pub use hash::HashHistory;
-pub use memory::MemoryHistory;
+pub use memory::{MemoryHistory, MemoryHistorySnapshot};
+pub use navigation::{NavigateEvent, NavigationHistory, NavigationType};
 
 #[cfg(feature = "query")]
 pub use error::{HistoryError, HistoryResult};
 pub use history::History;
-pub use listener::HistoryListener;
-pub use location::Location;
+pub use hook::HookHandle;
+pub use listener::{Action, Direction, HistoryListener, NavigationEvent};
+pub use location::{Location, LocationBuilder};
+pub use storage::EntryStorage;
+#[cfg(feature = "futures")]
+pub use stream::HistoryStream;
@@ -7,7 +7,7 @@ use std::rc::Rc;
 /// `gloo` that the underlying callback will be unregistered when the listener is dropped.
 #[must_use = "the listener is removed when `HistoryListener` is dropped"]
 pub struct HistoryListener {
-    pub(crate) _listener: Rc<dyn Fn()>,
+    pub(crate) _listener: Rc<dyn Fn(&NavigationEvent)>,
 }
 
 impl fmt::Debug for HistoryListener {
@@ -15,3 +15,79 @@ impl fmt::Debug for HistoryListener {
         f.debug_struct("HistoryListener").finish()
     }
 }
+
+/// The kind of navigation of a [`NavigationEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// A new entry was pushed, with `.push()` or one of its variants.
+    Push,
+    /// The current entry was replaced, with `.replace()` or one of its variants.
+    Replace,
+    /// An existing entry was visited, with `.go()`, `.back()`, `.forward()` or the buttons of
+    /// the browser.
+    Pop,
+}
+
+/// The direction of a [`NavigationEvent`] in the session history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Towards older entries.
+    Back,
+    /// Towards newer entries, including new ones.
+    Forward,
+}
+
+/// A navigation that happened, passed to the callbacks of
+/// [`History::listen_with_event`][crate::History::listen_with_event].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NavigationEvent {
+    action: Action,
+    delta: Option<isize>,
+}
+
+impl NavigationEvent {
+    pub(crate) fn push() -> Self {
+        Self {
+            action: Action::Push,
+            delta: Some(1),
+        }
+    }
+
+    pub(crate) fn replace() -> Self {
+        Self {
+            action: Action::Replace,
+            delta: Some(0),
+        }
+    }
+
+    pub(crate) fn pop(delta: Option<isize>) -> Self {
+        Self {
+            action: Action::Pop,
+            delta,
+        }
+    }
+
+    /// Returns the kind of this navigation.
+    pub fn action(&self) -> Action {
+        self.action
+    }
+
+    /// Returns the number of entries moved by this navigation: `1` for a push, `0` for a replace
+    /// and the `delta` of `.go()` for a pop.
+    ///
+    /// Returns [`None`] for pops to entries not created by this history, e.g. before the page was
+    /// loaded, whose position is not known.
+    pub fn delta(&self) -> Option<isize> {
+        self.delta
+    }
+
+    /// Returns the direction of this navigation, or [`None`] for replaces and pops of unknown
+    /// [`delta`](Self::delta).
+    pub fn direction(&self) -> Option<Direction> {
+        match self.delta {
+            Some(delta) if delta < 0 => Some(Direction::Back),
+            Some(delta) if delta > 0 => Some(Direction::Forward),
+            _ => None,
+        }
+    }
+}
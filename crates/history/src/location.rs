@@ -1,8 +1,14 @@
 use std::any::Any;
 use std::rc::Rc;
 
+use wasm_bindgen::JsValue;
+
+use crate::route::{Route, RouteMatch};
 #[cfg(feature = "query")]
-use crate::{error::HistoryResult, query::FromQuery};
+use crate::{
+    error::HistoryResult,
+    query::{FromQuery, ToQuery},
+};
 
 /// A history location.
 ///
@@ -14,10 +20,17 @@ pub struct Location {
     pub(crate) query_str: Rc<String>,
     pub(crate) hash: Rc<String>,
     pub(crate) state: Option<Rc<dyn Any>>,
+    pub(crate) js_state: Option<JsValue>,
     pub(crate) id: Option<u32>,
+    pub(crate) key: Option<Rc<str>>,
 }
 
 impl Location {
+    /// Returns the path, query and hash of this location, as they appear in the URL.
+    pub(crate) fn href(&self) -> String {
+        format!("{}{}{}", self.path, self.query_str, self.hash)
+    }
+
     /// Returns a unique id of current location.
     ///
     /// Returns [`None`] if current location is not created by `gloo::history`.
@@ -29,6 +42,18 @@ impl Location {
         self.id
     }
 
+    /// Returns the key of the history entry of current location.
+    ///
+    /// Unlike the [`id`](Self::id), the key is kept when the entry is replaced, so it identifies
+    /// a position in the history, e.g. to store UI state in the
+    /// [`entry_storage`][crate::History::entry_storage] of the history. Browser histories keep
+    /// it across reloads of the page.
+    ///
+    /// Returns [`None`] if current entry is not created by `gloo::history`.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
     /// Returns the `pathname` of current location.
     pub fn path(&self) -> &str {
         &self.path
@@ -49,6 +74,13 @@ impl Location {
         T::from_query(query)
     }
 
+    /// Matches the `pathname` of current location against `route`.
+    ///
+    /// Returns [`None`] if it doesn't match, see [`Route::matches`].
+    pub fn matches(&self, route: &Route) -> Option<RouteMatch> {
+        route.matches(self.path())
+    }
+
     /// Returns the hash fragment of current URL.
     pub fn hash(&self) -> &str {
         &self.hash
@@ -63,6 +95,27 @@ impl Location {
     {
         self.state.clone().and_then(|m| m.downcast().ok())
     }
+
+    /// Returns the state of current location stored in the session history of the browser, see
+    /// [`BrowserHistory::push_with_js_state`][crate::BrowserHistory::push_with_js_state].
+    ///
+    /// Returns [`None`] if there is no such state, which is always the case for a
+    /// [`MemoryHistory`][crate::MemoryHistory].
+    pub fn js_state(&self) -> Option<JsValue> {
+        self.js_state.clone()
+    }
+
+    /// Returns a [`LocationBuilder`] with the path, queries, hash and state of this location.
+    ///
+    /// The state stored in the session history of the browser is not included.
+    pub fn to_builder(&self) -> LocationBuilder {
+        LocationBuilder {
+            path: self.path.to_string(),
+            query_str: self.query_str.to_string(),
+            hash: self.hash.to_string(),
+            state: self.state.clone(),
+        }
+    }
 }
 
 impl PartialEq for Location {
@@ -75,3 +128,103 @@ impl PartialEq for Location {
         false
     }
 }
+
+/// A builder of a location to navigate to with
+/// [`History::push_location`][crate::History::push_location] or
+/// [`History::replace_location`][crate::History::replace_location].
+///
+/// Use [`Location::to_builder`] to change only some components of an existing location.
+///
+/// # Example
+///
+/// ```
+/// use gloo_history::{History, LocationBuilder, MemoryHistory};
+///
+/// let history = MemoryHistory::new();
+/// history.push_location(LocationBuilder::new("/posts").hash("comments").state(42));
+///
+/// // Changes the path, keeping the hash and the state.
+/// history.replace_location(history.location().to_builder().path("/articles"));
+///
+/// let location = history.location();
+/// assert_eq!(location.path(), "/articles");
+/// assert_eq!(location.hash(), "#comments");
+/// assert_eq!(*location.state::<i32>().unwrap(), 42);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LocationBuilder {
+    pub(crate) path: String,
+    /// The queries with their leading `?`, or an empty string.
+    pub(crate) query_str: String,
+    /// The hash with its leading `#`, or an empty string.
+    pub(crate) hash: String,
+    pub(crate) state: Option<Rc<dyn Any>>,
+}
+
+impl LocationBuilder {
+    /// Creates a builder of a location at `path`, without queries, hash or state.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            query_str: String::new(),
+            hash: String::new(),
+            state: None,
+        }
+    }
+
+    /// Sets the `pathname` of the location.
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets the queries of the location, with or without the leading `?`.
+    pub fn query_str(mut self, query: impl AsRef<str>) -> Self {
+        self.query_str = with_prefix('?', query.as_ref());
+        self
+    }
+
+    /// Sets the queries of the location to `query`.
+    #[cfg(feature = "query")]
+    pub fn query<Q>(self, query: Q) -> HistoryResult<Self, Q::Error>
+    where
+        Q: ToQuery,
+    {
+        let query = query.to_query()?;
+        Ok(self.query_str(query))
+    }
+
+    /// Sets the hash fragment of the location, with or without the leading `#`.
+    pub fn hash(mut self, hash: impl AsRef<str>) -> Self {
+        self.hash = with_prefix('#', hash.as_ref());
+        self
+    }
+
+    /// Sets the state of the location.
+    pub fn state<T>(mut self, state: T) -> Self
+    where
+        T: 'static,
+    {
+        self.state = Some(Rc::new(state));
+        self
+    }
+
+    /// Removes the state of the location.
+    pub fn clear_state(mut self) -> Self {
+        self.state = None;
+        self
+    }
+
+    /// Returns the path, query and hash of the location, as they appear in the URL.
+    pub(crate) fn href(&self) -> String {
+        format!("{}{}{}", self.path, self.query_str, self.hash)
+    }
+}
+
+/// Prefixes non-empty `s` with `prefix`, if it isn't already.
+fn with_prefix(prefix: char, s: &str) -> String {
+    match s.strip_prefix(prefix).unwrap_or(s) {
+        "" => String::new(),
+        m => format!("{prefix}{m}"),
+    }
+}
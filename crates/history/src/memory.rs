@@ -6,11 +6,16 @@ use std::collections::VecDeque;
 use std::fmt;
 use std::rc::Rc;
 
+use serde::{Deserialize, Serialize};
+
+use crate::blocker::{BlockerHandle, Transition, TransitionKind};
 use crate::history::History;
-use crate::listener::HistoryListener;
-use crate::location::Location;
+use crate::listener::{HistoryListener, NavigationEvent};
+use crate::location::{Location, LocationBuilder};
+use crate::storage::EntryStorage;
 use crate::utils::{
-    assert_absolute_path, assert_no_fragment, assert_no_query, get_id, WeakCallback,
+    assert_absolute_path, assert_no_fragment, assert_no_query, get_id, get_key, is_blocked,
+    WeakBlocker, WeakCallback, WeakHook,
 };
 #[cfg(feature = "query")]
 use crate::{error::HistoryResult, query::ToQuery};
@@ -32,7 +37,14 @@ impl LocationStack {
         self.prev.len() + self.next.len() + 1
     }
 
-    fn go(&mut self, delta: isize) {
+    fn index(&self) -> usize {
+        self.prev.len()
+    }
+
+    /// Moves by `delta` entries, or as far as possible, and returns the number of entries moved.
+    fn go(&mut self, delta: isize) -> isize {
+        let before = self.prev.len();
+
         match delta.cmp(&0) {
             // Go forward.
             Ordering::Greater => {
@@ -57,6 +69,28 @@ impl LocationStack {
             // Do nothing.
             Ordering::Equal => {}
         }
+
+        self.prev.len() as isize - before as isize
+    }
+
+    /// Returns the location `go(delta)` would move to.
+    fn peek(&self, delta: isize) -> &Location {
+        match delta.cmp(&0) {
+            Ordering::Greater => {
+                let steps = (delta as usize).min(self.next.len());
+                steps
+                    .checked_sub(1)
+                    .and_then(|index| self.next.get(index))
+                    .unwrap_or(&self.current)
+            }
+            Ordering::Less => {
+                let steps = delta.unsigned_abs().min(self.prev.len());
+                self.prev
+                    .get(self.prev.len() - steps)
+                    .unwrap_or(&self.current)
+            }
+            Ordering::Equal => &self.current,
+        }
     }
 
     fn push(&mut self, mut location: Location) {
@@ -67,7 +101,9 @@ impl LocationStack {
         self.next.clear();
     }
 
-    fn replace(&mut self, location: Location) {
+    fn replace(&mut self, mut location: Location) {
+        // The entry keeps its key.
+        location.key = self.current.key.clone();
         self.current = location;
     }
 }
@@ -82,7 +118,9 @@ impl Default for LocationStack {
                 query_str: "".to_string().into(),
                 hash: "".to_string().into(),
                 state: None,
+                js_state: None,
                 id: Some(get_id()),
+                key: Some(get_key()),
             },
         }
     }
@@ -97,6 +135,10 @@ impl Default for LocationStack {
 pub struct MemoryHistory {
     inner: Rc<RefCell<LocationStack>>,
     callbacks: Rc<RefCell<Vec<WeakCallback>>>,
+    blockers: Rc<RefCell<Vec<WeakBlocker>>>,
+    /// The hooks of the [`AnyHistory`][crate::AnyHistory]s wrapping this history.
+    pub(crate) hooks: Rc<RefCell<Vec<WeakHook>>>,
+    entry_storage: EntryStorage,
 }
 
 impl PartialEq for MemoryHistory {
@@ -116,8 +158,20 @@ impl History for MemoryHistory {
         self.inner.borrow().len()
     }
 
+    fn index(&self) -> usize {
+        self.inner.borrow().index()
+    }
+
     fn go(&self, delta: isize) {
-        self.inner.borrow_mut().go(delta)
+        let to = self.inner.borrow().peek(delta).href();
+        if is_blocked(&self.blockers, &Transition::new(TransitionKind::Pop, to)) {
+            return;
+        }
+
+        let moved = self.inner.borrow_mut().go(delta);
+        if moved != 0 {
+            self.notify_callbacks(NavigationEvent::pop(Some(moved)));
+        }
     }
 
     fn push<'a>(&self, route: impl Into<Cow<'a, str>>) {
@@ -132,12 +186,12 @@ impl History for MemoryHistory {
             query_str: "".to_string().into(),
             hash: "".to_string().into(),
             state: None,
+            js_state: None,
             id: Some(get_id()),
+            key: Some(get_key()),
         };
 
-        self.inner.borrow_mut().push(location);
-
-        self.notify_callbacks();
+        self.push_entry(location);
     }
 
     fn replace<'a>(&self, route: impl Into<Cow<'a, str>>) {
@@ -152,12 +206,12 @@ impl History for MemoryHistory {
             query_str: "".to_string().into(),
             hash: "".to_string().into(),
             state: None,
+            js_state: None,
             id: Some(get_id()),
+            key: Some(get_key()),
         };
 
-        self.inner.borrow_mut().replace(location);
-
-        self.notify_callbacks();
+        self.replace_entry(location);
     }
 
     fn push_with_state<'a, T>(&self, route: impl Into<Cow<'a, str>>, state: T)
@@ -175,12 +229,12 @@ impl History for MemoryHistory {
             query_str: "".to_string().into(),
             hash: "".to_string().into(),
             state: Some(Rc::new(state) as Rc<dyn Any>),
+            js_state: None,
             id: Some(get_id()),
+            key: Some(get_key()),
         };
 
-        self.inner.borrow_mut().push(location);
-
-        self.notify_callbacks();
+        self.push_entry(location);
     }
 
     fn replace_with_state<'a, T>(&self, route: impl Into<Cow<'a, str>>, state: T)
@@ -198,12 +252,12 @@ impl History for MemoryHistory {
             query_str: "".to_string().into(),
             hash: "".to_string().into(),
             state: Some(Rc::new(state) as Rc<dyn Any>),
+            js_state: None,
             id: Some(get_id()),
+            key: Some(get_key()),
         };
 
-        self.inner.borrow_mut().replace(location);
-
-        self.notify_callbacks();
+        self.replace_entry(location);
     }
 
     #[cfg(feature = "query")]
@@ -227,12 +281,12 @@ impl History for MemoryHistory {
             query_str: format!("?{query}").into(),
             hash: "".to_string().into(),
             state: None,
+            js_state: None,
             id: Some(get_id()),
+            key: Some(get_key()),
         };
 
-        self.inner.borrow_mut().push(location);
-
-        self.notify_callbacks();
+        self.push_entry(location);
 
         Ok(())
     }
@@ -257,12 +311,12 @@ impl History for MemoryHistory {
             query_str: format!("?{query}").into(),
             hash: "".to_string().into(),
             state: None,
+            js_state: None,
             id: Some(get_id()),
+            key: Some(get_key()),
         };
 
-        self.inner.borrow_mut().replace(location);
-
-        self.notify_callbacks();
+        self.replace_entry(location);
 
         Ok(())
     }
@@ -290,12 +344,12 @@ impl History for MemoryHistory {
             query_str: format!("?{query}").into(),
             hash: "".to_string().into(),
             state: Some(Rc::new(state) as Rc<dyn Any>),
+            js_state: None,
             id: Some(get_id()),
+            key: Some(get_key()),
         };
 
-        self.inner.borrow_mut().push(location);
-
-        self.notify_callbacks();
+        self.push_entry(location);
 
         Ok(())
     }
@@ -323,28 +377,82 @@ impl History for MemoryHistory {
             query_str: format!("?{query}").into(),
             hash: "".to_string().into(),
             state: Some(Rc::new(state) as Rc<dyn Any>),
+            js_state: None,
             id: Some(get_id()),
+            key: Some(get_key()),
         };
 
-        self.inner.borrow_mut().replace(location);
-
-        self.notify_callbacks();
+        self.replace_entry(location);
 
         Ok(())
     }
 
-    fn listen<CB>(&self, callback: CB) -> HistoryListener
+    fn push_location(&self, location: LocationBuilder) {
+        assert_absolute_path(&location.path);
+        assert_no_query(&location.path);
+        assert_no_fragment(&location.path);
+
+        let location = Location {
+            path: location.path.into(),
+            query_str: location.query_str.into(),
+            hash: location.hash.into(),
+            state: location.state,
+            js_state: None,
+            id: Some(get_id()),
+            key: Some(get_key()),
+        };
+
+        self.push_entry(location);
+    }
+
+    fn replace_location(&self, location: LocationBuilder) {
+        assert_absolute_path(&location.path);
+        assert_no_query(&location.path);
+        assert_no_fragment(&location.path);
+
+        let location = Location {
+            path: location.path.into(),
+            query_str: location.query_str.into(),
+            hash: location.hash.into(),
+            state: location.state,
+            js_state: None,
+            id: Some(get_id()),
+            key: Some(get_key()),
+        };
+
+        self.replace_entry(location);
+    }
+
+    fn listen_with_event<CB>(&self, callback: CB) -> HistoryListener
     where
-        CB: Fn() + 'static,
+        CB: Fn(&NavigationEvent) + 'static,
     {
         // Callbacks do not receive a copy of [`History`] to prevent reference cycle.
-        let cb = Rc::new(callback) as Rc<dyn Fn()>;
+        let cb = Rc::new(callback) as Rc<dyn Fn(&NavigationEvent)>;
 
         self.callbacks.borrow_mut().push(Rc::downgrade(&cb));
 
         HistoryListener { _listener: cb }
     }
 
+    fn block<CB>(&self, callback: CB) -> BlockerHandle
+    where
+        CB: Fn(&Transition) -> bool + 'static,
+    {
+        let blocker = Rc::new(callback) as Rc<dyn Fn(&Transition) -> bool>;
+
+        self.blockers.borrow_mut().push(Rc::downgrade(&blocker));
+
+        BlockerHandle {
+            _blocker: blocker,
+            _unload_guard: None,
+        }
+    }
+
+    fn entry_storage(&self) -> EntryStorage {
+        self.entry_storage.clone()
+    }
+
     fn location(&self) -> Location {
         self.inner.borrow().current()
     }
@@ -356,6 +464,91 @@ impl MemoryHistory {
         Self::default()
     }
 
+    /// Returns a snapshot of the entries of this history and of the position of the current one,
+    /// without their states.
+    ///
+    /// See [`MemoryHistorySnapshot`] for an example.
+    pub fn snapshot(&self) -> MemoryHistorySnapshot {
+        self.snapshot_with_state()
+    }
+
+    /// Returns a snapshot of the entries of this history and of the position of the current one,
+    /// with the states of type `S`.
+    ///
+    /// States of other types are left out of the snapshot.
+    pub fn snapshot_with_state<S>(&self) -> MemoryHistorySnapshot<S>
+    where
+        S: Clone + 'static,
+    {
+        let stack = self.inner.borrow();
+
+        let entries = stack
+            .prev
+            .iter()
+            .chain(Some(&stack.current))
+            .chain(stack.next.iter())
+            .map(|location| SnapshotEntry {
+                path: location.path().to_string(),
+                query: location.query_str().to_string(),
+                hash: location.hash().to_string(),
+                state: location.state::<S>().map(|m| S::clone(&m)),
+                key: location.key().map(str::to_owned),
+            })
+            .collect();
+
+        MemoryHistorySnapshot {
+            entries,
+            index: stack.prev.len(),
+        }
+    }
+
+    /// Creates a new [`MemoryHistory`] from a snapshot taken with
+    /// [`snapshot`](Self::snapshot) or [`snapshot_with_state`](Self::snapshot_with_state).
+    ///
+    /// Entries get new ids but keep their keys. An empty snapshot creates a history with a default entry of '/'.
+    pub fn from_snapshot<S>(snapshot: MemoryHistorySnapshot<S>) -> Self
+    where
+        S: 'static,
+    {
+        let MemoryHistorySnapshot { entries, index } = snapshot;
+
+        let mut locations = entries.into_iter().map(|entry| Location {
+            path: entry.path.into(),
+            query_str: entry.query.into(),
+            hash: entry.hash.into(),
+            state: entry.state.map(|m| Rc::new(m) as Rc<dyn Any>),
+            js_state: None,
+            id: Some(get_id()),
+            key: Some(entry.key.map(Rc::from).unwrap_or_else(get_key)),
+        });
+
+        let prev = locations.by_ref().take(index).collect::<Vec<_>>();
+        let stack = match locations.next() {
+            Some(current) => LocationStack {
+                prev,
+                next: locations.collect(),
+                current,
+            },
+            // An index past the end points to the last entry.
+            None => {
+                let mut prev = prev;
+                match prev.pop() {
+                    Some(current) => LocationStack {
+                        prev,
+                        next: VecDeque::new(),
+                        current,
+                    },
+                    None => LocationStack::default(),
+                }
+            }
+        };
+
+        Self {
+            inner: Rc::new(RefCell::new(stack)),
+            ..Self::default()
+        }
+    }
+
     /// Creates a new [`MemoryHistory`] with entries.
     pub fn with_entries<'a>(entries: impl IntoIterator<Item = impl Into<Cow<'a, str>>>) -> Self {
         let self_ = Self::new();
@@ -371,7 +564,86 @@ impl MemoryHistory {
         self_
     }
 
-    fn notify_callbacks(&self) {
-        crate::utils::notify_callbacks(self.callbacks.clone());
+    fn push_entry(&self, location: Location) {
+        let transition = Transition::new(TransitionKind::Push, location.href());
+        if is_blocked(&self.blockers, &transition) {
+            return;
+        }
+
+        self.inner.borrow_mut().push(location);
+
+        self.notify_callbacks(NavigationEvent::push());
+    }
+
+    fn replace_entry(&self, location: Location) {
+        let transition = Transition::new(TransitionKind::Replace, location.href());
+        if is_blocked(&self.blockers, &transition) {
+            return;
+        }
+
+        self.inner.borrow_mut().replace(location);
+
+        self.notify_callbacks(NavigationEvent::replace());
+    }
+
+    fn notify_callbacks(&self, event: NavigationEvent) {
+        crate::utils::notify_callbacks(self.callbacks.clone(), &event);
+    }
+}
+
+/// The entries of a [`MemoryHistory`] and the position of the current one, which can be
+/// serialized with `serde`, e.g. to hydrate the history of a server-side rendered page or to
+/// persist it across reloads.
+///
+/// The states of the entries are included if they are of type `S`, `()` meaning none.
+///
+/// # Example
+///
+/// ```
+/// use gloo_history::{History, MemoryHistory};
+///
+/// let history = MemoryHistory::with_entries(["/", "/posts", "/posts/1"]);
+/// history.back();
+///
+/// let snapshot = history.snapshot();
+/// assert_eq!(snapshot.len(), 3);
+/// assert_eq!(snapshot.index(), 1);
+///
+/// let restored = MemoryHistory::from_snapshot(snapshot);
+/// assert_eq!(restored.location().path(), "/posts");
+/// restored.forward();
+/// assert_eq!(restored.location().path(), "/posts/1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryHistorySnapshot<S = ()> {
+    entries: Vec<SnapshotEntry<S>>,
+    index: usize,
+}
+
+impl<S> MemoryHistorySnapshot<S> {
+    /// Returns the number of entries in the snapshot.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the snapshot has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the position of the current entry.
+    pub fn index(&self) -> usize {
+        self.index
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SnapshotEntry<S> {
+    path: String,
+    query: String,
+    hash: String,
+    state: Option<S>,
+    /// Missing in snapshots of older versions.
+    #[serde(default)]
+    key: Option<String>,
+}
@@ -0,0 +1,610 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use gloo_events::{EventListener, EventListenerOptions};
+use gloo_utils::window;
+use js_sys::{Array, Object, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::Url;
+
+use crate::blocker::{unload_guard, BlockerHandle, Transition, TransitionKind};
+use crate::history::History;
+use crate::listener::{HistoryListener, NavigationEvent};
+use crate::location::{Location, LocationBuilder};
+use crate::storage::EntryStorage;
+use crate::utils::{get_id, is_blocked, WeakBlocker, WeakCallback, WeakHook};
+#[cfg(feature = "query")]
+use crate::{browser::BrowserHistory, error::HistoryResult, query::ToQuery};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = web_sys::EventTarget)]
+    #[derive(Clone)]
+    type Navigation;
+
+    #[wasm_bindgen(method)]
+    fn entries(this: &Navigation) -> Array;
+
+    #[wasm_bindgen(method, getter, js_name = currentEntry)]
+    fn current_entry(this: &Navigation) -> Option<NavigationHistoryEntry>;
+
+    #[wasm_bindgen(method, catch)]
+    fn navigate(this: &Navigation, url: &str, options: &Object) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = traverseTo)]
+    fn traverse_to(this: &Navigation, key: &str) -> Result<JsValue, JsValue>;
+
+    type NavigationHistoryEntry;
+
+    #[wasm_bindgen(method, getter)]
+    fn id(this: &NavigationHistoryEntry) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    fn key(this: &NavigationHistoryEntry) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    fn index(this: &NavigationHistoryEntry) -> i32;
+
+    #[wasm_bindgen(method, catch, js_name = getState)]
+    fn get_state(this: &NavigationHistoryEntry) -> Result<JsValue, JsValue>;
+
+    /// A `NavigateEvent` of the [Navigation API], fired before every navigation of the
+    /// document.
+    ///
+    /// It dereferences to [`web_sys::Event`], so it can be cancelled with
+    /// [`prevent_default`](web_sys::Event::prevent_default).
+    ///
+    /// [Navigation API]: https://developer.mozilla.org/en-US/docs/Web/API/Navigation_API
+    #[wasm_bindgen(extends = web_sys::Event)]
+    #[derive(Debug, Clone)]
+    pub type NavigateEvent;
+
+    #[wasm_bindgen(method, getter, js_name = navigationType)]
+    fn raw_navigation_type(this: &NavigateEvent) -> String;
+
+    /// Returns whether the navigation stays in the document, or can be made to with
+    /// interception.
+    #[wasm_bindgen(method, getter, js_name = canIntercept)]
+    pub fn can_intercept(this: &NavigateEvent) -> bool;
+
+    /// Returns whether the navigation only changes the fragment of the url.
+    #[wasm_bindgen(method, getter, js_name = hashChange)]
+    pub fn hash_change(this: &NavigateEvent) -> bool;
+
+    /// Returns the file name of the download, if the navigation is one.
+    #[wasm_bindgen(method, getter, js_name = downloadRequest)]
+    pub fn download_request(this: &NavigateEvent) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = formData)]
+    fn form_data(this: &NavigateEvent) -> JsValue;
+
+    #[wasm_bindgen(method, getter)]
+    fn destination(this: &NavigateEvent) -> NavigationDestination;
+
+    #[wasm_bindgen(method, catch)]
+    fn intercept(this: &NavigateEvent) -> Result<(), JsValue>;
+
+    type NavigationDestination;
+
+    #[wasm_bindgen(method, getter)]
+    fn url(this: &NavigationDestination) -> String;
+
+    #[wasm_bindgen(extends = web_sys::Event)]
+    type NavigationCurrentEntryChangeEvent;
+
+    #[wasm_bindgen(method, getter, js_name = navigationType)]
+    fn navigation_type(this: &NavigationCurrentEntryChangeEvent) -> Option<String>;
+
+    #[wasm_bindgen(method, getter)]
+    fn from(this: &NavigationCurrentEntryChangeEvent) -> NavigationHistoryEntry;
+}
+
+/// The kind of navigation of a [`NavigateEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavigationType {
+    /// A new entry is pushed.
+    Push,
+    /// The current entry is replaced.
+    Replace,
+    /// The current entry is reloaded.
+    Reload,
+    /// An existing entry is visited.
+    Traverse,
+}
+
+impl NavigateEvent {
+    /// Returns the kind of this navigation.
+    pub fn navigation_type(&self) -> NavigationType {
+        match self.raw_navigation_type().as_str() {
+            "replace" => NavigationType::Replace,
+            "reload" => NavigationType::Reload,
+            "traverse" => NavigationType::Traverse,
+            _ => NavigationType::Push,
+        }
+    }
+
+    /// Returns the url this navigation goes to.
+    pub fn destination_url(&self) -> String {
+        self.destination().url()
+    }
+}
+
+struct EntryData {
+    id: u32,
+    state: Option<Rc<dyn Any>>,
+}
+
+/// A [`History`] that is implemented with the [Navigation API], the successor of
+/// [`web_sys::History`].
+///
+/// Besides the navigations of this history, it intercepts the same-document navigations of the
+/// page, such as clicks on links to the application, and turns them into soft navigations that
+/// its listeners are notified of. Use [`on_navigate`](Self::on_navigate) to handle the
+/// [`NavigateEvent`]s yourself.
+///
+/// The Navigation API is not available in all browsers, see
+/// [`is_supported`](Self::is_supported), and
+/// [`AnyHistory::navigation_or_browser`][crate::AnyHistory::navigation_or_browser] to fall back
+/// to a [`BrowserHistory`](crate::BrowserHistory).
+///
+/// [Navigation API]: https://developer.mozilla.org/en-US/docs/Web/API/Navigation_API
+#[derive(Clone)]
+pub struct NavigationHistory {
+    inner: Navigation,
+    entries: Rc<RefCell<HashMap<String, EntryData>>>,
+    /// The state of the navigation in progress, attached to its entry once it is committed.
+    pending_state: Rc<RefCell<Option<Rc<dyn Any>>>>,
+    callbacks: Rc<RefCell<Vec<WeakCallback>>>,
+    blockers: Rc<RefCell<Vec<WeakBlocker>>>,
+    /// The hooks of the [`AnyHistory`][crate::AnyHistory]s wrapping this history.
+    pub(crate) hooks: Rc<RefCell<Vec<WeakHook>>>,
+    entry_storage: EntryStorage,
+    ignore_rejection: Rc<Closure<dyn FnMut(JsValue)>>,
+}
+
+impl fmt::Debug for NavigationHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NavigationHistory").finish()
+    }
+}
+
+impl PartialEq for NavigationHistory {
+    fn eq(&self, _rhs: &Self) -> bool {
+        // All navigation histories are created equal.
+        true
+    }
+}
+
+impl History for NavigationHistory {
+    fn len(&self) -> usize {
+        self.inner.entries().length() as usize
+    }
+
+    fn index(&self) -> usize {
+        self.inner
+            .current_entry()
+            .map(|m| m.index().max(0) as usize)
+            .unwrap_or(0)
+    }
+
+    fn go(&self, delta: isize) {
+        if delta == 0 {
+            window()
+                .location()
+                .reload()
+                .expect_throw("failed to reload.");
+            return;
+        }
+
+        let current = match self.inner.current_entry() {
+            Some(m) => m,
+            None => return,
+        };
+        let index = current.index() as isize + delta;
+        if index < 0 {
+            return;
+        }
+
+        let entry = self.inner.entries().get(index as u32);
+        if !entry.is_undefined() {
+            let entry = entry.unchecked_into::<NavigationHistoryEntry>();
+            let result = self
+                .inner
+                .traverse_to(&entry.key())
+                .expect_throw("failed to traverse.");
+            self.ignore_rejections(&result);
+        }
+    }
+
+    fn push<'a>(&self, route: impl Into<Cow<'a, str>>) {
+        self.navigate(&route.into(), "push", None, None);
+    }
+
+    fn replace<'a>(&self, route: impl Into<Cow<'a, str>>) {
+        self.navigate(&route.into(), "replace", None, None);
+    }
+
+    fn push_with_state<'a, T>(&self, route: impl Into<Cow<'a, str>>, state: T)
+    where
+        T: 'static,
+    {
+        let state = Rc::new(state) as Rc<dyn Any>;
+        self.navigate(&route.into(), "push", Some(state), None);
+    }
+
+    fn replace_with_state<'a, T>(&self, route: impl Into<Cow<'a, str>>, state: T)
+    where
+        T: 'static,
+    {
+        let state = Rc::new(state) as Rc<dyn Any>;
+        self.navigate(&route.into(), "replace", Some(state), None);
+    }
+
+    #[cfg(feature = "query")]
+    fn push_with_query<'a, Q>(
+        &self,
+        route: impl Into<Cow<'a, str>>,
+        query: Q,
+    ) -> HistoryResult<(), Q::Error>
+    where
+        Q: ToQuery,
+    {
+        let query = query.to_query()?;
+        let url = BrowserHistory::combine_url(&route.into(), &query);
+
+        self.navigate(&url, "push", None, None);
+        Ok(())
+    }
+
+    #[cfg(feature = "query")]
+    fn replace_with_query<'a, Q>(
+        &self,
+        route: impl Into<Cow<'a, str>>,
+        query: Q,
+    ) -> HistoryResult<(), Q::Error>
+    where
+        Q: ToQuery,
+    {
+        let query = query.to_query()?;
+        let url = BrowserHistory::combine_url(&route.into(), &query);
+
+        self.navigate(&url, "replace", None, None);
+        Ok(())
+    }
+
+    #[cfg(feature = "query")]
+    fn push_with_query_and_state<'a, Q, T>(
+        &self,
+        route: impl Into<Cow<'a, str>>,
+        query: Q,
+        state: T,
+    ) -> HistoryResult<(), Q::Error>
+    where
+        Q: ToQuery,
+        T: 'static,
+    {
+        let query = query.to_query()?;
+        let url = BrowserHistory::combine_url(&route.into(), &query);
+
+        let state = Rc::new(state) as Rc<dyn Any>;
+        self.navigate(&url, "push", Some(state), None);
+        Ok(())
+    }
+
+    #[cfg(feature = "query")]
+    fn replace_with_query_and_state<'a, Q, T>(
+        &self,
+        route: impl Into<Cow<'a, str>>,
+        query: Q,
+        state: T,
+    ) -> HistoryResult<(), Q::Error>
+    where
+        Q: ToQuery,
+        T: 'static,
+    {
+        let query = query.to_query()?;
+        let url = BrowserHistory::combine_url(&route.into(), &query);
+
+        let state = Rc::new(state) as Rc<dyn Any>;
+        self.navigate(&url, "replace", Some(state), None);
+        Ok(())
+    }
+
+    fn push_location(&self, location: LocationBuilder) {
+        self.navigate(&location.href(), "push", location.state, None);
+    }
+
+    fn replace_location(&self, location: LocationBuilder) {
+        self.navigate(&location.href(), "replace", location.state, None);
+    }
+
+    fn listen_with_event<CB>(&self, callback: CB) -> HistoryListener
+    where
+        CB: Fn(&NavigationEvent) + 'static,
+    {
+        // Callbacks do not receive a copy of [`History`] to prevent reference cycle.
+        let cb = Rc::new(callback) as Rc<dyn Fn(&NavigationEvent)>;
+
+        self.callbacks.borrow_mut().push(Rc::downgrade(&cb));
+
+        HistoryListener { _listener: cb }
+    }
+
+    fn block<CB>(&self, callback: CB) -> BlockerHandle
+    where
+        CB: Fn(&Transition) -> bool + 'static,
+    {
+        let blocker = Rc::new(callback) as Rc<dyn Fn(&Transition) -> bool>;
+
+        self.blockers.borrow_mut().push(Rc::downgrade(&blocker));
+
+        BlockerHandle {
+            _unload_guard: Some(unload_guard(blocker.clone())),
+            _blocker: blocker,
+        }
+    }
+
+    fn entry_storage(&self) -> EntryStorage {
+        self.entry_storage.clone()
+    }
+
+    fn location(&self) -> Location {
+        let loc = window().location();
+
+        let current = self.inner.current_entry();
+        let key = current.as_ref().map(|m| Rc::from(m.key()));
+        let (id, state) = match &current {
+            Some(current) => {
+                let mut entries = self.entries.borrow_mut();
+                let data = entries.entry(current.id()).or_insert_with(|| EntryData {
+                    id: get_id(),
+                    state: None,
+                });
+                (Some(data.id), data.state.clone())
+            }
+            None => (None, None),
+        };
+
+        Location {
+            path: loc.pathname().expect_throw("failed to get pathname").into(),
+            query_str: loc
+                .search()
+                .expect_throw("failed to get location query.")
+                .into(),
+            hash: loc
+                .hash()
+                .expect_throw("failed to get location hash.")
+                .into(),
+            state,
+            js_state: current
+                .and_then(|m| m.get_state().ok())
+                .filter(|m| !m.is_undefined()),
+            id,
+            key,
+        }
+    }
+}
+
+impl Default for NavigationHistory {
+    fn default() -> Self {
+        // We create navigation history only once.
+        thread_local! {
+            static NAVIGATION_HISTORY: (NavigationHistory, [EventListener; 2]) = {
+                let inner = Reflect::get(&window(), &JsValue::from_str("navigation"))
+                    .ok()
+                    .filter(|m| !m.is_undefined())
+                    .expect_throw("Failed to create navigation history. Is the Navigation API supported?")
+                    .unchecked_into::<Navigation>();
+
+                let history = NavigationHistory {
+                    inner,
+                    entries: Rc::default(),
+                    pending_state: Rc::default(),
+                    callbacks: Rc::default(),
+                    blockers: Rc::default(),
+                    hooks: Rc::default(),
+                    entry_storage: EntryStorage::default(),
+                    ignore_rejection: Rc::new(Closure::wrap(
+                        Box::new(|_| {}) as Box<dyn FnMut(JsValue)>
+                    )),
+                };
+
+                let navigate_listener = {
+                    let history = history.clone();
+
+                    // Not passive, so that blocked navigations can be cancelled.
+                    EventListener::new_with_options(
+                        &history.inner.clone(),
+                        "navigate",
+                        EventListenerOptions::enable_prevent_default(),
+                        move |event| {
+                            history.handle_navigate(event.unchecked_ref());
+                        },
+                    )
+                };
+
+                let entry_change_listener = {
+                    let history = history.clone();
+
+                    EventListener::new(
+                        &history.inner.clone(),
+                        "currententrychange",
+                        move |event| {
+                            history.handle_current_entry_change(event.unchecked_ref());
+                        },
+                    )
+                };
+
+                (history, [navigate_listener, entry_change_listener])
+            };
+        }
+
+        NAVIGATION_HISTORY.with(|(history, _)| history.clone())
+    }
+}
+
+impl NavigationHistory {
+    /// Creates a new [`NavigationHistory`]
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the Navigation API is not supported, see
+    /// [`is_supported`](Self::is_supported).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the Navigation API is supported by the browser.
+    pub fn is_supported() -> bool {
+        Reflect::get(&window(), &JsValue::from_str("navigation"))
+            .map(|m| !m.is_undefined())
+            .unwrap_or(false)
+    }
+
+    /// Registers a callback for the [`NavigateEvent`]s of the document.
+    ///
+    /// The callback runs after this history decided whether to intercept the navigation.
+    /// Cancelling the event cancels the navigation.
+    pub fn on_navigate<F>(&self, mut callback: F) -> EventListener
+    where
+        F: FnMut(&NavigateEvent) + 'static,
+    {
+        EventListener::new_with_options(
+            &self.inner,
+            "navigate",
+            EventListenerOptions::enable_prevent_default(),
+            move |event| {
+                callback(event.unchecked_ref());
+            },
+        )
+    }
+
+    /// Pushes a route entry with a state that is stored by the browser.
+    ///
+    /// See [`BrowserHistory::push_with_js_state`](crate::BrowserHistory::push_with_js_state) for
+    /// details.
+    pub fn push_with_js_state<'a>(&self, route: impl Into<Cow<'a, str>>, state: &JsValue) {
+        self.navigate(&route.into(), "push", None, Some(state));
+    }
+
+    /// Replaces the current history entry with provided route and a state that is stored by the
+    /// browser.
+    ///
+    /// See [`BrowserHistory::push_with_js_state`](crate::BrowserHistory::push_with_js_state) for
+    /// details.
+    pub fn replace_with_js_state<'a>(&self, route: impl Into<Cow<'a, str>>, state: &JsValue) {
+        self.navigate(&route.into(), "replace", None, Some(state));
+    }
+
+    fn navigate(
+        &self,
+        url: &str,
+        mode: &str,
+        state: Option<Rc<dyn Any>>,
+        js_state: Option<&JsValue>,
+    ) {
+        let options = Object::new();
+        Reflect::set(
+            &options,
+            &JsValue::from_str("history"),
+            &JsValue::from_str(mode),
+        )
+        .expect_throw("failed to set navigation options.");
+        if let Some(js_state) = js_state {
+            Reflect::set(&options, &JsValue::from_str("state"), js_state)
+                .expect_throw("failed to set navigation options.");
+        }
+
+        // Same-document navigations are committed before `navigate` returns.
+        *self.pending_state.borrow_mut() = state;
+        let result = self.inner.navigate(url, &options);
+        self.pending_state.borrow_mut().take();
+
+        self.ignore_rejections(&result.expect_throw("failed to navigate."));
+    }
+
+    /// Blocked or interrupted navigations reject their promises, which shouldn't be reported as
+    /// uncaught errors.
+    fn ignore_rejections(&self, result: &JsValue) {
+        for name in ["committed", "finished"] {
+            if let Ok(promise) = Reflect::get(result, &JsValue::from_str(name)) {
+                if let Some(promise) = promise.dyn_ref::<Promise>() {
+                    let _ = promise.catch(&self.ignore_rejection);
+                }
+            }
+        }
+    }
+
+    fn handle_navigate(&self, event: &NavigateEvent) {
+        let is_soft = event.can_intercept()
+            && event.download_request().is_none()
+            && event.form_data().is_null();
+        let kind = match event.navigation_type() {
+            NavigationType::Push => TransitionKind::Push,
+            NavigationType::Replace => TransitionKind::Replace,
+            NavigationType::Traverse => TransitionKind::Pop,
+            NavigationType::Reload => return,
+        };
+        if !is_soft {
+            return;
+        }
+
+        let url = Url::new(&event.destination_url()).expect_throw("destination url is not valid.");
+        let to = format!("{}{}{}", url.pathname(), url.search(), url.hash());
+
+        // Some traversals, e.g. with the back button of the browser, can't be cancelled.
+        if event.cancelable() && is_blocked(&self.blockers, &Transition::new(kind, to)) {
+            self.pending_state.borrow_mut().take();
+            event.prevent_default();
+            return;
+        }
+
+        // Fragment navigations don't leave the document anyway.
+        if !event.hash_change() {
+            event
+                .intercept()
+                .expect_throw("failed to intercept navigation.");
+        }
+    }
+
+    fn handle_current_entry_change(&self, event: &NavigationCurrentEntryChangeEvent) {
+        let current = match self.inner.current_entry() {
+            Some(m) => m,
+            None => return,
+        };
+
+        let navigation_event = match event.navigation_type().as_deref() {
+            Some("push") | Some("replace") => {
+                let data = EntryData {
+                    id: get_id(),
+                    state: self.pending_state.borrow_mut().take(),
+                };
+                self.entries.borrow_mut().insert(current.id(), data);
+
+                if event.navigation_type().as_deref() == Some("push") {
+                    NavigationEvent::push()
+                } else {
+                    NavigationEvent::replace()
+                }
+            }
+            Some("traverse") => {
+                let (from, to) = (event.from().index(), current.index());
+                // Entries of other documents have no index.
+                let delta = Some(to as isize - from as isize).filter(|_| from >= 0 && to >= 0);
+                NavigationEvent::pop(delta)
+            }
+            _ => return,
+        };
+
+        self.notify_callbacks(navigation_event);
+    }
+
+    fn notify_callbacks(&self, event: NavigationEvent) {
+        crate::utils::notify_callbacks(self.callbacks.clone(), &event);
+    }
+}
@@ -15,8 +15,8 @@
 //! has an implementation of [`ToQuery`] or [`FromQuery`], respectively. This implementation uses
 //! the `serde_urlencoded` crate, which implements a standards-compliant `x-www-form-urlencoded`
 //! encoder and decoder. Some patterns are not supported by this crate, for example it is not
-//! possible to serialize arrays at the moment. If this is an issue for you, consider using the
-//! `serde_qs` crate.
+//! possible to serialize arrays or nested structures. For those, wrap the query in one of the
+//! strategies below.
 //!
 //! Example:
 //!
@@ -37,6 +37,56 @@
 //! history.push_with_query("index.html", &query).unwrap();
 //! ```
 //!
+//! ## Nested Strategies
+//!
+//! The [`Repeated`], [`Brackets`] and [`CommaSeparated`] wrappers encode arrays and nested
+//! structures, following the conventions of common web frameworks. Nested structures are always
+//! written with brackets, e.g. `page[size]=20`, and each wrapper writes arrays its own way:
+//!
+//! | Strategy             | `tags: vec!["a", "b"]` |
+//! |----------------------|------------------------|
+//! | [`Repeated`]         | `tags=a&tags=b`        |
+//! | [`Brackets`]         | `tags[]=a&tags[]=b`    |
+//! | [`CommaSeparated`]   | `tags=a,b`             |
+//!
+//! Arrays of structures are written with indices, e.g. `items[0][id]=1&items[1][id]=2`, with
+//! any of them.
+//!
+//! Example:
+//!
+//! ```rust
+//! use serde::{Serialize, Deserialize};
+//! use gloo_history::{MemoryHistory, History};
+//! use gloo_history::query::Brackets;
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Page {
+//!     number: u32,
+//!     size: u32,
+//! }
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Search {
+//!     tags: Vec<String>,
+//!     page: Page,
+//! }
+//!
+//! let search = Search {
+//!     tags: vec!["rust".into(), "wasm".into()],
+//!     page: Page { number: 2, size: 20 },
+//! };
+//!
+//! let history = MemoryHistory::new();
+//! history.push_with_query("/search", Brackets(&search)).unwrap();
+//!
+//! let location = history.location();
+//! assert_eq!(
+//!     location.query_str(),
+//!     "?tags%5B%5D=rust&tags%5B%5D=wasm&page%5Bnumber%5D=2&page%5Bsize%5D=20"
+//! );
+//! assert_eq!(location.query::<Brackets<Search>>().unwrap(), search);
+//! ```
+//!
 //! ## Custom Strategy
 //!
 //! If desired, the [`FromQuery`] and [`ToQuery`] traits can also be manually implemented on
@@ -47,6 +97,10 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::borrow::Cow;
 use std::convert::{AsRef, Infallible};
 
+mod nested;
+
+use nested::Style;
+
 /// Type that can be encoded into a query string.
 pub trait ToQuery {
     /// Error that can be returned from the conversion.
@@ -110,3 +164,78 @@ impl<T: for<'a> From<&'a str>> FromQuery for Raw<T> {
         Ok(query.into())
     }
 }
+
+/// # Encoding with repeated keys for arrays.
+///
+/// The [`Repeated`] wrapper encodes arrays by repeating their key, e.g. `tags=a&tags=b`, and
+/// nested structures with brackets. See the [module documentation](self) for more details.
+#[derive(Debug, Clone)]
+pub struct Repeated<T>(pub T);
+
+impl<T: Serialize> ToQuery for Repeated<T> {
+    type Error = HistoryError;
+
+    fn to_query(&self) -> Result<Cow<'_, str>, Self::Error> {
+        Ok(nested::encode(&self.0, Style::Repeat)?.into())
+    }
+}
+
+impl<T: DeserializeOwned> FromQuery for Repeated<T> {
+    type Target = T;
+    type Error = HistoryError;
+
+    fn from_query(query: &str) -> Result<Self::Target, Self::Error> {
+        Ok(nested::decode(query, Style::Repeat)?)
+    }
+}
+
+/// # Encoding with brackets for arrays.
+///
+/// The [`Brackets`] wrapper encodes arrays with empty brackets after their key, e.g.
+/// `tags[]=a&tags[]=b`, and nested structures with brackets. See the
+/// [module documentation](self) for more details.
+#[derive(Debug, Clone)]
+pub struct Brackets<T>(pub T);
+
+impl<T: Serialize> ToQuery for Brackets<T> {
+    type Error = HistoryError;
+
+    fn to_query(&self) -> Result<Cow<'_, str>, Self::Error> {
+        Ok(nested::encode(&self.0, Style::Brackets)?.into())
+    }
+}
+
+impl<T: DeserializeOwned> FromQuery for Brackets<T> {
+    type Target = T;
+    type Error = HistoryError;
+
+    fn from_query(query: &str) -> Result<Self::Target, Self::Error> {
+        Ok(nested::decode(query, Style::Brackets)?)
+    }
+}
+
+/// # Encoding with comma separated arrays.
+///
+/// The [`CommaSeparated`] wrapper encodes arrays as a single value with their items separated
+/// by commas, e.g. `tags=a,b`, and nested structures with brackets. Commas within items are
+/// percent-encoded, so they are kept apart from the separators. See the
+/// [module documentation](self) for more details.
+#[derive(Debug, Clone)]
+pub struct CommaSeparated<T>(pub T);
+
+impl<T: Serialize> ToQuery for CommaSeparated<T> {
+    type Error = HistoryError;
+
+    fn to_query(&self) -> Result<Cow<'_, str>, Self::Error> {
+        Ok(nested::encode(&self.0, Style::Comma)?.into())
+    }
+}
+
+impl<T: DeserializeOwned> FromQuery for CommaSeparated<T> {
+    type Target = T;
+    type Error = HistoryError;
+
+    fn from_query(query: &str) -> Result<Self::Target, Self::Error> {
+        Ok(nested::decode(query, Style::Comma)?)
+    }
+}
@@ -0,0 +1,767 @@
+//! Encoding and decoding of nested structures and arrays in query strings.
+//!
+//! Values are first serialized into a tree of [`Node`]s, which is then written as pairs whose
+//! keys are the paths to the leaves, like `user[name]=...`. Decoding parses the pairs back into
+//! a tree, which is deserialized with the values parsed from strings like `serde_urlencoded`
+//! does.
+use std::fmt::Display;
+use std::str::FromStr;
+
+use percent_encoding::percent_decode_str;
+use serde::de::value::{Error as DeError, MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::ser::{self, Impossible, Serialize};
+use serde_urlencoded::ser::Error as SerError;
+
+/// How arrays are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Style {
+    /// `tag=a&tag=b`
+    Repeat,
+    /// `tag[]=a&tag[]=b`
+    Brackets,
+    /// `tag=a,b`
+    Comma,
+}
+
+#[derive(Debug)]
+enum Node {
+    Leaf(String),
+    Seq(Vec<Node>),
+    Map(Vec<(String, Node)>),
+}
+
+pub(super) fn encode<T>(value: &T, style: Style) -> Result<String, SerError>
+where
+    T: Serialize + ?Sized,
+{
+    let entries = match value.serialize(NodeSerializer)? {
+        Some(Node::Map(entries)) => entries,
+        None => Vec::new(),
+        Some(_) => {
+            return Err(ser::Error::custom(
+                "top-level query must be a struct or a map",
+            ))
+        }
+    };
+
+    let mut pairs = Vec::new();
+    for (key, node) in entries {
+        write(&mut pairs, encode_component(&key), node, style);
+    }
+    Ok(pairs.join("&"))
+}
+
+pub(super) fn decode<T>(query: &str, style: Style) -> Result<T, DeError>
+where
+    T: DeserializeOwned,
+{
+    let mut entries = Vec::new();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let key = decode_component(key);
+        // Separators are literal commas, commas in items are percent-encoded.
+        let value = match style {
+            Style::Comma if value.contains(',') => Node::Seq(
+                value
+                    .split(',')
+                    .map(decode_component)
+                    .map(Node::Leaf)
+                    .collect(),
+            ),
+            _ => Node::Leaf(decode_component(value)),
+        };
+
+        let (name, path) = parse_key(&key);
+        insert(&mut entries, name, &path, value);
+    }
+
+    T::deserialize(Node::Map(entries))
+}
+
+fn encode_component(component: &str) -> String {
+    form_urlencoded::byte_serialize(component.as_bytes()).collect()
+}
+
+fn decode_component(component: &str) -> String {
+    percent_decode_str(&component.replace('+', " "))
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+/// Writes `node` as pairs under `key`, which is already encoded.
+fn write(pairs: &mut Vec<String>, key: String, node: Node, style: Style) {
+    match node {
+        Node::Leaf(value) => pairs.push(format!("{}={}", key, encode_component(&value))),
+        Node::Map(entries) => {
+            for (name, node) in entries {
+                let key = format!("{}%5B{}%5D", key, encode_component(&name));
+                write(pairs, key, node, style);
+            }
+        }
+        Node::Seq(items) => {
+            let all_leaves = items.iter().all(|item| matches!(item, Node::Leaf(_)));
+            match style {
+                Style::Comma if all_leaves && !items.is_empty() => {
+                    let values = items
+                        .into_iter()
+                        .filter_map(|item| match item {
+                            Node::Leaf(value) => Some(encode_component(&value)),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>();
+                    pairs.push(format!("{}={}", key, values.join(",")));
+                }
+                Style::Repeat | Style::Comma if all_leaves => {
+                    for item in items {
+                        write(pairs, key.clone(), item, style);
+                    }
+                }
+                Style::Brackets if all_leaves => {
+                    for item in items {
+                        write(pairs, format!("{key}%5B%5D"), item, style);
+                    }
+                }
+                // Arrays of structures are indexed, so that the fields of an item stay together.
+                _ => {
+                    for (index, item) in items.into_iter().enumerate() {
+                        write(pairs, format!("{key}%5B{index}%5D"), item, style);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Splits `user[tags][]` into `user` and `["tags", ""]`.
+fn parse_key(key: &str) -> (&str, Vec<&str>) {
+    let (name, mut rest) = match key.find('[') {
+        Some(index) if index > 0 && key.ends_with(']') => key.split_at(index),
+        _ => return (key, Vec::new()),
+    };
+
+    let mut path = Vec::new();
+    while let Some(inner) = rest.strip_prefix('[') {
+        match inner.find(']') {
+            Some(end) => {
+                path.push(&inner[..end]);
+                rest = &inner[end + 1..];
+            }
+            None => return (key, Vec::new()),
+        }
+    }
+    if !rest.is_empty() {
+        return (key, Vec::new());
+    }
+    (name, path)
+}
+
+fn insert(entries: &mut Vec<(String, Node)>, name: &str, path: &[&str], value: Node) {
+    let index = match entries.iter().position(|(key, _)| key == name) {
+        Some(index) => index,
+        None => {
+            let node = match path.first() {
+                None => {
+                    entries.push((name.to_string(), value));
+                    return;
+                }
+                Some(&"") => Node::Seq(Vec::new()),
+                Some(_) => Node::Map(Vec::new()),
+            };
+            entries.push((name.to_string(), node));
+            entries.len() - 1
+        }
+    };
+
+    let node = &mut entries[index].1;
+    match path.split_first() {
+        // A repeated key.
+        None => push(node, value),
+        Some((&"", rest)) => {
+            if !matches!(node, Node::Seq(_)) {
+                push(node, Node::Seq(Vec::new()));
+            }
+            if let Node::Seq(items) = node {
+                match rest.split_first() {
+                    None => items.push(value),
+                    Some((field, _)) => {
+                        // `items[][name]=a&items[][size]=b` fills the same item until a field
+                        // repeats.
+                        let reuse = matches!(
+                            items.last(),
+                            Some(Node::Map(fields)) if fields.iter().all(|(key, _)| key != field)
+                        );
+                        if !reuse {
+                            items.push(Node::Map(Vec::new()));
+                        }
+                        if let Some(Node::Map(fields)) = items.last_mut() {
+                            insert(fields, field, &rest[1..], value);
+                        }
+                    }
+                }
+            }
+        }
+        Some((field, rest)) => {
+            if !matches!(node, Node::Map(_)) {
+                *node = Node::Map(Vec::new());
+            }
+            if let Node::Map(fields) = node {
+                insert(fields, field, rest, value);
+            }
+        }
+    }
+}
+
+/// Adds `value` to `node`, turning it into a sequence if needed.
+fn push(node: &mut Node, value: Node) {
+    if !matches!(node, Node::Seq(_)) {
+        let previous = std::mem::replace(node, Node::Seq(Vec::new()));
+        if let Node::Seq(items) = node {
+            items.push(previous);
+        }
+    }
+    if let Node::Seq(items) = node {
+        match value {
+            Node::Seq(values) => items.extend(values),
+            value => items.push(value),
+        }
+    }
+}
+
+struct NodeSerializer;
+
+fn leaf(value: impl Display) -> Result<Option<Node>, SerError> {
+    Ok(Some(Node::Leaf(value.to_string())))
+}
+
+impl ser::Serializer for NodeSerializer {
+    type Ok = Option<Node>;
+    type Error = SerError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = Impossible<Option<Node>, SerError>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = Impossible<Option<Node>, SerError>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        leaf(v)
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom("bytes are not supported in queries"))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        leaf("")
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        leaf("")
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        leaf(variant)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let entries = value
+            .serialize(self)?
+            .map(|node| vec![(variant.to_string(), node)])
+            .unwrap_or_default();
+        Ok(Some(Node::Map(entries)))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SeqSerializer {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(ser::Error::custom(
+            "tuple variants are not supported in queries",
+        ))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(MapSerializer {
+            entries: Vec::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(ser::Error::custom(
+            "struct variants are not supported in queries",
+        ))
+    }
+}
+
+struct SeqSerializer {
+    items: Vec<Node>,
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Option<Node>;
+    type Error = SerError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        // There is no way to write a missing item.
+        let item = value.serialize(NodeSerializer)?;
+        self.items
+            .push(item.unwrap_or_else(|| Node::Leaf(String::new())));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Node::Seq(self.items)))
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Option<Node>;
+    type Error = SerError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Option<Node>;
+    type Error = SerError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    entries: Vec<(String, Node)>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Option<Node>;
+    type Error = SerError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        match key.serialize(NodeSerializer)? {
+            Some(Node::Leaf(key)) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            _ => Err(ser::Error::custom("map keys must be strings or numbers")),
+        }
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| <SerError as ser::Error>::custom("map value without a key"))?;
+        if let Some(node) = value.serialize(NodeSerializer)? {
+            self.entries.push((key, node));
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Node::Map(self.entries)))
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Option<Node>;
+    type Error = SerError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        if let Some(node) = value.serialize(NodeSerializer)? {
+            self.entries.push((key.to_string(), node));
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Node::Map(self.entries)))
+    }
+}
+
+impl<'de> IntoDeserializer<'de, DeError> for Node {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl Node {
+    fn into_leaf(self) -> Result<String, DeError> {
+        match self {
+            Node::Leaf(value) => Ok(value),
+            _ => Err(de::Error::custom("expected a single value")),
+        }
+    }
+
+    fn parse<T>(self) -> Result<T, DeError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.into_leaf()?;
+        value
+            .parse()
+            .map_err(|e| de::Error::custom(format_args!("invalid value {value:?}: {e}")))
+    }
+
+    /// The items of a sequence, a single value, or the values of a map indexed like `[0]`.
+    fn into_items(self) -> Result<Vec<Node>, DeError> {
+        match self {
+            Node::Leaf(value) => Ok(vec![Node::Leaf(value)]),
+            Node::Seq(items) => Ok(items),
+            Node::Map(entries) => {
+                let mut indexed = entries
+                    .into_iter()
+                    .map(|(key, node)| match key.parse::<usize>() {
+                        Ok(index) => Ok((index, node)),
+                        Err(_) => Err(de::Error::custom(format_args!(
+                            "expected an index, found {key:?}"
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>, DeError>>()?;
+                indexed.sort_by_key(|(index, _)| *index);
+                Ok(indexed.into_iter().map(|(_, node)| node).collect())
+            }
+        }
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Node {
+    type Error = DeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Node::Leaf(value) => visitor.visit_string(value),
+            Node::Seq(items) => visitor.visit_seq(SeqDeserializer::new(items.into_iter())),
+            Node::Map(entries) => visitor.visit_map(MapDeserializer::new(entries.into_iter())),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            // A comma separated value, read as a whole.
+            Node::Seq(items) => {
+                let values = items
+                    .into_iter()
+                    .map(Node::into_leaf)
+                    .collect::<Result<Vec<_>, _>>()?;
+                visitor.visit_string(values.join(","))
+            }
+            node => visitor.visit_string(node.into_leaf()?),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(SeqDeserializer::new(self.into_items()?.into_iter()))
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Node::Map(entries) => visitor.visit_map(MapDeserializer::new(entries.into_iter())),
+            Node::Leaf(value) if value.is_empty() => visitor.visit_map(MapDeserializer::new(
+                Vec::<(String, Node)>::new().into_iter(),
+            )),
+            _ => Err(de::Error::custom("expected a structure")),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let variant: serde::de::value::StringDeserializer<DeError> =
+            self.into_leaf()?.into_deserializer();
+        visitor.visit_enum(variant)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 bytes byte_buf
+    }
+}
@@ -0,0 +1,227 @@
+//! # Matching paths against route patterns.
+//!
+//! A [`Route`] is a pattern made of segments separated by `/`:
+//!
+//! - `users` matches the segment `users` exactly,
+//! - `:id` matches any single non-empty segment and captures it as `id`,
+//! - `*rest`, only as the last segment, matches the rest of the path, possibly empty, and
+//!   captures it as `rest`; a bare `*` matches it without capturing it.
+//!
+//! Captured segments are percent-decoded. With the `query` feature, the captures can be
+//! deserialized into a struct, the same way queries are, see `RouteMatch::params`.
+//!
+//! Example:
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use gloo_history::route::Route;
+//!
+//! #[derive(Deserialize)]
+//! struct PostParams {
+//!     id: u64,
+//!     rest: String,
+//! }
+//!
+//! let route = Route::new("/users/:id/posts/*rest");
+//! let matched = route.matches("/users/42/posts/2024/hello%20world").unwrap();
+//! assert_eq!(matched.get("id"), Some("42"));
+//!
+//! let params: PostParams = matched.params().unwrap();
+//! assert_eq!(params.id, 42);
+//! assert_eq!(params.rest, "2024/hello world");
+//!
+//! assert!(route.matches("/users/42").is_none());
+//! ```
+use std::borrow::Cow;
+use std::fmt;
+
+#[cfg(feature = "query")]
+use crate::error::{HistoryError, HistoryResult};
+#[cfg(feature = "query")]
+use serde::de::DeserializeOwned;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Static(String),
+    Param(String),
+    Rest(Option<String>),
+}
+
+/// A route pattern, like `/users/:id/posts/*rest`.
+///
+/// See the [module documentation](self) for the syntax of patterns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pattern: String,
+    segments: Vec<Segment>,
+}
+
+impl Route {
+    /// Parses a route pattern.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a `*` segment is not the last one, or if a `:` segment has no
+    /// name.
+    pub fn new(pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        let parts = split(&pattern);
+        let segments = parts
+            .iter()
+            .enumerate()
+            .map(|(index, part)| {
+                if let Some(name) = part.strip_prefix(':') {
+                    assert!(
+                        !name.is_empty(),
+                        "missing parameter name in route {pattern}"
+                    );
+                    Segment::Param(name.to_string())
+                } else if let Some(name) = part.strip_prefix('*') {
+                    assert!(
+                        index + 1 == parts.len(),
+                        "`*` must be the last segment of route {pattern}"
+                    );
+                    Segment::Rest(Some(name.to_string()).filter(|name| !name.is_empty()))
+                } else {
+                    Segment::Static(part.to_string())
+                }
+            })
+            .collect();
+        Self { pattern, segments }
+    }
+
+    /// Returns the pattern of this route.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Matches `path` against this route, returning the captured segments if it matches.
+    ///
+    /// A trailing slash in `path` is ignored.
+    pub fn matches(&self, path: &str) -> Option<RouteMatch> {
+        let parts = split(path);
+        let mut params = Vec::new();
+        let mut parts_iter = parts.iter().enumerate();
+        for segment in &self.segments {
+            match segment {
+                Segment::Rest(name) => {
+                    let rest = match parts_iter.next() {
+                        Some((index, _)) => parts[index..]
+                            .iter()
+                            .map(|part| percent_decode(part))
+                            .collect::<Vec<_>>()
+                            .join("/"),
+                        None => String::new(),
+                    };
+                    if let Some(name) = name {
+                        params.push((name.clone(), rest));
+                    }
+                    return Some(RouteMatch { params });
+                }
+                Segment::Static(expected) => {
+                    let (_, part) = parts_iter.next()?;
+                    if part != expected {
+                        return None;
+                    }
+                }
+                Segment::Param(name) => {
+                    let (_, part) = parts_iter.next()?;
+                    if part.is_empty() {
+                        return None;
+                    }
+                    params.push((name.clone(), percent_decode(part).into_owned()));
+                }
+            }
+        }
+        match parts_iter.next() {
+            Some(_) => None,
+            None => Some(RouteMatch { params }),
+        }
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+impl From<&str> for Route {
+    fn from(pattern: &str) -> Self {
+        Self::new(pattern)
+    }
+}
+
+/// The segments captured by a successful [`Route::matches`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteMatch {
+    params: Vec<(String, String)>,
+}
+
+impl RouteMatch {
+    /// Returns the percent-decoded value captured as `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns an iterator over the names and values of the captures, in the order of the
+    /// pattern.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Returns the captures deserialized as `T`, whose fields are named after them.
+    ///
+    /// Values are parsed like the ones of queries, so fields can be numbers, booleans or
+    /// anything else deserialized from a string.
+    #[cfg(feature = "query")]
+    pub fn params<T>(&self) -> HistoryResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        let encoded = serde_urlencoded::to_string(&self.params)?;
+        serde_urlencoded::from_str(&encoded).map_err(HistoryError::RouteParams)
+    }
+}
+
+/// Splits a path into its segments, ignoring the leading and the trailing slash.
+fn split(path: &str) -> Vec<&str> {
+    let path = path.strip_prefix('/').unwrap_or(path);
+    let path = path.strip_suffix('/').unwrap_or(path);
+    if path.is_empty() {
+        Vec::new()
+    } else {
+        path.split('/').collect()
+    }
+}
+
+fn percent_decode(segment: &str) -> Cow<'_, str> {
+    if !segment.contains('%') {
+        return Cow::Borrowed(segment);
+    }
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    Cow::Owned(String::from_utf8_lossy(&decoded).into_owned())
+}
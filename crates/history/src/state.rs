@@ -17,13 +17,25 @@ enum HistoryStateKind {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct HistoryState {
     id: u32,
+    /// The position of the entry in the session history, missing in states of older versions.
+    #[serde(default)]
+    index: Option<isize>,
+    /// The number of entries of the session history when the entry was last visited.
+    #[serde(default)]
+    length: Option<isize>,
+    /// The key of the entry, kept when it is replaced.
+    #[serde(default)]
+    key: Option<String>,
     kind: HistoryStateKind,
 }
 
 impl HistoryState {
-    pub fn new() -> HistoryState {
+    pub fn new(index: isize, length: isize, key: String) -> HistoryState {
         Self {
             id: get_id(),
+            index: Some(index),
+            length: Some(length),
+            key: Some(key),
             kind: HistoryStateKind::Gloo,
         }
     }
@@ -31,6 +43,18 @@ impl HistoryState {
     pub fn id(&self) -> u32 {
         self.id
     }
+
+    pub fn index(&self) -> Option<isize> {
+        self.index
+    }
+
+    pub fn length(&self) -> Option<isize> {
+        self.length
+    }
+
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
 }
 
 pub(crate) type StateMap = HashMap<u32, Rc<dyn Any>>;
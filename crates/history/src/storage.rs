@@ -0,0 +1,90 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+type EntryMap = HashMap<(String, TypeId), Rc<dyn Any>>;
+
+/// A scratch storage for the UI state of history entries, such as form drafts or scroll
+/// positions, returned by [`History::entry_storage`].
+///
+/// Values are stored under the key of an entry, see [`Location::key`], and their type, so
+/// unrelated components can store values for the same entry. As the key of an entry is kept
+/// when the entry is replaced, so are its values.
+///
+/// Like the states of [`History::push_with_state`], values are kept in memory and are lost
+/// when the page is reloaded.
+///
+/// # Example
+///
+/// ```
+/// use gloo_history::{History, MemoryHistory};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Scroll(f64);
+///
+/// let history = MemoryHistory::new();
+/// let key = history.location().key().unwrap().to_owned();
+/// history.entry_storage().set(&key, Scroll(120.0));
+///
+/// history.push("/posts");
+/// history.back();
+///
+/// let key = history.location().key().unwrap().to_owned();
+/// assert_eq!(*history.entry_storage().get::<Scroll>(&key).unwrap(), Scroll(120.0));
+/// ```
+///
+/// [`History::entry_storage`]: crate::History::entry_storage
+/// [`History::push_with_state`]: crate::History::push_with_state
+/// [`Location::key`]: crate::Location::key
+#[derive(Clone, Default)]
+pub struct EntryStorage {
+    inner: Rc<RefCell<EntryMap>>,
+}
+
+impl fmt::Debug for EntryStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EntryStorage").finish()
+    }
+}
+
+impl EntryStorage {
+    /// Stores `value` for the entry with `key`, replacing the value of the same type, if any.
+    pub fn set<T>(&self, key: &str, value: T)
+    where
+        T: 'static,
+    {
+        self.inner
+            .borrow_mut()
+            .insert((key.to_owned(), TypeId::of::<T>()), Rc::new(value));
+    }
+
+    /// Returns the value of type `T` stored for the entry with `key`.
+    pub fn get<T>(&self, key: &str) -> Option<Rc<T>>
+    where
+        T: 'static,
+    {
+        self.inner
+            .borrow()
+            .get(&(key.to_owned(), TypeId::of::<T>()))
+            .cloned()
+            .and_then(|m| m.downcast().ok())
+    }
+
+    /// Removes the value of type `T` stored for the entry with `key`, and returns it.
+    pub fn remove<T>(&self, key: &str) -> Option<Rc<T>>
+    where
+        T: 'static,
+    {
+        self.inner
+            .borrow_mut()
+            .remove(&(key.to_owned(), TypeId::of::<T>()))
+            .and_then(|m| m.downcast().ok())
+    }
+
+    /// Removes all the values stored for the entry with `key`.
+    pub fn clear(&self, key: &str) {
+        self.inner.borrow_mut().retain(|(m, _), _| m != key);
+    }
+}
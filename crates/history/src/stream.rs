@@ -0,0 +1,56 @@
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_channel::mpsc;
+use futures_core::Stream;
+
+use crate::history::History;
+use crate::listener::HistoryListener;
+use crate::location::Location;
+
+/// A [`Stream`] of the [`Location`]s a [`History`] navigates to.
+///
+/// It is created with [`History::stream`], and unregisters itself from the history when dropped.
+#[must_use = "streams do nothing unless polled"]
+pub struct HistoryStream {
+    receiver: mpsc::UnboundedReceiver<Location>,
+    _listener: HistoryListener,
+}
+
+impl HistoryStream {
+    pub(crate) fn new<H>(history: &H) -> Self
+    where
+        H: History + 'static,
+    {
+        let (sender, receiver) = mpsc::unbounded();
+
+        // Callbacks are held weakly by histories, so this clone doesn't create a reference cycle.
+        let listener = history.listen({
+            let history = history.clone();
+            move || {
+                // if the receiver was dropped we do nothing.
+                let _ = sender.unbounded_send(history.location());
+            }
+        });
+
+        Self {
+            receiver,
+            _listener: listener,
+        }
+    }
+}
+
+impl Stream for HistoryStream {
+    type Item = Location;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl fmt::Debug for HistoryStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HistoryStream").finish()
+    }
+}
@@ -4,6 +4,10 @@ use std::sync::atomic::{AtomicU32, Ordering};
 
 use wasm_bindgen::throw_str;
 
+use crate::blocker::Transition;
+use crate::hook::Hook;
+use crate::listener::NavigationEvent;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) fn get_id() -> u32 {
     static ID_CTR: AtomicU32 = AtomicU32::new(0);
@@ -28,6 +32,11 @@ pub(crate) fn get_id() -> u32 {
     ID_CTR.fetch_add(1, Ordering::SeqCst)
 }
 
+/// Creates a key for a history entry.
+pub(crate) fn get_key() -> Rc<str> {
+    format!("{:08x}", get_id()).into()
+}
+
 pub(crate) fn assert_absolute_path(path: &str) {
     if !path.starts_with('/') {
         throw_str("You cannot use relative path with this history type.");
@@ -46,9 +55,9 @@ pub(crate) fn assert_no_fragment(path: &str) {
     }
 }
 
-pub(crate) type WeakCallback = Weak<dyn Fn()>;
+pub(crate) type WeakCallback = Weak<dyn Fn(&NavigationEvent)>;
 
-pub(crate) fn notify_callbacks(callbacks: Rc<RefCell<Vec<WeakCallback>>>) {
+pub(crate) fn notify_callbacks(callbacks: Rc<RefCell<Vec<WeakCallback>>>, event: &NavigationEvent) {
     let callables = {
         let mut callbacks_ref = callbacks.borrow_mut();
 
@@ -71,6 +80,49 @@ pub(crate) fn notify_callbacks(callbacks: Rc<RefCell<Vec<WeakCallback>>>) {
     };
 
     for callback in callables {
-        callback()
+        callback(event)
+    }
+}
+
+pub(crate) type WeakBlocker = Weak<dyn Fn(&Transition) -> bool>;
+
+/// Asks the blockers whether `transition` may happen, returning `true` if one of them cancels it.
+pub(crate) fn is_blocked(blockers: &RefCell<Vec<WeakBlocker>>, transition: &Transition) -> bool {
+    let callables = {
+        let mut blockers = blockers.borrow_mut();
+        // Gone blockers are removed when asked.
+        blockers.retain(|m| m.strong_count() > 0);
+
+        blockers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect::<Vec<_>>()
+    };
+
+    callables.iter().any(|blocker| !blocker(transition))
+}
+
+pub(crate) type WeakHook = Weak<Hook>;
+
+/// Passes `transition` through `hooks` in the order they were registered, and returns its
+/// rewritten target, or [`None`] if a hook vetoed it.
+pub(crate) fn apply_hooks(
+    hooks: &RefCell<Vec<WeakHook>>,
+    transition: Transition,
+) -> Option<String> {
+    let callables = {
+        let mut hooks = hooks.borrow_mut();
+        // Gone hooks are removed when asked.
+        hooks.retain(|m| m.strong_count() > 0);
+
+        hooks.iter().filter_map(Weak::upgrade).collect::<Vec<_>>()
+    };
+
+    let mut transition = transition;
+    for hook in callables {
+        let to = hook(&transition)?;
+        transition = transition.map_to(|_| to);
     }
+
+    transition.to().map(str::to_owned)
 }
@@ -1,8 +1,9 @@
+use std::cell::RefCell;
 use std::rc::Rc;
 
 use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
 
-use gloo_history::{BrowserHistory, History};
+use gloo_history::{BrowserHistory, History, TransitionKind};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -56,3 +57,134 @@ async fn history_works() {
 
     history.push_with_state("/fish", String::from("/fish"));
 }
+
+#[test]
+async fn history_blocks() {
+    let history = BrowserHistory::new();
+
+    history.push("/saved");
+    history.push("/draft");
+
+    let blocker = history.block(|transition| transition.kind() == TransitionKind::Push);
+
+    history.replace("/other");
+    assert_eq!(history.location().path(), "/draft");
+
+    history.back();
+
+    // The back navigation happens, then is reverted.
+    {
+        let history = history.clone();
+        delayed_assert_eq(move || history.location().path().to_owned(), || "/draft").await;
+    }
+
+    history.push("/published");
+    assert_eq!(history.location().path(), "/published");
+
+    drop(blocker);
+
+    history.replace("/other");
+    assert_eq!(history.location().path(), "/other");
+}
+
+#[test]
+fn history_with_basename() {
+    let history = BrowserHistory::with_basename("/app/");
+    assert_eq!(history.basename(), Some("/app"));
+    assert_ne!(history, BrowserHistory::new());
+
+    history.push("/about");
+    assert_eq!(history.location().path(), "/about");
+    assert_eq!(BrowserHistory::new().location().path(), "/app/about");
+
+    history.replace("/");
+    assert_eq!(history.location().path(), "/");
+    assert_eq!(BrowserHistory::new().location().path(), "/app/");
+
+    history.replace("team");
+    assert_eq!(history.location().path(), "/team");
+}
+
+#[test]
+async fn history_blocks_pop_with_basename() {
+    let history = BrowserHistory::with_basename("/app");
+
+    history.push("/app/settings");
+    history.push("/draft");
+
+    let targets = Rc::new(RefCell::new(Vec::new()));
+    let _blocker = history.block({
+        let targets = targets.clone();
+        move |transition| {
+            targets
+                .borrow_mut()
+                .push(transition.to().map(str::to_owned));
+            true
+        }
+    });
+
+    history.back();
+
+    {
+        let history = history.clone();
+        delayed_assert_eq(
+            move || history.location().path().to_owned(),
+            || "/app/settings",
+        )
+        .await;
+    }
+    // The basename is only stripped once.
+    assert_eq!(*targets.borrow(), [Some("/app/settings".to_owned())]);
+}
+
+#[test]
+fn history_js_state() {
+    let history = BrowserHistory::new();
+
+    let state = js_sys::Uint8Array::from(&[1_u8, 2, 3][..]);
+    history.push_with_js_state("/blob", &state);
+
+    let location = history.location();
+    assert_eq!(location.path(), "/blob");
+    let cloned = js_sys::Uint8Array::from(location.js_state().unwrap());
+    assert_eq!(cloned.to_vec(), [1, 2, 3]);
+
+    history.replace("/blob");
+    assert!(history.location().js_state().is_none());
+}
+
+#[test]
+async fn history_introspects() {
+    let history = BrowserHistory::new();
+
+    history.push("/path-a");
+    let index = history.index();
+    assert!(history.can_go_back());
+    assert!(!history.can_go_forward());
+
+    history.push("/path-b");
+    assert_eq!(history.index(), index + 1);
+
+    history.back();
+
+    {
+        let history = history.clone();
+        delayed_assert_eq(move || history.index(), move || index).await;
+    }
+
+    assert!(history.can_go_forward());
+}
+
+#[test]
+fn history_keys_entries() {
+    let history = BrowserHistory::new();
+
+    history.push("/path-a");
+    let key = history.location().key().unwrap().to_owned();
+
+    history.replace("/path-b");
+    assert_eq!(history.location().key(), Some(key.as_str()));
+
+    history.push("/path-c");
+    assert_ne!(history.location().key(), Some(key.as_str()));
+}
@@ -51,3 +51,23 @@ async fn history_works() {
     delayed_assert_eq(|| window().location().pathname().unwrap(), || "/").await;
     delayed_assert_eq(|| window().location().hash().unwrap(), || "#/path-b").await;
 }
+
+#[test]
+async fn history_query_in_fragment() {
+    let history = HashHistory::new();
+
+    history.push("/search?q=rust");
+    {
+        let history = history.clone();
+        delayed_assert_eq(|| history.location().path().to_owned(), || "/search").await;
+    }
+    assert_eq!(history.location().query_str(), "?q=rust");
+    assert_eq!(window().location().search().unwrap(), "");
+    assert_eq!(window().location().hash().unwrap(), "#/search?q=rust");
+
+    history.replace("/search");
+    {
+        let history = history.clone();
+        delayed_assert_eq(|| history.location().query_str().to_owned(), || "").await;
+    }
+}
@@ -141,4 +141,23 @@ mod feat_serialize {
             .await;
         }
     }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct OptionalQuery {
+        a: Option<String>,
+    }
+
+    #[test]
+    async fn history_empty_query_works() {
+        let history = HashHistory::new();
+
+        history
+            .push_with_query("/empty", OptionalQuery { a: None })
+            .unwrap();
+        delayed_assert_eq(|| window().location().hash().unwrap(), || "#/empty").await;
+        assert_eq!(
+            history.location().query::<OptionalQuery>().unwrap(),
+            OptionalQuery { a: None }
+        );
+    }
 }
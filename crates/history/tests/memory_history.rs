@@ -1,6 +1,11 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
 
-use gloo_history::{History, MemoryHistory};
+use gloo_history::{
+    Action, AnyHistory, Direction, History, LocationBuilder, MemoryHistory, TransitionKind,
+};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -21,3 +26,222 @@ fn history_works() {
     history.forward();
     assert_eq!(history.location().path(), "/path-b");
 }
+
+#[test]
+fn history_blocks() {
+    let history = MemoryHistory::with_entries(["/", "/draft"]);
+    let transitions = Rc::new(RefCell::new(Vec::new()));
+
+    let blocker = history.block({
+        let transitions = transitions.clone();
+        move |transition| {
+            transitions
+                .borrow_mut()
+                .push((transition.kind(), transition.to().map(str::to_owned)));
+            false
+        }
+    });
+
+    history.push("/path-a");
+    history.replace("/path-b");
+    history.back();
+    assert_eq!(history.location().path(), "/draft");
+    assert_eq!(
+        *transitions.borrow(),
+        [
+            (TransitionKind::Push, Some("/path-a".to_owned())),
+            (TransitionKind::Replace, Some("/path-b".to_owned())),
+            (TransitionKind::Pop, Some("/".to_owned())),
+        ]
+    );
+
+    drop(blocker);
+
+    history.back();
+    assert_eq!(history.location().path(), "/");
+    assert_eq!(transitions.borrow().len(), 3);
+}
+
+#[cfg(feature = "futures")]
+#[test]
+async fn history_streams() {
+    use futures_util::StreamExt;
+
+    let history = MemoryHistory::new();
+    let mut locations = history.stream();
+
+    history.push("/path-a");
+    history.replace("/path-b");
+
+    let location = locations.next().await.unwrap();
+    assert_eq!(location.path(), "/path-a");
+    let location = locations.next().await.unwrap();
+    assert_eq!(location.path(), "/path-b");
+}
+
+#[test]
+fn history_snapshots() {
+    let history = MemoryHistory::new();
+    history.push_with_state("/path-a", String::from("a"));
+    history.push_with_state("/path-b", 2_u32);
+    history.back();
+
+    let snapshot = history.snapshot_with_state::<String>();
+    assert_eq!(snapshot.len(), 3);
+    assert_eq!(snapshot.index(), 1);
+    assert_eq!(history.snapshot_with_state::<String>(), snapshot);
+
+    let restored = MemoryHistory::from_snapshot(snapshot);
+    assert_eq!(restored.len(), 3);
+    let location = restored.location();
+    assert_eq!(location.path(), "/path-a");
+    assert_eq!(
+        location.state::<String>().as_deref(),
+        Some(&String::from("a"))
+    );
+
+    // Only states of the type of the snapshot are kept.
+    restored.forward();
+    let location = restored.location();
+    assert_eq!(location.path(), "/path-b");
+    assert_eq!(location.state::<u32>(), None);
+
+    let default = MemoryHistory::from_snapshot(MemoryHistory::new().snapshot());
+    assert_eq!(default.location().path(), "/");
+}
+
+#[test]
+fn history_listens_with_events() {
+    let history = MemoryHistory::with_entries(["/", "/path-a", "/path-b"]);
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let _listener = history.listen_with_event({
+        let events = events.clone();
+        move |event| {
+            events
+                .borrow_mut()
+                .push((event.action(), event.delta(), event.direction()));
+        }
+    });
+
+    history.go(-2);
+    history.forward();
+    history.push("/path-c");
+    history.replace("/path-d");
+    // Already at the newest entry.
+    history.forward();
+
+    assert_eq!(
+        *events.borrow(),
+        [
+            (Action::Pop, Some(-2), Some(Direction::Back)),
+            (Action::Pop, Some(1), Some(Direction::Forward)),
+            (Action::Push, Some(1), Some(Direction::Forward)),
+            (Action::Replace, Some(0), None),
+        ]
+    );
+}
+
+#[test]
+fn history_introspects() {
+    let history = MemoryHistory::with_entries(["/", "/path-a", "/path-b"]);
+    assert_eq!(history.index(), 2);
+    assert!(history.can_go_back());
+    assert!(!history.can_go_forward());
+
+    history.go(-2);
+    assert_eq!(history.index(), 0);
+    assert!(!history.can_go_back());
+    assert!(history.can_go_forward());
+
+    history.push("/path-c");
+    assert_eq!(history.index(), 1);
+    assert_eq!(history.len(), 2);
+    assert!(!history.can_go_forward());
+}
+
+#[test]
+fn history_builds_locations() {
+    let history = MemoryHistory::new();
+
+    history.push_location(
+        LocationBuilder::new("/posts")
+            .query_str("page=2")
+            .hash("#comments")
+            .state(42u32),
+    );
+    let location = history.location();
+    assert_eq!(location.path(), "/posts");
+    assert_eq!(location.query_str(), "?page=2");
+    assert_eq!(location.hash(), "#comments");
+    assert_eq!(*location.state::<u32>().unwrap(), 42);
+
+    history.replace_hash("");
+    let location = history.location();
+    assert_eq!(location.hash(), "");
+    assert_eq!(location.query_str(), "?page=2");
+    assert_eq!(history.len(), 2);
+
+    history.push_preserving_state("/posts/1");
+    let location = history.location();
+    assert_eq!(location.path(), "/posts/1");
+    assert_eq!(location.query_str(), "");
+    assert_eq!(*location.state::<u32>().unwrap(), 42);
+
+    history.replace_location(history.location().to_builder().clear_state());
+    assert!(history.location().state::<u32>().is_none());
+    assert_eq!(history.location().path(), "/posts/1");
+}
+
+#[test]
+fn history_hooks() {
+    let history = AnyHistory::from(MemoryHistory::new());
+
+    let locale = history.hook(|transition| Some(format!("/en{}", transition.to()?)));
+    let _auth = history.hook(|transition| match transition.to()? {
+        "/en/admin" => None,
+        m => Some(m.to_owned()),
+    });
+
+    history.push("/posts");
+    assert_eq!(history.location().path(), "/en/posts");
+
+    history.push_location(LocationBuilder::new("/admin"));
+    assert_eq!(history.location().path(), "/en/posts");
+    assert_eq!(history.len(), 2);
+
+    drop(locale);
+    history.replace_with_state("/admin", 42u32);
+    assert_eq!(history.location().path(), "/admin");
+}
+
+#[test]
+fn history_keys_entries() {
+    let history = MemoryHistory::new();
+    let first = history.location().key().unwrap().to_owned();
+    history.entry_storage().set(&first, "draft".to_string());
+
+    history.push("/path-a");
+    let second = history.location().key().unwrap().to_owned();
+    assert_ne!(first, second);
+    assert!(history.entry_storage().get::<String>(&second).is_none());
+
+    // Replacing an entry keeps its key, but not its id.
+    let id = history.location().id();
+    history.replace("/path-b");
+    assert_eq!(history.location().key(), Some(second.as_str()));
+    assert_ne!(history.location().id(), id);
+
+    history.back();
+    assert_eq!(history.location().key(), Some(first.as_str()));
+    assert_eq!(
+        *history.entry_storage().get::<String>(&first).unwrap(),
+        "draft"
+    );
+
+    let restored = MemoryHistory::from_snapshot(history.snapshot());
+    assert_eq!(restored.location().key(), Some(first.as_str()));
+
+    history.entry_storage().clear(&first);
+    assert!(history.entry_storage().get::<String>(&first).is_none());
+}
@@ -78,4 +78,23 @@ mod feat_serialize {
             })
         );
     }
+
+    #[test]
+    fn history_replaces_query() {
+        let history = MemoryHistory::new();
+        history.push_with_state("/path", 42u32);
+
+        history
+            .replace_query(Query {
+                a: "something".to_string(),
+                b: 123,
+            })
+            .unwrap();
+
+        let location = history.location();
+        assert_eq!(location.path(), "/path");
+        assert_eq!(location.query_str(), "?a=something&b=123");
+        assert_eq!(*location.state::<u32>().unwrap(), 42);
+        assert_eq!(history.len(), 2);
+    }
 }
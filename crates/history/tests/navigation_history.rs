@@ -0,0 +1,56 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+use gloo_history::{Action, AnyHistory, History, NavigationHistory};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+mod utils;
+use utils::delayed_assert_eq;
+
+#[test]
+async fn history_works() {
+    if !NavigationHistory::is_supported() {
+        return;
+    }
+
+    let history = NavigationHistory::new();
+    let actions = Rc::new(RefCell::new(Vec::new()));
+    let _listener = history.listen_with_event({
+        let actions = actions.clone();
+        move |event| actions.borrow_mut().push(event.action())
+    });
+
+    history.push_with_state("/path-a", 42u32);
+    assert_eq!(history.location().path(), "/path-a");
+    assert_eq!(*history.location().state::<u32>().unwrap(), 42);
+
+    history.replace("/path-b");
+    assert_eq!(history.location().path(), "/path-b");
+    assert!(history.location().state::<u32>().is_none());
+
+    history.back();
+
+    {
+        let history = history.clone();
+        delayed_assert_eq(move || history.location().path().to_owned(), || "/").await;
+    }
+
+    assert_eq!(
+        *actions.borrow(),
+        vec![Action::Push, Action::Replace, Action::Pop]
+    );
+}
+
+#[test]
+fn any_history_falls_back() {
+    let history = AnyHistory::navigation_or_browser();
+
+    if NavigationHistory::is_supported() {
+        assert!(matches!(history, AnyHistory::Navigation(_)));
+    } else {
+        assert!(matches!(history, AnyHistory::Browser(_)));
+    }
+}
@@ -56,3 +56,118 @@ fn test_urlencoded_decode_simple() {
         }
     );
 }
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Page {
+    number: u32,
+    size: u32,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Item {
+    id: u64,
+    name: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct NestedQuery {
+    tags: Vec<String>,
+    page: Page,
+    items: Vec<Item>,
+    optional: Option<String>,
+}
+
+fn nested_query() -> NestedQuery {
+    NestedQuery {
+        tags: vec!["a,b".into(), "c d".into()],
+        page: Page {
+            number: 2,
+            size: 20,
+        },
+        items: vec![
+            Item {
+                id: 1,
+                name: "one".into(),
+            },
+            Item {
+                id: 2,
+                name: "two".into(),
+            },
+        ],
+        optional: None,
+    }
+}
+
+const NESTED_ITEMS: &str = "items%5B0%5D%5Bid%5D=1&items%5B0%5D%5Bname%5D=one\
+                            &items%5B1%5D%5Bid%5D=2&items%5B1%5D%5Bname%5D=two";
+
+#[test]
+fn test_repeated_round_trip() {
+    let query = Repeated(nested_query());
+    let encoded = query.to_query().unwrap();
+    assert_eq!(
+        encoded,
+        format!("tags=a%2Cb&tags=c+d&page%5Bnumber%5D=2&page%5Bsize%5D=20&{NESTED_ITEMS}")
+    );
+    assert_eq!(
+        <Repeated<NestedQuery>>::from_query(&encoded).unwrap(),
+        nested_query()
+    );
+}
+
+#[test]
+fn test_brackets_round_trip() {
+    let query = Brackets(nested_query());
+    let encoded = query.to_query().unwrap();
+    assert_eq!(
+        encoded,
+        format!(
+            "tags%5B%5D=a%2Cb&tags%5B%5D=c+d&page%5Bnumber%5D=2&page%5Bsize%5D=20&{NESTED_ITEMS}"
+        )
+    );
+    assert_eq!(
+        <Brackets<NestedQuery>>::from_query(&encoded).unwrap(),
+        nested_query()
+    );
+}
+
+#[test]
+fn test_comma_separated_round_trip() {
+    let query = CommaSeparated(nested_query());
+    let encoded = query.to_query().unwrap();
+    assert_eq!(
+        encoded,
+        format!("tags=a%2Cb,c+d&page%5Bnumber%5D=2&page%5Bsize%5D=20&{NESTED_ITEMS}")
+    );
+    assert_eq!(
+        <CommaSeparated<NestedQuery>>::from_query(&encoded).unwrap(),
+        nested_query()
+    );
+}
+
+#[test]
+fn test_nested_decode_handwritten() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Filters {
+        ids: Vec<u32>,
+        sort: Vec<String>,
+    }
+
+    let decoded = <Brackets<Filters>>::from_query("ids[]=3&ids[]=1&sort[]=name").unwrap();
+    assert_eq!(
+        decoded,
+        Filters {
+            ids: vec![3, 1],
+            sort: vec!["name".into()],
+        }
+    );
+
+    let decoded = <Repeated<Filters>>::from_query("ids=3&sort=name&ids=1").unwrap();
+    assert_eq!(decoded.ids, [3, 1]);
+
+    let decoded = <CommaSeparated<Filters>>::from_query("ids=3,1&sort=name").unwrap();
+    assert_eq!(decoded.ids, [3, 1]);
+    assert_eq!(decoded.sort, ["name"]);
+
+    assert!(<Brackets<Filters>>::from_query("ids[]=x&sort[]=name").is_err());
+}
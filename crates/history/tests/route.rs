@@ -0,0 +1,100 @@
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+#[cfg(target_arch = "wasm32")]
+wasm_bindgen_test_configure!(run_in_browser);
+
+use gloo_history::route::Route;
+use gloo_history::{History, MemoryHistory};
+
+#[test]
+fn test_static_route() {
+    let route = Route::new("/about/team");
+    assert!(route.matches("/about/team").is_some());
+    assert!(route.matches("/about/team/").is_some());
+    assert!(route.matches("/about").is_none());
+    assert!(route.matches("/about/team/lead").is_none());
+    assert_eq!(route.matches("/about/team").unwrap().iter().count(), 0);
+
+    let root = Route::new("/");
+    assert!(root.matches("/").is_some());
+    assert!(root.matches("/about").is_none());
+}
+
+#[test]
+fn test_params() {
+    let route = Route::new("/users/:id/posts/:post");
+    let matched = route.matches("/users/42/posts/hello%20world").unwrap();
+    assert_eq!(matched.get("id"), Some("42"));
+    assert_eq!(matched.get("post"), Some("hello world"));
+    assert_eq!(matched.get("other"), None);
+    assert_eq!(
+        matched.iter().collect::<Vec<_>>(),
+        [("id", "42"), ("post", "hello world")]
+    );
+
+    assert!(route.matches("/users//posts/1").is_none());
+    assert!(route.matches("/users/42/comments/1").is_none());
+}
+
+#[test]
+fn test_rest() {
+    let route = Route::new("/files/*path");
+    let matched = route.matches("/files/a/b%2Fc/d").unwrap();
+    assert_eq!(matched.get("path"), Some("a/b/c/d"));
+    assert_eq!(route.matches("/files").unwrap().get("path"), Some(""));
+    assert!(route.matches("/folders/a").is_none());
+
+    let catch_all = Route::new("*");
+    assert!(catch_all.matches("/anything/at/all").is_some());
+    assert_eq!(catch_all.matches("/anything").unwrap().iter().count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_rest_not_last() {
+    Route::new("/files/*path/edit");
+}
+
+#[cfg(feature = "query")]
+#[test]
+fn test_typed_params() {
+    use gloo_history::HistoryError;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Params {
+        id: u64,
+        rest: String,
+    }
+
+    let route = Route::new("/users/:id/posts/*rest");
+    let params: Params = route
+        .matches("/users/42/posts/2024/a+b")
+        .unwrap()
+        .params()
+        .unwrap();
+    assert_eq!(
+        params,
+        Params {
+            id: 42,
+            rest: "2024/a+b".into(),
+        }
+    );
+
+    let result = route
+        .matches("/users/me/posts/")
+        .unwrap()
+        .params::<Params>();
+    assert!(matches!(result, Err(HistoryError::RouteParams(_))));
+}
+
+#[test]
+fn test_location_matches() {
+    let history = MemoryHistory::new();
+    history.push("/users/7");
+    let matched = history
+        .location()
+        .matches(&Route::new("/users/:id"))
+        .unwrap();
+    assert_eq!(matched.get("id"), Some("7"));
+}
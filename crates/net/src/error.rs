@@ -1,13 +1,82 @@
 use gloo_utils::errors::JsError;
 use thiserror::Error as ThisError;
 
+#[cfg(feature = "http")]
+use crate::http::Response;
+
+/// The parts of an unsuccessful response kept in [`Error::StatusError`].
+///
+/// Only plain data is kept, rather than the [`Response`] itself, so that [`Error`] stays `Send`
+/// and `Sync`.
+#[cfg(feature = "http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ErrorResponse {
+    /// The status code of the response, e.g. `404`.
+    pub status: u16,
+    /// The status message of the response, e.g. `Not Found`.
+    pub status_text: String,
+    /// The URL of the response, after redirects.
+    pub url: String,
+    /// The headers of the response.
+    pub headers: Vec<(String, String)>,
+    /// The start of the body of the response, or an empty string if it couldn't be read.
+    pub body_snippet: String,
+}
+
+#[cfg(feature = "http")]
+impl ErrorResponse {
+    /// How many characters of the body to keep in [`ErrorResponse::body_snippet`].
+    const BODY_SNIPPET_LEN: usize = 512;
+
+    pub(crate) async fn read(response: Response) -> Self {
+        let body = response.text().await.unwrap_or_default();
+        ErrorResponse {
+            status: response.status(),
+            status_text: response.status_text(),
+            url: response.url(),
+            headers: response.headers().entries().collect(),
+            body_snippet: body.chars().take(Self::BODY_SNIPPET_LEN).collect(),
+        }
+    }
+}
+
 /// All the errors returned by this crate.
 #[derive(Debug, ThisError)]
+#[non_exhaustive]
 pub enum Error {
     /// Error returned by JavaScript.
     #[error("{0}")]
     JsError(JsError),
-    /// Error returned by `serde` during deserialization.
+    /// The request failed before a response was received.
+    ///
+    /// This happens on DNS failures, refused connections and requests blocked by CORS. Browsers
+    /// deliberately report all of these the same way, so they can't be told apart.
+    #[error("network error: {0}")]
+    NetworkError(JsError),
+    /// The request was aborted through its `AbortSignal`.
+    #[error("request aborted: {0}")]
+    AbortError(JsError),
+    /// The request timed out, e.g. because its `AbortSignal` was created with
+    /// [`AbortSignal.timeout()`](https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal/timeout).
+    #[error("request timed out: {0}")]
+    TimeoutError(JsError),
+    /// The response is opaque, so neither its status nor its body can be read.
+    ///
+    /// This is the case for responses to `no-cors` requests and for redirects of requests made
+    /// with `redirect: "manual"`. Returned by [`Response::error_for_status`].
+    #[cfg(feature = "http")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+    #[error("opaque response")]
+    OpaqueResponse,
+    /// The server responded with a status code outside of `200 - 299`. Returned by
+    /// [`Response::error_for_status`].
+    #[cfg(feature = "http")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+    #[error("HTTP status {} {}", .0.status, .0.status_text)]
+    StatusError(ErrorResponse),
+    /// Error returned by `serde` during serialization.
     #[cfg(feature = "json")]
     #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
     #[error("{0}")]
@@ -16,11 +85,46 @@ pub enum Error {
         #[from]
         serde_json::Error,
     ),
+    /// Error returned by `serde` while deserializing a body.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    #[error("{source} near `{snippet}`")]
+    DeserializeError {
+        /// The error returned by `serde`.
+        source: serde_json::Error,
+        /// The part of the body around the position at which deserialization failed.
+        snippet: String,
+    },
     /// Error returned by this crate
     #[error("{0}")]
     GlooError(String),
 }
 
+#[cfg(all(feature = "json", feature = "http"))]
+impl Error {
+    /// How many characters to include on either side of the error position in
+    /// [`Error::DeserializeError`].
+    const SNIPPET_RADIUS: usize = 32;
+
+    pub(crate) fn deserialize(source: serde_json::Error, body: &str) -> Self {
+        // `line` is 1-based and 0 if the error has no position.
+        let (line, column) = match source.line() {
+            0 => (body, 0),
+            line => (
+                body.lines().nth(line - 1).unwrap_or_default(),
+                source.column().saturating_sub(1),
+            ),
+        };
+        let snippet = line
+            .chars()
+            .skip(column.saturating_sub(Self::SNIPPET_RADIUS))
+            .take(Self::SNIPPET_RADIUS * 2)
+            .collect();
+
+        Error::DeserializeError { source, snippet }
+    }
+}
+
 #[cfg(any(feature = "http", feature = "websocket", feature = "eventsource"))]
 pub(crate) use conversion::*;
 #[cfg(any(feature = "http", feature = "websocket", feature = "eventsource"))]
@@ -31,7 +135,29 @@ mod conversion {
 
     #[cfg(feature = "http")]
     pub(crate) fn js_to_error(js_value: JsValue) -> super::Error {
-        super::Error::JsError(js_to_js_error(js_value))
+        classify_error(js_to_js_error(js_value))
+    }
+
+    /// Converts the reason a `fetch` call was rejected with into an error.
+    #[cfg(feature = "http")]
+    pub(crate) fn fetch_to_error(js_value: JsValue) -> super::Error {
+        match JsError::try_from(js_value) {
+            Ok(error) if error.name == "TypeError" => super::Error::NetworkError(error),
+            Ok(error) => classify_error(error),
+            // Only a custom abort reason can be something other than an `Error`.
+            Err(reason) => {
+                super::Error::AbortError(JsError::from(js_sys::Error::new(&reason.to_string())))
+            }
+        }
+    }
+
+    #[cfg(feature = "http")]
+    fn classify_error(error: JsError) -> super::Error {
+        match error.name.as_str() {
+            "AbortError" => super::Error::AbortError(error),
+            "TimeoutError" => super::Error::TimeoutError(error),
+            _ => super::Error::JsError(error),
+        }
     }
 
     pub(crate) fn js_to_js_error(js_value: JsValue) -> JsError {
@@ -41,3 +167,14 @@ mod conversion {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Error>();
+    }
+}
@@ -176,6 +176,11 @@ impl EventSource {
         }
     }
 
+    #[cfg(feature = "transport")]
+    pub(crate) fn as_raw(&self) -> &web_sys::EventSource {
+        &self.es
+    }
+
     /// The current state of the EventSource.
     pub fn state(&self) -> State {
         let ready_state = self.es.ready_state();
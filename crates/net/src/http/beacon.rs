@@ -0,0 +1,194 @@
+use crate::http::request::fetch_promise;
+use crate::http::{Method, RequestBuilder};
+use crate::Error;
+use js_sys::{Reflect, Uint8Array};
+use std::fmt;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// The maximum body size, in bytes, accepted by [`send_beacon`].
+///
+/// Browsers limit the total size of the bodies of requests queued with `navigator.sendBeacon`
+/// or `fetch` with `keepalive` to 64 KiB.
+pub const BEACON_SIZE_LIMIT: usize = 64 * 1024;
+
+/// The body of a beacon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BeaconBody {
+    /// No body.
+    Empty,
+    /// A text body, sent with `Content-Type: text/plain;charset=UTF-8`.
+    Text(String),
+    /// A binary body, sent without a `Content-Type`.
+    Bytes(Vec<u8>),
+}
+
+impl BeaconBody {
+    /// Serializes `value` as JSON.
+    ///
+    /// The body is sent as text: setting `Content-Type: application/json` would require a CORS
+    /// preflight, which `navigator.sendBeacon` does not support.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json<T: serde::Serialize + ?Sized>(value: &T) -> Result<Self, Error> {
+        Ok(BeaconBody::Text(serde_json::to_string(value)?))
+    }
+
+    /// The size of the body in bytes.
+    pub fn len(&self) -> usize {
+        match self {
+            BeaconBody::Empty => 0,
+            BeaconBody::Text(text) => text.len(),
+            BeaconBody::Bytes(bytes) => bytes.len(),
+        }
+    }
+
+    /// Returns `true` if the body is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl From<String> for BeaconBody {
+    fn from(text: String) -> Self {
+        BeaconBody::Text(text)
+    }
+}
+
+impl From<&str> for BeaconBody {
+    fn from(text: &str) -> Self {
+        BeaconBody::Text(text.into())
+    }
+}
+
+impl From<Vec<u8>> for BeaconBody {
+    fn from(bytes: Vec<u8>) -> Self {
+        BeaconBody::Bytes(bytes)
+    }
+}
+
+impl From<&[u8]> for BeaconBody {
+    fn from(bytes: &[u8]) -> Self {
+        BeaconBody::Bytes(bytes.into())
+    }
+}
+
+/// How a beacon was handed over to the browser.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BeaconTransport {
+    /// The beacon was queued with `navigator.sendBeacon`.
+    SendBeacon,
+    /// The beacon was sent with `fetch` and `keepalive: true`, either because
+    /// `navigator.sendBeacon` is not available in this context (e.g. in a worker) or because it
+    /// refused to queue the beacon.
+    Keepalive,
+}
+
+/// Error returned by [`send_beacon`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BeaconError {
+    /// The body is larger than [`BEACON_SIZE_LIMIT`].
+    TooLarge {
+        /// The size of the body in bytes.
+        size: usize,
+        /// The maximum size in bytes.
+        limit: usize,
+    },
+    /// The `keepalive` request could not be created.
+    HttpError(Error),
+}
+
+impl fmt::Display for BeaconError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BeaconError::TooLarge { size, limit } => write!(
+                f,
+                "beacon body of {size} bytes exceeds the limit of {limit} bytes"
+            ),
+            BeaconError::HttpError(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for BeaconError {}
+
+impl From<Error> for BeaconError {
+    fn from(error: Error) -> Self {
+        BeaconError::HttpError(error)
+    }
+}
+
+/// Sends a `POST` request to `url` that is delivered even if the page is being unloaded.
+///
+/// This is meant for analytics and for saving state from `pagehide` or `visibilitychange`
+/// handlers. The request is queued with `navigator.sendBeacon` when possible, falling back to
+/// `fetch` with `keepalive: true`. The response cannot be observed; a successful result only
+/// means the browser accepted the request.
+///
+/// # Example
+///
+/// ```rust
+/// use gloo_net::http::send_beacon;
+///
+/// # fn no_run() {
+/// send_beacon("/analytics", r#"{"event":"leave"}"#).unwrap();
+/// # }
+/// ```
+pub fn send_beacon(url: &str, body: impl Into<BeaconBody>) -> Result<BeaconTransport, BeaconError> {
+    let body = body.into();
+    if body.len() > BEACON_SIZE_LIMIT {
+        return Err(BeaconError::TooLarge {
+            size: body.len(),
+            limit: BEACON_SIZE_LIMIT,
+        });
+    }
+
+    let data = match &body {
+        BeaconBody::Empty => None,
+        BeaconBody::Text(text) => Some(JsValue::from_str(text)),
+        BeaconBody::Bytes(bytes) => Some(Uint8Array::from(bytes.as_slice()).into()),
+    };
+
+    if let Some(navigator) = beacon_navigator() {
+        let queued = match (&body, &data) {
+            (BeaconBody::Text(text), _) => navigator.send_beacon_with_opt_str(url, Some(text)),
+            (_, Some(data)) => {
+                navigator.send_beacon_with_opt_buffer_source(url, Some(data.unchecked_ref()))
+            }
+            _ => navigator.send_beacon(url),
+        };
+        // `false` means the browser's beacon queue is full; `keepalive` may still have room.
+        if let Ok(true) = queued {
+            return Ok(BeaconTransport::SendBeacon);
+        }
+    }
+
+    let mut builder = RequestBuilder::new(url)
+        .method(Method::POST)
+        .keepalive(true);
+    if let BeaconBody::Text(_) = body {
+        builder = builder.header("Content-Type", "text/plain;charset=UTF-8");
+    }
+    let request = match data {
+        Some(data) => builder.body(data)?,
+        None => builder.build()?,
+    };
+    let promise = fetch_promise(&request.into())?;
+    // Nobody is left to observe the outcome; swallow it rather than report an unhandled rejection.
+    wasm_bindgen_futures::spawn_local(async move {
+        let _ = JsFuture::from(promise).await;
+    });
+    Ok(BeaconTransport::Keepalive)
+}
+
+/// The `Navigator` of the current global scope, if it supports `sendBeacon`.
+fn beacon_navigator() -> Option<web_sys::Navigator> {
+    let navigator = Reflect::get(&js_sys::global(), &JsValue::from_str("navigator")).ok()?;
+    let send_beacon = Reflect::get(&navigator, &JsValue::from_str("sendBeacon")).ok()?;
+    if send_beacon.is_function() {
+        Some(navigator.unchecked_into())
+    } else {
+        None
+    }
+}
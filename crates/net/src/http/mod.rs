@@ -13,15 +13,34 @@
 //! # }
 //! ```
 
+mod beacon;
 mod headers;
 mod query;
+mod range;
 mod request;
 mod response;
+#[cfg(feature = "resumable")]
+mod resumable;
+#[cfg(feature = "tracing")]
+mod trace;
+#[cfg(feature = "resumable")]
+mod upload;
 
+pub use beacon::{send_beacon, BeaconBody, BeaconError, BeaconTransport, BEACON_SIZE_LIMIT};
 pub use headers::Headers;
 #[doc(inline)]
 pub use http::Method;
 pub use query::QueryParams;
+pub use range::{ContentRange, ParseContentRangeError};
 
 pub use request::{Request, RequestBuilder};
 pub use response::{IntoRawResponse, Response, ResponseBuilder};
+#[cfg(feature = "resumable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "resumable")))]
+pub use resumable::{Chunk, DownloadError, ResumableDownload, DEFAULT_CHUNK_SIZE};
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub use trace::{ParseTraceParentError, TraceParent};
+#[cfg(feature = "resumable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "resumable")))]
+pub use upload::{ResumableUpload, UploadError, UploadProtocol};
@@ -0,0 +1,180 @@
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+use std::str::FromStr;
+
+/// A parsed [`Content-Range`](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Range)
+/// header, describing which part of a resource a `206 Partial Content` response contains.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::ContentRange;
+/// let range: ContentRange = "bytes 0-1023/4096".parse().unwrap();
+/// assert_eq!(
+///     range,
+///     ContentRange::Bytes {
+///         start: 0,
+///         end: 1023,
+///         complete_length: Some(4096)
+///     }
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContentRange {
+    /// The response contains bytes `start..=end` of the resource.
+    Bytes {
+        /// The offset of the first byte.
+        start: u64,
+        /// The offset of the last byte, inclusive.
+        end: u64,
+        /// The size of the whole resource, if the server knows it.
+        complete_length: Option<u64>,
+    },
+    /// The requested range could not be satisfied. Sent with `416 Range Not Satisfiable`.
+    Unsatisfied {
+        /// The size of the whole resource.
+        complete_length: u64,
+    },
+}
+
+impl ContentRange {
+    /// The size of the whole resource, if known.
+    pub fn complete_length(&self) -> Option<u64> {
+        match *self {
+            ContentRange::Bytes {
+                complete_length, ..
+            } => complete_length,
+            ContentRange::Unsatisfied { complete_length } => Some(complete_length),
+        }
+    }
+}
+
+impl fmt::Display for ContentRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentRange::Bytes {
+                start,
+                end,
+                complete_length: Some(length),
+            } => write!(f, "bytes {start}-{end}/{length}"),
+            ContentRange::Bytes {
+                start,
+                end,
+                complete_length: None,
+            } => write!(f, "bytes {start}-{end}/*"),
+            ContentRange::Unsatisfied { complete_length } => write!(f, "bytes */{complete_length}"),
+        }
+    }
+}
+
+/// Error returned when parsing an invalid `Content-Range` value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseContentRangeError;
+
+impl fmt::Display for ParseContentRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid Content-Range")
+    }
+}
+
+impl std::error::Error for ParseContentRangeError {}
+
+impl FromStr for ContentRange {
+    type Err = ParseContentRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (unit, rest) = s.split_once(' ').ok_or(ParseContentRangeError)?;
+        if !unit.eq_ignore_ascii_case("bytes") {
+            return Err(ParseContentRangeError);
+        }
+        let (range, length) = rest.trim().split_once('/').ok_or(ParseContentRangeError)?;
+        let parse = |n: &str| n.parse::<u64>().map_err(|_| ParseContentRangeError);
+
+        if range == "*" {
+            return Ok(ContentRange::Unsatisfied {
+                complete_length: parse(length)?,
+            });
+        }
+        let (start, end) = range.split_once('-').ok_or(ParseContentRangeError)?;
+        let (start, end) = (parse(start)?, parse(end)?);
+        let complete_length = match length {
+            "*" => None,
+            length => Some(parse(length)?),
+        };
+        if end < start || complete_length.map_or(false, |length| end >= length) {
+            return Err(ParseContentRangeError);
+        }
+        Ok(ContentRange::Bytes {
+            start,
+            end,
+            complete_length,
+        })
+    }
+}
+
+/// Formats `range` as the value of a `Range` header.
+///
+/// # Panics
+///
+/// Panics if `range` is empty.
+pub(crate) fn range_header(range: impl RangeBounds<u64>) -> String {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).expect("empty range"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => Some(end),
+        Bound::Excluded(&end) => Some(end.checked_sub(1).expect("empty range")),
+        Bound::Unbounded => None,
+    };
+    match end {
+        Some(end) => {
+            assert!(start <= end, "empty range");
+            format!("bytes={start}-{end}")
+        }
+        None => format!("bytes={start}-"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn parse_content_range() {
+        assert_eq!(
+            "bytes 10-19/*".parse(),
+            Ok(ContentRange::Bytes {
+                start: 10,
+                end: 19,
+                complete_length: None
+            })
+        );
+        assert_eq!(
+            "bytes */100".parse(),
+            Ok(ContentRange::Unsatisfied {
+                complete_length: 100
+            })
+        );
+        for invalid in [
+            "bytes 10-9/100",
+            "bytes 0-100/100",
+            "items 0-1/2",
+            "bytes 0-1",
+        ] {
+            assert_eq!(invalid.parse::<ContentRange>(), Err(ParseContentRangeError));
+        }
+        let range: ContentRange = "bytes 0-99/100".parse().unwrap();
+        assert_eq!(range.to_string(), "bytes 0-99/100");
+    }
+
+    #[wasm_bindgen_test]
+    fn format_range_header() {
+        assert_eq!(range_header(0..100), "bytes=0-99");
+        assert_eq!(range_header(100..), "bytes=100-");
+        assert_eq!(range_header(..=9), "bytes=0-9");
+    }
+}
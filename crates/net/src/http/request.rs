@@ -1,9 +1,13 @@
+use crate::http::range::range_header;
+#[cfg(feature = "tracing")]
+use crate::http::trace::{TraceParent, TRACEPARENT_HEADER};
 use crate::http::{Headers, QueryParams, Response};
-use crate::{js_to_error, Error};
+use crate::{fetch_to_error, js_to_error, Error};
 use http::Method;
 use js_sys::{ArrayBuffer, Reflect, Uint8Array};
 use std::convert::{From, TryFrom, TryInto};
 use std::fmt;
+use std::ops::RangeBounds;
 use std::str::FromStr;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
@@ -107,6 +111,19 @@ impl RequestBuilder {
         self
     }
 
+    /// Requests only part of the resource by setting the `Range` header, e.g. `.range(1024..)`
+    /// for everything after the first KiB.
+    ///
+    /// The server answers with `206 Partial Content` and a
+    /// [`Content-Range`](crate::http::Response::content_range) header if it honours the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    pub fn range(self, range: impl RangeBounds<u64>) -> Self {
+        self.header("Range", &range_header(range))
+    }
+
     /// The subresource integrity value of the request (e.g.,
     /// `sha256-BpfBw7ivV8q2jLiT13fxDYAe2tJllusRSZ273h2nFSE=`).
     pub fn integrity(mut self, integrity: &str) -> Self {
@@ -126,6 +143,21 @@ impl RequestBuilder {
         self.header("Content-Type", "application/json").body(json)
     }
 
+    /// Allows the request to outlive the page that started it, so that it can be sent while the
+    /// page is being unloaded.
+    ///
+    /// The bodies of all pending `keepalive` requests share a quota of 64 KiB. See
+    /// [`send_beacon`](crate::http::send_beacon) for a helper built on top of this.
+    pub fn keepalive(self, keepalive: bool) -> Self {
+        // `RequestInit` has no setter for `keepalive` in the `web-sys` versions we support.
+        let _ = Reflect::set(
+            &self.options,
+            &JsValue::from_str("keepalive"),
+            &JsValue::from_bool(keepalive),
+        );
+        self
+    }
+
     /// The request method, e.g., GET, POST.
     pub fn method(mut self, method: Method) -> Self {
         self.options.method(method.as_ref());
@@ -177,6 +209,16 @@ impl RequestBuilder {
         self.options.signal(signal);
         self
     }
+
+    /// Propagates a trace to the server by setting the `traceparent` header to a new child of
+    /// `parent`.
+    ///
+    /// The ids are also recorded on the `tracing` span emitted when sending the request.
+    #[cfg(feature = "tracing")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+    pub fn traceparent(self, parent: &TraceParent) -> Self {
+        self.header(TRACEPARENT_HEADER, &parent.child().to_string())
+    }
     /// Builds the request and send it to the server, returning the received response.
     pub async fn send(self) -> Result<Response, Error> {
         let req: Request = self.try_into()?;
@@ -280,7 +322,8 @@ impl Request {
     #[cfg(feature = "json")]
     #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
     pub async fn json<T: DeserializeOwned>(&self) -> Result<T, Error> {
-        serde_json::from_str::<T>(&self.text().await?).map_err(Error::from)
+        let text = self.text().await?;
+        serde_json::from_str::<T>(&text).map_err(|e| Error::deserialize(e, &text))
     }
 
     /// Reads the reqeust as a String.
@@ -317,34 +360,90 @@ impl Request {
         Method::from_str(self.0.method().as_str()).unwrap()
     }
 
+    /// How the request will interact with the browser's HTTP cache.
+    pub fn cache(&self) -> RequestCache {
+        self.0.cache()
+    }
+
+    /// Whether credentials are sent with the request.
+    pub fn credentials(&self) -> RequestCredentials {
+        self.0.credentials()
+    }
+
+    /// How redirects are handled.
+    pub fn redirect(&self) -> RequestRedirect {
+        self.0.redirect()
+    }
+
+    /// The referrer of the request, or an empty string for `no-referrer`.
+    pub fn referrer(&self) -> String {
+        self.0.referrer()
+    }
+
+    /// The referrer policy of the request.
+    pub fn referrer_policy(&self) -> ReferrerPolicy {
+        self.0.referrer_policy()
+    }
+
+    /// The subresource integrity value of the request.
+    pub fn integrity(&self) -> String {
+        self.0.integrity()
+    }
+
+    /// The signal that aborts the request.
+    pub fn signal(&self) -> AbortSignal {
+        self.0.signal()
+    }
+
+    /// Creates a copy of the request, so that its body can be read twice.
+    ///
+    /// Fails if the body has already been used.
+    pub fn try_clone(&self) -> Result<Self, Error> {
+        self.0.clone().map(Self).map_err(js_to_error)
+    }
+
     /// Executes the request.
+    ///
+    /// With the `tracing` feature enabled, this emits an `http.request` span recording the
+    /// method, URL, status code, elapsed time and the content length of the response.
     pub async fn send(self) -> Result<Response, Error> {
-        let request = self.0;
-        let global = js_sys::global();
-        let maybe_window =
-            Reflect::get(&global, &JsValue::from_str("Window")).map_err(js_to_error)?;
-        let promise = if !maybe_window.is_undefined() {
-            let window = global.dyn_into::<web_sys::Window>().unwrap();
-            window.fetch_with_request(&request)
-        } else {
-            let maybe_worker = Reflect::get(&global, &JsValue::from_str("WorkerGlobalScope"))
-                .map_err(js_to_error)?;
-            if !maybe_worker.is_undefined() {
-                let worker = global.dyn_into::<web_sys::WorkerGlobalScope>().unwrap();
-                worker.fetch_with_request(&request)
-            } else {
-                panic!("Unsupported JavaScript global context");
-            }
-        };
-
-        let response = JsFuture::from(promise).await.map_err(js_to_error)?;
+        #[cfg(feature = "tracing")]
+        let response = super::trace::send_traced(self.0).await;
+        #[cfg(not(feature = "tracing"))]
+        let response = fetch(self.0).await;
         response
-            .dyn_into::<web_sys::Response>()
-            .map_err(|e| panic!("fetch returned {:?}, not `Response` - this is a bug", e))
-            .map(Response::from)
     }
 }
 
+pub(crate) async fn fetch(request: web_sys::Request) -> Result<Response, Error> {
+    let promise = fetch_promise(&request)?;
+    let response = JsFuture::from(promise).await.map_err(fetch_to_error)?;
+    response
+        .dyn_into::<web_sys::Response>()
+        .map_err(|e| panic!("fetch returned {:?}, not `Response` - this is a bug", e))
+        .map(Response::from)
+}
+
+/// Starts fetching `request` from the current global scope, which may be a window or a worker.
+pub(crate) fn fetch_promise(request: &web_sys::Request) -> Result<js_sys::Promise, Error> {
+    let global = js_sys::global();
+    let maybe_window = Reflect::get(&global, &JsValue::from_str("Window")).map_err(js_to_error)?;
+    let promise = if !maybe_window.is_undefined() {
+        let window = global.dyn_into::<web_sys::Window>().unwrap();
+        window.fetch_with_request(request)
+    } else {
+        let maybe_worker =
+            Reflect::get(&global, &JsValue::from_str("WorkerGlobalScope")).map_err(js_to_error)?;
+        if !maybe_worker.is_undefined() {
+            let worker = global.dyn_into::<web_sys::WorkerGlobalScope>().unwrap();
+            worker.fetch_with_request(request)
+        } else {
+            panic!("Unsupported JavaScript global context");
+        }
+    };
+    Ok(promise)
+}
+
 impl From<web_sys::Request> for Request {
     fn from(raw: web_sys::Request) -> Self {
         Request(raw)
@@ -357,6 +456,20 @@ impl From<Request> for web_sys::Request {
     }
 }
 
+impl AsRef<web_sys::Request> for Request {
+    fn as_ref(&self) -> &web_sys::Request {
+        &self.0
+    }
+}
+
+impl TryFrom<RequestBuilder> for web_sys::Request {
+    type Error = crate::error::Error;
+
+    fn try_from(value: RequestBuilder) -> Result<Self, Self::Error> {
+        Request::try_from(value).map(Into::into)
+    }
+}
+
 impl fmt::Debug for Request {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Request")
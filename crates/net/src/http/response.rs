@@ -1,12 +1,12 @@
 use std::{convert::From, fmt};
 
-use crate::{js_to_error, Error};
+use crate::{js_to_error, Error, ErrorResponse};
 use js_sys::{ArrayBuffer, Uint8Array};
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
-use web_sys::ResponseInit;
+use web_sys::{ResponseInit, ResponseType};
 
-use crate::http::Headers;
+use crate::http::{ContentRange, Headers};
 #[cfg(feature = "json")]
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 use serde::de::DeserializeOwned;
@@ -71,11 +71,44 @@ impl Response {
         self.0.status_text()
     }
 
+    /// Returns an error if the response is opaque or has an unsuccessful status code.
+    ///
+    /// The status, headers and the start of the body of an unsuccessful response are kept in
+    /// [`Error::StatusError`], so they can still be inspected.
+    ///
+    /// ```
+    /// # use gloo_net::{http::Request, Error};
+    /// # async fn no_run() -> Result<(), Error> {
+    /// let resp = Request::get("/path").send().await?.error_for_status().await;
+    /// match resp {
+    ///     Ok(resp) => { /* 2xx */ }
+    ///     Err(Error::StatusError(resp)) if resp.status == 404 => { /* not found */ }
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn error_for_status(self) -> Result<Self, Error> {
+        match self.type_() {
+            ResponseType::Opaque | ResponseType::Opaqueredirect => Err(Error::OpaqueResponse),
+            _ if !self.ok() => Err(Error::StatusError(ErrorResponse::read(self).await)),
+            _ => Ok(self),
+        }
+    }
+
     /// Gets the headers.
     pub fn headers(&self) -> Headers {
         Headers::from_raw(self.0.headers())
     }
 
+    /// The parsed `Content-Range` header of a `206 Partial Content` or
+    /// `416 Range Not Satisfiable` response.
+    ///
+    /// Returns `None` if the header is missing or invalid.
+    pub fn content_range(&self) -> Option<ContentRange> {
+        self.headers().get("Content-Range")?.parse().ok()
+    }
+
     /// Has the response body been consumed?
     ///
     /// If true, then any future attempts to consume the body will error.
@@ -88,6 +121,14 @@ impl Response {
         self.0.body()
     }
 
+    /// Creates a copy of the response, so that its body can be read twice, e.g. to store it in a
+    /// `Cache` while also returning it from a service worker.
+    ///
+    /// Fails if the body has already been used.
+    pub fn try_clone(&self) -> Result<Self, Error> {
+        self.0.clone().map(Self).map_err(js_to_error)
+    }
+
     /// Reads the response to completion, returning it as `FormData`.
     pub async fn form_data(&self) -> Result<web_sys::FormData, Error> {
         let promise = self.0.form_data().map_err(js_to_error)?;
@@ -99,7 +140,8 @@ impl Response {
     #[cfg(feature = "json")]
     #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
     pub async fn json<T: DeserializeOwned>(&self) -> Result<T, Error> {
-        serde_json::from_str::<T>(&self.text().await?).map_err(Error::from)
+        let text = self.text().await?;
+        serde_json::from_str::<T>(&text).map_err(|e| Error::deserialize(e, &text))
     }
 
     /// Reads the response as a String.
@@ -139,6 +181,12 @@ impl From<Response> for web_sys::Response {
     }
 }
 
+impl AsRef<web_sys::Response> for Response {
+    fn as_ref(&self) -> &web_sys::Response {
+        &self.0
+    }
+}
+
 impl fmt::Debug for Response {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Response")
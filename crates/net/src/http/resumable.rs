@@ -0,0 +1,271 @@
+use std::fmt;
+
+use gloo_storage::errors::StorageError;
+use gloo_storage::{LocalStorage, Storage};
+
+use crate::http::{ContentRange, Request};
+use crate::Error;
+
+/// The default number of bytes fetched by each request of a [`ResumableDownload`].
+pub const DEFAULT_CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// A download that is fetched in chunks with `Range` requests and can continue where it left
+/// off after the page is closed or the network fails.
+///
+/// Progress is saved to [`LocalStorage`] under a key of your choosing. The downloaded bytes
+/// themselves are handed to you chunk by chunk and must be stored elsewhere, e.g. in IndexedDB.
+/// The progress covering a chunk is only saved when the next chunk is requested, so a chunk
+/// that was lost because the page closed before it was stored is downloaded again.
+///
+/// If the resource changes on the server in the meantime, detected through its `ETag` or
+/// `Last-Modified` header, [`DownloadError::Changed`] is returned and the download starts over.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::{DownloadError, ResumableDownload};
+/// # fn store(offset: u64, bytes: &[u8]) {}
+/// # async fn no_run() -> Result<(), DownloadError> {
+/// let mut download = ResumableDownload::new("model-download", "/models/large.bin");
+/// while let Some(chunk) = download.next_chunk().await? {
+///     store(chunk.offset(), chunk.bytes());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ResumableDownload {
+    key: String,
+    url: String,
+    chunk_size: u64,
+    progress: Progress,
+}
+
+/// The state saved to storage.
+#[derive(Debug, Default)]
+struct Progress {
+    offset: u64,
+    complete_length: Option<u64>,
+    validator: Option<String>,
+    complete: bool,
+}
+
+type SavedProgress = (String, u64, Option<u64>, Option<String>, bool);
+
+impl ResumableDownload {
+    /// Creates a download of `url`, resuming the progress saved under `key` if it was for the
+    /// same URL.
+    pub fn new(key: &str, url: &str) -> Self {
+        let progress = match LocalStorage::get::<SavedProgress>(key) {
+            Ok((saved_url, offset, complete_length, validator, complete)) if saved_url == url => {
+                Progress {
+                    offset,
+                    complete_length,
+                    validator,
+                    complete,
+                }
+            }
+            _ => Progress::default(),
+        };
+        Self {
+            key: key.into(),
+            url: url.into(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            progress,
+        }
+    }
+
+    /// Sets the number of bytes fetched by each request. Defaults to [`DEFAULT_CHUNK_SIZE`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn chunk_size(mut self, chunk_size: u64) -> Self {
+        assert!(chunk_size > 0, "chunk size must not be zero");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// The URL being downloaded.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The number of bytes downloaded so far, which is also the offset of the next chunk.
+    pub fn offset(&self) -> u64 {
+        self.progress.offset
+    }
+
+    /// The size of the whole resource, once known.
+    pub fn complete_length(&self) -> Option<u64> {
+        self.progress.complete_length
+    }
+
+    /// Whether the whole resource has been downloaded.
+    pub fn is_complete(&self) -> bool {
+        self.progress.complete
+    }
+
+    /// Downloads the next chunk, or returns `None` once the download is complete.
+    ///
+    /// Calling this saves the progress covering the previously returned chunk.
+    pub async fn next_chunk(&mut self) -> Result<Option<Chunk>, DownloadError> {
+        self.save()?;
+        if self.progress.complete {
+            return Ok(None);
+        }
+
+        let offset = self.progress.offset;
+        let mut request = Request::get(&self.url).range(offset..offset + self.chunk_size);
+        if let Some(validator) = &self.progress.validator {
+            request = request.header("If-Range", validator);
+        }
+        let response = request.send().await?;
+
+        match (response.status(), response.content_range()) {
+            (
+                206,
+                Some(ContentRange::Bytes {
+                    start,
+                    complete_length,
+                    ..
+                }),
+            ) if start == offset => {
+                if self.progress.validator.is_none() {
+                    self.progress.validator = validator(&response);
+                }
+                let bytes = response.binary().await?;
+                self.progress.offset += bytes.len() as u64;
+                self.progress.complete_length = complete_length;
+                self.progress.complete = match complete_length {
+                    Some(length) => self.progress.offset >= length,
+                    None => (bytes.len() as u64) < self.chunk_size,
+                };
+                Ok(Some(Chunk { offset, bytes }))
+            }
+            // The server ignored the range, so this is the whole resource.
+            (200, _) if offset == 0 => {
+                self.progress.validator = validator(&response);
+                let bytes = response.binary().await?;
+                self.progress.offset += bytes.len() as u64;
+                self.progress.complete_length = Some(self.progress.offset);
+                self.progress.complete = true;
+                Ok(Some(Chunk { offset, bytes }))
+            }
+            (416, Some(ContentRange::Unsatisfied { complete_length }))
+                if complete_length == offset =>
+            {
+                self.progress.complete_length = Some(complete_length);
+                self.progress.complete = true;
+                self.save()?;
+                Ok(None)
+            }
+            // `If-Range` did not match, or the resource shrank.
+            (200, _) | (416, _) => {
+                self.reset();
+                Err(DownloadError::Changed)
+            }
+            (206, _) => Err(DownloadError::UnexpectedRange),
+            _ => Err(match response.error_for_status().await {
+                Err(e) => e.into(),
+                Ok(_) => DownloadError::UnexpectedRange,
+            }),
+        }
+    }
+
+    /// Forgets the progress, so that the download starts over.
+    pub fn reset(&mut self) {
+        self.progress = Progress::default();
+        LocalStorage::delete(&self.key);
+    }
+
+    fn save(&self) -> Result<(), DownloadError> {
+        let progress = &self.progress;
+        let saved: SavedProgress = (
+            self.url.clone(),
+            progress.offset,
+            progress.complete_length,
+            progress.validator.clone(),
+            progress.complete,
+        );
+        LocalStorage::set(&self.key, saved)?;
+        Ok(())
+    }
+}
+
+/// A strong validator for `If-Range`: the `ETag` unless it is weak, otherwise `Last-Modified`.
+fn validator(response: &crate::http::Response) -> Option<String> {
+    let headers = response.headers();
+    headers
+        .get("ETag")
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| headers.get("Last-Modified"))
+}
+
+/// A part of a [`ResumableDownload`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    offset: u64,
+    bytes: Vec<u8>,
+}
+
+impl Chunk {
+    /// The offset of the first byte of this chunk in the resource.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// The bytes of this chunk.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Takes the bytes of this chunk.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Error returned by [`ResumableDownload`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DownloadError {
+    /// The request failed.
+    HttpError(Error),
+    /// The progress could not be saved.
+    StorageError(StorageError),
+    /// The resource changed on the server since the download started.
+    ///
+    /// The progress has been reset: discard the chunks stored so far, the next call to
+    /// [`ResumableDownload::next_chunk`] starts from the beginning.
+    Changed,
+    /// The server responded with a different range than requested.
+    UnexpectedRange,
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DownloadError::HttpError(e) => write!(f, "{e}"),
+            DownloadError::StorageError(e) => write!(f, "{e}"),
+            DownloadError::Changed => f.write_str("the resource changed during the download"),
+            DownloadError::UnexpectedRange => {
+                f.write_str("the server responded with an unexpected range")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DownloadError {}
+
+impl From<Error> for DownloadError {
+    fn from(error: Error) -> Self {
+        DownloadError::HttpError(error)
+    }
+}
+
+impl From<StorageError> for DownloadError {
+    fn from(error: StorageError) -> Self {
+        DownloadError::StorageError(error)
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt;
+use std::str::FromStr;
+
+use tracing::field::Empty;
+use tracing::Instrument;
+
+use crate::http::{Method, Response};
+use crate::Error;
+
+/// The name of the [W3C Trace Context](https://www.w3.org/TR/trace-context/) header.
+pub(crate) const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// A [W3C `traceparent`](https://www.w3.org/TR/trace-context/#traceparent-header) value,
+/// identifying a trace and the span within it that a request originates from.
+///
+/// Pass it to [`RequestBuilder::traceparent`](crate::http::RequestBuilder::traceparent) to
+/// propagate the trace to the server.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::{Request, TraceParent};
+/// # async fn no_run() {
+/// // e.g. rendered into the page by the server
+/// let page: TraceParent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+///     .parse()
+///     .unwrap();
+/// let resp = Request::get("/path")
+///     .traceparent(&page)
+///     .send()
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TraceParent {
+    trace_id: [u8; 16],
+    parent_id: [u8; 8],
+    sampled: bool,
+}
+
+impl TraceParent {
+    /// Starts a new, sampled trace with randomly generated ids.
+    ///
+    /// The ids are generated with `Math.random()` and are therefore not suitable for anything
+    /// other than correlation.
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: random_id(),
+            parent_id: random_id(),
+            sampled: true,
+        }
+    }
+
+    /// Creates the context of a child span: the same trace with a new parent id.
+    pub fn child(&self) -> Self {
+        Self {
+            parent_id: random_id(),
+            ..*self
+        }
+    }
+
+    /// The trace id, formatted as 32 lowercase hex characters.
+    pub fn trace_id(&self) -> String {
+        hex(&self.trace_id)
+    }
+
+    /// The id of the parent span, formatted as 16 lowercase hex characters.
+    pub fn parent_id(&self) -> String {
+        hex(&self.parent_id)
+    }
+
+    /// Whether the caller recorded this trace.
+    pub fn sampled(&self) -> bool {
+        self.sampled
+    }
+
+    /// Sets whether the caller recorded this trace.
+    pub fn set_sampled(&mut self, sampled: bool) {
+        self.sampled = sampled;
+    }
+}
+
+impl fmt::Display for TraceParent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            self.trace_id(),
+            self.parent_id(),
+            u8::from(self.sampled)
+        )
+    }
+}
+
+/// Error returned when parsing an invalid `traceparent` value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseTraceParentError;
+
+impl fmt::Display for ParseTraceParentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid traceparent")
+    }
+}
+
+impl std::error::Error for ParseTraceParentError {}
+
+impl FromStr for TraceParent {
+    type Err = ParseTraceParentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.trim().split('-').collect::<Vec<_>>();
+        if parts.len() < 4 {
+            return Err(ParseTraceParentError);
+        }
+        let (version, trace_id, parent_id, flags) = (parts[0], parts[1], parts[2], parts[3]);
+        // Version 00 has exactly four fields, later versions may append more.
+        if version == "ff" || (version == "00" && parts.len() != 4) {
+            return Err(ParseTraceParentError);
+        }
+        let _: [u8; 1] = parse_hex(version)?;
+        let trace_id: [u8; 16] = parse_hex(trace_id)?;
+        let parent_id: [u8; 8] = parse_hex(parent_id)?;
+        let [flags] = parse_hex::<1>(flags)?;
+        if trace_id == [0; 16] || parent_id == [0; 8] {
+            return Err(ParseTraceParentError);
+        }
+
+        Ok(Self {
+            trace_id,
+            parent_id,
+            sampled: flags & 0x01 == 0x01,
+        })
+    }
+}
+
+fn random_id<const N: usize>() -> [u8; N] {
+    loop {
+        let mut id = [0; N];
+        for byte in id.iter_mut() {
+            *byte = (js_sys::Math::random() * 256.0) as u8;
+        }
+        // An all-zero id is invalid.
+        if id != [0; N] {
+            return id;
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn parse_hex<const N: usize>(s: &str) -> Result<[u8; N], ParseTraceParentError> {
+    if s.len() != N * 2 || !s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        return Err(ParseTraceParentError);
+    }
+    let mut bytes = [0; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).map_err(|_| ParseTraceParentError)?;
+    }
+    Ok(bytes)
+}
+
+/// Sends `request`, wrapped in a `tracing` span describing it.
+pub(crate) async fn send_traced(request: web_sys::Request) -> Result<Response, Error> {
+    let traceparent = request
+        .headers()
+        .get(TRACEPARENT_HEADER)
+        .ok()
+        .flatten()
+        .and_then(|value| value.parse::<TraceParent>().ok());
+
+    let span = tracing::info_span!(
+        "http.request",
+        http.method = %request.method().parse::<Method>().unwrap_or_default(),
+        http.url = %request.url(),
+        http.status_code = Empty,
+        http.response_content_length = Empty,
+        elapsed_ms = Empty,
+        trace_id = traceparent.map(|t| t.trace_id()),
+        span_id = traceparent.map(|t| t.parent_id()),
+    );
+
+    let start = js_sys::Date::now();
+    let result = super::request::fetch(request)
+        .instrument(span.clone())
+        .await;
+    span.record("elapsed_ms", js_sys::Date::now() - start);
+
+    let _enter = span.enter();
+    match &result {
+        Ok(response) => {
+            span.record("http.status_code", response.status());
+            if let Some(len) = response
+                .headers()
+                .get("content-length")
+                .and_then(|value| value.parse::<u64>().ok())
+            {
+                span.record("http.response_content_length", len);
+            }
+            tracing::debug!(status = response.status(), "response received");
+        }
+        Err(error) => tracing::warn!(%error, "request failed"),
+    }
+
+    result
+}
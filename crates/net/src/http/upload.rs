@@ -0,0 +1,424 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::task::Poll;
+
+use gloo_storage::errors::StorageError;
+use gloo_storage::{LocalStorage, Storage};
+use http::Method;
+use wasm_bindgen::UnwrapThrowExt;
+
+use crate::http::{Request, RequestBuilder, Response, DEFAULT_CHUNK_SIZE};
+use crate::Error;
+
+/// The version of the tus protocol spoken by [`UploadProtocol::Tus`].
+const TUS_VERSION: &str = "1.0.0";
+
+/// How the chunks of a [`ResumableUpload`] are sent to the server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UploadProtocol {
+    /// Each chunk is sent with a `PUT` request to the upload URL, with a `Content-Range` header
+    /// telling where it belongs, e.g. `bytes 0-1048575/5000000`.
+    ///
+    /// Chunks may be sent concurrently and out of order. Servers may answer `308 Resume
+    /// Incomplete` to the chunks before the last one.
+    #[default]
+    ContentRange,
+    /// The [tus](https://tus.io/protocols/resumable-upload) resumable upload protocol.
+    ///
+    /// The upload is created with a `POST` request to the upload URL, and its chunks are sent
+    /// one after another with `PATCH` requests to the created upload.
+    Tus,
+}
+
+/// An upload of a blob in chunks, which can continue where it left off after the page is
+/// closed or the network fails.
+///
+/// Progress is saved to [`LocalStorage`] under a key of your choosing, after each chunk. The
+/// blob itself must be provided again to resume, e.g. by the user picking the same file.
+///
+/// Failed chunks are retried right away, up to [`retries`](Self::retries) times, if the request
+/// failed before a response was received or the server responded with an error status of
+/// `5xx`, `408` or `429`.
+///
+/// # Example
+///
+/// ```
+/// # use gloo_net::http::{ResumableUpload, UploadError};
+/// # async fn no_run(blob: web_sys::Blob) -> Result<(), UploadError> {
+/// let mut upload = ResumableUpload::new("video-upload", "/uploads/video", blob).concurrency(4);
+/// upload
+///     .upload(|uploaded, size| {
+///         // Update a progress bar with `uploaded` out of `size` bytes...
+///     })
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ResumableUpload {
+    key: String,
+    url: String,
+    blob: web_sys::Blob,
+    size: u64,
+    chunk_size: u64,
+    concurrency: usize,
+    retries: u32,
+    protocol: UploadProtocol,
+    progress: Progress,
+}
+
+/// The state saved to storage.
+#[derive(Debug, Default)]
+struct Progress {
+    /// The indices of the chunks sent with [`UploadProtocol::ContentRange`].
+    chunks: Vec<u64>,
+    /// The URL of the upload created with [`UploadProtocol::Tus`], and the offset it reached.
+    tus: Option<(String, u64)>,
+}
+
+type SavedProgress = (String, u64, u64, Vec<u64>, Option<(String, u64)>);
+
+impl ResumableUpload {
+    /// Creates an upload of `blob` to `url`, resuming the progress saved under `key` if it was
+    /// for the same URL and a blob of the same size.
+    ///
+    /// `blob` can be a `web_sys::Blob` or `web_sys::File`, or a `Blob` of `gloo-file`.
+    pub fn new(key: &str, url: &str, blob: impl Into<web_sys::Blob>) -> Self {
+        let blob = blob.into();
+        let size = blob.size() as u64;
+        let mut upload = Self {
+            key: key.into(),
+            url: url.into(),
+            blob,
+            size,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            concurrency: 1,
+            retries: 3,
+            protocol: UploadProtocol::default(),
+            progress: Progress::default(),
+        };
+        upload.load();
+        upload
+    }
+
+    /// Sets the number of bytes sent by each request. Defaults to [`DEFAULT_CHUNK_SIZE`].
+    ///
+    /// The progress saved for another chunk size is discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn chunk_size(mut self, chunk_size: u64) -> Self {
+        assert!(chunk_size > 0, "chunk size must not be zero");
+        self.chunk_size = chunk_size;
+        self.load();
+        self
+    }
+
+    /// Sets how many chunks are sent at the same time with [`UploadProtocol::ContentRange`].
+    /// Defaults to 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `concurrency` is zero.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        assert!(concurrency > 0, "concurrency must not be zero");
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Sets how many times a failed chunk is retried before the upload fails. Defaults to 3.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets how chunks are sent. Defaults to [`UploadProtocol::ContentRange`].
+    pub fn protocol(mut self, protocol: UploadProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// The URL being uploaded to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The size of the blob.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The number of bytes uploaded so far.
+    pub fn uploaded(&self) -> u64 {
+        match self.protocol {
+            UploadProtocol::ContentRange => self
+                .progress
+                .chunks
+                .iter()
+                .map(|index| self.chunk_end(*index) - index * self.chunk_size)
+                .sum(),
+            UploadProtocol::Tus => self.progress.tus.as_ref().map_or(0, |(_, offset)| *offset),
+        }
+    }
+
+    /// Whether the whole blob has been uploaded.
+    pub fn is_complete(&self) -> bool {
+        self.uploaded() >= self.size
+    }
+
+    /// Uploads the chunks that were not uploaded yet, calling `progress` with the number of
+    /// bytes uploaded so far and the size of the blob after each one.
+    ///
+    /// If this fails, the progress made until then is kept, and calling it again resumes the
+    /// upload.
+    pub async fn upload<F>(&mut self, mut progress: F) -> Result<(), UploadError>
+    where
+        F: FnMut(u64, u64),
+    {
+        match self.protocol {
+            UploadProtocol::ContentRange => self.upload_ranges(&mut progress).await,
+            UploadProtocol::Tus => self.upload_tus(&mut progress).await,
+        }
+    }
+
+    /// Forgets the progress, so that the upload starts over.
+    pub fn reset(&mut self) {
+        self.progress = Progress::default();
+        LocalStorage::delete(&self.key);
+    }
+
+    async fn upload_ranges(
+        &mut self,
+        progress: &mut dyn FnMut(u64, u64),
+    ) -> Result<(), UploadError> {
+        let chunk_count = (self.size + self.chunk_size - 1) / self.chunk_size;
+        let mut pending = (0..chunk_count.max(1))
+            .filter(|index| !self.progress.chunks.contains(index))
+            .collect::<VecDeque<_>>();
+
+        type ChunkFuture = Pin<Box<dyn Future<Output = (u64, Result<(), UploadError>)>>>;
+        let mut in_flight: Vec<ChunkFuture> = Vec::new();
+        loop {
+            while in_flight.len() < self.concurrency {
+                let index = match pending.pop_front() {
+                    Some(m) => m,
+                    None => break,
+                };
+                let start = index * self.chunk_size;
+                let end = self.chunk_end(index);
+                let range = match self.size {
+                    0 => "bytes */0".to_owned(),
+                    size => format!("bytes {}-{}/{}", start, end - 1, size),
+                };
+                let body = self.slice(start, end);
+                let request = Request::put(&self.url).header("Content-Range", &range);
+                let retries = self.retries;
+                in_flight.push(Box::pin(async move {
+                    let result = send_with_retries(request, body, retries).await;
+                    (
+                        index,
+                        match result {
+                            Ok(response) => expect_status(response, &[308]).await,
+                            Err(e) => Err(e),
+                        },
+                    )
+                }));
+            }
+            if in_flight.is_empty() {
+                return Ok(());
+            }
+
+            let (index, result) = poll_fn(|cx| {
+                for i in 0..in_flight.len() {
+                    if let Poll::Ready(output) = in_flight[i].as_mut().poll(cx) {
+                        drop(in_flight.swap_remove(i));
+                        return Poll::Ready(output);
+                    }
+                }
+                Poll::Pending
+            })
+            .await;
+            result?;
+
+            self.progress.chunks.push(index);
+            self.save()?;
+            progress(self.uploaded(), self.size);
+        }
+    }
+
+    async fn upload_tus(&mut self, progress: &mut dyn FnMut(u64, u64)) -> Result<(), UploadError> {
+        let (upload_url, mut offset) = match self.progress.tus.clone() {
+            Some((upload_url, _)) => match self.tus_offset(&upload_url).await? {
+                Some(offset) => (upload_url, offset),
+                // The upload expired on the server.
+                None => (self.tus_create().await?, 0),
+            },
+            None => (self.tus_create().await?, 0),
+        };
+        self.progress.tus = Some((upload_url.clone(), offset));
+        self.save()?;
+
+        while offset < self.size {
+            let end = self.size.min(offset + self.chunk_size);
+            let request = Request::patch(&upload_url)
+                .header("Tus-Resumable", TUS_VERSION)
+                .header("Upload-Offset", &offset.to_string())
+                .header("Content-Type", "application/offset+octet-stream");
+            let response =
+                send_with_retries(request, self.slice(offset, end), self.retries).await?;
+            let response = response.error_for_status().await?;
+
+            offset = upload_offset(&response).ok_or(UploadError::UnexpectedResponse)?;
+            self.progress.tus = Some((upload_url.clone(), offset));
+            self.save()?;
+            progress(offset, self.size);
+        }
+        Ok(())
+    }
+
+    /// Creates an upload on the server, and returns its URL.
+    async fn tus_create(&self) -> Result<String, UploadError> {
+        let response = Request::post(&self.url)
+            .header("Tus-Resumable", TUS_VERSION)
+            .header("Upload-Length", &self.size.to_string())
+            .send()
+            .await?
+            .error_for_status()
+            .await?;
+        let location = response
+            .headers()
+            .get("Location")
+            .ok_or(UploadError::UnexpectedResponse)?;
+
+        // The location may be relative to the URL the upload was created at.
+        Ok(web_sys::Url::new_with_base(&location, &response.url())
+            .map(|m| m.href())
+            .unwrap_or(location))
+    }
+
+    /// Returns the offset the upload at `upload_url` reached, or `None` if it doesn't exist.
+    async fn tus_offset(&self, upload_url: &str) -> Result<Option<u64>, UploadError> {
+        let response = RequestBuilder::new(upload_url)
+            .method(Method::HEAD)
+            .header("Tus-Resumable", TUS_VERSION)
+            .send()
+            .await?;
+        match response.status() {
+            404 | 410 => Ok(None),
+            _ => {
+                let response = response.error_for_status().await?;
+                Ok(Some(
+                    upload_offset(&response).ok_or(UploadError::UnexpectedResponse)?,
+                ))
+            }
+        }
+    }
+
+    fn chunk_end(&self, index: u64) -> u64 {
+        self.size.min((index + 1) * self.chunk_size)
+    }
+
+    fn slice(&self, start: u64, end: u64) -> web_sys::Blob {
+        self.blob
+            .slice_with_f64_and_f64(start as f64, end as f64)
+            .unwrap_throw()
+    }
+
+    fn load(&mut self) {
+        self.progress = match LocalStorage::get::<SavedProgress>(&self.key) {
+            Ok((url, size, chunk_size, chunks, tus))
+                if url == self.url && size == self.size && chunk_size == self.chunk_size =>
+            {
+                Progress { chunks, tus }
+            }
+            _ => Progress::default(),
+        };
+    }
+
+    fn save(&self) -> Result<(), UploadError> {
+        let saved: SavedProgress = (
+            self.url.clone(),
+            self.size,
+            self.chunk_size,
+            self.progress.chunks.clone(),
+            self.progress.tus.clone(),
+        );
+        LocalStorage::set(&self.key, saved)?;
+        Ok(())
+    }
+}
+
+/// Sends `request` with `body`, retrying up to `retries` times on transient failures.
+async fn send_with_retries(
+    request: RequestBuilder,
+    body: web_sys::Blob,
+    retries: u32,
+) -> Result<Response, UploadError> {
+    let request = request.body(body)?;
+    let mut attempt = 0;
+    loop {
+        let result = request.try_clone()?.send().await;
+        let retry = match &result {
+            Ok(response) => matches!(response.status(), 408 | 429 | 500..=599),
+            Err(Error::NetworkError(_)) => true,
+            Err(_) => false,
+        };
+        if !retry || attempt >= retries {
+            return Ok(result?);
+        }
+        attempt += 1;
+    }
+}
+
+/// Returns an error unless `response` is successful or has one of the `allowed` statuses.
+async fn expect_status(response: Response, allowed: &[u16]) -> Result<(), UploadError> {
+    if !allowed.contains(&response.status()) {
+        response.error_for_status().await?;
+    }
+    Ok(())
+}
+
+fn upload_offset(response: &Response) -> Option<u64> {
+    response.headers().get("Upload-Offset")?.parse().ok()
+}
+
+/// Error returned by [`ResumableUpload`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum UploadError {
+    /// The request failed, after the retries if it could be retried.
+    HttpError(Error),
+    /// The progress could not be saved.
+    StorageError(StorageError),
+    /// The server responded without the headers required by the protocol.
+    UnexpectedResponse,
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UploadError::HttpError(e) => write!(f, "{e}"),
+            UploadError::StorageError(e) => write!(f, "{e}"),
+            UploadError::UnexpectedResponse => {
+                f.write_str("the server responded without the expected headers")
+            }
+        }
+    }
+}
+
+impl std::error::Error for UploadError {}
+
+impl From<Error> for UploadError {
+    fn from(error: Error) -> Self {
+        UploadError::HttpError(error)
+    }
+}
+
+impl From<StorageError> for UploadError {
+    fn from(error: StorageError) -> Self {
+        UploadError::StorageError(error)
+    }
+}
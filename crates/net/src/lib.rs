@@ -1,7 +1,9 @@
 //! HTTP requests library for WASM apps. It provides idiomatic Rust bindings for the `web_sys`
 //! `fetch` and `WebSocket` API.
 //!
-//! See module level documentation for [`http`] and [`websocket`] to learn more.
+//! See module level documentation for [`http`] and [`websocket`] to learn more. A
+//! [Socket.IO](https://socket.io/) client is available in the `socketio` module behind the
+//! `socketio` feature.
 
 #![deny(
     missing_docs,
@@ -17,6 +19,12 @@ pub mod eventsource;
 #[cfg(feature = "http")]
 #[cfg_attr(docsrs, doc(cfg(feature = "http")))]
 pub mod http;
+#[cfg(feature = "socketio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "socketio")))]
+pub mod socketio;
+#[cfg(feature = "transport")]
+#[cfg_attr(docsrs, doc(cfg(feature = "transport")))]
+pub mod transport;
 #[cfg(feature = "websocket")]
 #[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
 pub mod websocket;
@@ -0,0 +1,649 @@
+use crate::js_to_js_error;
+use crate::socketio::packet::{EnginePacket, Packet, PacketKind};
+use crate::socketio::SocketIoError;
+use crate::websocket::{futures::WebSocket, Message, WebSocketError};
+use futures_channel::{mpsc, oneshot};
+use futures_core::Stream;
+use futures_util::{future, stream, SinkExt, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+/// The path at which Socket.IO servers serve Engine.IO by default.
+const DEFAULT_PATH: &str = "/socket.io/";
+
+#[derive(Debug)]
+enum Outgoing {
+    Packet(Packet),
+    Close,
+}
+
+#[derive(Default)]
+struct Shared {
+    sid: Option<String>,
+    namespaces: HashMap<String, Namespace>,
+    next_namespace_id: u64,
+}
+
+#[derive(Default)]
+struct Namespace {
+    /// Distinguishes this connection to the namespace from earlier ones.
+    id: u64,
+    sid: Option<String>,
+    connecting: Option<oneshot::Sender<Result<(), SocketIoError>>>,
+    listeners: HashMap<String, Vec<mpsc::UnboundedSender<Event>>>,
+    acks: HashMap<u64, oneshot::Sender<Vec<Value>>>,
+    next_ack_id: u64,
+}
+
+impl Shared {
+    fn dispatch(&mut self, packet: Packet, outgoing: &mpsc::UnboundedSender<Outgoing>) {
+        let namespace = match self.namespaces.get_mut(&packet.namespace) {
+            Some(namespace) => namespace,
+            None => return,
+        };
+
+        match packet.kind {
+            PacketKind::Connect => {
+                namespace.sid = packet
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.get("sid"))
+                    .and_then(Value::as_str)
+                    .map(str::to_owned);
+                if let Some(connecting) = namespace.connecting.take() {
+                    let _ = connecting.send(Ok(()));
+                }
+            }
+            PacketKind::ConnectError => {
+                let message = match packet.data {
+                    Some(Value::Object(mut data)) => match data.remove("message") {
+                        Some(Value::String(message)) => message,
+                        _ => Value::Object(data).to_string(),
+                    },
+                    Some(data) => data.to_string(),
+                    None => String::new(),
+                };
+                if let Some(connecting) = self
+                    .namespaces
+                    .remove(&packet.namespace)
+                    .and_then(|namespace| namespace.connecting)
+                {
+                    let _ = connecting.send(Err(SocketIoError::ConnectError(message)));
+                }
+            }
+            PacketKind::Disconnect => {
+                self.namespaces.remove(&packet.namespace);
+            }
+            PacketKind::Event => {
+                let mut args = match packet.data {
+                    Some(Value::Array(args)) if !args.is_empty() => args,
+                    _ => return,
+                };
+                let name = match args.remove(0) {
+                    Value::String(name) => name,
+                    _ => return,
+                };
+                if let Some(listeners) = namespace.listeners.get_mut(&name) {
+                    let event = Event {
+                        ack: packet.id.map(|id| Ack {
+                            id,
+                            namespace: packet.namespace,
+                            outgoing: outgoing.clone(),
+                        }),
+                        name,
+                        args,
+                    };
+                    listeners.retain(|listener| listener.unbounded_send(event.clone()).is_ok());
+                }
+            }
+            PacketKind::Ack => {
+                if let Some(ack) = packet.id.and_then(|id| namespace.acks.remove(&id)) {
+                    let args = match packet.data {
+                        Some(Value::Array(args)) => args,
+                        _ => Vec::new(),
+                    };
+                    let _ = ack.send(args);
+                }
+            }
+            // Binary attachments are not supported.
+            PacketKind::BinaryEvent | PacketKind::BinaryAck => {}
+        }
+    }
+}
+
+/// The connection shared by a [`Manager`] and its [`Socket`]s.
+struct Connection {
+    shared: Rc<RefCell<Shared>>,
+    outgoing: mpsc::UnboundedSender<Outgoing>,
+}
+
+impl Connection {
+    fn send(&self, packet: Packet) -> Result<(), SocketIoError> {
+        self.outgoing
+            .unbounded_send(Outgoing::Packet(packet))
+            .map_err(|_| SocketIoError::Disconnected)
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let _ = self.outgoing.unbounded_send(Outgoing::Close);
+    }
+}
+
+enum Io {
+    Incoming(Result<Message, WebSocketError>),
+    Outgoing(Outgoing),
+    Closed,
+}
+
+/// Forwards outgoing packets to the WebSocket and dispatches incoming ones until either side
+/// closes the connection.
+async fn drive(
+    ws: WebSocket,
+    outgoing: mpsc::UnboundedReceiver<Outgoing>,
+    sender: mpsc::UnboundedSender<Outgoing>,
+    shared: Rc<RefCell<Shared>>,
+) {
+    let (mut sink, incoming) = ws.split();
+    let incoming = incoming
+        .map(Io::Incoming)
+        .chain(stream::once(future::ready(Io::Closed)));
+    let mut io = stream::select(incoming, outgoing.map(Io::Outgoing));
+
+    while let Some(item) = io.next().await {
+        let reply = match item {
+            Io::Incoming(Ok(Message::Text(text))) => match EnginePacket::decode(&text) {
+                Some(EnginePacket::Open { sid }) => {
+                    shared.borrow_mut().sid = Some(sid);
+                    None
+                }
+                Some(EnginePacket::Ping) => Some(EnginePacket::Pong),
+                Some(EnginePacket::Message(data)) => {
+                    if let Some(packet) = Packet::decode(&data) {
+                        shared.borrow_mut().dispatch(packet, &sender);
+                    }
+                    None
+                }
+                Some(EnginePacket::Close) => break,
+                _ => None,
+            },
+            // Binary attachments are not supported.
+            Io::Incoming(Ok(Message::Bytes(_))) => None,
+            Io::Outgoing(Outgoing::Packet(packet)) => Some(EnginePacket::Message(packet.encode())),
+            Io::Incoming(Err(_)) | Io::Outgoing(Outgoing::Close) | Io::Closed => break,
+        };
+
+        if let Some(reply) = reply {
+            if sink.send(Message::Text(reply.encode())).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    // Dropping the namespaces ends their event streams and fails pending connections and acks.
+    let mut shared = shared.borrow_mut();
+    shared.sid = None;
+    shared.namespaces.clear();
+}
+
+/// A connection to a Socket.IO server, multiplexing any number of namespaces.
+///
+/// The connection is closed once the manager and all [`Socket`]s created from it are dropped.
+#[derive(Clone)]
+pub struct Manager {
+    connection: Rc<Connection>,
+}
+
+impl Manager {
+    /// Connects to the Socket.IO server at `url`, e.g. `https://example.com`.
+    ///
+    /// The Engine.IO endpoint is expected at the default `/socket.io/` path. Use
+    /// [`Manager::builder`] to change it.
+    pub fn connect(url: &str) -> Result<Self, SocketIoError> {
+        Self::builder(url).connect()
+    }
+
+    /// Creates a [`ManagerBuilder`] to configure the connection before it is established.
+    pub fn builder(url: &str) -> ManagerBuilder {
+        ManagerBuilder {
+            url: url.to_owned(),
+            path: DEFAULT_PATH.to_owned(),
+            query: Vec::new(),
+        }
+    }
+
+    /// The Engine.IO session id, once the handshake has completed.
+    pub fn id(&self) -> Option<String> {
+        self.connection.shared.borrow().sid.clone()
+    }
+
+    /// Connects to `namespace`, e.g. `/` or `/admin`.
+    pub async fn socket(&self, namespace: &str) -> Result<Socket, SocketIoError> {
+        self.connect_namespace(namespace, None).await
+    }
+
+    /// Connects to `namespace`, sending `auth` as the authentication payload.
+    pub async fn socket_with_auth<T: Serialize + ?Sized>(
+        &self,
+        namespace: &str,
+        auth: &T,
+    ) -> Result<Socket, SocketIoError> {
+        let auth = serde_json::to_value(auth)?;
+        self.connect_namespace(namespace, Some(auth)).await
+    }
+
+    async fn connect_namespace(
+        &self,
+        namespace: &str,
+        auth: Option<Value>,
+    ) -> Result<Socket, SocketIoError> {
+        let (sender, receiver) = oneshot::channel();
+        let id = {
+            let mut shared = self.connection.shared.borrow_mut();
+            let id = shared.next_namespace_id;
+            shared.next_namespace_id += 1;
+            shared.namespaces.insert(
+                namespace.to_owned(),
+                Namespace {
+                    id,
+                    connecting: Some(sender),
+                    ..Namespace::default()
+                },
+            );
+            id
+        };
+
+        let mut packet = Packet::new(PacketKind::Connect, namespace);
+        packet.data = auth;
+        self.connection.send(packet)?;
+        receiver.await.map_err(|_| SocketIoError::Disconnected)??;
+
+        Ok(Socket {
+            namespace: namespace.to_owned(),
+            id,
+            connection: Rc::clone(&self.connection),
+        })
+    }
+}
+
+impl fmt::Debug for Manager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Manager")
+            .field("id", &self.id())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A builder for configuring a [`Manager`] before the connection is established.
+///
+/// Created with [`Manager::builder`].
+#[derive(Debug)]
+pub struct ManagerBuilder {
+    url: String,
+    path: String,
+    query: Vec<(String, String)>,
+}
+
+impl ManagerBuilder {
+    /// The path at which the server serves Engine.IO. Defaults to `/socket.io/`.
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = path.to_owned();
+        self
+    }
+
+    /// Appends a query parameter to the connection URL, e.g. an authentication token.
+    pub fn query(mut self, name: &str, value: &str) -> Self {
+        self.query.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Establish the connection.
+    ///
+    /// This function may error if the URL is invalid or the port to which the connection is
+    /// being attempted is being blocked.
+    pub fn connect(self) -> Result<Manager, SocketIoError> {
+        let url =
+            web_sys::Url::new(&self.url).map_err(|e| SocketIoError::JsError(js_to_js_error(e)))?;
+        match url.protocol().as_str() {
+            "http:" => url.set_protocol("ws:"),
+            "https:" => url.set_protocol("wss:"),
+            _ => {}
+        }
+        url.set_pathname(&self.path);
+        let params = url.search_params();
+        for (name, value) in &self.query {
+            params.append(name, value);
+        }
+        params.set("EIO", "4");
+        params.set("transport", "websocket");
+
+        let ws = WebSocket::open(&String::from(url.to_string())).map_err(SocketIoError::JsError)?;
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        let (sender, receiver) = mpsc::unbounded();
+        wasm_bindgen_futures::spawn_local(drive(ws, receiver, sender.clone(), Rc::clone(&shared)));
+
+        Ok(Manager {
+            connection: Rc::new(Connection {
+                shared,
+                outgoing: sender,
+            }),
+        })
+    }
+}
+
+/// A connection to a single namespace, created with [`Manager::socket`].
+///
+/// Dropping the socket disconnects from the namespace.
+pub struct Socket {
+    namespace: String,
+    id: u64,
+    connection: Rc<Connection>,
+}
+
+impl Socket {
+    /// The namespace this socket is connected to.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// The id assigned to this socket by the server, or `None` once disconnected.
+    pub fn id(&self) -> Option<String> {
+        self.with_namespace(|namespace| namespace.sid.clone())
+            .flatten()
+    }
+
+    /// Whether the socket is still connected to its namespace.
+    pub fn connected(&self) -> bool {
+        self.with_namespace(|_| ()).is_some()
+    }
+
+    /// Emits `event` with `data` as its argument.
+    pub fn emit<T: Serialize + ?Sized>(&self, event: &str, data: &T) -> Result<(), SocketIoError> {
+        let packet = self.event_packet(event, data)?;
+        self.connection.send(packet)
+    }
+
+    /// Emits `event` with `data` as its argument and waits for the server to acknowledge it.
+    ///
+    /// Resolves to the first argument the server acknowledged the event with.
+    pub async fn emit_with_ack<T, R>(&self, event: &str, data: &T) -> Result<R, SocketIoError>
+    where
+        T: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let mut packet = self.event_packet(event, data)?;
+        let (sender, receiver) = oneshot::channel();
+        packet.id = Some(
+            self.with_namespace(|namespace| {
+                let id = namespace.next_ack_id;
+                namespace.next_ack_id += 1;
+                namespace.acks.insert(id, sender);
+                id
+            })
+            .ok_or(SocketIoError::Disconnected)?,
+        );
+        self.connection.send(packet)?;
+
+        let args = receiver.await.map_err(|_| SocketIoError::Disconnected)?;
+        let data = args.into_iter().next().unwrap_or(Value::Null);
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Subscribes to `event`.
+    ///
+    /// The stream ends when the socket is disconnected.
+    pub fn on(&self, event: &str) -> EventStream {
+        let (sender, receiver) = mpsc::unbounded();
+        self.with_namespace(|namespace| {
+            namespace
+                .listeners
+                .entry(event.to_owned())
+                .or_default()
+                .push(sender)
+        });
+        EventStream { receiver }
+    }
+
+    /// Disconnects from the namespace.
+    pub fn disconnect(self) {}
+
+    fn event_packet<T: Serialize + ?Sized>(
+        &self,
+        event: &str,
+        data: &T,
+    ) -> Result<Packet, SocketIoError> {
+        let mut packet = Packet::new(PacketKind::Event, &self.namespace);
+        packet.data = Some(Value::Array(vec![
+            Value::String(event.to_owned()),
+            serde_json::to_value(data)?,
+        ]));
+        Ok(packet)
+    }
+
+    fn with_namespace<R>(&self, f: impl FnOnce(&mut Namespace) -> R) -> Option<R> {
+        let mut shared = self.connection.shared.borrow_mut();
+        shared
+            .namespaces
+            .get_mut(&self.namespace)
+            .filter(|namespace| namespace.id == self.id)
+            .map(f)
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        if self.with_namespace(|_| ()).is_some() {
+            self.connection
+                .shared
+                .borrow_mut()
+                .namespaces
+                .remove(&self.namespace);
+            let _ = self
+                .connection
+                .send(Packet::new(PacketKind::Disconnect, &self.namespace));
+        }
+    }
+}
+
+impl fmt::Debug for Socket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Socket")
+            .field("namespace", &self.namespace)
+            .field("id", &self.id())
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Ack {
+    id: u64,
+    namespace: String,
+    outgoing: mpsc::UnboundedSender<Outgoing>,
+}
+
+/// An event emitted by the server.
+#[derive(Clone, Debug)]
+pub struct Event {
+    name: String,
+    args: Vec<Value>,
+    ack: Option<Ack>,
+}
+
+impl Event {
+    /// The name of the event.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The arguments the event was emitted with.
+    pub fn args(&self) -> &[Value] {
+        &self.args
+    }
+
+    /// Deserializes the first argument of the event.
+    pub fn data<T: DeserializeOwned>(&self) -> Result<T, SocketIoError> {
+        let data = self.args.first().cloned().unwrap_or(Value::Null);
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Whether the server expects this event to be acknowledged.
+    pub fn wants_ack(&self) -> bool {
+        self.ack.is_some()
+    }
+
+    /// Acknowledges the event with `data` as the argument.
+    ///
+    /// Does nothing if the server did not ask for an acknowledgement.
+    pub fn ack<T: Serialize + ?Sized>(self, data: &T) -> Result<(), SocketIoError> {
+        let ack = match self.ack {
+            Some(ack) => ack,
+            None => return Ok(()),
+        };
+        let mut packet = Packet::new(PacketKind::Ack, &ack.namespace);
+        packet.id = Some(ack.id);
+        packet.data = Some(Value::Array(vec![serde_json::to_value(data)?]));
+        ack.outgoing
+            .unbounded_send(Outgoing::Packet(packet))
+            .map_err(|_| SocketIoError::Disconnected)
+    }
+}
+
+/// A stream of the [`Event`]s with a given name, created with [`Socket::on`].
+#[derive(Debug)]
+pub struct EventStream {
+    receiver: mpsc::UnboundedReceiver<Event>,
+}
+
+impl Stream for EventStream {
+    type Item = Event;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wasm_bindgen_test::*;
+
+    fn shared_with(namespace: &str, state: Namespace) -> Shared {
+        let mut shared = Shared::default();
+        shared.namespaces.insert(namespace.to_owned(), state);
+        shared
+    }
+
+    fn dispatch(shared: &mut Shared, encoded: &str) -> mpsc::UnboundedReceiver<Outgoing> {
+        let (sender, receiver) = mpsc::unbounded();
+        shared.dispatch(Packet::decode(encoded).unwrap(), &sender);
+        receiver
+    }
+
+    #[wasm_bindgen_test]
+    fn namespace_connect() {
+        let (sender, mut receiver) = oneshot::channel();
+        let mut shared = shared_with(
+            "/admin",
+            Namespace {
+                connecting: Some(sender),
+                ..Namespace::default()
+            },
+        );
+
+        // Packets for other namespaces are ignored.
+        dispatch(&mut shared, r#"0{"sid":"other"}"#);
+        assert!(matches!(receiver.try_recv(), Ok(None)));
+
+        dispatch(&mut shared, r#"0/admin,{"sid":"abc"}"#);
+        assert!(matches!(receiver.try_recv(), Ok(Some(Ok(())))));
+        assert_eq!(shared.namespaces["/admin"].sid.as_deref(), Some("abc"));
+
+        let (sender, mut receiver) = oneshot::channel();
+        let mut shared = shared_with(
+            "/admin",
+            Namespace {
+                connecting: Some(sender),
+                ..Namespace::default()
+            },
+        );
+        dispatch(&mut shared, r#"4/admin,{"message":"not authorized"}"#);
+        match receiver.try_recv() {
+            Ok(Some(Err(SocketIoError::ConnectError(message)))) => {
+                assert_eq!(message, "not authorized")
+            }
+            other => panic!("expected a connect error, got {other:?}"),
+        }
+        assert!(shared.namespaces.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn ack_correlation() {
+        let (first, mut first_receiver) = oneshot::channel();
+        let (second, mut second_receiver) = oneshot::channel();
+        let mut shared = shared_with(
+            "/chat",
+            Namespace {
+                acks: HashMap::from([(1, first), (2, second)]),
+                ..Namespace::default()
+            },
+        );
+
+        dispatch(&mut shared, r#"3/chat,2[42]"#);
+        assert_eq!(second_receiver.try_recv().unwrap(), Some(vec![json!(42)]));
+        assert_eq!(first_receiver.try_recv().unwrap(), None);
+
+        // Unknown ids and acks for other namespaces are ignored.
+        dispatch(&mut shared, r#"3/chat,7["late"]"#);
+        dispatch(&mut shared, r#"3/other,1["wrong"]"#);
+        assert_eq!(first_receiver.try_recv().unwrap(), None);
+
+        dispatch(&mut shared, "3/chat,1[]");
+        assert_eq!(first_receiver.try_recv().unwrap(), Some(vec![]));
+        assert!(shared.namespaces["/chat"].acks.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn event_dispatch() {
+        let (first, mut first_receiver) = mpsc::unbounded();
+        let (second, mut second_receiver) = mpsc::unbounded();
+        let (other, mut other_receiver) = mpsc::unbounded();
+        let mut shared = shared_with(
+            "/chat",
+            Namespace {
+                listeners: HashMap::from([
+                    ("message".to_owned(), vec![first, second]),
+                    ("typing".to_owned(), vec![other]),
+                ]),
+                ..Namespace::default()
+            },
+        );
+
+        let mut outgoing = dispatch(&mut shared, r#"2/chat,5["message",{"text":"hi"}]"#);
+        let event = first_receiver.try_recv().unwrap();
+        assert_eq!(event.name(), "message");
+        assert_eq!(event.args(), [json!({"text": "hi"})]);
+        assert!(event.wants_ack());
+        assert!(second_receiver.try_recv().is_ok());
+        assert!(other_receiver.try_recv().is_err());
+
+        event.ack(&"ok").unwrap();
+        match outgoing.try_recv() {
+            Ok(Outgoing::Packet(packet)) => assert_eq!(packet.encode(), r#"3/chat,5["ok"]"#),
+            other => panic!("expected an ack packet, got {other:?}"),
+        }
+
+        // Closed listeners are removed.
+        drop(second_receiver);
+        dispatch(&mut shared, r#"2/chat,["message"]"#);
+        assert!(!first_receiver.try_recv().unwrap().wants_ack());
+        assert_eq!(shared.namespaces["/chat"].listeners["message"].len(), 1);
+    }
+}
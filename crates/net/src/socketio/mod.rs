@@ -0,0 +1,85 @@
+//! A [Socket.IO](https://socket.io/) client.
+//!
+//! Socket.IO is a protocol layered on top of WebSocket which adds namespaces, named events and
+//! acknowledgements. Many real-time backends speak it instead of raw WebSocket.
+//!
+//! This client connects over the WebSocket transport of Engine.IO (protocol version 4) and speaks
+//! Socket.IO protocol version 5, which is used by Socket.IO servers 3.x and later. Binary
+//! attachments and automatic reconnection are not supported.
+//!
+//! # Example
+//!
+//! ```rust
+//! use gloo_net::socketio::Manager;
+//! use futures::StreamExt;
+//!
+//! # macro_rules! console_log {
+//! #    ($($expr:expr),*) => {{}};
+//! # }
+//! # async fn no_run() {
+//! let manager = Manager::connect("https://example.com").unwrap();
+//! let socket = manager.socket("/chat").await.unwrap();
+//!
+//! let mut messages = socket.on("chat message");
+//! socket.emit("chat message", "hello").unwrap();
+//!
+//! let delivered: bool = socket.emit_with_ack("chat message", "anyone?").await.unwrap();
+//!
+//! while let Some(event) = messages.next().await {
+//!     let text: String = event.data().unwrap();
+//!     console_log!(text);
+//! }
+//! # }
+//! ```
+
+mod client;
+mod packet;
+
+pub use client::{Event, EventStream, Manager, ManagerBuilder, Socket};
+
+use gloo_utils::errors::JsError;
+use std::fmt;
+
+/// Error returned by the Socket.IO client.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SocketIoError {
+    /// The connection could not be established.
+    JsError(JsError),
+    /// The server refused the connection to a namespace.
+    ///
+    /// Contains the message sent by the server, usually from a middleware.
+    ConnectError(String),
+    /// The connection to the server or to the namespace was closed.
+    Disconnected,
+    /// Error returned by `serde` while serializing or deserializing event data.
+    SerdeError(serde_json::Error),
+}
+
+impl fmt::Display for SocketIoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SocketIoError::JsError(e) => write!(f, "{e}"),
+            SocketIoError::ConnectError(message) => {
+                write!(f, "Socket.IO server refused connection: {message}")
+            }
+            SocketIoError::Disconnected => write!(f, "Socket.IO connection closed"),
+            SocketIoError::SerdeError(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for SocketIoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SocketIoError::SerdeError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for SocketIoError {
+    fn from(error: serde_json::Error) -> Self {
+        SocketIoError::SerdeError(error)
+    }
+}
@@ -0,0 +1,226 @@
+//! Encoding and decoding of [Engine.IO](https://socket.io/docs/v4/engine-io-protocol/) and
+//! [Socket.IO](https://socket.io/docs/v4/socket-io-protocol/) packets.
+use serde_json::Value;
+
+/// An Engine.IO (protocol version 4) packet, as sent over the WebSocket transport.
+#[derive(Debug, PartialEq)]
+pub(crate) enum EnginePacket {
+    /// The handshake sent by the server, carrying the session id.
+    Open {
+        sid: String,
+    },
+    Close,
+    Ping,
+    Pong,
+    Message(String),
+    Upgrade,
+    Noop,
+}
+
+impl EnginePacket {
+    pub(crate) fn decode(data: &str) -> Option<Self> {
+        let mut chars = data.chars();
+        let packet = match chars.next()? {
+            '0' => {
+                let handshake: Value = serde_json::from_str(chars.as_str()).ok()?;
+                EnginePacket::Open {
+                    sid: handshake.get("sid")?.as_str()?.to_owned(),
+                }
+            }
+            '1' => EnginePacket::Close,
+            '2' => EnginePacket::Ping,
+            '3' => EnginePacket::Pong,
+            '4' => EnginePacket::Message(chars.as_str().to_owned()),
+            '5' => EnginePacket::Upgrade,
+            '6' => EnginePacket::Noop,
+            _ => return None,
+        };
+        Some(packet)
+    }
+
+    pub(crate) fn encode(&self) -> String {
+        match self {
+            EnginePacket::Open { .. } => unreachable!("only the server opens a session"),
+            EnginePacket::Close => "1".into(),
+            EnginePacket::Ping => "2".into(),
+            EnginePacket::Pong => "3".into(),
+            EnginePacket::Message(data) => format!("4{data}"),
+            EnginePacket::Upgrade => "5".into(),
+            EnginePacket::Noop => "6".into(),
+        }
+    }
+}
+
+/// The type of a Socket.IO packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PacketKind {
+    Connect,
+    Disconnect,
+    Event,
+    Ack,
+    ConnectError,
+    BinaryEvent,
+    BinaryAck,
+}
+
+/// A Socket.IO (protocol version 5) packet.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Packet {
+    pub kind: PacketKind,
+    pub namespace: String,
+    pub id: Option<u64>,
+    pub data: Option<Value>,
+}
+
+impl Packet {
+    pub(crate) fn new(kind: PacketKind, namespace: &str) -> Self {
+        Self {
+            kind,
+            namespace: namespace.to_owned(),
+            id: None,
+            data: None,
+        }
+    }
+
+    pub(crate) fn decode(data: &str) -> Option<Self> {
+        let kind = match data.as_bytes().first()? {
+            b'0' => PacketKind::Connect,
+            b'1' => PacketKind::Disconnect,
+            b'2' => PacketKind::Event,
+            b'3' => PacketKind::Ack,
+            b'4' => PacketKind::ConnectError,
+            b'5' => PacketKind::BinaryEvent,
+            b'6' => PacketKind::BinaryAck,
+            _ => return None,
+        };
+        let mut rest = &data[1..];
+
+        if matches!(kind, PacketKind::BinaryEvent | PacketKind::BinaryAck) {
+            let (_attachments, after) = rest.split_once('-')?;
+            rest = after;
+        }
+
+        let namespace = if rest.starts_with('/') {
+            match rest.split_once(',') {
+                Some((namespace, after)) => {
+                    rest = after;
+                    namespace
+                }
+                None => std::mem::take(&mut rest),
+            }
+        } else {
+            "/"
+        };
+
+        let id_len = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let id = match id_len {
+            0 => None,
+            _ => Some(rest[..id_len].parse().ok()?),
+        };
+        rest = &rest[id_len..];
+
+        let data = match rest {
+            "" => None,
+            rest => Some(serde_json::from_str(rest).ok()?),
+        };
+
+        Some(Self {
+            kind,
+            namespace: namespace.to_owned(),
+            id,
+            data,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> String {
+        let mut encoded = match self.kind {
+            PacketKind::Connect => "0",
+            PacketKind::Disconnect => "1",
+            PacketKind::Event => "2",
+            PacketKind::Ack => "3",
+            PacketKind::ConnectError => "4",
+            PacketKind::BinaryEvent => "5",
+            PacketKind::BinaryAck => "6",
+        }
+        .to_owned();
+        if self.namespace != "/" {
+            encoded.push_str(&self.namespace);
+            encoded.push(',');
+        }
+        if let Some(id) = self.id {
+            encoded.push_str(&id.to_string());
+        }
+        if let Some(data) = &self.data {
+            encoded.push_str(&data.to_string());
+        }
+        encoded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn engine_packets() {
+        assert_eq!(
+            EnginePacket::decode(r#"0{"sid":"lv_VI97HAXpY6yYWAAAC","pingInterval":25000}"#),
+            Some(EnginePacket::Open {
+                sid: "lv_VI97HAXpY6yYWAAAC".into()
+            })
+        );
+        assert_eq!(EnginePacket::decode("2"), Some(EnginePacket::Ping));
+        assert_eq!(
+            EnginePacket::decode("4hello"),
+            Some(EnginePacket::Message("hello".into()))
+        );
+        assert_eq!(EnginePacket::decode("9"), None);
+        assert_eq!(EnginePacket::Pong.encode(), "3");
+    }
+
+    #[wasm_bindgen_test]
+    fn socket_packets() {
+        assert_eq!(
+            Packet::decode(r#"2["hello",1]"#),
+            Some(Packet {
+                kind: PacketKind::Event,
+                namespace: "/".into(),
+                id: None,
+                data: Some(json!(["hello", 1])),
+            })
+        );
+        assert_eq!(
+            Packet::decode(r#"3/admin,13["bar"]"#),
+            Some(Packet {
+                kind: PacketKind::Ack,
+                namespace: "/admin".into(),
+                id: Some(13),
+                data: Some(json!(["bar"])),
+            })
+        );
+        assert_eq!(
+            Packet::decode("1/admin,"),
+            Some(Packet::new(PacketKind::Disconnect, "/admin"))
+        );
+        assert_eq!(
+            Packet::decode(r#"51-["hello",{"_placeholder":true,"num":0}]"#)
+                .unwrap()
+                .kind,
+            PacketKind::BinaryEvent
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn packets_round_trip() {
+        for encoded in [
+            "0",
+            r#"0/admin,{"token":"123"}"#,
+            r#"2/chat,12["message","hi"]"#,
+            "3/chat,12[]",
+        ] {
+            assert_eq!(Packet::decode(encoded).unwrap().encode(), encoded);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::http::Request;
+use crate::transport::post::{read_message, PostSender};
+use crate::transport::{Transport, TransportError, TransportKind};
+use crate::websocket::Message;
+use futures_core::{ready, Stream};
+use futures_sink::Sink;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+type PollFuture = Pin<Box<dyn Future<Output = Result<Option<Message>, TransportError>>>>;
+
+/// A [`Transport`] receiving messages through repeated `GET` requests and sending them with
+/// `POST` requests.
+///
+/// A new `GET` request is only issued while the stream is being polled.
+pub struct LongPollingTransport {
+    url: String,
+    in_flight: Option<PollFuture>,
+    sender: PostSender,
+    closed: bool,
+}
+
+impl LongPollingTransport {
+    /// Creates a transport polling `url` for messages and sending them to `post_url`.
+    ///
+    /// As a long poll only completes once a message is available, no request is made until the
+    /// stream is polled. This never fails to connect, which makes it suitable as the last
+    /// resort of a [`Connector`](crate::transport::Connector).
+    pub async fn connect(url: &str, post_url: &str) -> Result<Self, TransportError> {
+        Ok(Self {
+            url: url.into(),
+            in_flight: None,
+            sender: PostSender::new(post_url),
+            closed: false,
+        })
+    }
+}
+
+/// Waits for the next message, or `None` if the server responded with `204 No Content`.
+async fn poll_message(url: String) -> Result<Option<Message>, TransportError> {
+    let response = Request::get(&url).send().await?.error_for_status().await?;
+    if response.status() == 204 {
+        return Ok(None);
+    }
+    read_message(response).await.map(Some)
+}
+
+impl Transport for LongPollingTransport {
+    fn kind(&self) -> TransportKind {
+        TransportKind::LongPolling
+    }
+}
+
+impl fmt::Debug for LongPollingTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LongPollingTransport")
+            .field("url", &self.url)
+            .field("post_url", &self.sender.url())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for LongPollingTransport {
+    type Item = Result<Message, TransportError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if this.closed {
+                return Poll::Ready(None);
+            }
+            let url = &this.url;
+            let in_flight = this
+                .in_flight
+                .get_or_insert_with(|| Box::pin(poll_message(url.clone())));
+            let result = ready!(in_flight.as_mut().poll(cx));
+            this.in_flight = None;
+            match result {
+                Ok(Some(msg)) => return Poll::Ready(Some(Ok(msg))),
+                Ok(None) => continue,
+                Err(e) => {
+                    this.closed = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
+}
+
+impl Sink<Message> for LongPollingTransport {
+    type Error = TransportError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sender.poll_flush(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
+        self.sender.start_send(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sender.poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sender.poll_flush(cx)
+    }
+}
@@ -0,0 +1,255 @@
+//! A bidirectional message channel over whichever transport the network allows.
+//!
+//! Some proxies and corporate networks block WebSocket connections. This module provides a
+//! [`Transport`] trait implemented by:
+//! - [`WebSocketTransport`]: a plain WebSocket.
+//! - [`SseTransport`]: receives messages through an `EventSource` and sends them with `POST`
+//!   requests.
+//! - [`LongPollingTransport`]: receives messages through repeated `GET` requests and sends them
+//!   with `POST` requests.
+//!
+//! [`Connector`] tries a list of transports in order and settles on the first one that connects,
+//! giving a [`Connection`] which is a `Stream + Sink` of [`Message`]s regardless of the
+//! transport underneath.
+//!
+//! # Example
+//!
+//! ```rust
+//! use gloo_net::transport::Connector;
+//! use gloo_net::websocket::Message;
+//! use futures::{SinkExt, StreamExt};
+//!
+//! # macro_rules! console_log {
+//! #    ($($expr:expr),*) => {{}};
+//! # }
+//! # async fn no_run() {
+//! let mut connection = Connector::new()
+//!     .websocket("wss://example.com/ws")
+//!     .sse("https://example.com/events", "https://example.com/send")
+//!     .long_polling("https://example.com/poll", "https://example.com/send")
+//!     .connect()
+//!     .await
+//!     .unwrap();
+//! console_log!(format!("connected using {:?}", connection.kind()));
+//!
+//! connection.send(Message::Text("hello".into())).await.unwrap();
+//! while let Some(msg) = connection.next().await {
+//!     console_log!(format!("{:?}", msg))
+//! }
+//! # }
+//! ```
+//!
+//! # Server requirements
+//!
+//! The `POST` endpoint receives one message per request, with a `Content-Type` of
+//! `text/plain;charset=UTF-8` for text and `application/octet-stream` for binary messages.
+//!
+//! The `EventSource` endpoint sends each message as the data of an unnamed event. Only text
+//! messages can be received this way.
+//!
+//! The long-polling endpoint responds to each `GET` request with one message, using the same
+//! content types as above, or with `204 No Content` when no message arrived in time.
+
+mod long_polling;
+mod post;
+mod sse;
+mod websocket;
+
+pub use long_polling::LongPollingTransport;
+pub use sse::SseTransport;
+pub use websocket::WebSocketTransport;
+
+use crate::eventsource::EventSourceError;
+use crate::websocket::{Message, WebSocketError};
+use futures_core::Stream;
+use futures_sink::Sink;
+use gloo_utils::errors::JsError;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A bidirectional channel of [`Message`]s.
+pub trait Transport:
+    Stream<Item = Result<Message, TransportError>> + Sink<Message, Error = TransportError>
+{
+    /// The kind of this transport.
+    fn kind(&self) -> TransportKind;
+}
+
+/// The kinds of [`Transport`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TransportKind {
+    /// [`WebSocketTransport`]
+    WebSocket,
+    /// [`SseTransport`]
+    ServerSentEvents,
+    /// [`LongPollingTransport`]
+    LongPolling,
+}
+
+/// Error returned by a [`Transport`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TransportError {
+    /// Error returned by JavaScript while setting up the transport.
+    JsError(JsError),
+    /// Error returned by the WebSocket.
+    WebSocketError(WebSocketError),
+    /// Error returned by the EventSource.
+    EventSourceError(EventSourceError),
+    /// Error returned by an HTTP request.
+    HttpError(crate::Error),
+    /// None of the transports tried by the [`Connector`] could connect.
+    ///
+    /// Contains the error of each transport, in the order they were tried.
+    NegotiationError(Vec<TransportError>),
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransportError::JsError(e) => write!(f, "{e}"),
+            TransportError::WebSocketError(e) => write!(f, "{e}"),
+            TransportError::EventSourceError(e) => write!(f, "{e}"),
+            TransportError::HttpError(e) => write!(f, "{e}"),
+            TransportError::NegotiationError(errors) => {
+                write!(f, "no transport could connect")?;
+                for error in errors {
+                    write!(f, "; {error}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+impl From<crate::Error> for TransportError {
+    fn from(error: crate::Error) -> Self {
+        TransportError::HttpError(error)
+    }
+}
+
+#[derive(Debug)]
+enum Candidate {
+    WebSocket { url: String },
+    Sse { url: String, post_url: String },
+    LongPolling { url: String, post_url: String },
+}
+
+impl Candidate {
+    async fn connect(self) -> Result<Pin<Box<dyn Transport>>, TransportError> {
+        Ok(match self {
+            Candidate::WebSocket { url } => Box::pin(WebSocketTransport::connect(&url).await?),
+            Candidate::Sse { url, post_url } => {
+                Box::pin(SseTransport::connect(&url, &post_url).await?)
+            }
+            Candidate::LongPolling { url, post_url } => {
+                Box::pin(LongPollingTransport::connect(&url, &post_url).await?)
+            }
+        })
+    }
+}
+
+/// Connects using the first of a list of transports that works.
+///
+/// Transports are tried in the order they are added.
+#[derive(Debug, Default)]
+pub struct Connector {
+    candidates: Vec<Candidate>,
+}
+
+impl Connector {
+    /// Creates a connector without any transports.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tries a [`WebSocketTransport`] connecting to `url`.
+    pub fn websocket(mut self, url: &str) -> Self {
+        self.candidates
+            .push(Candidate::WebSocket { url: url.into() });
+        self
+    }
+
+    /// Tries an [`SseTransport`] receiving from `url` and sending to `post_url`.
+    pub fn sse(mut self, url: &str, post_url: &str) -> Self {
+        self.candidates.push(Candidate::Sse {
+            url: url.into(),
+            post_url: post_url.into(),
+        });
+        self
+    }
+
+    /// Tries a [`LongPollingTransport`] receiving from `url` and sending to `post_url`.
+    pub fn long_polling(mut self, url: &str, post_url: &str) -> Self {
+        self.candidates.push(Candidate::LongPolling {
+            url: url.into(),
+            post_url: post_url.into(),
+        });
+        self
+    }
+
+    /// Connects using the first transport that works.
+    ///
+    /// Returns [`TransportError::NegotiationError`] if none of them do.
+    pub async fn connect(self) -> Result<Connection, TransportError> {
+        let mut errors = Vec::new();
+        for candidate in self.candidates {
+            match candidate.connect().await {
+                Ok(transport) => return Ok(Connection { transport }),
+                Err(e) => errors.push(e),
+            }
+        }
+        Err(TransportError::NegotiationError(errors))
+    }
+}
+
+/// A message channel established by a [`Connector`].
+pub struct Connection {
+    transport: Pin<Box<dyn Transport>>,
+}
+
+impl Connection {
+    /// The kind of transport the connection uses.
+    pub fn kind(&self) -> TransportKind {
+        self.transport.kind()
+    }
+}
+
+impl fmt::Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Connection")
+            .field("kind", &self.kind())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for Connection {
+    type Item = Result<Message, TransportError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.transport.as_mut().poll_next(cx)
+    }
+}
+
+impl Sink<Message> for Connection {
+    type Error = TransportError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.transport.as_mut().poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
+        self.transport.as_mut().start_send(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.transport.as_mut().poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.transport.as_mut().poll_close(cx)
+    }
+}
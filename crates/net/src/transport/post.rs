@@ -0,0 +1,71 @@
+use crate::http::{Request, Response};
+use crate::transport::TransportError;
+use crate::websocket::Message;
+use futures_core::ready;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+const TEXT_CONTENT_TYPE: &str = "text/plain;charset=UTF-8";
+const BINARY_CONTENT_TYPE: &str = "application/octet-stream";
+
+type SendFuture = Pin<Box<dyn Future<Output = Result<(), TransportError>>>>;
+
+/// Sends messages with `POST` requests, one at a time so that they arrive in order.
+pub(super) struct PostSender {
+    url: String,
+    in_flight: Option<SendFuture>,
+}
+
+impl PostSender {
+    pub(super) fn new(url: &str) -> Self {
+        Self {
+            url: url.into(),
+            in_flight: None,
+        }
+    }
+
+    pub(super) fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Waits for the message currently being sent, if any.
+    pub(super) fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), TransportError>> {
+        if let Some(in_flight) = self.in_flight.as_mut() {
+            let result = ready!(in_flight.as_mut().poll(cx));
+            self.in_flight = None;
+            return Poll::Ready(result);
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    /// Starts sending `msg`. [`PostSender::poll_flush`] must have completed beforehand.
+    pub(super) fn start_send(&mut self, msg: Message) -> Result<(), TransportError> {
+        let request = match msg {
+            Message::Text(text) => Request::post(&self.url)
+                .header("Content-Type", TEXT_CONTENT_TYPE)
+                .body(text)?,
+            Message::Bytes(bytes) => Request::post(&self.url)
+                .header("Content-Type", BINARY_CONTENT_TYPE)
+                .body(js_sys::Uint8Array::from(bytes.as_slice()))?,
+        };
+        self.in_flight = Some(Box::pin(async move {
+            request.send().await?.error_for_status().await?;
+            Ok(())
+        }));
+        Ok(())
+    }
+}
+
+/// Reads the body of `response` as a message, based on its `Content-Type`.
+pub(super) async fn read_message(response: Response) -> Result<Message, TransportError> {
+    let is_binary = response
+        .headers()
+        .get("Content-Type")
+        .map_or(false, |ty| ty.starts_with(BINARY_CONTENT_TYPE));
+    Ok(if is_binary {
+        Message::Bytes(response.binary().await?)
+    } else {
+        Message::Text(response.text().await?)
+    })
+}
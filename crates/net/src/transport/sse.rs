@@ -0,0 +1,136 @@
+use crate::eventsource::futures::{EventSource, EventSourceSubscription};
+use crate::eventsource::EventSourceError;
+use crate::transport::post::PostSender;
+use crate::transport::{Transport, TransportError, TransportKind};
+use crate::websocket::Message;
+use futures_channel::oneshot;
+use futures_core::{ready, Stream};
+use futures_sink::Sink;
+use std::cell::RefCell;
+use std::fmt;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// A [`Transport`] receiving messages through an [`EventSource`] and sending them with `POST`
+/// requests.
+///
+/// Only text messages can be received.
+pub struct SseTransport {
+    // Kept alive for as long as the subscription.
+    _es: EventSource,
+    subscription: EventSourceSubscription,
+    sender: PostSender,
+    closed: bool,
+}
+
+impl SseTransport {
+    /// Opens an `EventSource` to `url` and waits for it to be established. Messages are sent to
+    /// `post_url`.
+    pub async fn connect(url: &str, post_url: &str) -> Result<Self, TransportError> {
+        let mut es = EventSource::new(url).map_err(TransportError::JsError)?;
+        let subscription = es.subscribe("message").map_err(TransportError::JsError)?;
+        if !opened(es.as_raw()).await {
+            return Err(TransportError::EventSourceError(
+                EventSourceError::ConnectionError,
+            ));
+        }
+
+        Ok(Self {
+            _es: es,
+            subscription,
+            sender: PostSender::new(post_url),
+            closed: false,
+        })
+    }
+}
+
+/// Resolves to `true` once `es` fires `open`, or to `false` if it fires `error` first.
+async fn opened(es: &web_sys::EventSource) -> bool {
+    let (sender, receiver) = oneshot::channel();
+    let sender = Rc::new(RefCell::new(Some(sender)));
+    let callback = |open: bool| {
+        let sender = Rc::clone(&sender);
+        Closure::wrap(Box::new(move || {
+            if let Some(sender) = sender.borrow_mut().take() {
+                let _ = sender.send(open);
+            }
+        }) as Box<dyn FnMut()>)
+    };
+    let open_callback = callback(true);
+    let error_callback = callback(false);
+
+    let listeners = [("open", &open_callback), ("error", &error_callback)];
+    for (ty, cb) in listeners {
+        if es
+            .add_event_listener_with_callback(ty, cb.as_ref().unchecked_ref())
+            .is_err()
+        {
+            return false;
+        }
+    }
+    let open = receiver.await.unwrap_or(false);
+    for (ty, cb) in listeners {
+        let _ = es.remove_event_listener_with_callback(ty, cb.as_ref().unchecked_ref());
+    }
+    open
+}
+
+impl Transport for SseTransport {
+    fn kind(&self) -> TransportKind {
+        TransportKind::ServerSentEvents
+    }
+}
+
+impl fmt::Debug for SseTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SseTransport")
+            .field("subscription", &self.subscription)
+            .field("post_url", &self.sender.url())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for SseTransport {
+    type Item = Result<Message, TransportError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.closed {
+            return Poll::Ready(None);
+        }
+        let msg = ready!(Pin::new(&mut self.subscription).poll_next(cx));
+        Poll::Ready(match msg {
+            Some(Ok((_, event))) => Some(Ok(Message::Text(
+                event.data().as_string().unwrap_or_default(),
+            ))),
+            Some(Err(e)) => {
+                // The browser gave up reconnecting.
+                self.closed = true;
+                Some(Err(TransportError::EventSourceError(e)))
+            }
+            None => None,
+        })
+    }
+}
+
+impl Sink<Message> for SseTransport {
+    type Error = TransportError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sender.poll_flush(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
+        self.sender.start_send(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sender.poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sender.poll_flush(cx)
+    }
+}
@@ -0,0 +1,85 @@
+use crate::transport::{Transport, TransportError, TransportKind};
+use crate::websocket::{futures::WebSocket, Message, State, WebSocketError};
+use futures_core::{ready, Stream};
+use futures_sink::Sink;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A [`Transport`] over a [`WebSocket`].
+pub struct WebSocketTransport {
+    ws: WebSocket,
+}
+
+impl WebSocketTransport {
+    /// Opens a WebSocket connection to `url` and waits for it to be established.
+    pub async fn connect(url: &str) -> Result<Self, TransportError> {
+        let mut ws = WebSocket::open(url).map_err(TransportError::JsError)?;
+        futures_util::future::poll_fn(|cx| Pin::new(&mut ws).poll_ready(cx))
+            .await
+            .map_err(TransportError::WebSocketError)?;
+        match ws.state() {
+            State::Open => Ok(Self { ws }),
+            _ => Err(TransportError::WebSocketError(
+                WebSocketError::ConnectionError,
+            )),
+        }
+    }
+}
+
+impl Transport for WebSocketTransport {
+    fn kind(&self) -> TransportKind {
+        TransportKind::WebSocket
+    }
+}
+
+impl From<WebSocketTransport> for WebSocket {
+    fn from(transport: WebSocketTransport) -> Self {
+        transport.ws
+    }
+}
+
+impl fmt::Debug for WebSocketTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocketTransport")
+            .field("state", &self.ws.state())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for WebSocketTransport {
+    type Item = Result<Message, TransportError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let msg = ready!(Pin::new(&mut self.ws).poll_next(cx));
+        Poll::Ready(msg.map(|msg| msg.map_err(TransportError::WebSocketError)))
+    }
+}
+
+impl Sink<Message> for WebSocketTransport {
+    type Error = TransportError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.ws)
+            .poll_ready(cx)
+            .map_err(TransportError::WebSocketError)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
+        Pin::new(&mut self.ws)
+            .start_send(item)
+            .map_err(TransportError::WebSocketError)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.ws)
+            .poll_flush(cx)
+            .map_err(TransportError::WebSocketError)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.ws)
+            .poll_close(cx)
+            .map_err(TransportError::WebSocketError)
+    }
+}
@@ -34,12 +34,14 @@ use futures_core::{ready, Stream};
 use futures_sink::Sink;
 use gloo_utils::errors::JsError;
 use pin_project::{pin_project, pinned_drop};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
 use std::pin::Pin;
 use std::rc::Rc;
 use std::task::{Context, Poll, Waker};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
 use web_sys::{BinaryType, MessageEvent};
 
 /// Wrapper around browser's WebSocket API.
@@ -50,6 +52,11 @@ pub struct WebSocket {
     sink_waker: Rc<RefCell<Option<Waker>>>,
     #[pin]
     message_receiver: mpsc::UnboundedReceiver<StreamMessage>,
+    /// The sub-protocols that the server is required to choose from, in order of priority.
+    required_protocols: Option<Vec<String>>,
+    negotiation_failed: Rc<Cell<bool>>,
+    /// A `Blob` message that is currently being read into memory.
+    pending_blob: Option<JsFuture>,
     #[allow(clippy::type_complexity)]
     closures: (
         Closure<dyn FnMut()>,
@@ -78,6 +85,26 @@ impl WebSocket {
         Self::setup(web_sys::WebSocket::new(url))
     }
 
+    /// Creates a [`WebSocketBuilder`] which allows configuring the connection before it is
+    /// established.
+    ///
+    /// ```rust
+    /// use gloo_net::websocket::futures::WebSocket;
+    /// use web_sys::BinaryType;
+    ///
+    /// # fn no_run() {
+    /// let ws = WebSocket::builder("wss://echo.websocket.org")
+    ///     .protocols(["v2.chat", "v1.chat"])
+    ///     .require_protocol(true)
+    ///     .binary_type(BinaryType::Blob)
+    ///     .open()
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn builder(url: &str) -> WebSocketBuilder {
+        WebSocketBuilder::new(url)
+    }
+
     /// Establish a WebSocket connection.
     ///
     /// This function may error in the following cases:
@@ -121,19 +148,39 @@ impl WebSocket {
     }
 
     fn setup(ws: Result<web_sys::WebSocket, JsValue>) -> Result<Self, JsError> {
+        Self::setup_with(ws, BinaryType::Arraybuffer, None)
+    }
+
+    fn setup_with(
+        ws: Result<web_sys::WebSocket, JsValue>,
+        binary_type: BinaryType,
+        required_protocols: Option<Vec<String>>,
+    ) -> Result<Self, JsError> {
         let waker: Rc<RefCell<Option<Waker>>> = Rc::new(RefCell::new(None));
+        let negotiation_failed = Rc::new(Cell::new(false));
         let ws = ws.map_err(js_to_js_error)?;
 
-        // We rely on this because the other type Blob can be converted to Vec<u8> only through a
-        // promise which makes it awkward to use in our event callbacks where we want to guarantee
-        // the order of the events stays the same.
-        ws.set_binary_type(BinaryType::Arraybuffer);
+        // `Blob` messages can only be converted to `Vec<u8>` through a promise. They are queued
+        // as they arrive and read one at a time while polling the stream so that the order of
+        // the messages stays the same.
+        ws.set_binary_type(binary_type);
 
         let (sender, receiver) = mpsc::unbounded();
 
         let open_callback: Closure<dyn FnMut()> = {
             let waker = Rc::clone(&waker);
+            let negotiation_failed = Rc::clone(&negotiation_failed);
+            let check_protocol = required_protocols.is_some();
+            let sender = sender.clone();
+            let ws = ws.clone();
             Closure::wrap(Box::new(move || {
+                // Browsers accept a handshake in which the server selects no sub-protocol at all,
+                // so we have to check for that ourselves.
+                if check_protocol && ws.protocol().is_empty() {
+                    negotiation_failed.set(true);
+                    let _ = sender.unbounded_send(StreamMessage::NegotiationError);
+                    let _ = ws.close();
+                }
                 if let Some(waker) = waker.borrow_mut().take() {
                     waker.wake();
                 }
@@ -151,7 +198,7 @@ impl WebSocket {
             let sender = sender.clone();
             Closure::wrap(Box::new(move |e: MessageEvent| {
                 let msg = parse_message(e);
-                let _ = sender.unbounded_send(StreamMessage::Message(msg));
+                let _ = sender.unbounded_send(msg);
             }) as Box<dyn FnMut(MessageEvent)>)
         };
 
@@ -195,6 +242,9 @@ impl WebSocket {
             ws,
             sink_waker: waker,
             message_receiver: receiver,
+            required_protocols,
+            negotiation_failed,
+            pending_blob: None,
             closures: (
                 open_callback,
                 message_callback,
@@ -234,15 +284,123 @@ impl WebSocket {
         }
     }
 
-    /// The extensions in use.
+    /// The extensions selected by the server.
+    ///
+    /// This is an empty string until the connection is open, or if the server did not accept
+    /// any extensions.
     pub fn extensions(&self) -> String {
         self.ws.extensions()
     }
 
-    /// The sub-protocol in use.
+    /// The sub-protocol selected by the server.
+    ///
+    /// This is an empty string until the connection is open, or if the server did not select
+    /// a sub-protocol.
     pub fn protocol(&self) -> String {
         self.ws.protocol()
     }
+
+    /// The type of binary data being received over this connection.
+    pub fn binary_type(&self) -> BinaryType {
+        self.ws.binary_type()
+    }
+
+    fn negotiation_error(&self) -> WebSocketError {
+        WebSocketError::ProtocolNegotiationError(
+            self.required_protocols.clone().unwrap_or_default(),
+        )
+    }
+}
+
+/// A builder for configuring a [`WebSocket`] before the connection is established.
+///
+/// Created with [`WebSocket::builder`].
+#[derive(Debug)]
+pub struct WebSocketBuilder {
+    url: String,
+    protocols: Vec<String>,
+    require_protocol: bool,
+    binary_type: BinaryType,
+}
+
+impl WebSocketBuilder {
+    fn new(url: &str) -> Self {
+        Self {
+            url: url.into(),
+            protocols: Vec::new(),
+            require_protocol: false,
+            binary_type: BinaryType::Arraybuffer,
+        }
+    }
+
+    /// Adds a sub-protocol to offer to the server.
+    ///
+    /// Protocols are offered in the order they are added, most preferred first.
+    pub fn protocol(mut self, protocol: &str) -> Self {
+        self.protocols.push(protocol.into());
+        self
+    }
+
+    /// Adds sub-protocols to offer to the server, most preferred first.
+    pub fn protocols<I, S>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.protocols
+            .extend(protocols.into_iter().map(|p| p.as_ref().to_owned()));
+        self
+    }
+
+    /// Whether the server must select one of the offered sub-protocols.
+    ///
+    /// When set and the server selects none of them, the connection is closed as soon as it
+    /// opens and [`WebSocketError::ProtocolNegotiationError`] is returned from both the
+    /// `Stream` and the `Sink`. Defaults to `false`.
+    pub fn require_protocol(mut self, require: bool) -> Self {
+        self.require_protocol = require;
+        self
+    }
+
+    /// The type of binary data to receive from the browser.
+    ///
+    /// Either way, binary messages are yielded as [`Message::Bytes`]. Receiving them as
+    /// [`Blob`](BinaryType::Blob)s lets the browser keep large messages out of memory until they
+    /// are read. Defaults to [`BinaryType::Arraybuffer`].
+    pub fn binary_type(mut self, binary_type: BinaryType) -> Self {
+        self.binary_type = binary_type;
+        self
+    }
+
+    /// Establish the WebSocket connection.
+    ///
+    /// This function may error in the following cases:
+    /// - The port to which the connection is being attempted is being blocked.
+    /// - The URL is invalid.
+    /// - The specified protocols are not supported or contain duplicates.
+    ///
+    /// The error returned is [`JsError`]. See the
+    /// [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/WebSocket/WebSocket#exceptions_thrown)
+    /// to learn more.
+    pub fn open(self) -> Result<WebSocket, JsError> {
+        let ws = if self.protocols.is_empty() {
+            web_sys::WebSocket::new(&self.url)
+        } else {
+            let protocols = self
+                .protocols
+                .iter()
+                .map(|p| JsValue::from_str(p))
+                .collect::<js_sys::Array>();
+            web_sys::WebSocket::new_with_str_sequence(&self.url, &protocols)
+        };
+        let required_protocols = if self.require_protocol && !self.protocols.is_empty() {
+            Some(self.protocols)
+        } else {
+            None
+        };
+
+        WebSocket::setup_with(ws, self.binary_type, required_protocols)
+    }
 }
 
 impl TryFrom<web_sys::WebSocket> for WebSocket {
@@ -256,17 +414,21 @@ impl TryFrom<web_sys::WebSocket> for WebSocket {
 #[derive(Clone)]
 enum StreamMessage {
     ErrorEvent,
+    NegotiationError,
     CloseEvent(CloseEvent),
     Message(Message),
+    Blob(web_sys::Blob),
     ConnectionClose,
 }
 
-fn parse_message(event: MessageEvent) -> Message {
+fn parse_message(event: MessageEvent) -> StreamMessage {
     if let Ok(array_buffer) = event.data().dyn_into::<js_sys::ArrayBuffer>() {
         let array = js_sys::Uint8Array::new(&array_buffer);
-        Message::Bytes(array.to_vec())
+        StreamMessage::Message(Message::Bytes(array.to_vec()))
+    } else if let Ok(blob) = event.data().dyn_into::<web_sys::Blob>() {
+        StreamMessage::Blob(blob)
     } else if let Ok(txt) = event.data().dyn_into::<js_sys::JsString>() {
-        Message::Text(String::from(&txt))
+        StreamMessage::Message(Message::Text(String::from(&txt)))
     } else {
         unreachable!("message event, received Unknown: {:?}", event.data());
     }
@@ -280,6 +442,8 @@ impl Sink<Message> for WebSocket {
         if ready_state == 0 {
             *self.sink_waker.borrow_mut() = Some(cx.waker().clone());
             Poll::Pending
+        } else if self.negotiation_failed.get() {
+            Poll::Ready(Err(self.negotiation_error()))
         } else {
             Poll::Ready(Ok(()))
         }
@@ -308,18 +472,38 @@ impl Sink<Message> for WebSocket {
 impl Stream for WebSocket {
     type Item = Result<Message, WebSocketError>;
 
-    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let msg = ready!(self.project().message_receiver.poll_next(cx));
-        match msg {
-            Some(StreamMessage::Message(msg)) => Poll::Ready(Some(Ok(msg))),
-            Some(StreamMessage::ErrorEvent) => {
-                Poll::Ready(Some(Err(WebSocketError::ConnectionError)))
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(pending) = self.as_mut().project().pending_blob.as_mut() {
+                let result = ready!(Pin::new(pending).poll(cx));
+                *self.as_mut().project().pending_blob = None;
+                return Poll::Ready(Some(match result {
+                    Ok(buffer) => Ok(Message::Bytes(js_sys::Uint8Array::new(&buffer).to_vec())),
+                    Err(e) => Err(WebSocketError::MessageReadError(js_to_js_error(e))),
+                }));
             }
-            Some(StreamMessage::CloseEvent(e)) => {
-                Poll::Ready(Some(Err(WebSocketError::ConnectionClose(e))))
+
+            let msg = ready!(self.as_mut().project().message_receiver.poll_next(cx));
+            if let Some(StreamMessage::Blob(blob)) = msg {
+                *self.as_mut().project().pending_blob = Some(JsFuture::from(blob.array_buffer()));
+                continue;
             }
-            Some(StreamMessage::ConnectionClose) => Poll::Ready(None),
-            None => Poll::Ready(None),
+
+            return match msg {
+                Some(StreamMessage::Message(msg)) => Poll::Ready(Some(Ok(msg))),
+                Some(StreamMessage::Blob(_)) => unreachable!("blobs are read before being yielded"),
+                Some(StreamMessage::ErrorEvent) => {
+                    Poll::Ready(Some(Err(WebSocketError::ConnectionError)))
+                }
+                Some(StreamMessage::NegotiationError) => {
+                    Poll::Ready(Some(Err(self.negotiation_error())))
+                }
+                Some(StreamMessage::CloseEvent(e)) => {
+                    Poll::Ready(Some(Err(WebSocketError::ConnectionClose(e))))
+                }
+                Some(StreamMessage::ConnectionClose) => Poll::Ready(None),
+                None => Poll::Ready(None),
+            };
         }
     }
 }
@@ -393,4 +577,32 @@ mod tests {
             );
         });
     }
+
+    #[wasm_bindgen_test]
+    fn websocket_builder_works() {
+        let ws_echo_server_url =
+            option_env!("WS_ECHO_SERVER_URL").expect("Did you set WS_ECHO_SERVER_URL?");
+
+        let ws = WebSocket::builder(ws_echo_server_url)
+            .binary_type(BinaryType::Blob)
+            .open()
+            .unwrap();
+        assert_eq!(ws.binary_type(), BinaryType::Blob);
+        let (mut sender, mut receiver) = ws.split();
+
+        spawn_local(async move {
+            sender.send(Message::Bytes(vec![1, 2, 3])).await.unwrap();
+        });
+
+        spawn_local(async move {
+            // ignore first message
+            // the echo-server uses it to send it's info in the first message
+            let _ = receiver.next().await;
+
+            assert_eq!(
+                receiver.next().await.unwrap().unwrap(),
+                Message::Bytes(vec![1, 2, 3])
+            );
+        });
+    }
 }
@@ -49,6 +49,12 @@ pub enum WebSocketError {
     ConnectionClose(CloseEvent),
     /// Message failed to send.
     MessageSendError(JsError),
+    /// A `Blob` message could not be read.
+    MessageReadError(JsError),
+    /// The server did not select any of the required sub-protocols.
+    ///
+    /// Contains the sub-protocols that were offered, in order of priority.
+    ProtocolNegotiationError(Vec<String>),
 }
 
 impl fmt::Display for WebSocketError {
@@ -61,6 +67,12 @@ impl fmt::Display for WebSocketError {
                 e.code, e.reason
             ),
             WebSocketError::MessageSendError(e) => write!(f, "{e}"),
+            WebSocketError::MessageReadError(e) => write!(f, "{e}"),
+            WebSocketError::ProtocolNegotiationError(protocols) => write!(
+                f,
+                "WebSocket server selected none of the offered protocols: {}",
+                protocols.join(", ")
+            ),
         }
     }
 }
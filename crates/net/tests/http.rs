@@ -1,4 +1,7 @@
-use gloo_net::http::Request;
+use gloo_net::http::{
+    send_beacon, BeaconError, BeaconTransport, ContentRange, Request, Response, BEACON_SIZE_LIMIT,
+};
+use gloo_net::Error;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen_test::*;
@@ -137,3 +140,99 @@ async fn query_preserve_duplicate_params() {
         .unwrap();
     assert_eq!(resp.url(), format!("{}/get?q=1&q=2", *HTTPBIN_URL));
 }
+
+#[wasm_bindgen_test]
+async fn error_for_status() {
+    let resp = Request::get(&format!("{}/status/404", *HTTPBIN_URL))
+        .send()
+        .await
+        .unwrap();
+    match resp.error_for_status().await {
+        Err(Error::StatusError(resp)) => assert_eq!(resp.status, 404),
+        other => panic!("expected a status error, got {other:?}"),
+    }
+}
+
+#[wasm_bindgen_test]
+async fn deserialize_error_snippet() {
+    #[derive(Deserialize, Debug)]
+    struct Payload {
+        #[allow(dead_code)]
+        data: String,
+    }
+
+    let resp = Response::builder()
+        .body(Some(r#"{"data": not a string}"#))
+        .unwrap();
+    match resp.json::<Payload>().await {
+        Err(Error::DeserializeError { snippet, .. }) => assert!(snippet.contains("not a string")),
+        other => panic!("expected a deserialize error, got {other:?}"),
+    }
+}
+
+#[wasm_bindgen_test]
+fn beacon() {
+    let transport = send_beacon(&format!("{}/post", *HTTPBIN_URL), "bye").unwrap();
+    assert_eq!(transport, BeaconTransport::SendBeacon);
+}
+
+#[wasm_bindgen_test]
+fn beacon_too_large() {
+    let body = vec![0; BEACON_SIZE_LIMIT + 1];
+    match send_beacon(&format!("{}/post", *HTTPBIN_URL), body) {
+        Err(BeaconError::TooLarge { size, limit }) => {
+            assert_eq!(size, limit + 1);
+        }
+        other => panic!("expected `TooLarge`, got {other:?}"),
+    }
+}
+
+#[wasm_bindgen_test]
+async fn raw_request_round_trip() {
+    let raw: web_sys::Request = Request::post("/path")
+        .header("X-Test", "1")
+        .cache(web_sys::RequestCache::NoStore)
+        .body("body")
+        .unwrap()
+        .into();
+    let request = Request::from(raw);
+    assert_eq!(request.method(), "POST");
+    assert_eq!(request.cache(), web_sys::RequestCache::NoStore);
+    assert_eq!(request.headers().get("X-Test"), Some("1".to_string()));
+    assert_eq!(request.as_ref().method(), "POST");
+
+    let copy = request.try_clone().unwrap();
+    assert_eq!(request.text().await.unwrap(), "body");
+    assert_eq!(copy.text().await.unwrap(), "body");
+    assert!(request.try_clone().is_err());
+}
+
+#[wasm_bindgen_test]
+async fn raw_response_round_trip() {
+    let response = Response::builder().status(201).body(Some("body")).unwrap();
+    let raw: web_sys::Response = response.try_clone().unwrap().into();
+    assert_eq!(raw.status(), 201);
+
+    let response = Response::from(raw);
+    assert_eq!(response.text().await.unwrap(), "body");
+    assert!(response.try_clone().is_err());
+}
+
+#[wasm_bindgen_test]
+async fn range_request() {
+    let resp = Request::get(&format!("{}/range/100", *HTTPBIN_URL))
+        .range(10..20)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 206);
+    assert_eq!(
+        resp.content_range(),
+        Some(ContentRange::Bytes {
+            start: 10,
+            end: 19,
+            complete_length: Some(100)
+        })
+    );
+    assert_eq!(resp.binary().await.unwrap().len(), 10);
+}
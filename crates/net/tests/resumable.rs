@@ -0,0 +1,70 @@
+#![cfg(feature = "resumable")]
+
+use gloo_net::http::{ResumableDownload, ResumableUpload};
+use once_cell::sync::Lazy;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+static HTTPBIN_URL: Lazy<&'static str> =
+    Lazy::new(|| option_env!("HTTPBIN_URL").expect("Did you set HTTPBIN_URL?"));
+
+#[wasm_bindgen_test]
+async fn download_in_chunks() {
+    let url = format!("{}/range/100", *HTTPBIN_URL);
+    let mut download = ResumableDownload::new("gloo-net-resumable", &url).chunk_size(30);
+    download.reset();
+
+    let mut offsets = Vec::new();
+    let mut body = Vec::new();
+    while let Some(chunk) = download.next_chunk().await.unwrap() {
+        offsets.push(chunk.offset());
+        body.extend_from_slice(chunk.bytes());
+    }
+    assert_eq!(offsets, [0, 30, 60, 90]);
+    assert_eq!(body.len(), 100);
+    assert_eq!(download.complete_length(), Some(100));
+    assert!(download.is_complete());
+}
+
+#[wasm_bindgen_test]
+async fn resume_download() {
+    let url = format!("{}/range/100", *HTTPBIN_URL);
+    let mut download = ResumableDownload::new("gloo-net-resume", &url).chunk_size(50);
+    download.reset();
+    download.next_chunk().await.unwrap().unwrap();
+    // The first chunk is only saved once the next one is requested.
+    assert_eq!(ResumableDownload::new("gloo-net-resume", &url).offset(), 0);
+    let second = download.next_chunk().await.unwrap().unwrap();
+    assert_eq!(second.offset(), 50);
+
+    let mut resumed = ResumableDownload::new("gloo-net-resume", &url).chunk_size(50);
+    assert_eq!(resumed.offset(), 50);
+    let chunk = resumed.next_chunk().await.unwrap().unwrap();
+    assert_eq!(chunk.offset(), 50);
+    resumed.reset();
+}
+
+#[wasm_bindgen_test]
+async fn upload_in_chunks() {
+    let url = format!("{}/put", *HTTPBIN_URL);
+    let blob =
+        web_sys::Blob::new_with_str_sequence(&js_sys::Array::of1(&"a".repeat(100).into())).unwrap();
+    let mut upload = ResumableUpload::new("gloo-net-upload", &url, blob.clone())
+        .chunk_size(30)
+        .concurrency(2);
+    upload.reset();
+
+    let mut reports = Vec::new();
+    upload
+        .upload(|uploaded, size| reports.push((uploaded, size)))
+        .await
+        .unwrap();
+    assert_eq!(reports.len(), 4);
+    assert_eq!(reports.last(), Some(&(100, 100)));
+    assert!(upload.is_complete());
+
+    let resumed = ResumableUpload::new("gloo-net-upload", &url, blob).chunk_size(30);
+    assert_eq!(resumed.uploaded(), 100);
+    upload.reset();
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "tracing")]
+
+use gloo_net::http::{Request, TraceParent};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+#[wasm_bindgen_test]
+fn traceparent_round_trip() {
+    let parent: TraceParent = TRACEPARENT.parse().unwrap();
+    assert_eq!(parent.trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(parent.parent_id(), "00f067aa0ba902b7");
+    assert!(parent.sampled());
+    assert_eq!(parent.to_string(), TRACEPARENT);
+}
+
+#[wasm_bindgen_test]
+fn traceparent_invalid() {
+    for invalid in [
+        "",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+        "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+    ] {
+        assert!(invalid.parse::<TraceParent>().is_err(), "{invalid}");
+    }
+}
+
+#[wasm_bindgen_test]
+fn traceparent_header() {
+    let parent: TraceParent = TRACEPARENT.parse().unwrap();
+    let request = Request::get("/").traceparent(&parent).build().unwrap();
+    let sent: TraceParent = request
+        .headers()
+        .get("traceparent")
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(sent.trace_id(), parent.trace_id());
+    assert_ne!(sent.parent_id(), parent.parent_id());
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "transport")]
+
+use futures::{SinkExt, StreamExt};
+use gloo_net::transport::{Connector, TransportError, TransportKind};
+use gloo_net::websocket::Message;
+use once_cell::sync::Lazy;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+static HTTPBIN_URL: Lazy<&'static str> =
+    Lazy::new(|| option_env!("HTTPBIN_URL").expect("Did you set HTTPBIN_URL?"));
+
+#[wasm_bindgen_test]
+async fn falls_back_to_long_polling() {
+    let mut connection = Connector::new()
+        .websocket("ws://127.0.0.1:1")
+        .long_polling(
+            &format!("{}/get", *HTTPBIN_URL),
+            &format!("{}/post", *HTTPBIN_URL),
+        )
+        .connect()
+        .await
+        .unwrap();
+    assert_eq!(connection.kind(), TransportKind::LongPolling);
+
+    connection
+        .send(Message::Text("hello".to_string()))
+        .await
+        .unwrap();
+    match connection.next().await {
+        Some(Ok(Message::Text(body))) => assert!(body.contains("/get")),
+        other => panic!("expected a text message, got {other:?}"),
+    }
+}
+
+#[wasm_bindgen_test]
+async fn negotiation_error() {
+    let result = Connector::new()
+        .websocket("ws://127.0.0.1:1")
+        .connect()
+        .await;
+    match result {
+        Err(TransportError::NegotiationError(errors)) => assert_eq!(errors.len(), 1),
+        other => panic!("expected a negotiation error, got {other:?}"),
+    }
+}
@@ -0,0 +1,15 @@
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod storage_key;
+
+use storage_key::storage_key_impl;
+
+#[proc_macro_derive(StorageKey, attributes(storage))]
+pub fn storage_key(item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as DeriveInput);
+
+    storage_key_impl(item)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
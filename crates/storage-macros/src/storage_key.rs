@@ -0,0 +1,76 @@
+use proc_macro2::{Span, TokenStream};
+use proc_macro_crate::{crate_name, FoundCrate};
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Ident, LitStr};
+
+pub fn storage_key_impl(item: DeriveInput) -> syn::Result<TokenStream> {
+    let mut key = None;
+    let mut session = false;
+
+    for attr in item
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("storage"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                let value: LitStr = meta.value()?.parse()?;
+                if value.value().is_empty() {
+                    return Err(syn::Error::new_spanned(value, "key must not be empty"));
+                }
+                key = Some(value);
+                Ok(())
+            } else if meta.path.is_ident("session") {
+                session = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `key = \"...\"` or `session`"))
+            }
+        })?;
+    }
+
+    let ident = &item.ident;
+    let key = key.unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+    let crate_name = storage_crate_name();
+    let storage: syn::Path = if session {
+        parse_quote!(#crate_name::SessionStorage)
+    } else {
+        parse_quote!(#crate_name::LocalStorage)
+    };
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #crate_name::StorageKey for #ident #ty_generics #where_clause {
+            type Storage = #storage;
+            const KEY: &'static str = #key;
+        }
+    })
+}
+
+// Sometimes users use gloo_storage directly, sometimes it is imported via gloo.
+// We inspect Cargo.toml to find out.
+fn storage_crate_name() -> syn::Path {
+    if let Ok(m) = crate_name("gloo-storage") {
+        return match m {
+            FoundCrate::Itself => parse_quote!(gloo_storage),
+            FoundCrate::Name(m) => {
+                let crate_name = Ident::new(&m, Span::mixed_site());
+
+                parse_quote!(#crate_name)
+            }
+        };
+    }
+
+    if let Ok(m) = crate_name("gloo") {
+        return match m {
+            FoundCrate::Itself => parse_quote!(gloo::storage),
+            FoundCrate::Name(m) => {
+                let crate_name = Ident::new(&m, Span::mixed_site());
+
+                parse_quote!(#crate_name::storage)
+            }
+        };
+    }
+
+    panic!("failed to find crate!")
+}
@@ -0,0 +1,8 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+#[test]
+fn macro_tests() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/storage_key/*-fail.rs");
+    t.pass("tests/storage_key/*-pass.rs");
+}
@@ -0,0 +1,20 @@
+use gloo::storage::{LocalStorage, SessionStorage, StorageKey};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, StorageKey)]
+struct Counter(u32);
+
+#[derive(Serialize, Deserialize, StorageKey)]
+#[storage(key = "app.settings", session)]
+struct Settings {
+    dark_mode: bool,
+}
+
+fn assert_storage<T: StorageKey<Storage = S>, S>() {}
+
+fn main() {
+    assert_eq!(Counter::KEY, "Counter");
+    assert_eq!(Settings::KEY, "app.settings");
+    assert_storage::<Counter, LocalStorage>();
+    assert_storage::<Settings, SessionStorage>();
+}
@@ -0,0 +1,8 @@
+use gloo::storage::StorageKey;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, StorageKey)]
+#[storage(name = "counter")]
+struct Counter(u32);
+
+fn main() {}
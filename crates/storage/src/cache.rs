@@ -0,0 +1,212 @@
+//! An async key-value store backed by the
+//! [Cache API](https://developer.mozilla.org/en-US/docs/Web/API/Cache).
+//!
+//! The Cache API shares the origin's quota with IndexedDB, which is far larger than the few
+//! megabytes allowed for [`LocalStorage`](crate::LocalStorage), so it is well suited to large
+//! binary blobs and whole HTTP responses. Since every operation is asynchronous,
+//! [`CacheStorage`] mirrors the methods of the [`Storage`](crate::Storage) trait as `async fn`s
+//! instead of implementing it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use gloo_storage::cache::CacheStorage;
+//!
+//! # async fn no_run() -> gloo_storage::Result<()> {
+//! let cache = CacheStorage::open("assets").await?;
+//! cache.set("manifest", &vec!["app.wasm", "app.js"]).await?;
+//! cache.set_bytes("app.wasm", &[0x00, 0x61, 0x73, 0x6d]).await?;
+//!
+//! let manifest: Vec<String> = cache.get("manifest").await?;
+//! for key in manifest {
+//!     let bytes = cache.get_bytes(&key).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use js_sys::{Array, Reflect, Uint8Array};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Cache, Request, Response, ResponseInit};
+
+use crate::errors::{rejection_to_error, StorageError};
+use crate::Result;
+
+/// Keys are stored as requests for URLs under this base, so that they never collide with
+/// responses cached by the application or a service worker.
+const KEY_BASE: &str = "https://gloo-storage.invalid/";
+
+async fn resolve(promise: js_sys::Promise) -> Result<JsValue> {
+    JsFuture::from(promise).await.map_err(rejection_to_error)
+}
+
+fn caches() -> Result<web_sys::CacheStorage> {
+    let caches = Reflect::get(&js_sys::global(), &JsValue::from_str("caches"))
+        .map_err(rejection_to_error)?;
+    if caches.is_undefined() || caches.is_null() {
+        // `caches` is only exposed to secure contexts.
+        let error = js_sys::Error::new("the Cache API is not available");
+        error.set_name("NotSupportedError");
+        return Err(rejection_to_error(error.into()));
+    }
+    Ok(caches.unchecked_into())
+}
+
+fn key_url(key: &str) -> String {
+    format!(
+        "{}{}",
+        KEY_BASE,
+        String::from(js_sys::encode_uri_component(key))
+    )
+}
+
+fn url_key(url: &str) -> Option<String> {
+    let key = url.strip_prefix(KEY_BASE)?;
+    js_sys::decode_uri_component(key).ok().map(String::from)
+}
+
+/// A named cache used as an async key-value store.
+///
+/// Values are stored as JSON, or as raw bytes with [`set_bytes`](Self::set_bytes).
+#[derive(Debug, Clone)]
+pub struct CacheStorage {
+    cache: Cache,
+}
+
+impl CacheStorage {
+    /// Opens the cache `name`, creating it if it does not exist.
+    pub async fn open(name: &str) -> Result<Self> {
+        let cache = resolve(caches()?.open(name)).await?;
+        Ok(Self {
+            cache: cache.unchecked_into(),
+        })
+    }
+
+    /// Deletes the cache `name` and everything stored in it.
+    ///
+    /// Returns `false` if there was no such cache.
+    pub async fn delete_cache(name: &str) -> Result<bool> {
+        let deleted = resolve(caches()?.delete(name)).await?;
+        Ok(deleted.is_truthy())
+    }
+
+    /// Get the raw [`web_sys::Cache`] instance
+    pub fn raw(&self) -> &Cache {
+        &self.cache
+    }
+
+    /// Get the value for the specified key
+    pub async fn get<T>(&self, key: impl AsRef<str>) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let response = self.response(key.as_ref()).await?;
+        let text = resolve(response.text().map_err(rejection_to_error)?).await?;
+        let text = text.as_string().unwrap_throw();
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// Get the bytes stored for the specified key
+    pub async fn get_bytes(&self, key: impl AsRef<str>) -> Result<Vec<u8>> {
+        let response = self.response(key.as_ref()).await?;
+        let buffer = resolve(response.array_buffer().map_err(rejection_to_error)?).await?;
+        Ok(Uint8Array::new(&buffer).to_vec())
+    }
+
+    /// Get the response stored for the specified key
+    ///
+    /// This returns the values stored with any of the `set` methods, not only with
+    /// [`set_response`](Self::set_response).
+    pub async fn get_response(&self, key: impl AsRef<str>) -> Result<Response> {
+        self.response(key.as_ref()).await
+    }
+
+    /// Insert a value for the specified key
+    pub async fn set<T>(&self, key: impl AsRef<str>, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = serde_json::to_string(value)?;
+        let response = Response::new_with_opt_str_and_init(Some(&value), &init("application/json"))
+            .map_err(rejection_to_error)?;
+        self.set_response(key, &response).await
+    }
+
+    /// Insert bytes for the specified key
+    pub async fn set_bytes(&self, key: impl AsRef<str>, value: &[u8]) -> Result<()> {
+        let value = Uint8Array::from(value);
+        let response = Response::new_with_opt_buffer_source_and_init(
+            Some(&value),
+            &init("application/octet-stream"),
+        )
+        .map_err(rejection_to_error)?;
+        self.set_response(key, &response).await
+    }
+
+    /// Insert a response for the specified key
+    ///
+    /// The body of `response` is consumed. Responses with a status of `206 Partial Content`
+    /// are rejected by the browser.
+    pub async fn set_response(&self, key: impl AsRef<str>, response: &Response) -> Result<()> {
+        let request = request(key.as_ref())?;
+        resolve(self.cache.put_with_request(&request, response)).await?;
+        Ok(())
+    }
+
+    /// Remove a key and its stored value
+    ///
+    /// Returns `false` if the key was not found.
+    pub async fn delete(&self, key: impl AsRef<str>) -> Result<bool> {
+        let request = request(key.as_ref())?;
+        let deleted = resolve(self.cache.delete_with_request(&request)).await?;
+        Ok(deleted.is_truthy())
+    }
+
+    /// Get all the stored keys
+    pub async fn keys(&self) -> Result<Vec<String>> {
+        let requests: Array = resolve(self.cache.keys()).await?.unchecked_into();
+        Ok(requests
+            .iter()
+            .filter_map(|request| url_key(&request.unchecked_into::<Request>().url()))
+            .collect())
+    }
+
+    /// Get the number of items stored
+    pub async fn length(&self) -> Result<u32> {
+        Ok(self.keys().await?.len() as u32)
+    }
+
+    /// Remove all the stored data
+    pub async fn clear(&self) -> Result<()> {
+        for key in self.keys().await? {
+            self.delete(key).await?;
+        }
+        Ok(())
+    }
+
+    async fn response(&self, key: &str) -> Result<Response> {
+        let request = request(key)?;
+        let response = resolve(self.cache.match_with_request(&request)).await?;
+        if response.is_undefined() {
+            return Err(StorageError::KeyNotFound(key.to_string()));
+        }
+        Ok(response.unchecked_into())
+    }
+}
+
+fn request(key: &str) -> Result<Request> {
+    Request::new_with_str(&key_url(key)).map_err(rejection_to_error)
+}
+
+fn init(content_type: &str) -> ResponseInit {
+    let headers = Array::of1(&Array::of2(
+        &JsValue::from_str("content-type"),
+        &JsValue::from_str(content_type),
+    ));
+    let mut init = ResponseInit::new();
+    init.headers(&headers);
+    init
+}
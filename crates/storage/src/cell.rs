@@ -0,0 +1,229 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll};
+
+use futures_channel::mpsc;
+use futures_core::Stream;
+use gloo_events::EventListener;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::StorageEvent;
+
+use crate::{ttl, Json, LocalStorage, Result, Storage};
+
+type Callback<T> = Rc<dyn Fn(&T)>;
+
+struct Inner<T> {
+    key: String,
+    value: RefCell<T>,
+    next_id: Cell<usize>,
+    callbacks: RefCell<Vec<(usize, Callback<T>)>>,
+    senders: RefCell<Vec<mpsc::UnboundedSender<T>>>,
+    _listener: EventListener,
+}
+
+impl<T: Clone> Inner<T> {
+    fn replace(&self, value: T) {
+        *self.value.borrow_mut() = value.clone();
+        // Snapshot the callbacks, so that they can subscribe or unsubscribe while being called.
+        let callbacks: Vec<_> = self
+            .callbacks
+            .borrow()
+            .iter()
+            .map(|(_, callback)| Rc::clone(callback))
+            .collect();
+        for callback in callbacks {
+            callback(&value);
+        }
+        self.senders
+            .borrow_mut()
+            .retain(|sender| sender.unbounded_send(value.clone()).is_ok());
+    }
+}
+
+/// A value persisted in a [`Storage`] that notifies its subscribers when it changes.
+///
+/// The value is loaded when the cell is created, falling back to [`Default::default`] if it is
+/// missing or can't be deserialized. It is then kept in sync with the changes made through
+/// this cell, or any of its clones, and with the changes made by other documents, e.g. other
+/// tabs. Other cells created for the same key in the same document are not kept in sync, so
+/// share a cell by cloning it.
+///
+/// ```rust
+/// use gloo_storage::StorageCell;
+///
+/// # fn no_run() {
+/// let count = StorageCell::<u32>::new("count");
+/// let _subscription = count.subscribe(|count| {
+///     // re-render with `count`
+/// });
+/// count.update(|count| *count += 1).unwrap();
+/// # }
+/// ```
+///
+/// Other documents can only be observed through a web storage, see [`Storage::try_raw`], so a
+/// cell using [`MemoryStorage`](crate::MemoryStorage) only sees the changes made through it.
+pub struct StorageCell<T, S = LocalStorage> {
+    inner: Rc<Inner<T>>,
+    _marker: PhantomData<S>,
+}
+
+impl<T, S> StorageCell<T, S>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Default + Clone + 'static,
+    S: Storage,
+{
+    /// Load the value stored for `key`
+    pub fn new(key: impl Into<String>) -> Self {
+        let key = key.into();
+        let storage = S::try_raw().ok();
+        let window = web_sys::window().expect_throw("no window");
+        let inner = Rc::new_cyclic(|weak: &Weak<Inner<T>>| {
+            let weak = weak.clone();
+            let listener = EventListener::new(&window, "storage", move |event| {
+                let inner = match weak.upgrade() {
+                    Some(inner) => inner,
+                    None => return,
+                };
+                let event = event.unchecked_ref::<StorageEvent>();
+                if storage.is_none() || event.storage_area() != storage {
+                    return;
+                }
+                match event.key() {
+                    Some(changed) if changed == inner.key => {}
+                    // `clear()` was called.
+                    None => {}
+                    Some(_) => return,
+                }
+                let value = event
+                    .new_value()
+                    .and_then(|value| ttl::from_str::<Json, T>(&value).ok().flatten())
+                    .unwrap_or_default();
+                inner.replace(value);
+            });
+            Inner {
+                value: RefCell::new(S::get(&key).unwrap_or_default()),
+                key,
+                next_id: Cell::new(0),
+                callbacks: RefCell::new(Vec::new()),
+                senders: RefCell::new(Vec::new()),
+                _listener: listener,
+            }
+        });
+
+        Self {
+            inner,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The key the value is stored under
+    pub fn key(&self) -> &str {
+        &self.inner.key
+    }
+
+    /// Get the current value
+    pub fn get(&self) -> T {
+        self.inner.value.borrow().clone()
+    }
+
+    /// Store a new value and notify the subscribers
+    ///
+    /// If storing fails, the value is left unchanged and nobody is notified.
+    pub fn set(&self, value: T) -> Result<()> {
+        S::set(&self.inner.key, &value)?;
+        self.inner.replace(value);
+        Ok(())
+    }
+
+    /// Modify the value in place, then store it and notify the subscribers
+    pub fn update(&self, f: impl FnOnce(&mut T)) -> Result<()> {
+        let mut value = self.get();
+        f(&mut value);
+        self.set(value)
+    }
+
+    /// Call `callback` with the new value whenever it changes, until the returned
+    /// [`StorageCellSubscription`] is dropped
+    pub fn subscribe(&self, callback: impl Fn(&T) + 'static) -> StorageCellSubscription<T> {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
+        self.inner
+            .callbacks
+            .borrow_mut()
+            .push((id, Rc::new(callback)));
+        StorageCellSubscription {
+            inner: Rc::downgrade(&self.inner),
+            id,
+        }
+    }
+
+    /// Get a [`Stream`] of the new values
+    pub fn changes(&self) -> StorageCellStream<T> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.inner.senders.borrow_mut().push(sender);
+        StorageCellStream { receiver }
+    }
+}
+
+impl<T, S> Clone for StorageCell<T, S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: fmt::Debug, S> fmt::Debug for StorageCell<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageCell")
+            .field("storage", &std::any::type_name::<S>())
+            .field("key", &self.inner.key)
+            .field("value", &self.inner.value)
+            .finish()
+    }
+}
+
+/// Keeps a callback registered with [`StorageCell::subscribe`] until it is dropped.
+#[must_use = "the callback is unsubscribed when this is dropped"]
+pub struct StorageCellSubscription<T> {
+    inner: Weak<Inner<T>>,
+    id: usize,
+}
+
+impl<T> Drop for StorageCellSubscription<T> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.upgrade() {
+            inner
+                .callbacks
+                .borrow_mut()
+                .retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+
+impl<T> fmt::Debug for StorageCellSubscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageCellSubscription")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+/// A [`Stream`] of the values of a [`StorageCell`], returned by [`StorageCell::changes`].
+#[derive(Debug)]
+pub struct StorageCellStream<T> {
+    receiver: mpsc::UnboundedReceiver<T>,
+}
+
+impl<T> Stream for StorageCellStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// Converts values to and from the strings kept in a [`Storage`](crate::Storage).
+///
+/// Values are stored as [`Json`] by default. Another codec can be used for a single call with
+/// [`Storage::get_with`](crate::Storage::get_with) and
+/// [`Storage::set_with`](crate::Storage::set_with), or for every call made through a handle
+/// returned by [`Storage::with_codec`](crate::Storage::with_codec).
+///
+/// Codecs for binary formats have to encode their output as text, e.g. as base64. For instance,
+/// a CBOR codec using [`ciborium`](https://docs.rs/ciborium) and
+/// [`gloo_utils::encoding`] may look like this:
+///
+/// ```rust,ignore
+/// use gloo_storage::errors::StorageError;
+/// use gloo_storage::Codec;
+/// use gloo_utils::encoding;
+///
+/// struct Cbor;
+///
+/// impl Codec for Cbor {
+///     fn encode<T>(value: &T) -> gloo_storage::Result<String>
+///     where
+///         T: Serialize + ?Sized,
+///     {
+///         let mut bytes = Vec::new();
+///         ciborium::into_writer(value, &mut bytes)
+///             .map_err(|e| StorageError::CodecError(e.into()))?;
+///         Ok(encoding::encode_base64(&bytes))
+///     }
+///
+///     fn decode<T>(item: &str) -> gloo_storage::Result<T>
+///     where
+///         T: for<'de> Deserialize<'de>,
+///     {
+///         let bytes =
+///             encoding::decode_base64(item).map_err(|e| StorageError::CodecError(e.into()))?;
+///         ciborium::from_reader(bytes.as_slice()).map_err(|e| StorageError::CodecError(e.into()))
+///     }
+/// }
+/// ```
+pub trait Codec {
+    /// Convert a value to the string to store
+    fn encode<T>(value: &T) -> Result<String>
+    where
+        T: Serialize + ?Sized;
+
+    /// Convert a stored string back to a value
+    fn decode<T>(item: &str) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>;
+}
+
+/// Stores values as JSON with [`serde_json`].
+#[derive(Debug)]
+pub struct Json;
+
+impl Codec for Json {
+    fn encode<T>(value: &T) -> Result<String>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(serde_json::to_string(value)?)
+    }
+
+    fn decode<T>(item: &str) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        Ok(serde_json::from_str(item)?)
+    }
+}
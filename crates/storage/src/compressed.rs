@@ -0,0 +1,159 @@
+//! Transparent compression of stored values.
+//!
+//! Values are serialized to JSON and, when that makes them smaller, compressed with the
+//! [Compression Streams API](https://developer.mozilla.org/en-US/docs/Web/API/Compression_Streams_API)
+//! and stored as base64. Small values, and all values in browsers without `CompressionStream`,
+//! are stored as plain JSON, so they can still be read with [`Storage::get`]. Browsers ship
+//! `DecompressionStream` along with `CompressionStream`, so compressed values can be read
+//! wherever they were written; elsewhere, reading them fails with
+//! [`StorageError::Unsupported`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use gloo_storage::compressed::Compressed;
+//! use gloo_storage::LocalStorage;
+//!
+//! # async fn no_run(articles: Vec<String>) -> gloo_storage::Result<()> {
+//! Compressed::<LocalStorage>::set("articles", &articles).await?;
+//! let articles: Vec<String> = Compressed::<LocalStorage>::get("articles").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use gloo_utils::encoding;
+use js_sys::{Array, Reflect, Uint8Array};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, ReadableWritablePair, Response};
+
+use crate::errors::{js_to_error, rejection_to_error, StorageError};
+use crate::{Result, Storage};
+
+/// Values whose JSON is shorter than this are never compressed.
+const MIN_COMPRESSED_LENGTH: usize = 256;
+
+const COMPRESSED_PREFIX: &str = "{\"__gloo_deflate\":";
+
+#[derive(Serialize, Deserialize)]
+struct CompressedValue {
+    #[serde(rename = "__gloo_deflate")]
+    deflate: String,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // `CompressionStream` and `DecompressionStream` are still unstable in `web-sys`. Both are
+    // transform streams, so they are used as a `ReadableWritablePair`.
+    #[wasm_bindgen(js_name = CompressionStream)]
+    type CompressionStream;
+
+    #[wasm_bindgen(constructor, js_class = "CompressionStream", catch)]
+    fn new(format: &str) -> std::result::Result<CompressionStream, JsValue>;
+
+    #[wasm_bindgen(js_name = DecompressionStream)]
+    type DecompressionStream;
+
+    #[wasm_bindgen(constructor, js_class = "DecompressionStream", catch)]
+    fn new(format: &str) -> std::result::Result<DecompressionStream, JsValue>;
+}
+
+fn supported(name: &str) -> bool {
+    Reflect::has(&js_sys::global(), &JsValue::from_str(name)).unwrap_or(false)
+}
+
+/// Pipes `data` through a transform stream and collects the output.
+async fn transform(data: &[u8], transform: &ReadableWritablePair) -> Result<Vec<u8>> {
+    let blob = Blob::new_with_u8_array_sequence(&Array::of1(&Uint8Array::from(data)))
+        .map_err(js_to_error)?;
+    let stream = blob.stream().pipe_through(transform);
+    let response = Response::new_with_opt_readable_stream(Some(&stream)).map_err(js_to_error)?;
+    let buffer = response.array_buffer().map_err(js_to_error)?;
+    let buffer = JsFuture::from(buffer).await.map_err(rejection_to_error)?;
+    Ok(Uint8Array::new(&buffer).to_vec())
+}
+
+async fn compress(data: &[u8]) -> Result<Vec<u8>> {
+    let stream = CompressionStream::new("deflate").map_err(rejection_to_error)?;
+    transform(data, stream.unchecked_ref()).await
+}
+
+async fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+    if !supported("DecompressionStream") {
+        return Err(StorageError::Unsupported(
+            "DecompressionStream is not available".to_string(),
+        ));
+    }
+    let stream = DecompressionStream::new("deflate").map_err(rejection_to_error)?;
+    transform(data, stream.unchecked_ref()).await
+}
+
+fn malformed(msg: impl fmt::Display) -> StorageError {
+    StorageError::SerdeError(serde::de::Error::custom(format!(
+        "malformed compressed value: {}",
+        msg
+    )))
+}
+
+/// Compresses the values stored in a [`Storage`].
+///
+/// Compression is asynchronous, so values are stored and retrieved with the `async` functions
+/// of this type rather than with the [`Storage`] trait.
+pub struct Compressed<S> {
+    _marker: PhantomData<S>,
+}
+
+impl<S: Storage> Compressed<S> {
+    /// Get and decompress the value for the specified key
+    ///
+    /// Values stored with [`Storage::set`] are read as well.
+    pub async fn get<T>(key: impl AsRef<str>) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let key = key.as_ref();
+        let item = S::get_item(key)?.ok_or_else(|| StorageError::KeyNotFound(key.to_string()))?;
+        if !item.starts_with(COMPRESSED_PREFIX) {
+            return S::get(key);
+        }
+        let item: CompressedValue = serde_json::from_str(&item)?;
+        let data = encoding::decode_base64(&item.deflate).map_err(malformed)?;
+        let data = decompress(&data).await?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// Compress and insert a value for the specified key
+    pub async fn set<T>(key: impl AsRef<str>, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let key = key.as_ref();
+        let mut item = serde_json::to_string(&value)?;
+        if item.len() >= MIN_COMPRESSED_LENGTH && supported("CompressionStream") {
+            let deflate = encoding::encode_base64(&compress(item.as_bytes()).await?);
+            // The quotes and closing brace add 3 more bytes.
+            if COMPRESSED_PREFIX.len() + deflate.len() + 3 < item.len() {
+                item = serde_json::to_string(&CompressedValue { deflate })?;
+            }
+        }
+        S::set_item(key, &item)
+    }
+
+    /// Remove a key and it's stored value
+    pub fn delete(key: impl AsRef<str>) {
+        S::delete(key)
+    }
+}
+
+impl<S> fmt::Debug for Compressed<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Compressed")
+            .field("storage", &std::any::type_name::<S>())
+            .finish()
+    }
+}
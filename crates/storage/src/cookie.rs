@@ -0,0 +1,264 @@
+//! Storage in cookies, for values the server needs to read.
+//!
+//! Names and values are percent-encoded, and values are stored as JSON like with the other
+//! backends. Browsers limit cookies to about 4 KiB each, and send them along with every request
+//! to the server, so they are only suited to small values.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use gloo_storage::cookie::{CookieOptions, CookieStorage, SameSite};
+//! use gloo_storage::Storage;
+//!
+//! # fn no_run() -> gloo_storage::Result<()> {
+//! // session cookie for the whole site
+//! CookieStorage::set("theme", "dark")?;
+//!
+//! let options = CookieOptions::new()
+//!     .max_age(Duration::from_secs(30 * 24 * 60 * 60))
+//!     .same_site(SameSite::Strict)
+//!     .secure(true);
+//! CookieStorage::set_with_options("locale", "en-GB", &options)?;
+//!
+//! let locale: String = CookieStorage::get("locale")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use serde::Serialize;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlDocument;
+
+use crate::errors::{js_to_error, StorageError};
+use crate::{Codec, Json, Result, Storage};
+
+/// The `SameSite` attribute of a cookie, which controls whether it is sent with cross-site
+/// requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSite {
+    /// Only sent with same-site requests
+    Strict,
+    /// Also sent when navigating to the site from another one; the default of most browsers
+    Lax,
+    /// Sent with all requests; requires [`secure`](CookieOptions::secure)
+    None,
+}
+
+impl SameSite {
+    fn as_str(self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
+    }
+}
+
+/// The attributes of a cookie.
+///
+/// The default is a session cookie with `Path=/` and `SameSite=Lax`, which is what
+/// [`CookieStorage::set`](Storage::set) uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieOptions {
+    path: Option<String>,
+    domain: Option<String>,
+    max_age: Option<Duration>,
+    same_site: Option<SameSite>,
+    secure: bool,
+}
+
+impl Default for CookieOptions {
+    fn default() -> Self {
+        Self {
+            path: Some("/".to_string()),
+            domain: None,
+            max_age: None,
+            same_site: Some(SameSite::Lax),
+            secure: false,
+        }
+    }
+}
+
+impl CookieOptions {
+    /// Create the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only send the cookie for the URLs under `path`
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Send the cookie to `domain` and its subdomains, instead of only the current host
+    pub fn domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// Keep the cookie for `max_age`, instead of until the browser is closed
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Set the `SameSite` attribute
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+
+    /// Only send the cookie over HTTPS
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    fn cookie(&self, name: &str, value: &str) -> String {
+        let mut cookie = format!("{}={}", encode(name), encode(value));
+        if let Some(path) = &self.path {
+            let _ = write!(cookie, "; Path={}", path);
+        }
+        if let Some(domain) = &self.domain {
+            let _ = write!(cookie, "; Domain={}", domain);
+        }
+        if let Some(max_age) = self.max_age {
+            let _ = write!(cookie, "; Max-Age={}", max_age.as_secs());
+        }
+        if let Some(same_site) = self.same_site {
+            let _ = write!(cookie, "; SameSite={}", same_site.as_str());
+        }
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+        cookie
+    }
+}
+
+fn encode(s: &str) -> String {
+    js_sys::encode_uri_component(s).into()
+}
+
+fn decode(s: &str) -> String {
+    // Cookies set by the server may not be encoded.
+    js_sys::decode_uri_component(s)
+        .map(String::from)
+        .unwrap_or_else(|_| s.to_string())
+}
+
+fn document() -> HtmlDocument {
+    gloo_utils::document().unchecked_into()
+}
+
+/// The cookies visible to the document, in the order the browser lists them.
+fn cookies() -> Vec<(String, String)> {
+    let cookies = document().cookie().unwrap_or_default();
+    cookies
+        .split(';')
+        .filter_map(|cookie| {
+            let (name, value) = cookie.trim().split_once('=')?;
+            Some((decode(name), decode(value)))
+        })
+        .collect()
+}
+
+/// Provides the [`Storage`] API over `document.cookie`
+///
+/// Cookies are written with the default [`CookieOptions`], unless
+/// [`set_with_options`](Self::set_with_options) is used. Cookies can only be deleted with the
+/// same path and domain as they were set with, so [`delete`](Storage::delete) and
+/// [`clear`](Storage::clear) only remove the cookies set with the default ones; use
+/// [`delete_with_options`](Self::delete_with_options) for the others. `HttpOnly` cookies are
+/// invisible to this API.
+///
+/// [`try_raw`](Storage::try_raw) and [`subscribe`](Storage::subscribe) need a web storage and
+/// return [`StorageError::Unsupported`] when called on `CookieStorage`, while
+/// [`raw`](Storage::raw) panics.
+#[derive(Debug)]
+pub struct CookieStorage;
+
+impl CookieStorage {
+    /// Insert a value for the specified key, with the given cookie attributes
+    pub fn set_with_options<T>(
+        key: impl AsRef<str>,
+        value: T,
+        options: &CookieOptions,
+    ) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let key = key.as_ref();
+        let value = Json::encode(&value)?;
+        Self::set_raw(key, &value, options)
+    }
+
+    /// Remove a cookie set with the given path and domain
+    pub fn delete_with_options(key: impl AsRef<str>, options: &CookieOptions) {
+        let options = options.clone().max_age(Duration::ZERO);
+        let _ = document().set_cookie(&options.cookie(key.as_ref(), ""));
+    }
+
+    fn set_raw(key: &str, value: &str, options: &CookieOptions) -> Result<()> {
+        document()
+            .set_cookie(&options.cookie(key, value))
+            .map_err(js_to_error)?;
+        // Browsers silently drop the cookies they refuse, e.g. oversized ones or `Secure` ones
+        // on plain HTTP.
+        if Self::get_item(key)?.as_deref() != Some(value) && options.max_age != Some(Duration::ZERO)
+        {
+            let error = js_sys::Error::new(&format!("the cookie {} was rejected", key));
+            error.set_name("NotAllowedError");
+            return Err(js_to_error(error.into()));
+        }
+        Ok(())
+    }
+}
+
+impl Storage for CookieStorage {
+    fn raw() -> web_sys::Storage {
+        panic!("CookieStorage is not backed by a web_sys::Storage")
+    }
+
+    fn try_raw() -> Result<web_sys::Storage> {
+        Err(StorageError::Unsupported(
+            "CookieStorage is not backed by a web_sys::Storage".to_string(),
+        ))
+    }
+
+    fn get_item(key: &str) -> Result<Option<String>> {
+        Ok(cookies()
+            .into_iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value))
+    }
+
+    fn set_item(key: &str, value: &str) -> Result<()> {
+        Self::set_raw(key, value, &CookieOptions::default())
+    }
+
+    fn key(index: u32) -> Result<Option<String>> {
+        Ok(cookies()
+            .into_iter()
+            .nth(index as usize)
+            .map(|(name, _)| name))
+    }
+
+    fn delete(key: impl AsRef<str>) {
+        Self::delete_with_options(key, &CookieOptions::default())
+    }
+
+    fn clear() {
+        for (name, _) in cookies() {
+            Self::delete(name);
+        }
+    }
+
+    fn length() -> u32 {
+        cookies().len() as u32
+    }
+}
@@ -0,0 +1,199 @@
+//! Encryption of stored values with the
+//! [Web Crypto API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Crypto_API).
+//!
+//! Values are serialized to JSON, encrypted with AES-GCM and stored as base64 in any
+//! [`Storage`]. Each value gets a fresh random IV and is bound to its key, so an encrypted value
+//! copied to another key fails to decrypt instead of being silently accepted.
+//!
+//! # Example
+//!
+//! ```rust
+//! use gloo_storage::encrypted::EncryptedStorage;
+//! use gloo_storage::LocalStorage;
+//!
+//! # async fn no_run(password: &str, salt: &[u8]) -> gloo_storage::Result<()> {
+//! let storage = EncryptedStorage::<LocalStorage>::from_password(password, salt, 600_000).await?;
+//! storage.set("token", "secret").await?;
+//! let token: String = storage.get("token").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use gloo_utils::encoding;
+use js_sys::{Array, Reflect, Uint8Array};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AesDerivedKeyParams, AesGcmParams, CryptoKey, Pbkdf2Params, SubtleCrypto};
+
+use crate::errors::{rejection_to_error, StorageError};
+use crate::{Result, Storage};
+
+/// The length of the AES-GCM IV, prepended to the ciphertext.
+const IV_LENGTH: usize = 12;
+
+fn crypto() -> Result<web_sys::Crypto> {
+    let crypto = Reflect::get(&js_sys::global(), &JsValue::from_str("crypto"))
+        .map_err(rejection_to_error)?;
+    if crypto.is_undefined() || crypto.is_null() {
+        let error = js_sys::Error::new("the Web Crypto API is not available");
+        error.set_name("NotSupportedError");
+        return Err(rejection_to_error(error.into()));
+    }
+    Ok(crypto.unchecked_into())
+}
+
+fn subtle() -> Result<SubtleCrypto> {
+    // `crypto.subtle` is only exposed to secure contexts.
+    let subtle = crypto()?.subtle();
+    if subtle.is_undefined() {
+        let error = js_sys::Error::new("the Web Crypto API is not available");
+        error.set_name("NotSupportedError");
+        return Err(rejection_to_error(error.into()));
+    }
+    Ok(subtle)
+}
+
+async fn resolve(promise: std::result::Result<js_sys::Promise, JsValue>) -> Result<JsValue> {
+    JsFuture::from(promise.map_err(rejection_to_error)?)
+        .await
+        .map_err(rejection_to_error)
+}
+
+fn malformed(msg: impl fmt::Display) -> StorageError {
+    StorageError::SerdeError(serde::de::Error::custom(format!(
+        "malformed encrypted value: {}",
+        msg
+    )))
+}
+
+fn params(iv: &[u8], key: &str) -> AesGcmParams {
+    let mut params = AesGcmParams::new("AES-GCM", &Uint8Array::from(iv));
+    params.additional_data(&Uint8Array::from(key.as_bytes()));
+    params
+}
+
+/// A [`Storage`] whose values are encrypted with an AES-GCM [`CryptoKey`].
+///
+/// Since the Web Crypto API is asynchronous, the methods to get and set values are `async`.
+pub struct EncryptedStorage<S> {
+    key: CryptoKey,
+    _marker: PhantomData<S>,
+}
+
+impl<S: Storage> EncryptedStorage<S> {
+    /// Encrypt values with `key`, which must be an AES-GCM key usable for both encryption and
+    /// decryption
+    pub fn new(key: CryptoKey) -> Self {
+        Self {
+            key,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Encrypt values with a 256-bit AES-GCM key derived from `password` with PBKDF2-SHA-256
+    ///
+    /// The `salt` should be random, at least 16 bytes long and stored alongside the data, and
+    /// `iterations` as high as the app can afford; at least 600 000 are currently recommended.
+    pub async fn from_password(password: &str, salt: &[u8], iterations: u32) -> Result<Self> {
+        let subtle = subtle()?;
+        let password = subtle.import_key_with_str(
+            "raw",
+            &Uint8Array::from(password.as_bytes()),
+            "PBKDF2",
+            false,
+            &Array::of1(&JsValue::from_str("deriveKey")),
+        );
+        let password: CryptoKey = resolve(password).await?.unchecked_into();
+        let key = subtle.derive_key_with_object_and_object(
+            &Pbkdf2Params::new(
+                "PBKDF2",
+                &JsValue::from_str("SHA-256"),
+                iterations,
+                &Uint8Array::from(salt),
+            ),
+            &password,
+            &AesDerivedKeyParams::new("AES-GCM", 256),
+            false,
+            &Array::of2(&JsValue::from_str("encrypt"), &JsValue::from_str("decrypt")),
+        );
+        Ok(Self::new(resolve(key).await?.unchecked_into()))
+    }
+
+    /// Get the key used to encrypt the values
+    pub fn crypto_key(&self) -> &CryptoKey {
+        &self.key
+    }
+
+    /// Get and decrypt the value for the specified key
+    ///
+    /// Values encrypted with another [`CryptoKey`], or stored under another key, fail to
+    /// decrypt with an `OperationError`.
+    pub async fn get<T>(&self, key: impl AsRef<str>) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let key = key.as_ref();
+        let data: String = S::get(key)?;
+        let data = encoding::decode_base64(&data).map_err(malformed)?;
+        if data.len() < IV_LENGTH {
+            return Err(malformed("missing IV"));
+        }
+        let (iv, ciphertext) = data.split_at(IV_LENGTH);
+        let plaintext = subtle()?.decrypt_with_object_and_buffer_source(
+            &params(iv, key),
+            &self.key,
+            &Uint8Array::from(ciphertext),
+        );
+        let plaintext = Uint8Array::new(&resolve(plaintext).await?).to_vec();
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
+    /// Encrypt and insert a value for the specified key
+    pub async fn set<T>(&self, key: impl AsRef<str>, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let key = key.as_ref();
+        let plaintext = serde_json::to_vec(&value)?;
+        let mut iv = [0; IV_LENGTH];
+        crypto()?
+            .get_random_values_with_u8_array(&mut iv)
+            .map_err(rejection_to_error)?;
+        let ciphertext = subtle()?.encrypt_with_object_and_buffer_source(
+            &params(&iv, key),
+            &self.key,
+            &Uint8Array::from(plaintext.as_slice()),
+        );
+        let mut data = iv.to_vec();
+        data.extend(Uint8Array::new(&resolve(ciphertext).await?).to_vec());
+        S::set(key, encoding::encode_base64(&data))
+    }
+
+    /// Remove a key and it's stored value
+    pub fn delete(&self, key: impl AsRef<str>) {
+        S::delete(key)
+    }
+}
+
+impl<S> Clone for EncryptedStorage<S> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S> fmt::Debug for EncryptedStorage<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedStorage")
+            .field("storage", &std::any::type_name::<S>())
+            .field("key", &self.key)
+            .finish()
+    }
+}
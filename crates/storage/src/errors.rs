@@ -1,6 +1,7 @@
 //! All the errors.
 
 use gloo_utils::errors::JsError;
+use js_sys::Reflect;
 use wasm_bindgen::{JsCast, JsValue};
 
 /// Error returned by this crate
@@ -12,14 +13,95 @@ pub enum StorageError {
     /// Error if the requested key is not found
     #[error("key {0} not found")]
     KeyNotFound(String),
-    /// Error returned from JavaScript
+    /// Error if the storage is full, either because the origin used up its quota or because the
+    /// browser only allows a few megabytes, as with [`LocalStorage`](crate::LocalStorage)
+    ///
+    /// Some browsers also report this error when storage is disabled, e.g. in private browsing.
+    #[error("{0}")]
+    QuotaExceeded(JsError),
+    /// Error if access to the storage is denied, e.g. because the user blocked cookies for the
+    /// site or because the document is sandboxed
+    #[error("{0}")]
+    SecurityError(JsError),
+    /// Any other error returned from JavaScript
     #[error("{0}")]
     JsError(JsError),
+    /// Error if the operation needs a [`web_sys::Storage`], and the backend isn't backed by one,
+    /// e.g. subscribing to the changes of a [`MemoryStorage`](crate::MemoryStorage)
+    #[error("{0}")]
+    Unsupported(String),
+    /// Error from a [`Codec`](crate::Codec) other than [`Json`](crate::Json)
+    ///
+    /// The error is boxed as `Send + Sync`, so a `StorageError` can be moved across threads.
+    #[error("{0}")]
+    CodecError(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl StorageError {
+    /// The JavaScript error behind this one, if any
+    ///
+    /// The `DOMException` behind [`QuotaExceeded`](Self::QuotaExceeded) and
+    /// [`SecurityError`](Self::SecurityError) is deliberately reduced to its name and message,
+    /// which keeps `StorageError` `Send` and `Sync`. Its legacy `code` is implied by the name.
+    pub fn js_error(&self) -> Option<&JsError> {
+        match self {
+            StorageError::QuotaExceeded(error)
+            | StorageError::SecurityError(error)
+            | StorageError::JsError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<JsError> for StorageError {
+    fn from(error: JsError) -> Self {
+        match error.name.as_str() {
+            // Older versions of Firefox use their own name.
+            "QuotaExceededError" | "NS_ERROR_DOM_QUOTA_REACHED" => {
+                StorageError::QuotaExceeded(error)
+            }
+            "SecurityError" => StorageError::SecurityError(error),
+            _ => StorageError::JsError(error),
+        }
+    }
 }
 
 pub(crate) fn js_to_error(js_value: JsValue) -> StorageError {
     match js_value.dyn_into::<js_sys::Error>() {
-        Ok(error) => StorageError::JsError(JsError::from(error)),
+        Ok(error) => JsError::from(error).into(),
         Err(_) => unreachable!("JsValue passed is not an Error type - this is a bug"),
     }
 }
+
+/// Converts the rejection of a promise, or any exception that may be a `DOMException`, into a
+/// [`StorageError`].
+pub(crate) fn rejection_to_error(error: JsValue) -> StorageError {
+    match JsError::try_from(error) {
+        Ok(error) => error.into(),
+        // Older browsers don't make `DOMException` inherit from `Error`, so copy what makes
+        // it one.
+        Err(e) => {
+            let field = |name: &str| {
+                Reflect::get(&e.js_value, &JsValue::from_str(name))
+                    .ok()
+                    .and_then(|value| value.as_string())
+            };
+            let error = js_sys::Error::new(&field("message").unwrap_or_else(|| e.to_string()));
+            if let Some(name) = field("name") {
+                error.set_name(&name);
+            }
+            JsError::from(error).into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_error_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<StorageError>();
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_channel::mpsc;
+use futures_core::{ready, Stream};
+use serde::de::DeserializeOwned;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{IdbCursorWithValue, IdbRequest};
+
+use super::request::dom_to_error;
+use super::store::from_js;
+use crate::errors::js_to_error;
+use crate::Result;
+
+/// A [`Stream`] of the `(key, value)` pairs of an [`ObjectStore`](super::ObjectStore) or
+/// [`Index`](super::Index).
+///
+/// The next record is only read when the stream is polled. Like any other request, this must
+/// happen before the transaction commits.
+pub struct Cursor<K, V> {
+    request: IdbRequest,
+    receiver: mpsc::UnboundedReceiver<Result<JsValue>>,
+    current: Option<IdbCursorWithValue>,
+    done: bool,
+    _on_success: Closure<dyn FnMut()>,
+    _on_error: Closure<dyn FnMut()>,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> Cursor<K, V> {
+    pub(crate) fn new(request: std::result::Result<IdbRequest, JsValue>) -> Result<Self> {
+        let request = request.map_err(js_to_error)?;
+        let (sender, receiver) = mpsc::unbounded();
+        let on_success = {
+            let sender = sender.clone();
+            let request = request.clone();
+            Closure::wrap(Box::new(move || {
+                let _ = sender.unbounded_send(request.result().map_err(js_to_error));
+            }) as Box<dyn FnMut()>)
+        };
+        let on_error = {
+            let request = request.clone();
+            Closure::wrap(Box::new(move || {
+                let error = dom_to_error(request.error().ok().flatten());
+                let _ = sender.unbounded_send(Err(error));
+            }) as Box<dyn FnMut()>)
+        };
+        request.set_onsuccess(Some(on_success.as_ref().unchecked_ref()));
+        request.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        Ok(Self {
+            request,
+            receiver,
+            current: None,
+            done: false,
+            _on_success: on_success,
+            _on_error: on_error,
+            _marker: PhantomData,
+        })
+    }
+}
+
+fn read<K: DeserializeOwned, V: DeserializeOwned>(cursor: &IdbCursorWithValue) -> Result<(K, V)> {
+    let key = cursor.key().map_err(js_to_error)?;
+    let value = cursor.value().map_err(js_to_error)?;
+    Ok((from_js(&key)?, from_js(&value)?))
+}
+
+impl<K, V> Stream for Cursor<K, V>
+where
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+{
+    type Item = Result<(K, V)>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        if let Some(cursor) = self.current.take() {
+            if let Err(e) = cursor.continue_() {
+                self.done = true;
+                return Poll::Ready(Some(Err(js_to_error(e))));
+            }
+        }
+        let result = ready!(Pin::new(&mut self.receiver).poll_next(cx));
+        Poll::Ready(match result {
+            Some(Ok(cursor)) if !cursor.is_null() => {
+                let cursor = cursor.unchecked_into::<IdbCursorWithValue>();
+                let item = read(&cursor);
+                self.current = Some(cursor);
+                Some(item)
+            }
+            Some(Err(e)) => {
+                self.done = true;
+                Some(Err(e))
+            }
+            // The cursor went past the last record.
+            Some(Ok(_)) | None => {
+                self.done = true;
+                None
+            }
+        })
+    }
+}
+
+impl<K, V> Drop for Cursor<K, V> {
+    fn drop(&mut self) {
+        self.request.set_onsuccess(None);
+        self.request.set_onerror(None);
+    }
+}
+
+impl<K, V> fmt::Debug for Cursor<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cursor")
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,290 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use js_sys::{Array, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    IdbDatabase, IdbFactory, IdbIndexParameters, IdbObjectStore, IdbObjectStoreParameters,
+    IdbOpenDbRequest, IdbTransaction, IdbTransactionMode, IdbVersionChangeEvent,
+};
+
+use super::request::{send, RequestFuture};
+use super::transaction::{Transaction, TransactionMode};
+use crate::errors::js_to_error;
+use crate::Result;
+
+fn factory() -> Result<IdbFactory> {
+    let factory =
+        Reflect::get(&js_sys::global(), &JsValue::from_str("indexedDB")).map_err(js_to_error)?;
+    if factory.is_undefined() || factory.is_null() {
+        let error = js_sys::Error::new("IndexedDB is not available");
+        error.set_name("NotSupportedError");
+        return Err(js_to_error(error.into()));
+    }
+    Ok(factory.unchecked_into())
+}
+
+fn names(list: web_sys::DomStringList) -> Vec<String> {
+    (0..list.length()).filter_map(|i| list.item(i)).collect()
+}
+
+/// A connection to an IndexedDB database.
+#[derive(Debug)]
+pub struct Database {
+    db: IdbDatabase,
+}
+
+impl Database {
+    /// Opens the database `name`, creating it if it does not exist.
+    ///
+    /// If the database is older than `version`, `upgrade` is called to create or migrate its
+    /// object stores and indexes. Returning an error from `upgrade` aborts the upgrade and makes
+    /// `open` fail with that error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `version` is zero.
+    pub async fn open<F>(name: &str, version: u32, upgrade: F) -> Result<Self>
+    where
+        F: FnOnce(&Upgrade) -> Result<()> + 'static,
+    {
+        assert!(version > 0, "database version must not be zero");
+        let request = factory()?
+            .open_with_u32(name, version)
+            .map_err(js_to_error)?;
+
+        let upgrade_error = Rc::new(RefCell::new(None));
+        let mut upgrade = Some(upgrade);
+        let on_upgrade_needed = {
+            let request = request.clone();
+            let upgrade_error = Rc::clone(&upgrade_error);
+            Closure::wrap(Box::new(move |event: IdbVersionChangeEvent| {
+                let upgrade = match upgrade.take() {
+                    Some(upgrade) => upgrade,
+                    None => return,
+                };
+                let result = Upgrade::from_request(&request, &event).and_then(|ctx| {
+                    let result = upgrade(&ctx);
+                    if result.is_err() {
+                        let _ = ctx.transaction.abort();
+                    }
+                    result
+                });
+                if let Err(e) = result {
+                    *upgrade_error.borrow_mut() = Some(e);
+                }
+            }) as Box<dyn FnMut(IdbVersionChangeEvent)>)
+        };
+        request.set_onupgradeneeded(Some(on_upgrade_needed.as_ref().unchecked_ref()));
+
+        let result = RequestFuture::new(request.clone().into()).await;
+        request.set_onupgradeneeded(None);
+        drop(on_upgrade_needed);
+
+        if let Some(e) = upgrade_error.borrow_mut().take() {
+            return Err(e);
+        }
+        Ok(Self {
+            db: result?.unchecked_into(),
+        })
+    }
+
+    /// Deletes the database `name`.
+    ///
+    /// This waits until all other connections to the database are closed.
+    pub async fn delete(name: &str) -> Result<()> {
+        send(factory()?.delete_database(name).map(Into::into)).await?;
+        Ok(())
+    }
+
+    /// The name of the database.
+    pub fn name(&self) -> String {
+        self.db.name()
+    }
+
+    /// The version of the database.
+    pub fn version(&self) -> u32 {
+        self.db.version() as u32
+    }
+
+    /// The names of the object stores in the database.
+    pub fn object_store_names(&self) -> Vec<String> {
+        names(self.db.object_store_names())
+    }
+
+    /// Starts a transaction over the object stores `stores`.
+    pub fn transaction(&self, stores: &[&str], mode: TransactionMode) -> Result<Transaction> {
+        let stores = stores
+            .iter()
+            .map(|s| JsValue::from_str(s))
+            .collect::<Array>();
+        let mode = match mode {
+            TransactionMode::ReadOnly => IdbTransactionMode::Readonly,
+            TransactionMode::ReadWrite => IdbTransactionMode::Readwrite,
+        };
+        self.db
+            .transaction_with_str_sequence_and_mode(&stores, mode)
+            .map(Transaction::new)
+            .map_err(js_to_error)
+    }
+
+    /// Closes the connection once all its transactions are complete.
+    pub fn close(&self) {
+        self.db.close()
+    }
+
+    /// Gets the raw [`web_sys::IdbDatabase`].
+    pub fn raw(&self) -> &IdbDatabase {
+        &self.db
+    }
+}
+
+/// Changes the schema of a database while it is being upgraded to a new version.
+///
+/// Passed to the `upgrade` callback of [`Database::open`].
+#[derive(Debug)]
+pub struct Upgrade {
+    db: IdbDatabase,
+    transaction: IdbTransaction,
+    old_version: u32,
+    new_version: u32,
+}
+
+impl Upgrade {
+    fn from_request(request: &IdbOpenDbRequest, event: &IdbVersionChangeEvent) -> Result<Self> {
+        let db = request.result().map_err(js_to_error)?.unchecked_into();
+        let transaction = request.transaction().ok_or_else(|| {
+            js_to_error(js_sys::Error::new("upgrade without a version change transaction").into())
+        })?;
+        Ok(Self {
+            db,
+            transaction,
+            old_version: event.old_version() as u32,
+            new_version: event.new_version().unwrap_or_default() as u32,
+        })
+    }
+
+    /// The version being upgraded from, `0` if the database was just created.
+    pub fn old_version(&self) -> u32 {
+        self.old_version
+    }
+
+    /// The version being upgraded to.
+    pub fn new_version(&self) -> u32 {
+        self.new_version
+    }
+
+    /// The names of the object stores in the database.
+    pub fn object_store_names(&self) -> Vec<String> {
+        names(self.db.object_store_names())
+    }
+
+    /// Creates an object store.
+    pub fn create_object_store(&self, name: &str, options: &StoreOptions) -> Result<StoreSchema> {
+        let mut params = IdbObjectStoreParameters::new();
+        params.auto_increment(options.auto_increment);
+        if let Some(key_path) = &options.key_path {
+            params.key_path(Some(&JsValue::from_str(key_path)));
+        }
+        self.db
+            .create_object_store_with_optional_parameters(name, &params)
+            .map(|store| StoreSchema { store })
+            .map_err(js_to_error)
+    }
+
+    /// Gets an existing object store, e.g. to add an index to it.
+    pub fn object_store(&self, name: &str) -> Result<StoreSchema> {
+        self.transaction
+            .object_store(name)
+            .map(|store| StoreSchema { store })
+            .map_err(js_to_error)
+    }
+
+    /// Deletes an object store and all its records.
+    pub fn delete_object_store(&self, name: &str) -> Result<()> {
+        self.db.delete_object_store(name).map_err(js_to_error)
+    }
+}
+
+/// Options for [`Upgrade::create_object_store`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StoreOptions {
+    key_path: Option<String>,
+    auto_increment: bool,
+}
+
+impl StoreOptions {
+    /// Options for a store whose keys are given explicitly when adding records.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes the key of each record from the property at `key_path` of its value, e.g. `"id"`
+    /// or `"author.name"`.
+    pub fn key_path(mut self, key_path: &str) -> Self {
+        self.key_path = Some(key_path.into());
+        self
+    }
+
+    /// Generates increasing integer keys for records added without one.
+    pub fn auto_increment(mut self, auto_increment: bool) -> Self {
+        self.auto_increment = auto_increment;
+        self
+    }
+}
+
+/// An object store whose indexes can be changed during an [`Upgrade`].
+#[derive(Debug)]
+pub struct StoreSchema {
+    store: IdbObjectStore,
+}
+
+impl StoreSchema {
+    /// The names of the indexes of the store.
+    pub fn index_names(&self) -> Vec<String> {
+        names(self.store.index_names())
+    }
+
+    /// Creates an index over the property at `key_path` of the values in the store.
+    pub fn create_index(&self, name: &str, key_path: &str, options: &IndexOptions) -> Result<()> {
+        let mut params = IdbIndexParameters::new();
+        params.unique(options.unique);
+        params.multi_entry(options.multi_entry);
+        self.store
+            .create_index_with_str_and_optional_parameters(name, key_path, &params)
+            .map(drop)
+            .map_err(js_to_error)
+    }
+
+    /// Deletes an index.
+    pub fn delete_index(&self, name: &str) -> Result<()> {
+        self.store.delete_index(name).map_err(js_to_error)
+    }
+}
+
+/// Options for [`StoreSchema::create_index`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexOptions {
+    unique: bool,
+    multi_entry: bool,
+}
+
+impl IndexOptions {
+    /// Options for a non-unique index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects records whose key for this index is already used by another record.
+    pub fn unique(mut self, unique: bool) -> Self {
+        self.unique = unique;
+        self
+    }
+
+    /// Adds one entry per element when the key of a record is an array.
+    pub fn multi_entry(mut self, multi_entry: bool) -> Self {
+        self.multi_entry = multi_entry;
+        self
+    }
+}
@@ -0,0 +1,58 @@
+//! An async wrapper around the
+//! [IndexedDB API](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API).
+//!
+//! Unlike [`LocalStorage`](crate::LocalStorage), IndexedDB is asynchronous, can hold far more
+//! data and supports transactions, indexes and range queries. Keys and values are converted to
+//! and from JavaScript with [`serde`](https://serde.rs), through their JSON representation.
+//!
+//! # Example
+//!
+//! ```rust
+//! use futures::StreamExt;
+//! use gloo_storage::indexeddb::{
+//!     CursorDirection, Database, IndexOptions, StoreOptions, TransactionMode,
+//! };
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!     id: u32,
+//!     email: String,
+//! }
+//!
+//! # async fn no_run() -> gloo_storage::Result<()> {
+//! let db = Database::open("app", 1, |upgrade| {
+//!     if upgrade.old_version() < 1 {
+//!         let users = upgrade.create_object_store("users", &StoreOptions::new().key_path("id"))?;
+//!         users.create_index("by_email", "email", &IndexOptions::new().unique(true))?;
+//!     }
+//!     Ok(())
+//! })
+//! .await?;
+//!
+//! let tx = db.transaction(&["users"], TransactionMode::ReadWrite)?;
+//! let users = tx.object_store::<u32, User>("users")?;
+//! users.put(&User { id: 1, email: "ferris@example.com".into() }).await?;
+//! let ferris = users
+//!     .index::<String>("by_email")?
+//!     .get(&"ferris@example.com".to_string())
+//!     .await?;
+//! let mut cursor = users.cursor_in(1.., CursorDirection::Next)?;
+//! while let Some(entry) = cursor.next().await {
+//!     let (id, user) = entry?;
+//! }
+//! tx.commit().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod cursor;
+mod database;
+mod request;
+mod store;
+mod transaction;
+
+pub use cursor::Cursor;
+pub use database::{Database, IndexOptions, StoreOptions, StoreSchema, Upgrade};
+pub use store::{CursorDirection, Index, ObjectStore};
+pub use transaction::{Transaction, TransactionMode};
@@ -0,0 +1,140 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use futures_channel::oneshot;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{DomException, IdbRequest, IdbTransaction};
+
+use crate::errors::{js_to_error, rejection_to_error, StorageError};
+use crate::Result;
+
+/// Converts the error of a failed request or transaction into a [`StorageError`].
+///
+/// `None` means the transaction was aborted without an error, i.e. by calling `abort()`.
+pub(crate) fn dom_to_error(error: Option<DomException>) -> StorageError {
+    let error: JsValue = match error {
+        Some(error) => error.into(),
+        None => {
+            let error = js_sys::Error::new("the transaction was aborted");
+            error.set_name("AbortError");
+            error.into()
+        }
+    };
+    rejection_to_error(error)
+}
+
+type Sender<T> = Rc<RefCell<Option<oneshot::Sender<T>>>>;
+
+fn callback<T: 'static>(sender: &Sender<T>, f: impl Fn() -> T + 'static) -> Closure<dyn FnMut()> {
+    let sender = Rc::clone(sender);
+    Closure::wrap(Box::new(move || {
+        if let Some(sender) = sender.borrow_mut().take() {
+            let _ = sender.send(f());
+        }
+    }) as Box<dyn FnMut()>)
+}
+
+/// Resolves to the result of an `IDBRequest`.
+pub(crate) struct RequestFuture {
+    request: IdbRequest,
+    receiver: oneshot::Receiver<Result<JsValue>>,
+    _on_success: Closure<dyn FnMut()>,
+    _on_error: Closure<dyn FnMut()>,
+}
+
+impl RequestFuture {
+    pub(crate) fn new(request: IdbRequest) -> Self {
+        let (sender, receiver) = oneshot::channel();
+        let sender = Rc::new(RefCell::new(Some(sender)));
+        let on_success = callback(&sender, {
+            let request = request.clone();
+            move || request.result().map_err(js_to_error)
+        });
+        let on_error = callback(&sender, {
+            let request = request.clone();
+            move || Err(dom_to_error(request.error().ok().flatten()))
+        });
+        request.set_onsuccess(Some(on_success.as_ref().unchecked_ref()));
+        request.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        Self {
+            request,
+            receiver,
+            _on_success: on_success,
+            _on_error: on_error,
+        }
+    }
+}
+
+impl Future for RequestFuture {
+    type Output = Result<JsValue>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver)
+            .poll(cx)
+            .map(|result| result.expect_throw("request callbacks dropped before completion"))
+    }
+}
+
+impl Drop for RequestFuture {
+    fn drop(&mut self) {
+        self.request.set_onsuccess(None);
+        self.request.set_onerror(None);
+    }
+}
+
+/// Sends a request and waits for its result.
+pub(crate) async fn send(request: std::result::Result<IdbRequest, JsValue>) -> Result<JsValue> {
+    RequestFuture::new(request.map_err(js_to_error)?).await
+}
+
+/// Resolves once an `IDBTransaction` completes or aborts.
+///
+/// Listeners are attached as soon as this is created, so that completion is not missed if the
+/// transaction commits before it is awaited.
+pub(crate) struct TransactionDone {
+    transaction: IdbTransaction,
+    receiver: oneshot::Receiver<Result<()>>,
+    _on_complete: Closure<dyn FnMut()>,
+    _on_abort: Closure<dyn FnMut()>,
+}
+
+impl TransactionDone {
+    pub(crate) fn new(transaction: IdbTransaction) -> Self {
+        let (sender, receiver) = oneshot::channel();
+        let sender = Rc::new(RefCell::new(Some(sender)));
+        let on_complete = callback(&sender, || Ok(()));
+        let on_abort = callback(&sender, {
+            let transaction = transaction.clone();
+            move || Err(dom_to_error(transaction.error()))
+        });
+        transaction.set_oncomplete(Some(on_complete.as_ref().unchecked_ref()));
+        transaction.set_onabort(Some(on_abort.as_ref().unchecked_ref()));
+        Self {
+            transaction,
+            receiver,
+            _on_complete: on_complete,
+            _on_abort: on_abort,
+        }
+    }
+}
+
+impl Future for TransactionDone {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver)
+            .poll(cx)
+            .map(|result| result.expect_throw("transaction callbacks dropped before completion"))
+    }
+}
+
+impl Drop for TransactionDone {
+    fn drop(&mut self) {
+        self.transaction.set_oncomplete(None);
+        self.transaction.set_onabort(None);
+    }
+}
@@ -0,0 +1,297 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+
+use gloo_utils::format::JsValueSerdeExt;
+use js_sys::Uint8Array;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+use web_sys::{IdbCursorDirection, IdbIndex, IdbKeyRange, IdbObjectStore};
+
+use super::cursor::Cursor;
+use super::request::send;
+use crate::errors::js_to_error;
+use crate::Result;
+
+/// The order in which a [`Cursor`] visits records.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CursorDirection {
+    /// In increasing key order.
+    #[default]
+    Next,
+    /// In increasing key order, skipping records with the same key as the previous one.
+    NextUnique,
+    /// In decreasing key order.
+    Prev,
+    /// In decreasing key order, skipping records with the same key as the previous one.
+    PrevUnique,
+}
+
+impl From<CursorDirection> for IdbCursorDirection {
+    fn from(direction: CursorDirection) -> Self {
+        match direction {
+            CursorDirection::Next => IdbCursorDirection::Next,
+            CursorDirection::NextUnique => IdbCursorDirection::Nextunique,
+            CursorDirection::Prev => IdbCursorDirection::Prev,
+            CursorDirection::PrevUnique => IdbCursorDirection::Prevunique,
+        }
+    }
+}
+
+pub(crate) fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<JsValue> {
+    Ok(JsValue::from_serde(value)?)
+}
+
+pub(crate) fn from_js<T: DeserializeOwned>(value: &JsValue) -> Result<T> {
+    Ok(value.into_serde()?)
+}
+
+/// Converts `range` into an `IDBKeyRange`, or `undefined` if it is unbounded.
+fn key_range<K: Serialize>(range: impl RangeBounds<K>) -> Result<JsValue> {
+    let bound = |bound: Bound<&K>| -> Result<Option<(JsValue, bool)>> {
+        Ok(match bound {
+            Bound::Included(key) => Some((to_js(key)?, false)),
+            Bound::Excluded(key) => Some((to_js(key)?, true)),
+            Bound::Unbounded => None,
+        })
+    };
+    let range = match (bound(range.start_bound())?, bound(range.end_bound())?) {
+        (None, None) => return Ok(JsValue::UNDEFINED),
+        (Some((lower, open)), None) => IdbKeyRange::lower_bound_with_open(&lower, open),
+        (None, Some((upper, open))) => IdbKeyRange::upper_bound_with_open(&upper, open),
+        (Some((lower, lower_open)), Some((upper, upper_open))) => {
+            IdbKeyRange::bound_with_lower_open_and_upper_open(
+                &lower, &upper, lower_open, upper_open,
+            )
+        }
+    };
+    range.map(Into::into).map_err(js_to_error)
+}
+
+fn optional<T: DeserializeOwned>(value: JsValue) -> Result<Option<T>> {
+    if value.is_undefined() {
+        Ok(None)
+    } else {
+        from_js(&value).map(Some)
+    }
+}
+
+/// An object store holding values of type `V` under keys of type `K`.
+///
+/// Keys and values are converted to JavaScript through their JSON representation. Keys must map
+/// to a valid IndexedDB key: a number, a string, or an array of those.
+pub struct ObjectStore<K, V> {
+    pub(crate) store: IdbObjectStore,
+    pub(crate) _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> ObjectStore<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    /// The name of the store.
+    pub fn name(&self) -> String {
+        self.store.name()
+    }
+
+    /// Gets the value stored under `key`.
+    pub async fn get(&self, key: &K) -> Result<Option<V>> {
+        optional(send(self.store.get(&to_js(key)?)).await?)
+    }
+
+    /// Gets all the values, in key order.
+    pub async fn get_all(&self) -> Result<Vec<V>> {
+        from_js(&send(self.store.get_all()).await?)
+    }
+
+    /// Gets the values whose keys are in `range`, in key order.
+    pub async fn get_all_in(&self, range: impl RangeBounds<K>) -> Result<Vec<V>> {
+        from_js(&send(self.store.get_all_with_key(&key_range(range)?)).await?)
+    }
+
+    /// Gets all the keys, in order.
+    pub async fn get_all_keys(&self) -> Result<Vec<K>> {
+        from_js(&send(self.store.get_all_keys()).await?)
+    }
+
+    /// Counts the records in the store.
+    pub async fn count(&self) -> Result<u32> {
+        from_js(&send(self.store.count()).await?)
+    }
+
+    /// Adds `value`, failing if its key is already used.
+    ///
+    /// The key is taken from the value or generated, depending on the
+    /// [`StoreOptions`](super::StoreOptions) of the store. Returns the key.
+    pub async fn add(&self, value: &V) -> Result<K> {
+        from_js(&send(self.store.add(&to_js(value)?)).await?)
+    }
+
+    /// Adds `value` under `key`, failing if `key` is already used.
+    pub async fn add_with_key(&self, value: &V, key: &K) -> Result<()> {
+        send(self.store.add_with_key(&to_js(value)?, &to_js(key)?)).await?;
+        Ok(())
+    }
+
+    /// Inserts or replaces `value`.
+    ///
+    /// The key is taken from the value or generated, depending on the
+    /// [`StoreOptions`](super::StoreOptions) of the store. Returns the key.
+    pub async fn put(&self, value: &V) -> Result<K> {
+        from_js(&send(self.store.put(&to_js(value)?)).await?)
+    }
+
+    /// Inserts or replaces the value under `key`.
+    pub async fn put_with_key(&self, value: &V, key: &K) -> Result<()> {
+        send(self.store.put_with_key(&to_js(value)?, &to_js(key)?)).await?;
+        Ok(())
+    }
+
+    /// Gets the bytes stored under `key` with [`set_bytes`](Self::set_bytes).
+    pub async fn get_bytes(&self, key: &K) -> Result<Option<Vec<u8>>> {
+        let value = send(self.store.get(&to_js(key)?)).await?;
+        if value.is_undefined() {
+            return Ok(None);
+        }
+        // Also accepts `ArrayBuffer`s and other typed arrays stored by other code.
+        Ok(Some(Uint8Array::new(&value).to_vec()))
+    }
+
+    /// Inserts or replaces the bytes under `key`.
+    ///
+    /// The bytes are stored as a `Uint8Array` rather than through JSON, so they can only be
+    /// read back with [`get_bytes`](Self::get_bytes), and are skipped by the typed getters.
+    pub async fn set_bytes(&self, key: &K, value: &[u8]) -> Result<()> {
+        let value = Uint8Array::from(value);
+        send(self.store.put_with_key(&value, &to_js(key)?)).await?;
+        Ok(())
+    }
+
+    /// Deletes the value under `key`, if any.
+    pub async fn delete(&self, key: &K) -> Result<()> {
+        send(self.store.delete(&to_js(key)?)).await?;
+        Ok(())
+    }
+
+    /// Deletes all the values.
+    pub async fn clear(&self) -> Result<()> {
+        send(self.store.clear()).await?;
+        Ok(())
+    }
+
+    /// Gets one of the indexes of the store, typed with its key type `IK`.
+    pub fn index<IK>(&self, name: &str) -> Result<Index<IK, V>> {
+        self.store
+            .index(name)
+            .map(|index| Index {
+                index,
+                _marker: PhantomData,
+            })
+            .map_err(js_to_error)
+    }
+
+    /// Iterates over all the records.
+    pub fn cursor(&self, direction: CursorDirection) -> Result<Cursor<K, V>> {
+        Cursor::new(
+            self.store
+                .open_cursor_with_range_and_direction(&JsValue::UNDEFINED, direction.into()),
+        )
+    }
+
+    /// Iterates over the records whose keys are in `range`.
+    pub fn cursor_in(
+        &self,
+        range: impl RangeBounds<K>,
+        direction: CursorDirection,
+    ) -> Result<Cursor<K, V>> {
+        Cursor::new(
+            self.store
+                .open_cursor_with_range_and_direction(&key_range(range)?, direction.into()),
+        )
+    }
+
+    /// Gets the raw [`web_sys::IdbObjectStore`].
+    pub fn raw(&self) -> &IdbObjectStore {
+        &self.store
+    }
+}
+
+impl<K, V> fmt::Debug for ObjectStore<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObjectStore")
+            .field("name", &self.store.name())
+            .finish()
+    }
+}
+
+/// An index of an [`ObjectStore`] holding values of type `V`, keyed by `K`.
+pub struct Index<K, V> {
+    index: IdbIndex,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> Index<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: DeserializeOwned,
+{
+    /// The name of the index.
+    pub fn name(&self) -> String {
+        self.index.name()
+    }
+
+    /// Gets the first value whose index key is `key`.
+    pub async fn get(&self, key: &K) -> Result<Option<V>> {
+        optional(send(self.index.get(&to_js(key)?)).await?)
+    }
+
+    /// Gets all the values, in index key order.
+    pub async fn get_all(&self) -> Result<Vec<V>> {
+        from_js(&send(self.index.get_all()).await?)
+    }
+
+    /// Gets the values whose index keys are in `range`, in index key order.
+    pub async fn get_all_in(&self, range: impl RangeBounds<K>) -> Result<Vec<V>> {
+        from_js(&send(self.index.get_all_with_key(&key_range(range)?)).await?)
+    }
+
+    /// Counts the records in the index.
+    pub async fn count(&self) -> Result<u32> {
+        from_js(&send(self.index.count()).await?)
+    }
+
+    /// Iterates over all the records, yielding their index keys.
+    pub fn cursor(&self, direction: CursorDirection) -> Result<Cursor<K, V>> {
+        Cursor::new(
+            self.index
+                .open_cursor_with_range_and_direction(&JsValue::UNDEFINED, direction.into()),
+        )
+    }
+
+    /// Iterates over the records whose index keys are in `range`, yielding their index keys.
+    pub fn cursor_in(
+        &self,
+        range: impl RangeBounds<K>,
+        direction: CursorDirection,
+    ) -> Result<Cursor<K, V>> {
+        Cursor::new(
+            self.index
+                .open_cursor_with_range_and_direction(&key_range(range)?, direction.into()),
+        )
+    }
+
+    /// Gets the raw [`web_sys::IdbIndex`].
+    pub fn raw(&self) -> &IdbIndex {
+        &self.index
+    }
+}
+
+impl<K, V> fmt::Debug for Index<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Index")
+            .field("name", &self.index.name())
+            .finish()
+    }
+}
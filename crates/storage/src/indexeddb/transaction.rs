@@ -0,0 +1,77 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use web_sys::IdbTransaction;
+
+use super::request::TransactionDone;
+use super::store::ObjectStore;
+use crate::errors::js_to_error;
+use crate::Result;
+
+/// Whether a [`Transaction`] may change the database.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TransactionMode {
+    /// Only reads records. Read-only transactions over the same stores can run concurrently.
+    ReadOnly,
+    /// Reads and writes records.
+    ReadWrite,
+}
+
+/// A transaction over one or more object stores, created by
+/// [`Database::transaction`](super::Database::transaction).
+///
+/// The transaction commits automatically once none of its requests are pending, which means it
+/// must not be kept across an `await` of anything other than its own requests. A failed request
+/// aborts the transaction.
+pub struct Transaction {
+    transaction: IdbTransaction,
+    done: TransactionDone,
+}
+
+impl Transaction {
+    pub(crate) fn new(transaction: IdbTransaction) -> Self {
+        Self {
+            done: TransactionDone::new(transaction.clone()),
+            transaction,
+        }
+    }
+
+    /// Gets one of the object stores of the transaction, typed with its key type `K` and value
+    /// type `V`.
+    pub fn object_store<K, V>(&self, name: &str) -> Result<ObjectStore<K, V>> {
+        self.transaction
+            .object_store(name)
+            .map(|store| ObjectStore {
+                store,
+                _marker: PhantomData,
+            })
+            .map_err(js_to_error)
+    }
+
+    /// Commits the transaction and waits until its changes are written.
+    ///
+    /// Fails if the transaction was aborted, e.g. because one of its requests failed.
+    pub async fn commit(self) -> Result<()> {
+        // Not supported everywhere; the transaction commits by itself regardless.
+        let _ = self.transaction.commit();
+        self.done.await
+    }
+
+    /// Aborts the transaction, rolling back all its changes.
+    pub fn abort(self) -> Result<()> {
+        self.transaction.abort().map_err(js_to_error)
+    }
+
+    /// Gets the raw [`web_sys::IdbTransaction`].
+    pub fn raw(&self) -> &IdbTransaction {
+        &self.transaction
+    }
+}
+
+impl fmt::Debug for Transaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transaction")
+            .field("transaction", &self.transaction)
+            .finish_non_exhaustive()
+    }
+}
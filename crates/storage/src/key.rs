@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, Storage, StorageSubscription};
+
+/// A value stored under a fixed key, so that every call site agrees on both the key and the
+/// type stored under it.
+///
+/// This is usually derived. The key defaults to the name of the type and can be set with
+/// `#[storage(key = "...")]`. Values go to [`LocalStorage`](crate::LocalStorage) unless
+/// `#[storage(session)]` selects [`SessionStorage`](crate::SessionStorage).
+///
+/// ```rust
+/// use gloo_storage::StorageKey;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, Default, StorageKey)]
+/// #[storage(key = "app.settings")]
+/// struct Settings {
+///     dark_mode: bool,
+/// }
+///
+/// # fn no_run() {
+/// let mut settings = Settings::load_or_default();
+/// settings.dark_mode = true;
+/// settings.save().unwrap();
+/// # }
+/// ```
+pub trait StorageKey: Serialize + for<'de> Deserialize<'de> {
+    /// The storage the value is kept in
+    type Storage: Storage;
+
+    /// The key the value is stored under
+    const KEY: &'static str;
+
+    /// Get the stored value
+    fn load() -> Result<Self> {
+        Self::Storage::get(Self::KEY)
+    }
+
+    /// Get the stored value, or the default value if it is missing or can't be deserialized
+    fn load_or_default() -> Self
+    where
+        Self: Default,
+    {
+        Self::load().unwrap_or_default()
+    }
+
+    /// Store this value
+    fn save(&self) -> Result<()> {
+        Self::Storage::set(Self::KEY, self)
+    }
+
+    /// Remove the stored value
+    fn delete() {
+        Self::Storage::delete(Self::KEY)
+    }
+
+    /// Subscribe to the changes made to the stored value by other documents
+    ///
+    /// See [`Storage::subscribe`].
+    fn subscribe() -> Result<StorageSubscription<Self>> {
+        Self::Storage::subscribe(Self::KEY)
+    }
+}
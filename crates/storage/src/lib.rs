@@ -1,23 +1,70 @@
 //! This crate provides wrappers for the
 //! [Web Storage API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Storage_API)
 //!
-//! The data is stored in JSON form. We use [`serde`](https://serde.rs) for
-//! serialization and deserialization.
+//! By default, the data is stored in JSON form, see [`Codec`]. We use
+//! [`serde`](https://serde.rs) for serialization and deserialization.
 
 #![deny(missing_docs, missing_debug_implementations)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 
+use std::time::Duration;
+
+use gloo_utils::encoding;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
-use crate::errors::js_to_error;
+use crate::errors::rejection_to_error;
 use errors::StorageError;
 use serde_json::{Map, Value};
 
+#[cfg(feature = "cache")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+pub mod cache;
+mod cell;
+mod codec;
+#[cfg(feature = "compressed")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compressed")))]
+pub mod compressed;
+#[cfg(feature = "cookie")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cookie")))]
+pub mod cookie;
+#[cfg(feature = "encrypted")]
+#[cfg_attr(docsrs, doc(cfg(feature = "encrypted")))]
+pub mod encrypted;
 pub mod errors;
+#[cfg(feature = "indexeddb")]
+#[cfg_attr(docsrs, doc(cfg(feature = "indexeddb")))]
+pub mod indexeddb;
+mod key;
 mod local_storage;
+mod lru;
+mod memory_storage;
+#[cfg(feature = "opfs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "opfs")))]
+pub mod opfs;
+mod quota;
+mod scoped;
 mod session_storage;
+mod subscription;
+#[cfg(feature = "synced")]
+#[cfg_attr(docsrs, doc(cfg(feature = "synced")))]
+pub mod synced;
+mod transaction;
+mod ttl;
+mod versioned;
+pub use cell::{StorageCell, StorageCellStream, StorageCellSubscription};
+pub use codec::{Codec, Json};
+pub use gloo_storage_macros::StorageKey;
+pub use key::StorageKey;
 pub use local_storage::LocalStorage;
+pub use lru::LruStorage;
+pub use memory_storage::MemoryStorage;
+pub use quota::{estimate, persist, persisted, StorageEstimate};
+pub use scoped::ScopedStorage;
 pub use session_storage::SessionStorage;
+pub use subscription::{StorageChange, StorageSubscription};
+pub use transaction::StorageTransaction;
+pub use versioned::{Migrations, Versioned};
 
 /// `gloo-storage`'s `Result`
 pub type Result<T> = std::result::Result<T, StorageError>;
@@ -25,20 +72,69 @@ pub type Result<T> = std::result::Result<T, StorageError>;
 /// Trait which provides implementations for managing storage in the browser.
 pub trait Storage {
     /// Get the raw [`web_sys::Storage`] instance
+    ///
+    /// Panics if the storage can't be accessed, or if the backend isn't backed by a web storage,
+    /// like [`MemoryStorage`]. None of the other methods call it.
     fn raw() -> web_sys::Storage;
 
+    /// Get the raw [`web_sys::Storage`] instance, or the reason it can't be accessed
+    ///
+    /// Browsers deny access to the storage when the user blocked cookies for the site, in which
+    /// case this returns a [`StorageError::SecurityError`] where [`raw`](Self::raw) panics.
+    /// Backends that aren't backed by a web storage return [`StorageError::Unsupported`]. It is
+    /// used by the default implementations of the other methods, and defaults to `raw`.
+    fn try_raw() -> Result<web_sys::Storage> {
+        Ok(Self::raw())
+    }
+
+    /// Get the raw string stored for the specified key, or `None` if the key is not found
+    ///
+    /// This and the other methods working on raw strings are used by all the other methods.
+    /// They are implemented with [`try_raw`](Self::try_raw) by default, and can be overridden
+    /// to store the data elsewhere, as [`MemoryStorage`] does.
+    fn get_item(key: &str) -> Result<Option<String>> {
+        Self::try_raw()?.get_item(key).map_err(rejection_to_error)
+    }
+
+    /// Insert a raw string for the specified key
+    fn set_item(key: &str, value: &str) -> Result<()> {
+        Self::try_raw()?
+            .set_item(key, value)
+            .map_err(rejection_to_error)
+    }
+
+    /// Get the name of the key at `index`, in the order chosen by the storage
+    fn key(index: u32) -> Result<Option<String>> {
+        Self::try_raw()?.key(index).map_err(rejection_to_error)
+    }
+
     /// Get the value for the specified key
     fn get<T>(key: impl AsRef<str>) -> Result<T>
     where
         T: for<'de> Deserialize<'de>,
+    {
+        Self::get_with::<Json, T>(key)
+    }
+
+    /// Get the value for the specified key, decoded with the codec `C`
+    ///
+    /// Values stored with [`set_with_ttl`](Self::set_with_ttl) are always decoded as JSON.
+    fn get_with<C, T>(key: impl AsRef<str>) -> Result<T>
+    where
+        C: Codec,
+        T: for<'de> Deserialize<'de>,
     {
         let key = key.as_ref();
-        let item = Self::raw()
-            .get_item(key)
-            .expect_throw("unreachable: get_item does not throw an exception")
-            .ok_or_else(|| StorageError::KeyNotFound(key.to_string()))?;
-        let item = serde_json::from_str(&item)?;
-        Ok(item)
+        let item =
+            Self::get_item(key)?.ok_or_else(|| StorageError::KeyNotFound(key.to_string()))?;
+        match ttl::from_str::<C, T>(&item)? {
+            Some(item) => Ok(item),
+            None => {
+                // Remove expired entries as they are found.
+                Self::delete(key);
+                Err(StorageError::KeyNotFound(key.to_string()))
+            }
+        }
     }
 
     /// Get all the stored keys and their values
@@ -46,16 +142,20 @@ pub trait Storage {
     where
         T: for<'a> Deserialize<'a>,
     {
-        let local_storage = Self::raw();
-        let length = Self::length();
-        let mut map = Map::with_capacity(length as usize);
-        for index in 0..length {
-            let key = local_storage
-                .key(index)
-                .map_err(js_to_error)?
-                .unwrap_throw();
-            let value: Value = Self::get(&key)?;
-            map.insert(key, value);
+        // Collect first, as expired entries are removed when read, which shifts the indexes of
+        // the remaining ones.
+        let keys = (0..Self::length())
+            .filter_map(|index| Self::key(index).transpose())
+            .collect::<Result<Vec<_>>>()?;
+        let mut map = Map::with_capacity(keys.len());
+        for key in keys {
+            match Self::get::<Value>(&key) {
+                Ok(value) => {
+                    map.insert(key, value);
+                }
+                Err(StorageError::KeyNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
         }
         Ok(serde_json::from_value(Value::Object(map))?)
     }
@@ -64,34 +164,225 @@ pub trait Storage {
     fn set<T>(key: impl AsRef<str>, value: T) -> Result<()>
     where
         T: Serialize,
+    {
+        Self::set_with::<Json, T>(key, value)
+    }
+
+    /// Insert a value for the specified key, encoded with the codec `C`
+    fn set_with<C, T>(key: impl AsRef<str>, value: T) -> Result<()>
+    where
+        C: Codec,
+        T: Serialize,
     {
         let key = key.as_ref();
-        let value = serde_json::to_string(&value)?;
-        Self::raw()
-            .set_item(key, &value)
-            .map_err(errors::js_to_error)?;
-        Ok(())
+        let value = C::encode(&value)?;
+        Self::set_item(key, &value)
+    }
+
+    /// Insert a value for the specified key, which is treated as absent once `ttl` has elapsed
+    ///
+    /// Expired entries are removed when they are read. Until then, they still count towards
+    /// [`length`](Self::length).
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use gloo_storage::{SessionStorage, Storage};
+    ///
+    /// # fn no_run() {
+    /// SessionStorage::set_with_ttl("search", vec!["gloo"], Duration::from_secs(5 * 60)).unwrap();
+    /// let results: Vec<String> = SessionStorage::get("search").unwrap();
+    /// # }
+    /// ```
+    fn set_with_ttl<T>(key: impl AsRef<str>, value: T, ttl: Duration) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let key = key.as_ref();
+        let value = ttl::to_string(&value, ttl)?;
+        Self::set_item(key, &value)
+    }
+
+    /// Get the value for the specified key, migrating it from the layout it was stored with
+    ///
+    /// If the value was stored by an older version, it is stored again with the current
+    /// version once migrated. See [`Versioned`].
+    fn get_versioned<T>(key: impl AsRef<str>) -> Result<T>
+    where
+        T: Versioned,
+    {
+        versioned::get::<Self, T>(key.as_ref())
+    }
+
+    /// Insert a value for the specified key, along with the current version of its layout
+    fn set_versioned<T>(key: impl AsRef<str>, value: &T) -> Result<()>
+    where
+        T: Versioned,
+    {
+        versioned::set::<Self, T>(key.as_ref(), value)
+    }
+
+    /// Get the bytes stored for the specified key with [`set_bytes`](Self::set_bytes)
+    fn get_bytes(key: impl AsRef<str>) -> Result<Vec<u8>> {
+        let key = key.as_ref();
+        let item =
+            Self::get_item(key)?.ok_or_else(|| StorageError::KeyNotFound(key.to_string()))?;
+        encoding::decode_base64(&item).map_err(|e| StorageError::CodecError(Box::new(e)))
+    }
+
+    /// Insert bytes for the specified key
+    ///
+    /// Web storages only hold strings, so the bytes are stored as base64 rather than as a JSON
+    /// array of numbers, and can only be read back with [`get_bytes`](Self::get_bytes).
+    fn set_bytes(key: impl AsRef<str>, value: &[u8]) -> Result<()> {
+        Self::set_item(key.as_ref(), &encoding::encode_base64(value))
+    }
+
+    /// Get the values for the specified keys, or `None` for the keys that are not found
+    fn get_many<K, T>(keys: impl IntoIterator<Item = K>) -> Result<Vec<Option<T>>>
+    where
+        K: AsRef<str>,
+        T: for<'de> Deserialize<'de>,
+    {
+        keys.into_iter()
+            .map(|key| match Self::get(key) {
+                Ok(value) => Ok(Some(value)),
+                Err(StorageError::KeyNotFound(_)) => Ok(None),
+                Err(e) => Err(e),
+            })
+            .collect()
+    }
+
+    /// Insert several values at once
+    ///
+    /// Either all the values are stored, or none are: all the values are serialized before
+    /// anything is written, and if a write fails, e.g. because the quota is exceeded, the values
+    /// written so far are restored.
+    fn set_many<K, T>(items: impl IntoIterator<Item = (K, T)>) -> Result<()>
+    where
+        K: AsRef<str>,
+        T: Serialize,
+    {
+        let items = items
+            .into_iter()
+            .map(|(key, value)| Ok((key.as_ref().to_string(), Some(Json::encode(&value)?))))
+            .collect::<Result<Vec<_>>>()?;
+        transaction::write_all::<Self>(items)
+    }
+
+    /// Stage writes in a [`StorageTransaction`] and apply them only if `f` succeeds
+    ///
+    /// If `f` returns an error, nothing is written. Otherwise, the writes are applied all at
+    /// once, with the same guarantees as [`set_many`](Self::set_many).
+    ///
+    /// ```rust
+    /// use gloo_storage::{LocalStorage, Storage};
+    ///
+    /// # fn no_run() -> gloo_storage::Result<()> {
+    /// LocalStorage::transaction(|txn| {
+    ///     let balance: u64 = txn.get("balance")?;
+    ///     txn.set("balance", balance - 10)?;
+    ///     txn.set("last_payment", 10)?;
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn transaction<F, R>(f: F) -> Result<R>
+    where
+        Self: Sized,
+        F: FnOnce(&mut StorageTransaction<Self>) -> Result<R>,
+    {
+        let mut txn = StorageTransaction::new();
+        let result = f(&mut txn)?;
+        txn.commit()?;
+        Ok(result)
     }
 
     /// Remove a key and it's stored value
+    ///
+    /// Does nothing if the storage can't be accessed.
     fn delete(key: impl AsRef<str>) {
-        let key = key.as_ref();
-        Self::raw()
-            .remove_item(key)
-            .expect_throw("unreachable: remove_item does not throw an exception");
+        if let Ok(storage) = Self::try_raw() {
+            storage
+                .remove_item(key.as_ref())
+                .expect_throw("unreachable: remove_item does not throw an exception");
+        }
     }
 
     /// Remove all the stored data
+    ///
+    /// Does nothing if the storage can't be accessed.
     fn clear() {
-        Self::raw()
-            .clear()
-            .expect_throw("unreachable: clear does not throw an exception");
+        if let Ok(storage) = Self::try_raw() {
+            storage
+                .clear()
+                .expect_throw("unreachable: clear does not throw an exception");
+        }
     }
 
-    /// Get the number of items stored
+    /// Get the number of items stored, or 0 if the storage can't be accessed
     fn length() -> u32 {
-        Self::raw()
-            .length()
-            .expect_throw("unreachable: length does not throw an exception")
+        Self::try_raw().map_or(0, |storage| {
+            storage
+                .length()
+                .expect_throw("unreachable: length does not throw an exception")
+        })
+    }
+
+    /// Subscribe to the changes made to the value of the specified key by other documents, e.g.
+    /// other tabs
+    ///
+    /// Browsers don't report changes made by the current document. Clearing the storage is
+    /// reported as a change with no new value.
+    ///
+    /// Fails if the storage can't be accessed, or if the backend isn't backed by a web storage,
+    /// see [`try_raw`](Self::try_raw).
+    ///
+    /// ```rust
+    /// use futures::StreamExt;
+    /// use gloo_storage::{LocalStorage, Storage};
+    ///
+    /// # async fn no_run() -> gloo_storage::Result<()> {
+    /// let mut changes = LocalStorage::subscribe::<String>("theme")?;
+    /// while let Some(Ok(change)) = changes.next().await {
+    ///     // apply `change.new_value`
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn subscribe<T>(key: impl AsRef<str>) -> Result<StorageSubscription<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        Ok(StorageSubscription::new(
+            Self::try_raw()?,
+            key.as_ref().to_string(),
+        ))
+    }
+
+    /// Get a view of the keys starting with `prefix`, see [`ScopedStorage`]
+    fn scoped(prefix: impl Into<String>) -> ScopedStorage<Self>
+    where
+        Self: Sized,
+    {
+        ScopedStorage::new(prefix)
+    }
+
+    /// Get a handle which encodes all the values with the codec `C`, see [`Codec`]
+    ///
+    /// ```rust
+    /// use gloo_storage::{Json, LocalStorage, Storage};
+    ///
+    /// # fn no_run() {
+    /// let storage = LocalStorage::with_codec::<Json>();
+    /// storage.set("key", "value").unwrap();
+    /// # }
+    /// ```
+    fn with_codec<C>() -> ScopedStorage<Self, C>
+    where
+        Self: Sized,
+        C: Codec,
+    {
+        ScopedStorage::<Self>::new("").with_codec()
     }
 }
@@ -0,0 +1,211 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{js_to_error, StorageError};
+use crate::{Codec, Json, LocalStorage, Result, Storage};
+
+/// The index of the entries is stored under this prefix followed by the prefix of the entries.
+const INDEX_PREFIX: &str = "__gloo_lru:";
+
+/// An entry of the index: the key, without the prefix, and its size in bytes.
+type Entry = (String, usize);
+
+/// A cache of the keys starting with a prefix, which evicts the least recently used entries to
+/// stay within a byte budget.
+///
+/// Browsers throw once an origin fills its few megabytes of [`LocalStorage`], and never evict
+/// anything on their own. `LruStorage` keeps track of the size of the entries and of the order
+/// in which they were used, and removes the least recently used ones whenever a new value would
+/// exceed the budget, or when the browser reports the storage as full.
+///
+/// ```rust
+/// use gloo_storage::{LocalStorage, LruStorage};
+///
+/// # fn no_run() {
+/// let cache = LruStorage::<LocalStorage>::new("thumbnails:", 1024 * 1024);
+/// cache.set("cat.png", "data:image/png;base64,...").unwrap();
+/// let thumbnail: String = cache.get("cat.png").unwrap();
+/// # }
+/// ```
+///
+/// The size of an entry is the length of its key and of its serialized value, in UTF-8. The
+/// order of the entries is stored alongside them, so it survives reloads but is not synchronized
+/// between tabs using the cache at the same time.
+pub struct LruStorage<S = LocalStorage> {
+    prefix: String,
+    budget: usize,
+    _marker: PhantomData<S>,
+}
+
+impl<S: Storage> LruStorage<S> {
+    /// Create a cache of the keys of `S` starting with `prefix`, holding at most `budget` bytes
+    pub fn new(prefix: impl Into<String>, budget: usize) -> Self {
+        Self {
+            prefix: prefix.into(),
+            budget,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The prefix of the keys
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The maximum number of bytes of the entries
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// The number of bytes currently used by the entries
+    pub fn size(&self) -> usize {
+        self.index().iter().map(|(_, size)| size).sum()
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    fn index_key(&self) -> String {
+        format!("{}{}", INDEX_PREFIX, self.prefix)
+    }
+
+    /// The entries, from the least to the most recently used.
+    fn index(&self) -> Vec<Entry> {
+        S::get_item(&self.index_key())
+            .ok()
+            .flatten()
+            .and_then(|index| serde_json::from_str(&index).ok())
+            .unwrap_or_default()
+    }
+
+    fn set_index(&self, index: &[Entry]) -> Result<()> {
+        S::set_item(&self.index_key(), &serde_json::to_string(index)?)
+    }
+
+    /// Get the value for the specified key, and mark it as the most recently used
+    pub fn get<T>(&self, key: impl AsRef<str>) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let key = key.as_ref();
+        let mut index = self.index();
+        let item = match S::get_item(&self.key(key))? {
+            Some(item) => item,
+            None => {
+                // Removed behind our back.
+                if let Some(position) = index.iter().position(|(k, _)| k == key) {
+                    index.remove(position);
+                    self.set_index(&index)?;
+                }
+                return Err(StorageError::KeyNotFound(key.to_string()));
+            }
+        };
+        let value = Json::decode(&item)?;
+        if let Some(position) = index.iter().position(|(k, _)| k == key) {
+            let entry = index.remove(position);
+            index.push(entry);
+            self.set_index(&index)?;
+        }
+        Ok(value)
+    }
+
+    /// Insert a value for the specified key, evicting the least recently used entries as needed
+    ///
+    /// Fails with [`StorageError::QuotaExceeded`] without evicting anything if the value alone
+    /// exceeds the budget.
+    pub fn set<T>(&self, key: impl AsRef<str>, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let key = key.as_ref();
+        let item = Json::encode(&value)?;
+        let size = key.len() + item.len();
+        if size > self.budget {
+            let error = js_sys::Error::new(&format!(
+                "the value of {} takes {} bytes, more than the budget of {}",
+                key, size, self.budget
+            ));
+            error.set_name("QuotaExceededError");
+            return Err(js_to_error(error.into()));
+        }
+
+        let mut index = self.index();
+        index.retain(|(k, _)| k != key);
+        let mut used: usize = index.iter().map(|(_, size)| size).sum();
+        while used + size > self.budget {
+            used -= self.evict(&mut index);
+        }
+        loop {
+            match S::set_item(&self.key(key), &item) {
+                Ok(()) => break,
+                // The origin is full before the budget is, so make room and retry.
+                Err(StorageError::QuotaExceeded(_)) if !index.is_empty() => {
+                    self.evict(&mut index);
+                }
+                Err(e) => {
+                    self.set_index(&index)?;
+                    return Err(e);
+                }
+            }
+        }
+        index.push((key.to_string(), size));
+        self.set_index(&index)
+    }
+
+    /// Removes the least recently used entry, and returns its size.
+    fn evict(&self, index: &mut Vec<Entry>) -> usize {
+        let (key, size) = index.remove(0);
+        S::delete(self.key(&key));
+        size
+    }
+
+    /// Remove a key and its stored value
+    pub fn delete(&self, key: impl AsRef<str>) -> Result<()> {
+        let key = key.as_ref();
+        S::delete(self.key(key));
+        let mut index = self.index();
+        index.retain(|(k, _)| k != key);
+        self.set_index(&index)
+    }
+
+    /// Remove all the entries
+    pub fn clear(&self) {
+        for (key, _) in self.index() {
+            S::delete(self.key(&key));
+        }
+        S::delete(self.index_key());
+    }
+
+    /// Get the keys of the entries, from the least to the most recently used
+    pub fn keys(&self) -> Vec<String> {
+        self.index().into_iter().map(|(key, _)| key).collect()
+    }
+
+    /// Get the number of entries
+    pub fn length(&self) -> u32 {
+        self.index().len() as u32
+    }
+}
+
+impl<S> Clone for LruStorage<S> {
+    fn clone(&self) -> Self {
+        Self {
+            prefix: self.prefix.clone(),
+            budget: self.budget,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S> fmt::Debug for LruStorage<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LruStorage")
+            .field("storage", &std::any::type_name::<S>())
+            .field("prefix", &self.prefix)
+            .field("budget", &self.budget)
+            .finish()
+    }
+}
@@ -0,0 +1,81 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use crate::errors::StorageError;
+use crate::{Result, Storage};
+
+thread_local! {
+    static ITEMS: RefCell<BTreeMap<String, String>> = RefCell::new(BTreeMap::new());
+}
+
+/// Provides the [`Storage`] API over an in-memory map
+///
+/// The data lives as long as the thread, or the page, and is shared by all the code using
+/// `MemoryStorage` on that thread. Unlike [`LocalStorage`](crate::LocalStorage), it works on
+/// any target, so code that is generic over [`Storage`] can be unit tested natively or run
+/// during server-side rendering:
+///
+/// ```rust
+/// use gloo_storage::{LocalStorage, MemoryStorage, Storage};
+///
+/// fn visit<S: Storage>() -> u32 {
+///     let visits = S::get("visits").unwrap_or(0) + 1;
+///     S::set("visits", visits).unwrap();
+///     visits
+/// }
+///
+/// # fn no_run() {
+/// // in the browser
+/// visit::<LocalStorage>();
+/// # }
+/// // in tests
+/// assert_eq!(visit::<MemoryStorage>(), 1);
+/// assert_eq!(visit::<MemoryStorage>(), 2);
+/// ```
+///
+/// [`try_raw`](Storage::try_raw) and [`subscribe`](Storage::subscribe) need a browser storage
+/// and return [`StorageError::Unsupported`] when called on `MemoryStorage`, while
+/// [`raw`](Storage::raw) panics.
+#[derive(Debug)]
+pub struct MemoryStorage;
+
+impl Storage for MemoryStorage {
+    fn raw() -> web_sys::Storage {
+        panic!("MemoryStorage is not backed by a web_sys::Storage")
+    }
+
+    fn try_raw() -> Result<web_sys::Storage> {
+        Err(StorageError::Unsupported(
+            "MemoryStorage is not backed by a web_sys::Storage".to_string(),
+        ))
+    }
+
+    fn get_item(key: &str) -> Result<Option<String>> {
+        Ok(ITEMS.with(|items| items.borrow().get(key).cloned()))
+    }
+
+    fn set_item(key: &str, value: &str) -> Result<()> {
+        ITEMS.with(|items| {
+            items
+                .borrow_mut()
+                .insert(key.to_string(), value.to_string())
+        });
+        Ok(())
+    }
+
+    fn key(index: u32) -> Result<Option<String>> {
+        Ok(ITEMS.with(|items| items.borrow().keys().nth(index as usize).cloned()))
+    }
+
+    fn delete(key: impl AsRef<str>) {
+        ITEMS.with(|items| items.borrow_mut().remove(key.as_ref()));
+    }
+
+    fn clear() {
+        ITEMS.with(|items| items.borrow_mut().clear());
+    }
+
+    fn length() -> u32 {
+        ITEMS.with(|items| items.borrow().len() as u32)
+    }
+}
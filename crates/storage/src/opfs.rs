@@ -0,0 +1,247 @@
+//! Storage in the
+//! [Origin Private File System](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system).
+//!
+//! The OPFS is a file system private to the origin, backed by the same quota as IndexedDB and
+//! the Cache API, so it can hold far more than [`LocalStorage`](crate::LocalStorage). Each key
+//! is stored as a file in a directory, which makes it well suited to large binary values. Since
+//! every operation is asynchronous, [`OpfsStorage`] mirrors the methods of the
+//! [`Storage`](crate::Storage) trait as `async fn`s instead of implementing it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use gloo_storage::opfs::OpfsStorage;
+//!
+//! # async fn no_run() -> gloo_storage::Result<()> {
+//! let models = OpfsStorage::open("models").await?;
+//! models.set_bytes("weights.bin", &[0x00, 0x01, 0x02]).await?;
+//! models.set("manifest", &vec!["weights.bin"]).await?;
+//!
+//! // large files can be read lazily, as a `Blob`
+//! let weights = models.get_file("weights.bin").await?;
+//! let size = weights.size();
+//! # Ok(())
+//! # }
+//! ```
+
+use js_sys::{AsyncIterator, Function, Reflect, Uint8Array};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    File, FileSystemDirectoryHandle, FileSystemFileHandle, FileSystemGetDirectoryOptions,
+    FileSystemGetFileOptions, FileSystemRemoveOptions, FileSystemWritableFileStream,
+    StorageManager,
+};
+
+use crate::errors::{rejection_to_error, StorageError};
+use crate::{Codec, Json, Result};
+
+async fn resolve(promise: js_sys::Promise) -> Result<JsValue> {
+    JsFuture::from(promise).await.map_err(rejection_to_error)
+}
+
+fn is_not_found(error: &JsValue) -> bool {
+    Reflect::get(error, &JsValue::from_str("name"))
+        .ok()
+        .and_then(|name| name.as_string())
+        .map_or(false, |name| name == "NotFoundError")
+}
+
+fn manager() -> Result<StorageManager> {
+    let navigator = Reflect::get(&js_sys::global(), &JsValue::from_str("navigator"))
+        .map_err(rejection_to_error)?;
+    let manager = if navigator.is_object() {
+        Reflect::get(&navigator, &JsValue::from_str("storage")).map_err(rejection_to_error)?
+    } else {
+        JsValue::UNDEFINED
+    };
+    let supported = manager.is_object()
+        && Reflect::has(&manager, &JsValue::from_str("getDirectory")).unwrap_or(false);
+    if !supported {
+        let error = js_sys::Error::new("the Origin Private File System is not available");
+        error.set_name("NotSupportedError");
+        return Err(rejection_to_error(error.into()));
+    }
+    Ok(manager.unchecked_into())
+}
+
+/// Encodes `key` into a valid file name.
+fn file_name(key: &str) -> String {
+    let name = String::from(js_sys::encode_uri_component(key));
+    // `encodeURIComponent` leaves dots alone, but these two are not valid file names.
+    match name.as_str() {
+        "." | ".." => name.replace('.', "%2E"),
+        _ => name,
+    }
+}
+
+fn file_key(name: &str) -> Option<String> {
+    js_sys::decode_uri_component(name).ok().map(String::from)
+}
+
+/// A directory of the Origin Private File System used as an async key-value store.
+///
+/// Values are stored as JSON, or as raw bytes with [`set_bytes`](Self::set_bytes), one file per
+/// key.
+#[derive(Debug, Clone)]
+pub struct OpfsStorage {
+    dir: FileSystemDirectoryHandle,
+}
+
+impl OpfsStorage {
+    /// Opens the root directory of the origin.
+    ///
+    /// The root is shared with any other code using the OPFS, so prefer [`open`](Self::open).
+    pub async fn root() -> Result<Self> {
+        let dir = resolve(manager()?.get_directory()).await?;
+        Ok(Self {
+            dir: dir.unchecked_into(),
+        })
+    }
+
+    /// Opens the directory `name` of the root, creating it if it does not exist.
+    pub async fn open(name: &str) -> Result<Self> {
+        let root = Self::root().await?;
+        let mut options = FileSystemGetDirectoryOptions::new();
+        options.create(true);
+        let dir = resolve(
+            root.dir
+                .get_directory_handle_with_options(&file_name(name), &options),
+        )
+        .await?;
+        Ok(Self {
+            dir: dir.unchecked_into(),
+        })
+    }
+
+    /// Deletes the directory `name` of the root and everything stored in it.
+    ///
+    /// Returns `false` if the directory did not exist.
+    pub async fn delete_dir(name: &str) -> Result<bool> {
+        let root = Self::root().await?;
+        root.remove(&file_name(name), true).await
+    }
+
+    /// Gets the raw [`web_sys::FileSystemDirectoryHandle`].
+    pub fn raw(&self) -> &FileSystemDirectoryHandle {
+        &self.dir
+    }
+
+    /// Get the value for the specified key
+    pub async fn get<T>(&self, key: impl AsRef<str>) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let file = self.get_file(key).await?;
+        let text = resolve(file.text()).await?;
+        Json::decode(&text.as_string().unwrap_or_default())
+    }
+
+    /// Get the bytes stored for the specified key
+    pub async fn get_bytes(&self, key: impl AsRef<str>) -> Result<Vec<u8>> {
+        let file = self.get_file(key).await?;
+        let buffer = resolve(file.array_buffer()).await?;
+        Ok(Uint8Array::new(&buffer).to_vec())
+    }
+
+    /// Get the file stored for the specified key
+    ///
+    /// The content of the file is only read when requested, e.g. with
+    /// [`Blob::stream`](web_sys::Blob::stream).
+    pub async fn get_file(&self, key: impl AsRef<str>) -> Result<File> {
+        let key = key.as_ref();
+        let handle = match JsFuture::from(self.dir.get_file_handle(&file_name(key))).await {
+            Ok(handle) => handle.unchecked_into::<FileSystemFileHandle>(),
+            Err(e) if is_not_found(&e) => return Err(StorageError::KeyNotFound(key.to_string())),
+            Err(e) => return Err(rejection_to_error(e)),
+        };
+        Ok(resolve(handle.get_file()).await?.unchecked_into())
+    }
+
+    /// Insert a value for the specified key
+    pub async fn set<T>(&self, key: impl AsRef<str>, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = Json::encode(value)?;
+        self.set_bytes(key, value.as_bytes()).await
+    }
+
+    /// Insert bytes for the specified key
+    ///
+    /// The file is replaced atomically once all the bytes are written.
+    pub async fn set_bytes(&self, key: impl AsRef<str>, value: &[u8]) -> Result<()> {
+        let mut options = FileSystemGetFileOptions::new();
+        options.create(true);
+        let handle: FileSystemFileHandle = resolve(
+            self.dir
+                .get_file_handle_with_options(&file_name(key.as_ref()), &options),
+        )
+        .await?
+        .unchecked_into();
+        let writable: FileSystemWritableFileStream =
+            resolve(handle.create_writable()).await?.unchecked_into();
+        let written = match writable.write_with_u8_array(value) {
+            Ok(promise) => resolve(promise).await.map(drop),
+            Err(e) => Err(rejection_to_error(e)),
+        };
+        if let Err(e) = written {
+            // Discard the partial write, keeping the previous content.
+            let _ = JsFuture::from(writable.abort()).await;
+            return Err(e);
+        }
+        resolve(writable.close()).await?;
+        Ok(())
+    }
+
+    /// Remove a key and its stored value
+    ///
+    /// Returns `false` if the key was not found.
+    pub async fn delete(&self, key: impl AsRef<str>) -> Result<bool> {
+        self.remove(&file_name(key.as_ref()), false).await
+    }
+
+    /// Get all the stored keys
+    pub async fn keys(&self) -> Result<Vec<String>> {
+        // `keys()` returns an async iterator, which web-sys does not bind.
+        let keys = Reflect::get(&self.dir, &JsValue::from_str("keys"))
+            .and_then(|keys| keys.unchecked_into::<Function>().call0(&self.dir))
+            .map_err(rejection_to_error)?
+            .unchecked_into::<AsyncIterator>();
+        let mut names = Vec::new();
+        loop {
+            let next = resolve(keys.next().map_err(rejection_to_error)?).await?;
+            let next = next.unchecked_into::<js_sys::IteratorNext>();
+            if next.done() {
+                break;
+            }
+            names.extend(next.value().as_string());
+        }
+        Ok(names.iter().filter_map(|name| file_key(name)).collect())
+    }
+
+    /// Get the number of items stored
+    pub async fn length(&self) -> Result<u32> {
+        Ok(self.keys().await?.len() as u32)
+    }
+
+    /// Remove all the stored data
+    pub async fn clear(&self) -> Result<()> {
+        for key in self.keys().await? {
+            self.delete(key).await?;
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, name: &str, recursive: bool) -> Result<bool> {
+        let mut options = FileSystemRemoveOptions::new();
+        options.recursive(recursive);
+        match JsFuture::from(self.dir.remove_entry_with_options(name, &options)).await {
+            Ok(_) => Ok(true),
+            Err(e) if is_not_found(&e) => Ok(false),
+            Err(e) => Err(rejection_to_error(e)),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::StorageManager;
+
+use crate::errors::rejection_to_error;
+use crate::Result;
+
+/// How much storage the origin uses and may use, returned by [`estimate`].
+///
+/// Browsers deliberately make both numbers imprecise, so they should only be used to decide
+/// whether a large write is likely to fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageEstimate {
+    usage: u64,
+    quota: u64,
+}
+
+impl StorageEstimate {
+    /// The number of bytes used by the origin, across all its storage APIs
+    pub fn usage(&self) -> u64 {
+        self.usage
+    }
+
+    /// The number of bytes the origin may use
+    pub fn quota(&self) -> u64 {
+        self.quota
+    }
+
+    /// The number of bytes that may still be written
+    pub fn available(&self) -> u64 {
+        self.quota.saturating_sub(self.usage)
+    }
+}
+
+fn manager() -> Result<StorageManager> {
+    let navigator = Reflect::get(&js_sys::global(), &JsValue::from_str("navigator"))
+        .map_err(rejection_to_error)?;
+    let manager = if navigator.is_object() {
+        Reflect::get(&navigator, &JsValue::from_str("storage")).map_err(rejection_to_error)?
+    } else {
+        JsValue::UNDEFINED
+    };
+    if manager.is_undefined() || manager.is_null() {
+        // `navigator.storage` is only exposed to secure contexts.
+        let error = js_sys::Error::new("the Storage API is not available");
+        error.set_name("NotSupportedError");
+        return Err(rejection_to_error(error.into()));
+    }
+    Ok(manager.unchecked_into())
+}
+
+async fn resolve(promise: std::result::Result<js_sys::Promise, JsValue>) -> Result<JsValue> {
+    JsFuture::from(promise.map_err(rejection_to_error)?)
+        .await
+        .map_err(rejection_to_error)
+}
+
+fn field(estimate: &JsValue, name: &str) -> u64 {
+    Reflect::get(estimate, &JsValue::from_str(name))
+        .ok()
+        .and_then(|value| value.as_f64())
+        .unwrap_or(0.0) as u64
+}
+
+/// Estimate how much storage the origin uses and how much it may use.
+///
+/// ```rust
+/// # async fn no_run() -> gloo_storage::Result<()> {
+/// let estimate = gloo_storage::estimate().await?;
+/// if estimate.available() < 50 * 1024 * 1024 && !gloo_storage::persist().await? {
+///     // warn the user before downloading
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn estimate() -> Result<StorageEstimate> {
+    let estimate = resolve(manager()?.estimate()).await?;
+    Ok(StorageEstimate {
+        usage: field(&estimate, "usage"),
+        quota: field(&estimate, "quota"),
+    })
+}
+
+/// Ask the browser to keep the origin's data under storage pressure, instead of evicting it.
+///
+/// Returns whether the storage is now persistent. Browsers may prompt the user, or decide on
+/// their own based on how the site is used.
+pub async fn persist() -> Result<bool> {
+    Ok(resolve(manager()?.persist()).await?.is_truthy())
+}
+
+/// Whether the origin's data is kept under storage pressure, see [`persist`].
+pub async fn persisted() -> Result<bool> {
+    Ok(resolve(manager()?.persisted()).await?.is_truthy())
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::errors::StorageError;
+use crate::{Codec, Json, Result, Storage, StorageSubscription};
+
+/// A view of a [`Storage`] restricted to the keys starting with a prefix, returned by
+/// [`Storage::scoped`].
+///
+/// Keys are given without the prefix and all operations, including [`clear`](Self::clear), only
+/// touch the keys within it. This keeps several apps, or several versions of the same app, from
+/// overwriting each other's data when they share an origin.
+///
+/// ```rust
+/// use gloo_storage::{LocalStorage, Storage};
+///
+/// # fn no_run() {
+/// let storage = LocalStorage::scoped("myapp:");
+/// // stored under `myapp:theme`
+/// storage.set("theme", "dark").unwrap();
+/// storage.clear();
+/// # }
+/// ```
+pub struct ScopedStorage<S, C = Json> {
+    prefix: String,
+    _marker: PhantomData<(S, C)>,
+}
+
+impl<S: Storage, C: Codec> ScopedStorage<S, C> {
+    /// Create a view of the keys of `S` starting with `prefix`
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Encode the values with the codec `D` instead, see [`Codec`]
+    pub fn with_codec<D: Codec>(self) -> ScopedStorage<S, D> {
+        ScopedStorage {
+            prefix: self.prefix,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The prefix of the keys
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// Get the value for the specified key
+    pub fn get<T>(&self, key: impl AsRef<str>) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        S::get_with::<C, T>(self.key(key.as_ref()))
+    }
+
+    /// Get all the keys within the prefix and their values
+    ///
+    /// The keys of the returned map have the prefix stripped.
+    pub fn get_all<T>(&self) -> Result<T>
+    where
+        T: for<'a> Deserialize<'a>,
+    {
+        let keys = self.keys()?;
+        let mut map = Map::with_capacity(keys.len());
+        for key in keys {
+            match self.get::<Value>(&key) {
+                Ok(value) => {
+                    map.insert(key, value);
+                }
+                Err(StorageError::KeyNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(serde_json::from_value(Value::Object(map))?)
+    }
+
+    /// Get all the keys within the prefix, with the prefix stripped
+    pub fn keys(&self) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        for index in 0..S::length() {
+            let key = match S::key(index)? {
+                Some(key) => key,
+                None => continue,
+            };
+            if let Some(key) = key.strip_prefix(&self.prefix) {
+                keys.push(key.to_string());
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Insert a value for the specified key
+    pub fn set<T>(&self, key: impl AsRef<str>, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        S::set_with::<C, T>(self.key(key.as_ref()), value)
+    }
+
+    /// Insert a value for the specified key, which is treated as absent once `ttl` has elapsed
+    ///
+    /// See [`Storage::set_with_ttl`]. The value is stored as JSON, whatever the codec.
+    pub fn set_with_ttl<T>(&self, key: impl AsRef<str>, value: T, ttl: Duration) -> Result<()>
+    where
+        T: Serialize,
+    {
+        S::set_with_ttl(self.key(key.as_ref()), value, ttl)
+    }
+
+    /// Remove a key and it's stored value
+    pub fn delete(&self, key: impl AsRef<str>) {
+        S::delete(self.key(key.as_ref()))
+    }
+
+    /// Remove all the keys within the prefix
+    ///
+    /// Does nothing if the storage can't be accessed.
+    pub fn clear(&self) {
+        // Collect first, as removing keys shifts the indexes of the remaining ones.
+        for key in self.keys().unwrap_or_default() {
+            self.delete(key);
+        }
+    }
+
+    /// Get the number of items stored within the prefix, or 0 if the storage can't be accessed
+    pub fn length(&self) -> u32 {
+        self.keys().map_or(0, |keys| keys.len() as u32)
+    }
+
+    /// Subscribe to the changes made to the value of the specified key by other documents
+    ///
+    /// See [`Storage::subscribe`].
+    pub fn subscribe<T>(&self, key: impl AsRef<str>) -> Result<StorageSubscription<T, C>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        Ok(StorageSubscription::new(
+            S::try_raw()?,
+            self.key(key.as_ref()),
+        ))
+    }
+}
+
+impl<S, C> Clone for ScopedStorage<S, C> {
+    fn clone(&self) -> Self {
+        Self {
+            prefix: self.prefix.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S, C> fmt::Debug for ScopedStorage<S, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopedStorage")
+            .field("storage", &std::any::type_name::<S>())
+            .field("codec", &std::any::type_name::<C>())
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
@@ -1,6 +1,7 @@
 use wasm_bindgen::UnwrapThrowExt;
 
-use crate::Storage;
+use crate::errors::rejection_to_error;
+use crate::{Result, Storage};
 
 /// Provides API to deal with `sessionStorage`
 #[derive(Debug)]
@@ -14,4 +15,16 @@ impl Storage for SessionStorage {
             .expect_throw("failed to get session_storage")
             .expect_throw("no session storage")
     }
+
+    fn try_raw() -> Result<web_sys::Storage> {
+        let storage = web_sys::window()
+            .expect_throw("no window")
+            .session_storage()
+            .map_err(rejection_to_error)?;
+        storage.ok_or_else(|| {
+            let error = js_sys::Error::new("sessionStorage is not available");
+            error.set_name("NotSupportedError");
+            rejection_to_error(error.into())
+        })
+    }
 }
@@ -0,0 +1,146 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_channel::mpsc;
+use futures_core::{ready, Stream};
+use gloo_events::EventListener;
+use serde::Deserialize;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::StorageEvent;
+
+use crate::{ttl, Codec, Json, Result};
+
+/// A change to a stored value, made from another document.
+///
+/// Changes received through a `SyncedStorage` may also come from the current document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageChange<T> {
+    /// The value before the change, or `None` if the key did not exist.
+    pub old_value: Option<T>,
+    /// The value after the change, or `None` if the key was removed or the storage was cleared.
+    pub new_value: Option<T>,
+}
+
+struct RawChange {
+    old_value: Option<String>,
+    new_value: Option<String>,
+}
+
+/// A [`Stream`] of the changes to a key, returned by
+/// [`Storage::subscribe`](crate::Storage::subscribe).
+///
+/// Values that fail to deserialize are reported as errors without ending the stream.
+pub struct StorageSubscription<T, C = Json> {
+    receiver: mpsc::UnboundedReceiver<RawChange>,
+    _listener: EventListener,
+    #[cfg(feature = "synced")]
+    channel: Option<web_sys::BroadcastChannel>,
+    _marker: PhantomData<fn() -> (T, C)>,
+}
+
+impl<T, C> StorageSubscription<T, C> {
+    pub(crate) fn new(storage: web_sys::Storage, key: String) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+        let window = web_sys::window().expect_throw("no window");
+        let listener = EventListener::new(&window, "storage", move |event| {
+            let event = event.unchecked_ref::<StorageEvent>();
+            if event.storage_area().as_ref() != Some(&storage) {
+                return;
+            }
+            match event.key() {
+                Some(changed) if changed == key => {}
+                // `clear()` was called.
+                None => {}
+                Some(_) => return,
+            }
+            let _ = sender.unbounded_send(RawChange {
+                old_value: event.old_value(),
+                new_value: event.new_value(),
+            });
+        });
+        Self {
+            receiver,
+            _listener: listener,
+            #[cfg(feature = "synced")]
+            channel: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Listens to the changes to `key` posted on `channel` by a
+    /// [`SyncedStorage`](crate::synced::SyncedStorage), and closes it when dropped.
+    #[cfg(feature = "synced")]
+    pub(crate) fn with_channel(channel: web_sys::BroadcastChannel, key: String) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+        let listener = EventListener::new(&channel, "message", move |event| {
+            let event = event.unchecked_ref::<web_sys::MessageEvent>();
+            let message = match event
+                .data()
+                .as_string()
+                .and_then(|data| serde_json::from_str::<crate::synced::Message>(&data).ok())
+            {
+                Some(message) => message,
+                None => return,
+            };
+            match message.key {
+                Some(changed) if changed == key => {}
+                // `clear()` was called.
+                None => {}
+                Some(_) => return,
+            }
+            let _ = sender.unbounded_send(RawChange {
+                old_value: message.old_value,
+                new_value: message.new_value,
+            });
+        });
+        Self {
+            receiver,
+            _listener: listener,
+            channel: Some(channel),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "synced")]
+impl<T, C> Drop for StorageSubscription<T, C> {
+    fn drop(&mut self) {
+        if let Some(channel) = &self.channel {
+            channel.close();
+        }
+    }
+}
+
+impl<T, C> Stream for StorageSubscription<T, C>
+where
+    C: Codec,
+    T: for<'de> Deserialize<'de>,
+{
+    type Item = Result<StorageChange<T>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let change = ready!(Pin::new(&mut self.receiver).poll_next(cx));
+        let parse = |value: Option<String>| -> Result<Option<T>> {
+            match value {
+                Some(value) => ttl::from_str::<C, T>(&value),
+                None => Ok(None),
+            }
+        };
+        Poll::Ready(change.map(|change| {
+            Ok(StorageChange {
+                old_value: parse(change.old_value)?,
+                new_value: parse(change.new_value)?,
+            })
+        }))
+    }
+}
+
+impl<T, C> fmt::Debug for StorageSubscription<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageSubscription")
+            .field("listener", &self._listener)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,158 @@
+//! A storage wrapper that notifies all the tabs of changes.
+//!
+//! The `storage` event behind [`Storage::subscribe`] only fires in the *other* documents of the
+//! origin, so a tab never observes its own writes. [`SyncedStorage`] also posts every write on a
+//! [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel),
+//! and [`SyncedStorage::subscribe`] listens to that channel instead, so every subscriber sees
+//! every change, wherever it was made.
+//!
+//! # Example
+//!
+//! ```rust
+//! use futures::StreamExt;
+//! use gloo_storage::synced::SyncedStorage;
+//! use gloo_storage::LocalStorage;
+//!
+//! # async fn no_run() -> gloo_storage::Result<()> {
+//! let storage = SyncedStorage::<LocalStorage>::new("app")?;
+//! let mut changes = storage.subscribe::<String>("theme")?;
+//!
+//! storage.set("theme", "dark")?;
+//! let change = changes.next().await.unwrap()?;
+//! assert_eq!(change.new_value.as_deref(), Some("dark"));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+use web_sys::BroadcastChannel;
+
+use crate::errors::js_to_error;
+use crate::{LocalStorage, Result, Storage, StorageSubscription};
+
+/// A change posted on the channel, with the raw stored strings.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Message {
+    /// `None` when the storage was cleared.
+    pub(crate) key: Option<String>,
+    pub(crate) old_value: Option<String>,
+    pub(crate) new_value: Option<String>,
+}
+
+fn open(name: &str) -> Result<BroadcastChannel> {
+    BroadcastChannel::new(&format!("gloo-storage:{}", name)).map_err(js_to_error)
+}
+
+/// Wraps a [`Storage`] to post its changes to all the tabs, see the [module docs](self).
+///
+/// All the `SyncedStorage`s opened with the same `name` share their changes, so `name` should
+/// be unique for each underlying storage. Only the writes made through a `SyncedStorage` are
+/// observed. Since [`SessionStorage`](crate::SessionStorage) is not shared between tabs, it is
+/// only synchronized within the current one.
+pub struct SyncedStorage<S = LocalStorage> {
+    name: String,
+    channel: BroadcastChannel,
+    _marker: PhantomData<S>,
+}
+
+impl<S: Storage> SyncedStorage<S> {
+    /// Open the channel `name`
+    pub fn new(name: impl Into<String>) -> Result<Self> {
+        let name = name.into();
+        Ok(Self {
+            channel: open(&name)?,
+            name,
+            _marker: PhantomData,
+        })
+    }
+
+    /// The name of the channel
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the value for the specified key
+    pub fn get<T>(&self, key: impl AsRef<str>) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        S::get(key)
+    }
+
+    /// Insert a value for the specified key, and notify the subscribers
+    pub fn set<T>(&self, key: impl AsRef<str>, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let key = key.as_ref();
+        let old_value = S::get_item(key)?;
+        S::set(key, value)?;
+        self.post(Message {
+            key: Some(key.to_string()),
+            old_value,
+            new_value: S::get_item(key)?,
+        })
+    }
+
+    /// Remove a key and its stored value, and notify the subscribers
+    pub fn delete(&self, key: impl AsRef<str>) -> Result<()> {
+        let key = key.as_ref();
+        let old_value = S::get_item(key)?;
+        S::delete(key);
+        self.post(Message {
+            key: Some(key.to_string()),
+            old_value,
+            new_value: None,
+        })
+    }
+
+    /// Remove all the stored data, and notify the subscribers
+    pub fn clear(&self) -> Result<()> {
+        S::clear();
+        self.post(Message {
+            key: None,
+            old_value: None,
+            new_value: None,
+        })
+    }
+
+    /// Subscribe to the changes made to `key` through any `SyncedStorage` with the same name,
+    /// in this tab or another one
+    ///
+    /// The subscription holds its own channel, which is closed when it is dropped.
+    pub fn subscribe<T>(&self, key: impl AsRef<str>) -> Result<StorageSubscription<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        Ok(StorageSubscription::with_channel(
+            open(&self.name)?,
+            key.as_ref().to_string(),
+        ))
+    }
+
+    fn post(&self, message: Message) -> Result<()> {
+        let message = serde_json::to_string(&message)?;
+        self.channel
+            .post_message(&JsValue::from_str(&message))
+            .map_err(js_to_error)
+    }
+}
+
+impl<S> Drop for SyncedStorage<S> {
+    fn drop(&mut self) {
+        self.channel.close();
+    }
+}
+
+impl<S> fmt::Debug for SyncedStorage<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncedStorage")
+            .field("storage", &std::any::type_name::<S>())
+            .field("name", &self.name)
+            .finish()
+    }
+}
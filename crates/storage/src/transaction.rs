@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::StorageError;
+use crate::{Codec, Json, Result, Storage};
+
+/// Writes the items in order, restoring the previous values if one of the writes fails.
+///
+/// `None` removes the item.
+pub(crate) fn write_all<S: Storage + ?Sized>(
+    items: impl IntoIterator<Item = (String, Option<String>)>,
+) -> Result<()> {
+    let mut previous: Vec<(String, Option<String>)> = Vec::new();
+    for (key, value) in items {
+        let result = S::get_item(&key).and_then(|old| {
+            match &value {
+                Some(value) => S::set_item(&key, value)?,
+                None => S::delete(&key),
+            }
+            Ok(old)
+        });
+        let old = match result {
+            Ok(old) => old,
+            Err(e) => {
+                // Undo in reverse order, so that a key written twice ends up with its original
+                // value. Restoring can only free space, so it is not expected to fail.
+                for (key, old) in previous.into_iter().rev() {
+                    match old {
+                        Some(old) => {
+                            let _ = S::set_item(&key, &old);
+                        }
+                        None => S::delete(&key),
+                    }
+                }
+                return Err(e);
+            }
+        };
+        previous.push((key, old));
+    }
+    Ok(())
+}
+
+/// Writes staged by [`Storage::transaction`], applied all at once when the closure succeeds.
+///
+/// Reads made through the transaction see its own staged writes.
+pub struct StorageTransaction<S> {
+    staged: BTreeMap<String, Option<String>>,
+    _marker: PhantomData<S>,
+}
+
+impl<S: Storage> StorageTransaction<S> {
+    pub(crate) fn new() -> Self {
+        Self {
+            staged: BTreeMap::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get the value for the specified key, including the writes staged so far
+    pub fn get<T>(&self, key: impl AsRef<str>) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let key = key.as_ref();
+        match self.staged.get(key) {
+            Some(Some(item)) => Json::decode(item),
+            Some(None) => Err(StorageError::KeyNotFound(key.to_string())),
+            None => S::get(key),
+        }
+    }
+
+    /// Stage a value for the specified key
+    ///
+    /// The value is serialized right away, so serialization errors are reported here rather
+    /// than when committing.
+    pub fn set<T>(&mut self, key: impl AsRef<str>, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let item = Json::encode(&value)?;
+        self.staged.insert(key.as_ref().to_string(), Some(item));
+        Ok(())
+    }
+
+    /// Stage the removal of a key
+    pub fn delete(&mut self, key: impl AsRef<str>) {
+        self.staged.insert(key.as_ref().to_string(), None);
+    }
+
+    pub(crate) fn commit(self) -> Result<()> {
+        write_all::<S>(self.staged)
+    }
+}
+
+impl<S> fmt::Debug for StorageTransaction<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageTransaction")
+            .field("storage", &std::any::type_name::<S>())
+            .field("staged", &self.staged.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
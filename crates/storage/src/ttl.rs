@@ -0,0 +1,71 @@
+//! The format of the entries stored with [`Storage::set_with_ttl`](crate::Storage::set_with_ttl).
+//!
+//! The value is wrapped in an object whose first field is the expiry, so that entries without a
+//! TTL can be told apart by looking at the start of the stored string, and are only parsed once.
+
+use std::time::Duration;
+
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+
+use crate::{Codec, Result};
+
+const EXPIRING_PREFIX: &str = "{\"__gloo_expires_at\":";
+
+#[derive(Serialize)]
+struct ExpiringRef<'a, T> {
+    // Must stay the first field, see `EXPIRING_PREFIX`.
+    #[serde(rename = "__gloo_expires_at")]
+    expires_at: f64,
+    value: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Expiring<T> {
+    #[serde(rename = "__gloo_expires_at")]
+    expires_at: f64,
+    value: T,
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+// Lets `MemoryStorage` use TTLs outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|now| now.as_secs_f64() * 1000.0)
+        .unwrap_or_default()
+}
+
+/// Serializes `value` so that it is considered absent once `ttl` has elapsed.
+pub(crate) fn to_string<T: Serialize>(value: &T, ttl: Duration) -> Result<String> {
+    Ok(serde_json::to_string(&ExpiringRef {
+        expires_at: now() + ttl.as_secs_f64() * 1000.0,
+        value,
+    })?)
+}
+
+/// Deserializes a stored item, returning `None` if it has expired.
+///
+/// Items stored without a TTL are decoded with `C`.
+pub(crate) fn from_str<C, T>(item: &str) -> Result<Option<T>>
+where
+    C: Codec,
+    T: for<'de> Deserialize<'de>,
+{
+    if !item.starts_with(EXPIRING_PREFIX) {
+        return Ok(Some(C::decode(item)?));
+    }
+    // Check the expiry first, so that stale values whose type has since changed are still
+    // reported as absent rather than as errors.
+    let expiring: Expiring<IgnoredAny> = serde_json::from_str(item)?;
+    if expiring.expires_at <= now() {
+        return Ok(None);
+    }
+    let expiring: Expiring<T> = serde_json::from_str(item)?;
+    Ok(Some(expiring.value))
+}
@@ -0,0 +1,148 @@
+use std::fmt;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::StorageError;
+use crate::{Result, Storage};
+
+const VERSIONED_PREFIX: &str = "{\"__gloo_version\":";
+
+#[derive(Serialize)]
+struct VersionedRef<'a, T> {
+    // Must stay the first field, see `VERSIONED_PREFIX`.
+    #[serde(rename = "__gloo_version")]
+    version: u32,
+    value: &'a T,
+}
+
+#[derive(Deserialize)]
+struct VersionedValue {
+    #[serde(rename = "__gloo_version")]
+    version: u32,
+    value: Value,
+}
+
+type Migration = Box<dyn Fn(Value) -> Result<Value>>;
+
+/// The steps to upgrade the stored form of a [`Versioned`] type to its current layout.
+///
+/// Versions start at 1, and each step migrates the value stored by one version to the next
+/// one. Steps work on the JSON of the value, so that the types of the old layouts don't have to
+/// be kept around.
+#[derive(Default)]
+pub struct Migrations {
+    steps: Vec<Migration>,
+}
+
+impl Migrations {
+    /// Create an empty list, for a type still at version 1
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the step from the current version to the next one
+    pub fn step(mut self, step: impl Fn(Value) -> Result<Value> + 'static) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    /// The version of the current layout
+    pub fn version(&self) -> u32 {
+        self.steps.len() as u32 + 1
+    }
+
+    fn migrate(&self, mut value: Value, from: u32) -> Result<Value> {
+        let current = self.version();
+        if from == 0 || from > current {
+            return Err(StorageError::SerdeError(serde_json::Error::custom(
+                format!("stored version {} is not between 1 and {}", from, current),
+            )));
+        }
+        for step in &self.steps[from as usize - 1..] {
+            value = step(value)?;
+        }
+        Ok(value)
+    }
+}
+
+impl fmt::Debug for Migrations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migrations")
+            .field("version", &self.version())
+            .finish()
+    }
+}
+
+/// A type whose stored layout is versioned, so that values stored by older versions of an app
+/// are migrated instead of failing to deserialize.
+///
+/// Values are stored along with their version by [`Storage::set_versioned`], and upgraded by
+/// [`Storage::get_versioned`]. Values stored without a version, e.g. with [`Storage::set`], are
+/// considered to be at version 1.
+///
+/// ```rust
+/// use gloo_storage::{LocalStorage, Migrations, Storage, Versioned};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Settings {
+///     theme: String,
+/// }
+///
+/// impl Versioned for Settings {
+///     fn migrations() -> Migrations {
+///         // version 1 was `{ "dark_mode": bool }`
+///         Migrations::new().step(|mut value| {
+///             let dark_mode = value["dark_mode"].take().as_bool().unwrap_or_default();
+///             Ok(serde_json::json!({ "theme": if dark_mode { "dark" } else { "light" } }))
+///         })
+///     }
+/// }
+///
+/// # fn no_run() {
+/// let settings: Settings = LocalStorage::get_versioned("settings").unwrap();
+/// # }
+/// ```
+pub trait Versioned: Serialize + for<'de> Deserialize<'de> {
+    /// The migrations from the first version to the current one
+    fn migrations() -> Migrations;
+}
+
+/// Reads a value stored by [`Storage::set_versioned`], migrating and storing it again if it is
+/// out of date.
+pub(crate) fn get<S, T>(key: &str) -> Result<T>
+where
+    S: Storage + ?Sized,
+    T: Versioned,
+{
+    let item = S::get_item(key)?.ok_or_else(|| StorageError::KeyNotFound(key.to_string()))?;
+    let migrations = T::migrations();
+    let current = migrations.version();
+    let (version, value) = if item.starts_with(VERSIONED_PREFIX) {
+        let item: VersionedValue = serde_json::from_str(&item)?;
+        (item.version, item.value)
+    } else {
+        (1, serde_json::from_str(&item)?)
+    };
+    if version == current {
+        return Ok(serde_json::from_value(value)?);
+    }
+    let value: T = serde_json::from_value(migrations.migrate(value, version)?)?;
+    // Store the migrated value, so that it is only migrated once.
+    set::<S, T>(key, &value)?;
+    Ok(value)
+}
+
+pub(crate) fn set<S, T>(key: &str, value: &T) -> Result<()>
+where
+    S: Storage + ?Sized,
+    T: Versioned,
+{
+    let item = serde_json::to_string(&VersionedRef {
+        version: T::migrations().version(),
+        value,
+    })?;
+    S::set_item(key, &item)
+}
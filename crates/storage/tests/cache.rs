@@ -0,0 +1,61 @@
+#![cfg(feature = "cache")]
+
+use gloo_storage::cache::CacheStorage;
+use gloo_storage::errors::StorageError;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Data {
+    name: String,
+    size: u32,
+}
+
+async fn open(name: &str) -> CacheStorage {
+    CacheStorage::delete_cache(name).await.unwrap();
+    CacheStorage::open(name).await.unwrap()
+}
+
+#[test]
+async fn get_and_set() {
+    let cache = open("get_and_set").await;
+    let data = Data {
+        name: "app.wasm".to_string(),
+        size: 4,
+    };
+    cache.set("data", &data).await.unwrap();
+    cache
+        .set_bytes("app.wasm/1?v=2", &[0, 97, 115, 109])
+        .await
+        .unwrap();
+
+    assert_eq!(cache.get::<Data>("data").await.unwrap(), data);
+    assert_eq!(
+        cache.get_bytes("app.wasm/1?v=2").await.unwrap(),
+        vec![0, 97, 115, 109]
+    );
+    assert!(matches!(
+        cache.get::<Data>("missing").await,
+        Err(StorageError::KeyNotFound(key)) if key == "missing"
+    ));
+}
+
+#[test]
+async fn keys_and_delete() {
+    let cache = open("keys_and_delete").await;
+    cache.set("a", "1").await.unwrap();
+    cache.set("b c", "2").await.unwrap();
+
+    let mut keys = cache.keys().await.unwrap();
+    keys.sort();
+    assert_eq!(keys, vec!["a", "b c"]);
+
+    assert!(cache.delete("a").await.unwrap());
+    assert!(!cache.delete("a").await.unwrap());
+    assert_eq!(cache.length().await.unwrap(), 1);
+
+    cache.clear().await.unwrap();
+    assert_eq!(cache.length().await.unwrap(), 0);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "compressed")]
+
+use gloo_storage::compressed::Compressed;
+use gloo_storage::{LocalStorage, Storage};
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+async fn round_trip() {
+    let articles: Vec<String> = (0..100).map(|i| format!("article number {i}")).collect();
+    Compressed::<LocalStorage>::set("compressed:large", &articles)
+        .await
+        .unwrap();
+
+    let raw = LocalStorage::raw()
+        .get_item("compressed:large")
+        .unwrap()
+        .unwrap();
+    assert!(raw.len() < serde_json::to_string(&articles).unwrap().len());
+    assert_eq!(
+        Compressed::<LocalStorage>::get::<Vec<String>>("compressed:large")
+            .await
+            .unwrap(),
+        articles
+    );
+}
+
+#[test]
+async fn small_values_are_plain() {
+    Compressed::<LocalStorage>::set("compressed:small", "value")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        LocalStorage::get::<String>("compressed:small").unwrap(),
+        "value"
+    );
+    assert_eq!(
+        Compressed::<LocalStorage>::get::<String>("compressed:small")
+            .await
+            .unwrap(),
+        "value"
+    );
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "cookie")]
+
+use std::time::Duration;
+
+use gloo_storage::cookie::{CookieOptions, CookieStorage, SameSite};
+use gloo_storage::errors::StorageError;
+use gloo_storage::Storage;
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn get_and_set() {
+    CookieStorage::set("cookie name", vec!["a; b", "c=d"]).unwrap();
+    assert_eq!(
+        CookieStorage::get::<Vec<String>>("cookie name").unwrap(),
+        vec!["a; b", "c=d"]
+    );
+    assert!(CookieStorage::length() >= 1);
+
+    CookieStorage::delete("cookie name");
+    assert!(CookieStorage::get::<Vec<String>>("cookie name").is_err());
+}
+
+#[test]
+fn options() {
+    let options = CookieOptions::new()
+        .path("/")
+        .max_age(Duration::from_secs(60))
+        .same_site(SameSite::Strict);
+    CookieStorage::set_with_options("with_options", 1, &options).unwrap();
+    assert_eq!(CookieStorage::get::<u32>("with_options").unwrap(), 1);
+
+    CookieStorage::delete_with_options("with_options", &options);
+    assert!(CookieStorage::get::<u32>("with_options").is_err());
+}
+
+#[test]
+fn web_storage_only_methods_fail() {
+    assert!(matches!(
+        CookieStorage::subscribe::<u32>("cookie"),
+        Err(StorageError::Unsupported(_))
+    ));
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "encrypted")]
+
+use gloo_storage::encrypted::EncryptedStorage;
+use gloo_storage::{LocalStorage, Storage};
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+async fn storage(password: &str) -> EncryptedStorage<LocalStorage> {
+    EncryptedStorage::from_password(password, b"0123456789abcdef", 1000)
+        .await
+        .unwrap()
+}
+
+#[test]
+async fn round_trip() {
+    let storage = storage("hunter2").await;
+    storage.set("encrypted:token", vec![1, 2, 3]).await.unwrap();
+
+    let stored: String = LocalStorage::get("encrypted:token").unwrap();
+    assert!(!stored.contains("[1,2,3]"));
+    assert_eq!(
+        storage.get::<Vec<u32>>("encrypted:token").await.unwrap(),
+        vec![1, 2, 3]
+    );
+}
+
+#[test]
+async fn wrong_password_or_key() {
+    storage("hunter2")
+        .await
+        .set("encrypted:a", "value")
+        .await
+        .unwrap();
+
+    assert!(storage("hunter3")
+        .await
+        .get::<String>("encrypted:a")
+        .await
+        .is_err());
+
+    let moved: String = LocalStorage::get("encrypted:a").unwrap();
+    LocalStorage::set("encrypted:b", moved).unwrap();
+    assert!(storage("hunter2")
+        .await
+        .get::<String>("encrypted:b")
+        .await
+        .is_err());
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "indexeddb")]
+
+use futures::TryStreamExt;
+use gloo_storage::indexeddb::{
+    CursorDirection, Database, IndexOptions, StoreOptions, TransactionMode,
+};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct User {
+    id: u32,
+    email: String,
+}
+
+fn user(id: u32) -> User {
+    User {
+        id,
+        email: format!("user{id}@example.com"),
+    }
+}
+
+async fn open(name: &str) -> Database {
+    Database::delete(name).await.unwrap();
+    Database::open(name, 1, |upgrade| {
+        assert_eq!(upgrade.old_version(), 0);
+        assert_eq!(upgrade.new_version(), 1);
+        let users = upgrade.create_object_store("users", &StoreOptions::new().key_path("id"))?;
+        users.create_index("by_email", "email", &IndexOptions::new().unique(true))?;
+        upgrade.create_object_store("notes", &StoreOptions::new().auto_increment(true))?;
+        Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[test]
+async fn put_and_get() {
+    let db = open("gloo-put-and-get").await;
+    assert_eq!(db.version(), 1);
+    assert_eq!(db.object_store_names(), ["notes", "users"]);
+
+    let tx = db
+        .transaction(&["users"], TransactionMode::ReadWrite)
+        .unwrap();
+    let users = tx.object_store::<u32, User>("users").unwrap();
+    assert_eq!(users.put(&user(1)).await.unwrap(), 1);
+    users.put(&user(2)).await.unwrap();
+    tx.commit().await.unwrap();
+
+    let tx = db
+        .transaction(&["users"], TransactionMode::ReadOnly)
+        .unwrap();
+    let users = tx.object_store::<u32, User>("users").unwrap();
+    assert_eq!(users.get(&1).await.unwrap(), Some(user(1)));
+    assert_eq!(users.get(&3).await.unwrap(), None);
+    assert_eq!(users.count().await.unwrap(), 2);
+    assert_eq!(users.get_all_keys().await.unwrap(), [1, 2]);
+    let by_email = users.index::<String>("by_email").unwrap();
+    assert_eq!(
+        by_email
+            .get(&"user2@example.com".to_string())
+            .await
+            .unwrap(),
+        Some(user(2))
+    );
+}
+
+#[test]
+async fn auto_increment() {
+    let db = open("gloo-auto-increment").await;
+    let tx = db
+        .transaction(&["notes"], TransactionMode::ReadWrite)
+        .unwrap();
+    let notes = tx.object_store::<u32, String>("notes").unwrap();
+    assert_eq!(notes.add(&"first".to_string()).await.unwrap(), 1);
+    assert_eq!(notes.add(&"second".to_string()).await.unwrap(), 2);
+    notes
+        .put_with_key(&"replaced".to_string(), &1)
+        .await
+        .unwrap();
+    notes.delete(&2).await.unwrap();
+    assert_eq!(notes.get_all().await.unwrap(), ["replaced"]);
+    tx.commit().await.unwrap();
+}
+
+#[test]
+async fn cursor() {
+    let db = open("gloo-cursor").await;
+    let tx = db
+        .transaction(&["users"], TransactionMode::ReadWrite)
+        .unwrap();
+    let users = tx.object_store::<u32, User>("users").unwrap();
+    for id in 1..=5 {
+        users.put(&user(id)).await.unwrap();
+    }
+    let keys: Vec<u32> = users
+        .cursor_in(2..5, CursorDirection::Prev)
+        .unwrap()
+        .map_ok(|(key, _)| key)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(keys, [4, 3, 2]);
+    assert_eq!(users.get_all_in(4..).await.unwrap(), [user(4), user(5)]);
+    tx.commit().await.unwrap();
+}
+
+#[test]
+async fn failed_request_aborts_transaction() {
+    let db = open("gloo-abort").await;
+    let tx = db
+        .transaction(&["users"], TransactionMode::ReadWrite)
+        .unwrap();
+    let users = tx.object_store::<u32, User>("users").unwrap();
+    users.add(&user(1)).await.unwrap();
+    assert!(users.add(&user(1)).await.is_err());
+    assert!(tx.commit().await.is_err());
+
+    let tx = db
+        .transaction(&["users"], TransactionMode::ReadOnly)
+        .unwrap();
+    let users = tx.object_store::<u32, User>("users").unwrap();
+    assert_eq!(users.count().await.unwrap(), 0);
+}
+
+#[test]
+async fn bytes() {
+    let db = open("gloo-bytes").await;
+    let tx = db
+        .transaction(&["notes"], TransactionMode::ReadWrite)
+        .unwrap();
+    let notes = tx.object_store::<u32, String>("notes").unwrap();
+    notes.set_bytes(&1, &[0, 1, 2, 255]).await.unwrap();
+    assert_eq!(notes.get_bytes(&1).await.unwrap(), Some(vec![0, 1, 2, 255]));
+    assert_eq!(notes.get_bytes(&2).await.unwrap(), None);
+    tx.commit().await.unwrap();
+}
+
+#[test]
+async fn upgrade_error() {
+    let name = "gloo-upgrade-error";
+    Database::delete(name).await.unwrap();
+    let result = Database::open(name, 1, |upgrade| {
+        upgrade.create_object_store("users", &StoreOptions::new())?;
+        upgrade.create_object_store("users", &StoreOptions::new())?;
+        Ok(())
+    })
+    .await;
+    assert!(result.is_err());
+}
@@ -1,5 +1,10 @@
-use gloo_storage::{LocalStorage, Storage};
-use serde::Deserialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures::StreamExt;
+use gloo_storage::errors::StorageError;
+use gloo_storage::{Codec, Json, LocalStorage, Storage, StorageCell};
+use serde::{Deserialize, Serialize};
 use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -40,3 +45,189 @@ fn set_and_length() {
     LocalStorage::clear();
     assert_eq!(LocalStorage::length(), 0);
 }
+
+fn dispatch_storage_event(key: Option<&str>, old_value: Option<&str>, new_value: Option<&str>) {
+    let mut init = web_sys::StorageEventInit::new();
+    init.key(key)
+        .old_value(old_value)
+        .new_value(new_value)
+        .storage_area(Some(&LocalStorage::raw()));
+    let event = web_sys::StorageEvent::new_with_event_init_dict("storage", &init).unwrap();
+    web_sys::window().unwrap().dispatch_event(&event).unwrap();
+}
+
+#[test]
+async fn subscribe() {
+    let mut changes = LocalStorage::subscribe::<u32>("counter").unwrap();
+    dispatch_storage_event(Some("other"), None, Some("1"));
+    dispatch_storage_event(Some("counter"), Some("1"), Some("2"));
+    dispatch_storage_event(Some("counter"), Some("2"), Some("not json"));
+    dispatch_storage_event(None, None, None);
+
+    let change = changes.next().await.unwrap().unwrap();
+    assert_eq!(change.old_value, Some(1));
+    assert_eq!(change.new_value, Some(2));
+    assert!(changes.next().await.unwrap().is_err());
+    let change = changes.next().await.unwrap().unwrap();
+    assert_eq!(change.new_value, None);
+}
+
+#[test]
+fn scoped() {
+    let v1 = LocalStorage::scoped("scoped:v1:");
+    let v2 = LocalStorage::scoped("scoped:v2:");
+    v1.clear();
+    v2.clear();
+    LocalStorage::set("scoped:other", 0).unwrap();
+
+    v1.set("count", 1).unwrap();
+    v2.set("count", 2).unwrap();
+    v2.set("name", "v2").unwrap();
+
+    assert_eq!(v1.get::<u32>("count").unwrap(), 1);
+    assert_eq!(LocalStorage::get::<u32>("scoped:v2:count").unwrap(), 2);
+    assert_eq!(v1.length(), 1);
+
+    #[derive(Deserialize)]
+    struct V2 {
+        count: u32,
+        name: String,
+    }
+    let all: V2 = v2.get_all().unwrap();
+    assert_eq!((all.count, all.name.as_str()), (2, "v2"));
+
+    v2.clear();
+    assert_eq!(v2.length(), 0);
+    assert_eq!(v1.get::<u32>("count").unwrap(), 1);
+    assert_eq!(LocalStorage::get::<u32>("scoped:other").unwrap(), 0);
+}
+
+/// Stores JSON backwards, to tell it apart from the default codec.
+struct Reversed;
+
+impl Codec for Reversed {
+    fn encode<T>(value: &T) -> gloo_storage::Result<String>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(Json::encode(value)?.chars().rev().collect())
+    }
+
+    fn decode<T>(item: &str) -> gloo_storage::Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        Json::decode(&item.chars().rev().collect::<String>())
+    }
+}
+
+#[test]
+fn codec() {
+    LocalStorage::set_with::<Reversed, _>("codec:call", [1, 2]).unwrap();
+    assert_eq!(
+        LocalStorage::raw().get_item("codec:call").unwrap().unwrap(),
+        "]2,1["
+    );
+    assert_eq!(
+        LocalStorage::get_with::<Reversed, Vec<u32>>("codec:call").unwrap(),
+        vec![1, 2]
+    );
+
+    let storage = LocalStorage::with_codec::<Reversed>();
+    storage.set("codec:handle", "value").unwrap();
+    assert_eq!(
+        LocalStorage::raw()
+            .get_item("codec:handle")
+            .unwrap()
+            .unwrap(),
+        "\"eulav\""
+    );
+    assert_eq!(storage.get::<String>("codec:handle").unwrap(), "value");
+}
+
+#[test]
+fn get_and_set_many() {
+    LocalStorage::set_many([("many:a", 1), ("many:b", 2)]).unwrap();
+    let values: Vec<Option<u32>> =
+        LocalStorage::get_many(["many:a", "many:missing", "many:b"]).unwrap();
+    assert_eq!(values, vec![Some(1), None, Some(2)]);
+}
+
+#[test]
+fn set_many_rolls_back() {
+    LocalStorage::set("rollback:a", "old").unwrap();
+    LocalStorage::delete("rollback:b");
+
+    // Far above any browser's quota, so that the last write throws.
+    let huge = "x".repeat(16 * 1024 * 1024);
+    let result = LocalStorage::set_many([
+        ("rollback:a", "new"),
+        ("rollback:b", "new"),
+        ("rollback:huge", huge.as_str()),
+    ]);
+    assert!(result.is_err());
+    assert_eq!(LocalStorage::get::<String>("rollback:a").unwrap(), "old");
+    assert!(LocalStorage::get::<String>("rollback:b").is_err());
+}
+
+#[test]
+fn quota_exceeded() {
+    let huge = "x".repeat(16 * 1024 * 1024);
+    match LocalStorage::set("quota:huge", huge) {
+        Err(StorageError::QuotaExceeded(error)) => assert_eq!(error.name, "QuotaExceededError"),
+        result => panic!("expected QuotaExceeded, got {:?}", result),
+    }
+    assert!(LocalStorage::get::<String>("quota:huge").is_err());
+}
+
+#[test]
+fn transaction() {
+    LocalStorage::set("txn:balance", 100).unwrap();
+
+    let result = LocalStorage::transaction(|txn| {
+        let balance: u32 = txn.get("txn:balance")?;
+        txn.set("txn:balance", balance - 10)?;
+        assert_eq!(txn.get::<u32>("txn:balance")?, 90);
+        txn.delete("txn:balance");
+        assert!(txn.get::<u32>("txn:balance").is_err());
+        txn.set("txn:balance", balance - 10)?;
+        Ok(balance - 10)
+    });
+    assert_eq!(result.unwrap(), 90);
+    assert_eq!(LocalStorage::get::<u32>("txn:balance").unwrap(), 90);
+
+    let result: gloo_storage::Result<()> = LocalStorage::transaction(|txn| {
+        txn.set("txn:balance", 0)?;
+        txn.get::<u32>("txn:missing")?;
+        Ok(())
+    });
+    assert!(result.is_err());
+    assert_eq!(LocalStorage::get::<u32>("txn:balance").unwrap(), 90);
+}
+
+#[test]
+async fn storage_cell() {
+    LocalStorage::set("cell:count", 1).unwrap();
+    let cell = StorageCell::<u32>::new("cell:count");
+    assert_eq!(cell.get(), 1);
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let subscription = cell.subscribe({
+        let seen = Rc::clone(&seen);
+        move |value| seen.borrow_mut().push(*value)
+    });
+    let mut changes = cell.changes();
+
+    cell.update(|count| *count += 1).unwrap();
+    assert_eq!(LocalStorage::get::<u32>("cell:count").unwrap(), 2);
+    assert_eq!(changes.next().await, Some(2));
+
+    // Changes made by other documents.
+    dispatch_storage_event(Some("cell:count"), Some("2"), Some("5"));
+    assert_eq!(cell.get(), 5);
+    assert_eq!(changes.next().await, Some(5));
+
+    drop(subscription);
+    cell.set(6).unwrap();
+    assert_eq!(*seen.borrow(), vec![2, 5]);
+}
@@ -0,0 +1,45 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use gloo_storage::{LruStorage, MemoryStorage, Storage};
+
+#[test]
+fn evicts_least_recently_used() {
+    // Each entry takes 1 byte of key and 3 of value.
+    let cache = LruStorage::<MemoryStorage>::new("lru:", 12);
+    cache.set("a", "a").unwrap();
+    cache.set("b", "b").unwrap();
+    cache.set("c", "c").unwrap();
+    assert_eq!(cache.size(), 12);
+
+    assert_eq!(cache.get::<String>("a").unwrap(), "a");
+    cache.set("d", "d").unwrap();
+    assert_eq!(cache.keys(), ["c", "a", "d"]);
+    assert!(cache.get::<String>("b").is_err());
+    assert!(MemoryStorage::get::<String>("lru:b").is_err());
+
+    // Replacing an entry only accounts for its new size.
+    cache.set("d", "dd").unwrap();
+    assert_eq!(cache.keys(), ["a", "d"]);
+    assert_eq!(cache.size(), 9);
+}
+
+#[test]
+fn delete_and_clear() {
+    let cache = LruStorage::<MemoryStorage>::new("lru:", 100);
+    cache.set("a", 1).unwrap();
+    cache.set("b", 2).unwrap();
+    MemoryStorage::set("other", 3).unwrap();
+
+    cache.delete("a").unwrap();
+    assert_eq!(cache.keys(), ["b"]);
+
+    // Entries removed directly from the storage are dropped from the index.
+    MemoryStorage::delete("lru:b");
+    assert!(cache.get::<u32>("b").is_err());
+    assert_eq!(cache.length(), 0);
+
+    cache.set("c", 4).unwrap();
+    cache.clear();
+    assert_eq!(cache.length(), 0);
+    assert_eq!(MemoryStorage::length(), 1);
+}
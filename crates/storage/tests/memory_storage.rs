@@ -0,0 +1,131 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use gloo_storage::errors::StorageError;
+use gloo_storage::{MemoryStorage, Storage};
+use serde::Deserialize;
+
+#[test]
+fn get_set_and_delete() {
+    MemoryStorage::set("key", "value").unwrap();
+    assert_eq!(MemoryStorage::get::<String>("key").unwrap(), "value");
+
+    MemoryStorage::delete("key");
+    assert!(matches!(
+        MemoryStorage::get::<String>("key"),
+        Err(StorageError::KeyNotFound(key)) if key == "key"
+    ));
+}
+
+#[test]
+fn get_all_and_clear() {
+    #[derive(Deserialize)]
+    struct Data {
+        key1: String,
+        key2: u32,
+    }
+
+    MemoryStorage::set("key1", "value").unwrap();
+    MemoryStorage::set("key2", 2).unwrap();
+    assert_eq!(MemoryStorage::length(), 2);
+
+    let data: Data = MemoryStorage::get_all().unwrap();
+    assert_eq!((data.key1.as_str(), data.key2), ("value", 2));
+
+    MemoryStorage::clear();
+    assert_eq!(MemoryStorage::length(), 0);
+}
+
+#[test]
+fn ttl_and_scoped() {
+    MemoryStorage::set_with_ttl("stale", "value", Duration::ZERO).unwrap();
+    assert!(MemoryStorage::get::<String>("stale").is_err());
+    assert_eq!(MemoryStorage::length(), 0);
+
+    let scoped = MemoryStorage::scoped("app:");
+    scoped.set("key", 1).unwrap();
+    assert_eq!(MemoryStorage::get::<u32>("app:key").unwrap(), 1);
+    assert_eq!(scoped.keys().unwrap(), vec!["key"]);
+}
+
+#[test]
+fn get_all_with_expired_entries() {
+    MemoryStorage::set_with_ttl("a", 1, Duration::ZERO).unwrap();
+    MemoryStorage::set("b", 2).unwrap();
+
+    // Removing the expired entry doesn't skip the next one.
+    let all: HashMap<String, u32> = MemoryStorage::get_all().unwrap();
+    assert_eq!(all, HashMap::from([("b".to_string(), 2)]));
+    assert_eq!(MemoryStorage::length(), 1);
+}
+
+#[test]
+fn transaction() {
+    let result: gloo_storage::Result<()> = MemoryStorage::transaction(|txn| {
+        txn.set("a", 1)?;
+        txn.get::<u32>("missing")?;
+        Ok(())
+    });
+    assert!(result.is_err());
+    assert_eq!(MemoryStorage::length(), 0);
+
+    MemoryStorage::transaction(|txn| txn.set("a", 1)).unwrap();
+    assert_eq!(MemoryStorage::get::<u32>("a").unwrap(), 1);
+}
+
+#[test]
+fn bytes() {
+    MemoryStorage::set_bytes("bytes", &[0, 1, 2, 255]).unwrap();
+    assert_eq!(
+        MemoryStorage::get_item("bytes").unwrap().unwrap(),
+        "AAEC/w=="
+    );
+    assert_eq!(MemoryStorage::get_bytes("bytes").unwrap(), [0, 1, 2, 255]);
+    assert!(MemoryStorage::get_bytes("missing").is_err());
+
+    MemoryStorage::set("bytes", "not base64!").unwrap();
+    assert!(MemoryStorage::get_bytes("bytes").is_err());
+}
+
+#[test]
+fn web_storage_only_methods_fail() {
+    assert!(matches!(
+        MemoryStorage::try_raw(),
+        Err(StorageError::Unsupported(_))
+    ));
+    assert!(matches!(
+        MemoryStorage::subscribe::<u32>("key"),
+        Err(StorageError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn default_methods_return_storage_errors() {
+    // A backend whose web storage can't be accessed, e.g. because cookies are blocked.
+    struct Blocked;
+
+    impl Storage for Blocked {
+        fn raw() -> web_sys::Storage {
+            unreachable!("only try_raw is called")
+        }
+
+        fn try_raw() -> gloo_storage::Result<web_sys::Storage> {
+            Err(StorageError::Unsupported("blocked".to_string()))
+        }
+    }
+
+    assert!(matches!(
+        Blocked::get::<u32>("key"),
+        Err(StorageError::Unsupported(_))
+    ));
+    assert!(matches!(
+        Blocked::set("key", 1),
+        Err(StorageError::Unsupported(_))
+    ));
+    assert!(matches!(Blocked::key(0), Err(StorageError::Unsupported(_))));
+    assert_eq!(Blocked::length(), 0);
+    Blocked::delete("key");
+    Blocked::clear();
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "opfs")]
+
+use gloo_storage::errors::StorageError;
+use gloo_storage::opfs::OpfsStorage;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Data {
+    name: String,
+    size: u32,
+}
+
+async fn open(name: &str) -> OpfsStorage {
+    OpfsStorage::delete_dir(name).await.unwrap();
+    OpfsStorage::open(name).await.unwrap()
+}
+
+#[test]
+async fn get_and_set() {
+    let storage = open("get_and_set").await;
+    let data = Data {
+        name: "weights.bin".to_string(),
+        size: 4,
+    };
+    storage.set("data", &data).await.unwrap();
+    storage
+        .set_bytes("models/weights", &[0, 1, 2, 255])
+        .await
+        .unwrap();
+
+    assert_eq!(storage.get::<Data>("data").await.unwrap(), data);
+    assert_eq!(
+        storage.get_bytes("models/weights").await.unwrap(),
+        vec![0, 1, 2, 255]
+    );
+    assert_eq!(
+        storage.get_file("models/weights").await.unwrap().size(),
+        4.0
+    );
+    assert!(matches!(
+        storage.get::<Data>("missing").await,
+        Err(StorageError::KeyNotFound(key)) if key == "missing"
+    ));
+}
+
+#[test]
+async fn keys_and_delete() {
+    let storage = open("keys_and_delete").await;
+    storage.set("a", "1").await.unwrap();
+    storage.set("..", "2").await.unwrap();
+
+    let mut keys = storage.keys().await.unwrap();
+    keys.sort();
+    assert_eq!(keys, vec!["..", "a"]);
+
+    assert!(storage.delete("a").await.unwrap());
+    assert!(!storage.delete("a").await.unwrap());
+    assert_eq!(storage.length().await.unwrap(), 1);
+
+    storage.clear().await.unwrap();
+    assert_eq!(storage.length().await.unwrap(), 0);
+}
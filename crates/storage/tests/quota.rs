@@ -0,0 +1,18 @@
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+async fn estimate() {
+    let estimate = gloo_storage::estimate().await.unwrap();
+    assert!(estimate.quota() > 0);
+    assert!(estimate.usage() <= estimate.quota());
+    assert_eq!(estimate.available(), estimate.quota() - estimate.usage());
+}
+
+#[test]
+async fn persisted() {
+    // Headless browsers may deny the request, but both calls must agree.
+    let persistent = gloo_storage::persist().await.unwrap();
+    assert_eq!(gloo_storage::persisted().await.unwrap(), persistent);
+}
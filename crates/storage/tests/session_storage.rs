@@ -1,5 +1,8 @@
-use gloo_storage::{SessionStorage, Storage};
-use serde::Deserialize;
+use std::time::Duration;
+
+use gloo_storage::errors::StorageError;
+use gloo_storage::{SessionStorage, Storage, StorageKey};
+use serde::{Deserialize, Serialize};
 use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -40,3 +43,37 @@ fn set_and_length() {
     SessionStorage::clear();
     assert_eq!(SessionStorage::length(), 0);
 }
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, StorageKey)]
+#[storage(key = "settings", session)]
+struct Settings {
+    dark_mode: bool,
+}
+
+#[test]
+fn storage_key() {
+    Settings::delete();
+    assert_eq!(Settings::load_or_default(), Settings::default());
+
+    Settings { dark_mode: true }.save().unwrap();
+    let stored: Settings = SessionStorage::get("settings").unwrap();
+    assert_eq!(stored, Settings { dark_mode: true });
+    assert_eq!(Settings::load().unwrap(), Settings { dark_mode: true });
+
+    Settings::delete();
+    assert!(Settings::load().is_err());
+}
+
+#[test]
+fn set_with_ttl() {
+    SessionStorage::set_with_ttl("fresh", "value", Duration::from_secs(60)).unwrap();
+    SessionStorage::set_with_ttl("stale", "value", Duration::ZERO).unwrap();
+
+    assert_eq!(SessionStorage::get::<String>("fresh").unwrap(), "value");
+    assert!(matches!(
+        SessionStorage::get::<String>("stale"),
+        Err(StorageError::KeyNotFound(key)) if key == "stale"
+    ));
+    // Expired entries are removed once read.
+    assert!(SessionStorage::raw().get_item("stale").unwrap().is_none());
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "synced")]
+
+use futures::StreamExt;
+use gloo_storage::synced::SyncedStorage;
+use gloo_storage::{LocalStorage, Storage};
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+async fn same_tab_changes() {
+    let writer = SyncedStorage::<LocalStorage>::new("synced-test").unwrap();
+    let reader = SyncedStorage::<LocalStorage>::new("synced-test").unwrap();
+    LocalStorage::delete("synced:count");
+    let mut changes = reader.subscribe::<u32>("synced:count").unwrap();
+
+    writer.set("synced:other", 0).unwrap();
+    writer.set("synced:count", 1).unwrap();
+    writer.set("synced:count", 2).unwrap();
+    writer.delete("synced:count").unwrap();
+
+    let change = changes.next().await.unwrap().unwrap();
+    assert_eq!((change.old_value, change.new_value), (None, Some(1)));
+    let change = changes.next().await.unwrap().unwrap();
+    assert_eq!((change.old_value, change.new_value), (Some(1), Some(2)));
+    let change = changes.next().await.unwrap().unwrap();
+    assert_eq!((change.old_value, change.new_value), (Some(2), None));
+    assert!(reader.get::<u32>("synced:count").is_err());
+}
@@ -0,0 +1,75 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use gloo_storage::{MemoryStorage, Migrations, Storage, Versioned};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    theme: String,
+    font_size: u32,
+}
+
+impl Versioned for Settings {
+    fn migrations() -> Migrations {
+        Migrations::new()
+            // 1 -> 2: `dark_mode: bool` became `theme: String`
+            .step(|value| {
+                let dark_mode = value["dark_mode"].as_bool().unwrap_or_default();
+                Ok(json!({ "theme": if dark_mode { "dark" } else { "light" } }))
+            })
+            // 2 -> 3: added `font_size`
+            .step(|mut value| {
+                value["font_size"] = json!(16);
+                Ok(value)
+            })
+    }
+}
+
+#[test]
+fn migrates_unversioned_values() {
+    MemoryStorage::set("settings", json!({ "dark_mode": true })).unwrap();
+
+    let settings: Settings = MemoryStorage::get_versioned("settings").unwrap();
+    assert_eq!(
+        settings,
+        Settings {
+            theme: "dark".to_string(),
+            font_size: 16,
+        }
+    );
+    // Stored again with the current version.
+    assert_eq!(
+        MemoryStorage::get::<serde_json::Value>("settings").unwrap(),
+        json!({ "__gloo_version": 3, "value": { "theme": "dark", "font_size": 16 } })
+    );
+}
+
+#[test]
+fn migrates_from_intermediate_version() {
+    MemoryStorage::set(
+        "settings",
+        json!({ "__gloo_version": 2, "value": { "theme": "light" } }),
+    )
+    .unwrap();
+
+    let settings: Settings = MemoryStorage::get_versioned("settings").unwrap();
+    assert_eq!(settings.font_size, 16);
+    assert_eq!(settings.theme, "light");
+}
+
+#[test]
+fn round_trip_and_newer_versions() {
+    let settings = Settings {
+        theme: "dark".to_string(),
+        font_size: 12,
+    };
+    MemoryStorage::set_versioned("settings", &settings).unwrap();
+    assert_eq!(
+        MemoryStorage::get_versioned::<Settings>("settings").unwrap(),
+        settings
+    );
+
+    MemoryStorage::set("settings", json!({ "__gloo_version": 4, "value": {} })).unwrap();
+    assert!(MemoryStorage::get_versioned::<Settings>("settings").is_err());
+}
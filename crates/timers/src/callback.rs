@@ -1,9 +1,14 @@
 //! Callback-style timer APIs.
 
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
 use js_sys::Function;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::{JsCast, JsValue};
 
+use crate::instant::Instant;
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_name = "setTimeout", catch)]
@@ -25,11 +30,18 @@ extern "C" {
 ///
 /// Once scheduled, you can [`drop`] the [`Timeout`] to clear it or [`forget`](Timeout::forget) to leak it. Once forgotten, the interval will keep running forever.
 /// This pattern is known as Resource Acquisition Is Initialization (RAII).
+///
+/// It can also be [`pause`](Timeout::pause)d and [`resume`](Timeout::resume)d, e.g. while a
+/// modal is open, without losing track of the time remaining.
 #[derive(Debug)]
 #[must_use = "timeouts cancel on drop; either call `forget` or `drop` explicitly"]
 pub struct Timeout {
     id: Option<JsValue>,
     closure: Option<Closure<dyn FnMut()>>,
+    /// When the timeout is due, or, while paused, the milliseconds remaining until then.
+    deadline: Instant,
+    remaining: Option<f64>,
+    fired: Rc<Cell<bool>>,
 }
 
 impl Drop for Timeout {
@@ -59,7 +71,14 @@ impl Timeout {
     where
         F: 'static + FnOnce(),
     {
-        let closure = Closure::once(callback);
+        let fired = Rc::new(Cell::new(false));
+        let closure = Closure::once({
+            let fired = fired.clone();
+            move || {
+                fired.set(true);
+                callback();
+            }
+        });
 
         let id = set_timeout(
             closure.as_ref().unchecked_ref::<js_sys::Function>(),
@@ -70,15 +89,68 @@ impl Timeout {
         Timeout {
             id: Some(id),
             closure: Some(closure),
+            deadline: Instant::now() + std::time::Duration::from_millis(millis.into()),
+            remaining: None,
+            fired,
+        }
+    }
+
+    /// Pause this timeout, keeping track of the time remaining until it is due.
+    ///
+    /// This does nothing if the timeout is already paused or has already fired.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::callback::Timeout;
+    ///
+    /// let mut timeout = Timeout::new(10_000, || {
+    ///     // Close the notification...
+    /// });
+    ///
+    /// // Keep the notification open while it is hovered.
+    /// timeout.pause();
+    /// // ...
+    /// timeout.resume();
+    /// ```
+    pub fn pause(&mut self) {
+        if self.fired.get() || self.remaining.is_some() {
+            return;
+        }
+        if let Some(id) = self.id.take() {
+            clear_timeout(id);
+            let remaining = self.deadline.as_millis_f64() - Instant::now().as_millis_f64();
+            self.remaining = Some(remaining.max(0.0));
+        }
+    }
+
+    /// Resume this timeout after [`pause`](Timeout::pause), firing it once the time that was
+    /// remaining when it was paused is up.
+    ///
+    /// This does nothing if the timeout is not paused.
+    pub fn resume(&mut self) {
+        if let (Some(remaining), Some(closure)) = (self.remaining.take(), &self.closure) {
+            let millis = remaining.ceil() as i32;
+            let id = set_timeout(closure.as_ref().unchecked_ref::<js_sys::Function>(), millis)
+                .unwrap_throw();
+            self.id = Some(id);
+            self.deadline = Instant::now() + std::time::Duration::from_millis(millis as u64);
         }
     }
 
+    /// Whether this timeout is paused.
+    pub fn is_paused(&self) -> bool {
+        self.remaining.is_some()
+    }
+
     /// Forgets this resource without clearing the timeout.
     ///
     /// Returns the identifier returned by the original `setTimeout` call, and
     /// therefore you can still cancel the timeout by calling `clearTimeout`
     /// directly (perhaps via `web_sys::clear_timeout_with_handle`).
     ///
+    /// A paused timeout stays paused forever, and `undefined` is returned.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -91,7 +163,7 @@ impl Timeout {
     /// }).forget();
     /// ```
     pub fn forget(mut self) -> JsValue {
-        let id = self.id.take().unwrap_throw();
+        let id = self.id.take().unwrap_or(JsValue::UNDEFINED);
         self.closure.take().unwrap_throw().forget();
         id
     }
@@ -127,18 +199,28 @@ impl Timeout {
 ///
 /// Once scheduled, you can [`drop`] the [`Interval`] to clear it or [`forget`](Interval::forget) to leak it. Once forgotten, the interval will keep running forever.
 /// This pattern is known as Resource Acquisition Is Initialization (RAII).
+///
+/// It can also be [`pause`](Interval::pause)d and [`resume`](Interval::resume)d, e.g. while the
+/// tab is hidden, without losing track of the time remaining until the next tick.
 #[derive(Debug)]
 #[must_use = "intervals cancel on drop; either call `forget` or `drop` explicitly"]
 pub struct Interval {
-    id: Option<JsValue>,
+    /// The id of the `setInterval` call, or of the `setTimeout` call leading to it after a
+    /// resume; both are cleared by `clearInterval`.
+    id: Rc<RefCell<Option<JsValue>>>,
     closure: Option<Closure<dyn FnMut()>>,
+    millis: u32,
+    last_tick: Rc<Cell<Instant>>,
+    /// While paused, the milliseconds remaining until the next tick.
+    remaining: Option<f64>,
+    resume_closure: Option<Closure<dyn FnMut()>>,
 }
 
 impl Drop for Interval {
     /// Disposes of the interval, dually cancelling this interval by calling
     /// `clearInterval` directly.
     fn drop(&mut self) {
-        if let Some(id) = self.id.take() {
+        if let Some(id) = self.id.borrow_mut().take() {
             clear_interval(id);
         }
     }
@@ -156,11 +238,18 @@ impl Interval {
     ///     // Do something...
     /// });
     /// ```
-    pub fn new<F>(millis: u32, callback: F) -> Interval
+    pub fn new<F>(millis: u32, mut callback: F) -> Interval
     where
         F: 'static + FnMut(),
     {
-        let closure = Closure::wrap(Box::new(callback) as Box<dyn FnMut()>);
+        let last_tick = Rc::new(Cell::new(Instant::now()));
+        let closure = Closure::wrap(Box::new({
+            let last_tick = last_tick.clone();
+            move || {
+                last_tick.set(Instant::now());
+                callback();
+            }
+        }) as Box<dyn FnMut()>);
 
         let id = set_interval(
             closure.as_ref().unchecked_ref::<js_sys::Function>(),
@@ -169,17 +258,98 @@ impl Interval {
         .unwrap_throw();
 
         Interval {
-            id: Some(id),
+            id: Rc::new(RefCell::new(Some(id))),
             closure: Some(closure),
+            millis,
+            last_tick,
+            remaining: None,
+            resume_closure: None,
+        }
+    }
+
+    /// Pause this interval, keeping track of the time remaining until the next tick.
+    ///
+    /// This does nothing if the interval is already paused.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::callback::Interval;
+    ///
+    /// let mut interval = Interval::new(1_000, || {
+    ///     // Advance the slideshow...
+    /// });
+    ///
+    /// // Stop the slideshow while the modal is open.
+    /// interval.pause();
+    /// // ...
+    /// interval.resume();
+    /// ```
+    pub fn pause(&mut self) {
+        if self.remaining.is_some() {
+            return;
+        }
+        if let Some(id) = self.id.borrow_mut().take() {
+            clear_interval(id);
+            let elapsed = Instant::now().as_millis_f64() - self.last_tick.get().as_millis_f64();
+            self.remaining = Some((f64::from(self.millis) - elapsed).max(0.0));
         }
     }
 
+    /// Resume this interval after [`pause`](Interval::pause): the next tick fires once the
+    /// time that was remaining when it was paused is up, and the following ones every `millis`
+    /// milliseconds again.
+    ///
+    /// This does nothing if the interval is not paused.
+    pub fn resume(&mut self) {
+        let remaining = match (self.remaining.take(), &self.closure) {
+            (Some(remaining), Some(_)) => remaining,
+            _ => return,
+        };
+        let handler = self
+            .closure
+            .as_ref()
+            .unwrap_throw()
+            .as_ref()
+            .unchecked_ref::<js_sys::Function>()
+            .clone();
+        let resume_closure = Closure::wrap(Box::new({
+            let id = self.id.clone();
+            let millis = self.millis;
+            move || {
+                let interval = set_interval(&handler, millis as i32).unwrap_throw();
+                *id.borrow_mut() = Some(interval);
+                let _ = handler.call0(&JsValue::NULL);
+            }
+        }) as Box<dyn FnMut()>);
+
+        let id = set_timeout(
+            resume_closure.as_ref().unchecked_ref::<js_sys::Function>(),
+            remaining.ceil() as i32,
+        )
+        .unwrap_throw();
+        *self.id.borrow_mut() = Some(id);
+        self.resume_closure = Some(resume_closure);
+        // As if the last tick was `millis` before the next one, should it be paused again.
+        let next_tick = Instant::now().as_millis_f64() + remaining;
+        self.last_tick
+            .set(Instant::from_millis(next_tick - f64::from(self.millis)));
+    }
+
+    /// Whether this interval is paused.
+    pub fn is_paused(&self) -> bool {
+        self.remaining.is_some()
+    }
+
     /// Forget this resource without clearing the interval.
     ///
     /// Returns the identifier returned by the original `setInterval` call, and
     /// therefore you can still cancel the interval by calling `clearInterval`
     /// directly (perhaps via `web_sys::clear_interval_with_handle`).
     ///
+    /// Right after a resume, this is the identifier of the `setTimeout` call leading to the
+    /// next tick instead. A paused interval stays paused forever, and `undefined` is returned.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -191,8 +361,11 @@ impl Interval {
     /// }).forget();
     /// ```
     pub fn forget(mut self) -> JsValue {
-        let id = self.id.take().unwrap_throw();
+        let id = self.id.borrow_mut().take().unwrap_or(JsValue::UNDEFINED);
         self.closure.take().unwrap_throw().forget();
+        if let Some(resume_closure) = self.resume_closure.take() {
+            resume_closure.forget();
+        }
         id
     }
 
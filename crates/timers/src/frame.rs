@@ -0,0 +1,362 @@
+//! Timers firing on animation frames with `requestAnimationFrame`.
+//!
+//! A [`FrameInterval`] runs its callback right before the browser paints, so visual polling
+//! loops, such as tracking the scroll position or the size of an element, update in sync with
+//! the screen instead of drifting against it like `setTimeout` does. An optional minimum spacing
+//! throttles the callback to, say, every third frame, and each call gets a [`Frame`] with the
+//! timestamp of the frame and the time left to do work in it.
+//!
+//! Where `requestAnimationFrame` is not available, e.g. in Node.js, frames are simulated with a
+//! `setTimeout` of 16 ms.
+//!
+//! ```no_run
+//! use gloo_timers::frame::FrameInterval;
+//!
+//! let interval = FrameInterval::with_min_spacing(50, move |frame| {
+//!     // Read the layout about every third frame at 60 Hz...
+//! });
+//! interval.forget();
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+use js_sys::{Function, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::instant::Instant;
+
+/// The duration of a frame at 60 Hz, until the actual one is measured.
+const DEFAULT_FRAME_MILLIS: f64 = 1_000.0 / 60.0;
+
+/// How early a frame can be and still count as spaced enough, to absorb the jitter of the
+/// timestamps of frames.
+const SPACING_TOLERANCE_MILLIS: f64 = 1.0;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = "requestAnimationFrame", catch)]
+    fn request_animation_frame(handler: &Function) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = "cancelAnimationFrame")]
+    fn cancel_animation_frame(handle: JsValue);
+
+    #[wasm_bindgen(js_name = "setTimeout", catch)]
+    fn set_timeout(handler: &Function, timeout: i32) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = "clearTimeout")]
+    fn clear_timeout(handle: JsValue) -> JsValue;
+}
+
+fn is_supported() -> bool {
+    Reflect::has(
+        &js_sys::global(),
+        &JsValue::from_str("requestAnimationFrame"),
+    )
+    .unwrap_or(false)
+}
+
+/// An animation frame, passed to the callbacks of [`FrameInterval`].
+#[derive(Debug, Clone, Copy)]
+pub struct Frame {
+    timestamp: Instant,
+    elapsed: Duration,
+    frames: u32,
+    frame_millis: f64,
+}
+
+impl Frame {
+    /// The time at which the frame started, shared by all the animation callbacks of the frame
+    pub fn timestamp(&self) -> Instant {
+        self.timestamp
+    }
+
+    /// The time elapsed since the previous call of the callback, or zero for the first one
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The number of frames since the previous call of the callback, or one for the first one
+    ///
+    /// This is more than one when the interval has a minimum spacing, or when the browser
+    /// dropped frames.
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// The time left before the next frame, estimated from the duration of the previous frames,
+    /// or zero if the frame is over
+    ///
+    /// Work done past this budget delays the next frame and makes animations stutter.
+    pub fn time_remaining(&self) -> Duration {
+        let end = self.timestamp.as_millis_f64() + self.frame_millis;
+        Duration::from_secs_f64((end - Instant::now().as_millis_f64()).max(0.0) / 1_000.0)
+    }
+}
+
+struct FrameLoop {
+    callback: RefCell<Box<dyn FnMut(Frame)>>,
+    closure: Closure<dyn FnMut(JsValue)>,
+    id: RefCell<Option<JsValue>>,
+    native: bool,
+    min_spacing: f64,
+    /// The timestamps of the last frame, and of the last call of the callback.
+    last_frame: Cell<Option<f64>>,
+    last_call: Cell<Option<f64>>,
+    frames: Cell<u32>,
+    frame_millis: Cell<f64>,
+    active: Cell<bool>,
+}
+
+impl FrameLoop {
+    fn request(&self) {
+        let handler = self.closure.as_ref().unchecked_ref();
+        let id = if self.native {
+            request_animation_frame(handler)
+        } else {
+            set_timeout(handler, DEFAULT_FRAME_MILLIS as i32)
+        }
+        .unwrap_throw();
+        *self.id.borrow_mut() = Some(id);
+    }
+
+    fn cancel(&self) {
+        self.active.set(false);
+        if let Some(id) = self.id.borrow_mut().take() {
+            if self.native {
+                cancel_animation_frame(id);
+            } else {
+                clear_timeout(id);
+            }
+        }
+    }
+
+    fn tick(&self, timestamp: JsValue) {
+        self.id.borrow_mut().take();
+        let now = timestamp
+            .as_f64()
+            .unwrap_or_else(|| Instant::now().as_millis_f64());
+
+        if let Some(last) = self.last_frame.replace(Some(now)) {
+            // Smoothed, so that one long frame doesn't throw the budget off.
+            let frame_millis = self.frame_millis.get();
+            self.frame_millis
+                .set(frame_millis + (now - last - frame_millis) / 8.0);
+        }
+        self.frames.set(self.frames.get() + 1);
+
+        let last_call = self.last_call.get();
+        let spaced = match last_call {
+            Some(last) => now - last + SPACING_TOLERANCE_MILLIS >= self.min_spacing,
+            None => true,
+        };
+        if spaced {
+            let frame = Frame {
+                timestamp: Instant::from_millis(now),
+                elapsed: last_call
+                    .map(|last| Duration::from_secs_f64((now - last).max(0.0) / 1_000.0))
+                    .unwrap_or_default(),
+                frames: self.frames.replace(0),
+                frame_millis: self.frame_millis.get(),
+            };
+            self.last_call.set(Some(now));
+            (self.callback.borrow_mut())(frame);
+        }
+
+        // The callback may have cancelled the interval.
+        if self.active.get() {
+            self.request();
+        }
+    }
+}
+
+/// A callback invoked on every animation frame, or every few frames.
+///
+/// See `FrameInterval::new` for scheduling new frame intervals.
+///
+/// Once scheduled, you can [`drop`] the [`FrameInterval`] to cancel it or
+/// [`forget`](FrameInterval::forget) to leak it. Once forgotten, the callback will keep being
+/// invoked forever.
+#[must_use = "frame intervals cancel on drop; either call `forget` or `drop` explicitly"]
+pub struct FrameInterval {
+    inner: Rc<FrameLoop>,
+}
+
+impl Drop for FrameInterval {
+    /// Disposes of the frame interval, dually cancelling the pending animation frame request.
+    fn drop(&mut self) {
+        self.inner.cancel();
+    }
+}
+
+impl FrameInterval {
+    /// Schedule `callback` to be invoked on every animation frame.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::frame::FrameInterval;
+    ///
+    /// let interval = FrameInterval::new(move |frame| {
+    ///     // Move the sprite by `frame.elapsed()` worth of motion...
+    /// });
+    /// ```
+    pub fn new<F>(callback: F) -> FrameInterval
+    where
+        F: 'static + FnMut(Frame),
+    {
+        Self::with_min_spacing(0, callback)
+    }
+
+    /// Schedule `callback` to be invoked on the first animation frame at least `millis`
+    /// milliseconds after its previous call.
+    ///
+    /// As frames only come every 16 ms or so, the callback is invoked on the first frame after
+    /// the spacing, give or take a millisecond to absorb the jitter of the frame timestamps.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::frame::FrameInterval;
+    ///
+    /// let interval = FrameInterval::with_min_spacing(100, move |frame| {
+    ///     // Poll the position of the video about ten times per second...
+    /// });
+    /// ```
+    pub fn with_min_spacing<F>(millis: u32, callback: F) -> FrameInterval
+    where
+        F: 'static + FnMut(Frame),
+    {
+        let inner = Rc::new_cyclic(|weak: &Weak<FrameLoop>| FrameLoop {
+            callback: RefCell::new(Box::new(callback)),
+            closure: {
+                let weak = weak.clone();
+                Closure::wrap(Box::new(move |timestamp: JsValue| {
+                    // Kept alive until the end of the tick, even if the callback drops the
+                    // interval.
+                    if let Some(inner) = weak.upgrade() {
+                        inner.tick(timestamp);
+                    }
+                }) as Box<dyn FnMut(JsValue)>)
+            },
+            id: RefCell::new(None),
+            native: is_supported(),
+            min_spacing: millis.into(),
+            last_frame: Cell::new(None),
+            last_call: Cell::new(None),
+            frames: Cell::new(0),
+            frame_millis: Cell::new(DEFAULT_FRAME_MILLIS),
+            active: Cell::new(true),
+        });
+        inner.request();
+
+        FrameInterval { inner }
+    }
+
+    /// Forget this resource without cancelling the frame interval.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::frame::FrameInterval;
+    ///
+    /// FrameInterval::new(|frame| {
+    ///     // Do stuff on every frame...
+    /// }).forget();
+    /// ```
+    pub fn forget(self) {
+        std::mem::forget(self);
+    }
+
+    /// Cancel this frame interval so that the callback is no longer invoked.
+    pub fn cancel(self) {
+        drop(self);
+    }
+}
+
+impl fmt::Debug for FrameInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameInterval")
+            .field("min_spacing", &self.inner.min_spacing)
+            .field("active", &self.inner.active.get())
+            .finish()
+    }
+}
+
+#[cfg(feature = "futures")]
+pub use self::future::FrameStream;
+
+#[cfg(feature = "futures")]
+mod future {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use futures_channel::mpsc;
+    use futures_core::stream::Stream;
+
+    use super::{Frame, FrameInterval};
+
+    /// Animation frames as a `Stream` of [`Frame`]s.
+    ///
+    /// Once scheduled, if you want to stop receiving frames, you can `drop` the stream.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::stream::StreamExt;
+    /// use gloo_timers::frame::FrameStream;
+    /// use wasm_bindgen_futures::spawn_local;
+    ///
+    /// spawn_local(async {
+    ///     let mut frames = FrameStream::with_min_spacing(50);
+    ///     while let Some(frame) = frames.next().await {
+    ///         // Check the scroll position...
+    ///     }
+    /// });
+    /// ```
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or spawned"]
+    pub struct FrameStream {
+        receiver: mpsc::UnboundedReceiver<Frame>,
+        _inner: FrameInterval,
+    }
+
+    impl FrameStream {
+        /// Create a stream yielding every animation frame.
+        pub fn new() -> FrameStream {
+            Self::with_min_spacing(0)
+        }
+
+        /// Create a stream yielding the first animation frame at least `millis` milliseconds
+        /// after the previous one.
+        pub fn with_min_spacing(millis: u32) -> FrameStream {
+            let (sender, receiver) = mpsc::unbounded();
+            let inner = FrameInterval::with_min_spacing(millis, move |frame| {
+                // if the receiver was dropped we do nothing.
+                let _ = sender.unbounded_send(frame);
+            });
+
+            FrameStream {
+                receiver,
+                _inner: inner,
+            }
+        }
+    }
+
+    impl Default for FrameStream {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Stream for FrameStream {
+        type Item = Frame;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            Stream::poll_next(Pin::new(&mut self.receiver), cx)
+        }
+    }
+}
@@ -1,13 +1,26 @@
 //! `Future`- and `Stream`-backed timers APIs.
+//!
+//! With the `tokio` feature, the timeouts and intervals of this module are backed by the timers
+//! of tokio when compiled for other targets than wasm32, so that code using them also runs in
+//! native tests or when rendering on the server. They then need to be run within a tokio
+//! runtime with the time driver enabled. This doesn't apply to [`sleep_with_handle`], nor to
+//! the other modules, which still need JavaScript timers.
 
-use crate::callback::{Interval, Timeout};
+#[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+use crate::callback::Interval;
+use crate::callback::Timeout;
+use crate::instant::Instant;
 
-use futures_channel::{mpsc, oneshot};
+#[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+use futures_channel::oneshot;
 use futures_core::stream::Stream;
+use std::cell::RefCell;
 use std::convert::TryFrom;
+use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll, Waker};
 use std::time::Duration;
 use wasm_bindgen::prelude::*;
 
@@ -43,8 +56,12 @@ use wasm_bindgen::prelude::*;
 #[derive(Debug)]
 #[must_use = "futures do nothing unless polled or spawned"]
 pub struct TimeoutFuture {
+    #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
     _inner: Timeout,
+    #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
     rx: oneshot::Receiver<()>,
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    sleep: Pin<Box<tokio::time::Sleep>>,
 }
 
 impl TimeoutFuture {
@@ -65,6 +82,7 @@ impl TimeoutFuture {
     ///     // Do stuff after one second...
     /// });
     /// ```
+    #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
     pub fn new(millis: u32) -> TimeoutFuture {
         let (tx, rx) = oneshot::channel();
         let inner = Timeout::new(millis, move || {
@@ -73,6 +91,15 @@ impl TimeoutFuture {
         });
         TimeoutFuture { _inner: inner, rx }
     }
+
+    /// Create a new timeout future.
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    pub fn new(millis: u32) -> TimeoutFuture {
+        let sleep = tokio::time::sleep(Duration::from_millis(millis.into()));
+        TimeoutFuture {
+            sleep: Box::pin(sleep),
+        }
+    }
 }
 
 /// Waits until the specified duration has elapsed.
@@ -97,13 +124,456 @@ pub fn sleep(dur: Duration) -> TimeoutFuture {
     TimeoutFuture::new(millis)
 }
 
+fn duration_to_millis(dur: Duration) -> u32 {
+    u32::try_from(dur.as_millis())
+        .expect_throw("failed to cast the duration into a u32 with Duration::as_millis.")
+}
+
+/// Waits until the specified duration has elapsed, returning a handle to cancel or reset the
+/// wait from elsewhere.
+///
+/// Unlike [`sleep`], the wait can be changed after it started, e.g. to postpone an inactivity
+/// timeout whenever the user does something.
+///
+/// # Panics
+///
+/// This function, and [`SleepHandle::reset`], will panic if the specified [`Duration`] cannot
+/// be casted into a u32 in milliseconds.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use gloo_timers::future::sleep_with_handle;
+/// use wasm_bindgen_futures::spawn_local;
+///
+/// let (logout, handle) = sleep_with_handle(Duration::from_secs(15 * 60));
+/// spawn_local(async {
+///     if logout.await.is_ok() {
+///         // Log the user out after 15 minutes of inactivity...
+///     }
+/// });
+///
+/// // Whenever the user does something:
+/// handle.reset(Duration::from_secs(15 * 60));
+/// ```
+pub fn sleep_with_handle(dur: Duration) -> (SleepFuture, SleepHandle) {
+    let state = Rc::new(RefCell::new(SleepState {
+        timeout: None,
+        result: None,
+        waker: None,
+    }));
+    SleepState::start(&state, duration_to_millis(dur));
+    let handle = SleepHandle {
+        state: Rc::downgrade(&state),
+    };
+    (SleepFuture { state }, handle)
+}
+
+/// The error returned by a [`SleepFuture`] cancelled with [`SleepHandle::cancel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the sleep was cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+#[derive(Debug)]
+struct SleepState {
+    timeout: Option<Timeout>,
+    result: Option<Result<(), Cancelled>>,
+    waker: Option<Waker>,
+}
+
+impl SleepState {
+    /// (Re)starts the timeout, replacing and thus clearing the previous one.
+    fn start(state: &Rc<RefCell<SleepState>>, millis: u32) {
+        // The timeout is owned by the state, so only keep a weak reference to it.
+        let weak = Rc::downgrade(state);
+        let timeout = Timeout::new(millis, move || {
+            if let Some(state) = weak.upgrade() {
+                state.borrow_mut().finish(Ok(()));
+            }
+        });
+        state.borrow_mut().timeout = Some(timeout);
+    }
+
+    fn finish(&mut self, result: Result<(), Cancelled>) {
+        self.timeout = None;
+        self.result = Some(result);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A wait started with [`sleep_with_handle`], as a `Future`.
+///
+/// It resolves to `Ok(())` once the time is up, or to `Err(Cancelled)` as soon as
+/// [`SleepHandle::cancel`] is called. Dropping it clears the timeout.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled or spawned"]
+pub struct SleepFuture {
+    state: Rc<RefCell<SleepState>>,
+}
+
+impl Future for SleepFuture {
+    type Output = Result<(), Cancelled>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        match state.result {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for SleepFuture {
+    fn drop(&mut self) {
+        self.state.borrow_mut().timeout = None;
+    }
+}
+
+/// Cancels or resets the wait of a [`SleepFuture`], returned by [`sleep_with_handle`].
+///
+/// Handles can be cloned, and do nothing once the future completed or was dropped.
+#[derive(Debug, Clone)]
+pub struct SleepHandle {
+    state: Weak<RefCell<SleepState>>,
+}
+
+impl SleepHandle {
+    /// Whether the future is still waiting
+    pub fn is_pending(&self) -> bool {
+        self.state
+            .upgrade()
+            .map_or(false, |state| state.borrow().result.is_none())
+    }
+
+    /// Stop waiting, making the future resolve to `Err(Cancelled)`
+    pub fn cancel(&self) {
+        if let Some(state) = self.state.upgrade() {
+            let mut state = state.borrow_mut();
+            if state.result.is_none() {
+                state.finish(Err(Cancelled));
+            }
+        }
+    }
+
+    /// Restart the wait, so that the future resolves once `dur` has elapsed from now
+    pub fn reset(&self, dur: Duration) {
+        let millis = duration_to_millis(dur);
+        if let Some(state) = self.state.upgrade() {
+            if state.borrow().result.is_none() {
+                SleepState::start(&state, millis);
+            }
+        }
+    }
+}
+
+/// How often deadlines on the system clock are checked again, to follow changes of the clock.
+const WALL_CLOCK_RECHECK_MILLIS: f64 = 60_000.0;
+
+/// A point in time to wait for, with [`sleep_until`] or [`timeout_at`].
+///
+/// Unlike a duration, it doesn't drift when timers fire late, e.g. because the tab was in the
+/// background: the time remaining is computed again every time a timer fires.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Deadline {
+    /// An instant of the monotonic clock of `performance.now()`.
+    ///
+    /// Depending on the browser, this clock may stop while the computer is asleep.
+    Instant(Instant),
+    /// A time of the system clock, in milliseconds since the Unix epoch like `Date.now()`.
+    ///
+    /// This follows the system clock while the computer is asleep or when it is changed,
+    /// checking it at least every minute.
+    Date(f64),
+}
+
+impl Deadline {
+    /// The time remaining until the deadline, or zero if it has passed
+    pub fn remaining(&self) -> Duration {
+        Duration::from_secs_f64(self.remaining_millis().max(0.0) / 1_000.0)
+    }
+
+    fn remaining_millis(&self) -> f64 {
+        match self {
+            Deadline::Instant(instant) => instant.as_millis_f64() - Instant::now().as_millis_f64(),
+            Deadline::Date(millis) => millis - crate::instant::date_now(),
+        }
+    }
+
+    /// The milliseconds to wait before checking the deadline again, or `None` if it has passed.
+    fn next_check(&self) -> Option<u32> {
+        let remaining = self.remaining_millis();
+        if remaining <= 0.0 {
+            return None;
+        }
+        let max = match self {
+            Deadline::Instant(_) => f64::from(i32::MAX),
+            Deadline::Date(_) => WALL_CLOCK_RECHECK_MILLIS,
+        };
+        Some(remaining.ceil().min(max) as u32)
+    }
+}
+
+impl From<Instant> for Deadline {
+    fn from(instant: Instant) -> Self {
+        Deadline::Instant(instant)
+    }
+}
+
+impl From<&js_sys::Date> for Deadline {
+    fn from(date: &js_sys::Date) -> Self {
+        Deadline::Date(date.get_time())
+    }
+}
+
+impl From<js_sys::Date> for Deadline {
+    fn from(date: js_sys::Date) -> Self {
+        Deadline::from(&date)
+    }
+}
+
+/// Waits until the specified deadline has passed.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use gloo_timers::future::sleep_until;
+/// use gloo_timers::instant::Instant;
+/// use wasm_bindgen_futures::spawn_local;
+///
+/// spawn_local(async {
+///     let start = Instant::now();
+///     for step in 1..=10 {
+///         // Steps stay on schedule even if one of them takes longer.
+///         sleep_until(start + Duration::from_secs(step)).await;
+///     }
+///
+///     // Or at a time of the system clock.
+///     sleep_until(js_sys::Date::new(&"2030-01-01T00:00:00Z".into())).await;
+/// });
+/// ```
+pub fn sleep_until(deadline: impl Into<Deadline>) -> SleepUntil {
+    SleepUntil {
+        deadline: deadline.into(),
+        timeout: None,
+    }
+}
+
+/// A wait for a [`Deadline`] as a `Future`, returned by [`sleep_until`].
+///
+/// Dropping it clears the pending timeout.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled or spawned"]
+pub struct SleepUntil {
+    deadline: Deadline,
+    timeout: Option<TimeoutFuture>,
+}
+
+impl SleepUntil {
+    /// The deadline waited for
+    pub fn deadline(&self) -> Deadline {
+        self.deadline
+    }
+
+    /// Wait for another deadline instead
+    pub fn reset(&mut self, deadline: impl Into<Deadline>) {
+        self.deadline = deadline.into();
+        self.timeout = None;
+    }
+}
+
+impl Future for SleepUntil {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        loop {
+            if let Some(timeout) = &mut self.timeout {
+                futures_core::ready!(Future::poll(Pin::new(timeout), cx));
+                self.timeout = None;
+            }
+            // Timers can fire late, or early for long waits, so check the clock again.
+            match self.deadline.next_check() {
+                Some(millis) => self.timeout = Some(TimeoutFuture::new(millis)),
+                None => return Poll::Ready(()),
+            }
+        }
+    }
+}
+
+/// Requires `future` to complete before the specified duration has elapsed.
+///
+/// The returned future resolves to the output of `future`, or to `Err(Elapsed)` once the
+/// duration has elapsed, in which case `future` is dropped. See also [`TimeoutExt::timeout`].
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use gloo_timers::future::{timeout, TimeoutFuture};
+/// use wasm_bindgen_futures::spawn_local;
+///
+/// spawn_local(async {
+///     let slow = async {
+///         TimeoutFuture::new(2_000).await;
+///         "done"
+///     };
+///     assert!(timeout(Duration::from_secs(1), slow).await.is_err());
+/// });
+/// ```
+pub fn timeout<F: Future>(dur: Duration, future: F) -> WithTimeout<F> {
+    timeout_at(Instant::now() + dur, future)
+}
+
+/// Requires `future` to complete before the specified deadline has passed.
+///
+/// The returned future resolves to the output of `future`, or to `Err(Elapsed)` once the
+/// deadline has passed, in which case `future` is dropped. See also
+/// [`TimeoutExt::timeout_at`].
+///
+/// # Example
+///
+/// ```no_run
+/// use gloo_timers::future::{timeout_at, TimeoutFuture};
+/// use wasm_bindgen_futures::spawn_local;
+///
+/// spawn_local(async {
+///     let closing_time = js_sys::Date::new(&"2030-01-01T18:00:00Z".into());
+///     let answer = async {
+///         TimeoutFuture::new(2_000).await;
+///         "yes"
+///     };
+///     assert!(timeout_at(closing_time, answer).await.is_ok());
+/// });
+/// ```
+pub fn timeout_at<F: Future>(deadline: impl Into<Deadline>, future: F) -> WithTimeout<F> {
+    WithTimeout {
+        future,
+        delay: sleep_until(deadline),
+    }
+}
+
+/// The error returned by [`WithTimeout`] when the duration elapsed first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the deadline has elapsed")
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+/// A future with a deadline, returned by [`timeout`], [`timeout_at`] and [`TimeoutExt`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled or spawned"]
+pub struct WithTimeout<F> {
+    future: F,
+    delay: SleepUntil,
+}
+
+impl<F> WithTimeout<F> {
+    /// Get the wrapped future back, cancelling the deadline
+    pub fn into_inner(self) -> F {
+        self.future
+    }
+}
+
+impl<F: Future> Future for WithTimeout<F> {
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        // SAFETY: `future` is structurally pinned: it is never moved out of a pinned
+        // `WithTimeout`, whose `Drop` is the default one. `delay` is `Unpin`.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        if let Poll::Ready(output) = future.poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+        Future::poll(Pin::new(&mut this.delay), cx).map(|()| Err(Elapsed))
+    }
+}
+
+/// Adds [`timeout`](TimeoutExt::timeout) to all the futures.
+pub trait TimeoutExt: Future + Sized {
+    /// Requires this future to complete before the specified duration has elapsed, see
+    /// [`timeout`](fn@timeout).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use gloo_timers::future::{TimeoutExt, TimeoutFuture};
+    /// use wasm_bindgen_futures::spawn_local;
+    ///
+    /// spawn_local(async {
+    ///     let result = TimeoutFuture::new(10).timeout(Duration::from_secs(1)).await;
+    ///     assert!(result.is_ok());
+    /// });
+    /// ```
+    fn timeout(self, dur: Duration) -> WithTimeout<Self> {
+        timeout(dur, self)
+    }
+
+    /// Requires this future to complete before the specified deadline has passed, see
+    /// [`timeout_at`].
+    fn timeout_at(self, deadline: impl Into<Deadline>) -> WithTimeout<Self> {
+        timeout_at(deadline, self)
+    }
+}
+
+impl<F: Future> TimeoutExt for F {}
+
 impl Future for TimeoutFuture {
     type Output = ();
 
+    #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
         Future::poll(Pin::new(&mut self.rx), cx).map(|t| t.unwrap_throw())
     }
+
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        self.sleep.as_mut().poll(cx)
+    }
+}
+/// What an [`IntervalStream`] does with the ticks that fire while its consumer lags behind, e.g.
+/// while it awaits something else or while the tab was suspended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MissedTicks {
+    /// Yield every tick, one after the other, once the consumer catches up.
+    #[default]
+    Queue,
+    /// Drop the missed ticks, and only yield one tick once the consumer catches up.
+    Skip,
+    /// Yield one tick once the consumer catches up, and count the ticks it replaces, see
+    /// [`IntervalStream::missed`].
+    Coalesce,
 }
+
+#[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+#[derive(Debug, Default)]
+struct IntervalState {
+    /// The ticks that fired but were not yielded yet.
+    pending: u32,
+    /// The ticks coalesced into the last one yielded.
+    missed: u32,
+    waker: Option<Waker>,
+}
+
 /// A scheduled interval as a `Stream`.
 ///
 /// See `IntervalStream::new` for scheduling new intervals.
@@ -115,8 +585,15 @@ impl Future for TimeoutFuture {
 #[derive(Debug)]
 #[must_use = "streams do nothing unless polled or spawned"]
 pub struct IntervalStream {
-    receiver: mpsc::UnboundedReceiver<()>,
+    missed_ticks: MissedTicks,
+    #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+    state: Rc<RefCell<IntervalState>>,
+    #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
     _inner: Interval,
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    interval: tokio::time::Interval,
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    missed: u32,
 }
 
 impl IntervalStream {
@@ -126,6 +603,9 @@ impl IntervalStream {
     /// spawn this stream via `wasm_bindgen_futures::spawn_local` or use it inside
     /// another stream or future.
     ///
+    /// The ticks missed while the stream is not polled are queued, see
+    /// [`IntervalStream::with_missed_ticks`] to change that.
+    ///
     /// # Example
     ///
     /// ```compile_fail
@@ -140,23 +620,115 @@ impl IntervalStream {
     /// });
     /// ```
     pub fn new(millis: u32) -> IntervalStream {
-        let (sender, receiver) = mpsc::unbounded();
-        let inner = Interval::new(millis, move || {
-            // if the receiver was dropped we do nothing.
-            sender.unbounded_send(()).unwrap_throw();
+        Self::with_missed_ticks(millis, MissedTicks::Queue)
+    }
+
+    /// Create a new interval stream, handling the ticks missed while it is not polled according
+    /// to `missed_ticks`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::stream::StreamExt;
+    /// use gloo_timers::future::{IntervalStream, MissedTicks};
+    /// use wasm_bindgen_futures::spawn_local;
+    ///
+    /// spawn_local(async {
+    ///     let mut ticks = IntervalStream::with_missed_ticks(1_000, MissedTicks::Coalesce);
+    ///     while ticks.next().await.is_some() {
+    ///         // Advance the countdown by `1 + ticks.missed()` seconds...
+    ///     }
+    /// });
+    /// ```
+    #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+    pub fn with_missed_ticks(millis: u32, missed_ticks: MissedTicks) -> IntervalStream {
+        let state = Rc::new(RefCell::new(IntervalState::default()));
+        let inner = Interval::new(millis, {
+            let state = Rc::downgrade(&state);
+            move || {
+                // if the stream was dropped we do nothing.
+                if let Some(state) = state.upgrade() {
+                    let mut state = state.borrow_mut();
+                    state.pending = state.pending.saturating_add(1);
+                    if let Some(waker) = state.waker.take() {
+                        waker.wake();
+                    }
+                }
+            }
         });
 
         IntervalStream {
-            receiver,
+            missed_ticks,
+            state,
             _inner: inner,
         }
     }
+
+    /// Create a new interval stream, handling the ticks missed while it is not polled according
+    /// to `missed_ticks`.
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    pub fn with_missed_ticks(millis: u32, missed_ticks: MissedTicks) -> IntervalStream {
+        use tokio::time::MissedTickBehavior;
+
+        // Like `setInterval`, which fires after the first period rather than right away.
+        let period = Duration::from_millis(millis.max(1).into());
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        interval.set_missed_tick_behavior(match missed_ticks {
+            MissedTicks::Queue => MissedTickBehavior::Burst,
+            MissedTicks::Skip | MissedTicks::Coalesce => MissedTickBehavior::Skip,
+        });
+        IntervalStream {
+            missed_ticks,
+            interval,
+            missed: 0,
+        }
+    }
+
+    /// How the ticks missed while the stream is not polled are handled
+    pub fn missed_ticks(&self) -> MissedTicks {
+        self.missed_ticks
+    }
+
+    /// The number of ticks that were coalesced into the last one yielded
+    ///
+    /// This is always zero unless the stream was created with [`MissedTicks::Coalesce`].
+    pub fn missed(&self) -> u32 {
+        #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+        let missed = self.state.borrow().missed;
+        #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+        let missed = self.missed;
+        missed
+    }
 }
 
 impl Stream for IntervalStream {
     type Item = ();
 
+    #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut state = self.state.borrow_mut();
+        if state.pending == 0 {
+            state.waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        match self.missed_ticks {
+            MissedTicks::Queue => state.pending -= 1,
+            MissedTicks::Skip => state.pending = 0,
+            MissedTicks::Coalesce => {
+                state.missed = state.pending - 1;
+                state.pending = 0;
+            }
+        }
+        Poll::Ready(Some(()))
+    }
+
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        Stream::poll_next(Pin::new(&mut self.receiver), cx)
+        let tick = futures_core::ready!(self.interval.poll_tick(cx));
+        if self.missed_ticks == MissedTicks::Coalesce {
+            let period = self.interval.period().as_secs_f64();
+            self.missed = (tick.elapsed().as_secs_f64() / period) as u32;
+        }
+        Poll::Ready(Some(()))
     }
 }
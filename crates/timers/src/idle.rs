@@ -0,0 +1,394 @@
+//! Scheduling low-priority work with `requestIdleCallback`.
+//!
+//! The browser runs idle callbacks when it has nothing else to do, e.g. between frames, and
+//! tells them how long it expects to stay idle with an [`IdleDeadline`]. Browsers without
+//! `requestIdleCallback`, such as Safari, fall back to a `setTimeout` of 1 ms with a deadline of
+//! 50 ms, the longest idle period browsers grant.
+//!
+//! ```no_run
+//! use gloo_timers::idle::IdleCallback;
+//!
+//! let mut tasks = vec![1, 2, 3];
+//! IdleCallback::new(move |deadline| {
+//!     while !deadline.time_remaining().is_zero() {
+//!         match tasks.pop() {
+//!             Some(task) => { /* do some of the work */ }
+//!             None => break,
+//!         }
+//!     }
+//! })
+//! .forget();
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use js_sys::{Function, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// How long a deadline lasts when `requestIdleCallback` is not supported.
+const FALLBACK_IDLE_MILLIS: f64 = 50.0;
+
+#[wasm_bindgen]
+extern "C" {
+    type RawIdleDeadline;
+
+    #[wasm_bindgen(method, js_name = "timeRemaining")]
+    fn time_remaining(this: &RawIdleDeadline) -> f64;
+
+    #[wasm_bindgen(method, getter, js_name = "didTimeout")]
+    fn did_timeout(this: &RawIdleDeadline) -> bool;
+
+    #[wasm_bindgen(js_name = "requestIdleCallback", catch)]
+    fn request_idle_callback(handler: &Function, options: &JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = "cancelIdleCallback")]
+    fn cancel_idle_callback(handle: JsValue);
+
+    #[wasm_bindgen(js_name = "setTimeout", catch)]
+    fn set_timeout(handler: &Function, timeout: i32) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = "clearTimeout")]
+    fn clear_timeout(handle: JsValue) -> JsValue;
+}
+
+fn is_supported() -> bool {
+    Reflect::has(&js_sys::global(), &JsValue::from_str("requestIdleCallback")).unwrap_or(false)
+}
+
+/// How much longer the browser expects to stay idle, passed to idle callbacks.
+pub struct IdleDeadline {
+    inner: Deadline,
+}
+
+enum Deadline {
+    Native(RawIdleDeadline),
+    /// The time at which the deadline ends, from `Date.now()`.
+    Fallback(f64),
+}
+
+impl IdleDeadline {
+    fn new(deadline: JsValue) -> Self {
+        let inner = if deadline.is_undefined() {
+            Deadline::Fallback(js_sys::Date::now() + FALLBACK_IDLE_MILLIS)
+        } else {
+            Deadline::Native(deadline.unchecked_into())
+        };
+        IdleDeadline { inner }
+    }
+
+    /// The time left before the browser needs to do other work, or zero if the idle period is
+    /// over.
+    ///
+    /// This is updated as time passes, so long-running work should check it regularly and stop
+    /// once it reaches zero.
+    pub fn time_remaining(&self) -> Duration {
+        let millis = match &self.inner {
+            Deadline::Native(deadline) => deadline.time_remaining(),
+            Deadline::Fallback(end) => end - js_sys::Date::now(),
+        };
+        Duration::from_secs_f64(millis.max(0.0) / 1_000.0)
+    }
+
+    /// Whether the callback is run because its timeout elapsed, rather than because the browser
+    /// became idle.
+    pub fn did_timeout(&self) -> bool {
+        match &self.inner {
+            Deadline::Native(deadline) => deadline.did_timeout(),
+            Deadline::Fallback(_) => false,
+        }
+    }
+}
+
+impl fmt::Debug for IdleDeadline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdleDeadline")
+            .field("time_remaining", &self.time_remaining())
+            .field("did_timeout", &self.did_timeout())
+            .finish()
+    }
+}
+
+/// A scheduled idle callback.
+///
+/// See `IdleCallback::new` for scheduling new idle callbacks.
+///
+/// Once scheduled, you can [`drop`] the [`IdleCallback`] to cancel it or
+/// [`forget`](IdleCallback::forget) to leak it.
+#[derive(Debug)]
+#[must_use = "idle callbacks cancel on drop; either call `forget` or `drop` explicitly"]
+pub struct IdleCallback {
+    id: Option<JsValue>,
+    native: bool,
+    closure: Option<Closure<dyn FnMut(JsValue)>>,
+}
+
+impl Drop for IdleCallback {
+    /// Disposes of the idle callback, dually cancelling it by calling `cancelIdleCallback`
+    /// directly.
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            if self.native {
+                cancel_idle_callback(id);
+            } else {
+                clear_timeout(id);
+            }
+        }
+    }
+}
+
+impl IdleCallback {
+    /// Schedule `callback` to be invoked the next time the browser is idle.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::idle::IdleCallback;
+    ///
+    /// let idle = IdleCallback::new(move |deadline| {
+    ///     // Do something for up to `deadline.time_remaining()`...
+    /// });
+    /// ```
+    pub fn new<F>(callback: F) -> IdleCallback
+    where
+        F: 'static + FnOnce(IdleDeadline),
+    {
+        Self::schedule(None, callback)
+    }
+
+    /// Schedule `callback` to be invoked the next time the browser is idle, or in `millis`
+    /// milliseconds from now if the browser is not idle by then.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::idle::IdleCallback;
+    ///
+    /// let idle = IdleCallback::with_timeout(1_000, move |deadline| {
+    ///     if deadline.did_timeout() {
+    ///         // The browser was busy for a whole second...
+    ///     }
+    /// });
+    /// ```
+    pub fn with_timeout<F>(millis: u32, callback: F) -> IdleCallback
+    where
+        F: 'static + FnOnce(IdleDeadline),
+    {
+        Self::schedule(Some(millis), callback)
+    }
+
+    fn schedule<F>(timeout: Option<u32>, callback: F) -> IdleCallback
+    where
+        F: 'static + FnOnce(IdleDeadline),
+    {
+        let closure = Closure::once(move |deadline: JsValue| callback(IdleDeadline::new(deadline)));
+        let handler = closure.as_ref().unchecked_ref::<Function>();
+
+        let native = is_supported();
+        let id = if native {
+            let options = js_sys::Object::new();
+            if let Some(millis) = timeout {
+                Reflect::set(&options, &"timeout".into(), &millis.into()).unwrap_throw();
+            }
+            request_idle_callback(handler, &options)
+        } else {
+            // Called without arguments, so that the closure falls back to its own deadline.
+            set_timeout(handler, 1)
+        }
+        .unwrap_throw();
+
+        IdleCallback {
+            id: Some(id),
+            native,
+            closure: Some(closure),
+        }
+    }
+
+    /// Forgets this resource without cancelling the idle callback.
+    ///
+    /// Returns the identifier returned by the original `requestIdleCallback` call, or by
+    /// `setTimeout` when falling back to it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::idle::IdleCallback;
+    ///
+    /// IdleCallback::new(|deadline| {
+    ///     // Do stuff...
+    /// }).forget();
+    /// ```
+    pub fn forget(mut self) -> JsValue {
+        let id = self.id.take().unwrap_throw();
+        self.closure.take().unwrap_throw().forget();
+        id
+    }
+
+    /// Cancel this idle callback so that it is not invoked.
+    ///
+    /// The scheduled callback is returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::idle::IdleCallback;
+    ///
+    /// let idle = IdleCallback::new(|deadline| {
+    ///     // Do stuff...
+    /// });
+    ///
+    /// // If actually we didn't need to do it, then cancel it.
+    /// if nevermind() {
+    ///     idle.cancel();
+    /// }
+    /// # fn nevermind() -> bool { true }
+    /// ```
+    pub fn cancel(mut self) -> Closure<dyn FnMut(JsValue)> {
+        self.closure.take().unwrap_throw()
+    }
+}
+
+#[cfg(feature = "futures")]
+pub use self::future::{IdleFuture, IdleStream};
+
+#[cfg(feature = "futures")]
+mod future {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use futures_channel::oneshot;
+    use futures_core::stream::Stream;
+    use wasm_bindgen::UnwrapThrowExt;
+
+    use super::{IdleCallback, IdleDeadline};
+
+    /// A scheduled idle callback as a `Future`, resolving to its [`IdleDeadline`].
+    ///
+    /// Once scheduled, if you change your mind and don't want to wait for the browser to be
+    /// idle, you can `drop` the future.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::idle::IdleFuture;
+    /// use wasm_bindgen_futures::spawn_local;
+    ///
+    /// spawn_local(async {
+    ///     let deadline = IdleFuture::new().await;
+    ///     // Do stuff for up to `deadline.time_remaining()`...
+    /// });
+    /// ```
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless polled or spawned"]
+    pub struct IdleFuture {
+        _inner: IdleCallback,
+        rx: oneshot::Receiver<IdleDeadline>,
+    }
+
+    impl IdleFuture {
+        /// Create a future resolving the next time the browser is idle.
+        pub fn new() -> IdleFuture {
+            Self::schedule(None)
+        }
+
+        /// Create a future resolving the next time the browser is idle, or in `millis`
+        /// milliseconds from now if the browser is not idle by then.
+        pub fn with_timeout(millis: u32) -> IdleFuture {
+            Self::schedule(Some(millis))
+        }
+
+        fn schedule(timeout: Option<u32>) -> IdleFuture {
+            let (tx, rx) = oneshot::channel();
+            let inner = IdleCallback::schedule(timeout, move |deadline| {
+                // if the receiver was dropped we do nothing.
+                let _ = tx.send(deadline);
+            });
+            IdleFuture { _inner: inner, rx }
+        }
+    }
+
+    impl Default for IdleFuture {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Future for IdleFuture {
+        type Output = IdleDeadline;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            Future::poll(Pin::new(&mut self.rx), cx).map(|t| t.unwrap_throw())
+        }
+    }
+
+    /// The idle periods of the browser as a `Stream` of [`IdleDeadline`]s.
+    ///
+    /// The next idle callback is only requested once the stream is polled again, so work done
+    /// between two items never overlaps with the next idle period.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::stream::StreamExt;
+    /// use gloo_timers::idle::IdleStream;
+    /// use wasm_bindgen_futures::spawn_local;
+    ///
+    /// spawn_local(async {
+    ///     let mut tasks = vec![1, 2, 3];
+    ///     let mut idle = IdleStream::new();
+    ///     while !tasks.is_empty() {
+    ///         let deadline = idle.next().await.unwrap();
+    ///         while !deadline.time_remaining().is_zero() && tasks.pop().is_some() {
+    ///             // Do some of the work...
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or spawned"]
+    pub struct IdleStream {
+        timeout: Option<u32>,
+        pending: Option<IdleFuture>,
+    }
+
+    impl IdleStream {
+        /// Create a stream yielding every time the browser is idle.
+        pub fn new() -> IdleStream {
+            IdleStream {
+                timeout: None,
+                pending: None,
+            }
+        }
+
+        /// Create a stream yielding every time the browser is idle, or `millis` milliseconds
+        /// after being polled if the browser is not idle by then.
+        pub fn with_timeout(millis: u32) -> IdleStream {
+            IdleStream {
+                timeout: Some(millis),
+                pending: None,
+            }
+        }
+    }
+
+    impl Default for IdleStream {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Stream for IdleStream {
+        type Item = IdleDeadline;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            let timeout = self.timeout;
+            let pending = self
+                .pending
+                .get_or_insert_with(|| IdleFuture::schedule(timeout));
+            let deadline = futures_core::ready!(Future::poll(Pin::new(pending), cx));
+            self.pending = None;
+            Poll::Ready(Some(deadline))
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Measuring time with `performance.now()`.
+//!
+//! [`std::time::Instant`] panics on `wasm32-unknown-unknown`, as there is no clock in the
+//! standard library on that target. [`Instant`] provides the same API on top of the monotonic
+//! clock of the browser, and [`Stopwatch`] builds on it to time laps.
+//!
+//! ```no_run
+//! use gloo_timers::instant::Instant;
+//!
+//! let start = Instant::now();
+//! // Do some work...
+//! let elapsed = start.elapsed();
+//! ```
+
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::time::Duration;
+
+use wasm_bindgen::prelude::*;
+
+#[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = performance, js_name = "now")]
+    fn performance_now() -> f64;
+}
+
+/// The milliseconds since the first instant taken, with the clock of tokio so that it follows
+/// its paused time in tests.
+#[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+fn performance_now() -> f64 {
+    use std::sync::Mutex;
+
+    static ORIGIN: Mutex<Option<tokio::time::Instant>> = Mutex::new(None);
+    let origin = *ORIGIN
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(tokio::time::Instant::now);
+    origin.elapsed().as_secs_f64() * 1_000.0
+}
+
+/// The milliseconds since the Unix epoch, like `Date.now()`.
+#[cfg(feature = "futures")]
+pub(crate) fn date_now() -> f64 {
+    #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+    let now = js_sys::Date::now();
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |since| since.as_secs_f64() * 1_000.0);
+    now
+}
+
+fn to_duration(millis: f64) -> Duration {
+    Duration::from_secs_f64(millis.max(0.0) / 1_000.0)
+}
+
+fn to_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000.0
+}
+
+/// A point in time from a monotonic clock, like [`std::time::Instant`].
+///
+/// It is backed by `performance.now()`, so it keeps increasing when the system clock is
+/// changed, has a precision of a few microseconds to a millisecond depending on the browser,
+/// and can only be compared with the instants of the same page or worker.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Instant {
+    /// Milliseconds since the time origin of the page or worker.
+    millis: f64,
+}
+
+impl Instant {
+    /// The current time
+    pub fn now() -> Instant {
+        Instant {
+            millis: performance_now(),
+        }
+    }
+
+    /// The instant returned by `performance.now()`, or passed to `requestAnimationFrame`
+    /// callbacks, as `millis`.
+    pub(crate) fn from_millis(millis: f64) -> Instant {
+        Instant { millis }
+    }
+
+    /// The time elapsed since this instant, or zero if it is in the future
+    pub fn elapsed(&self) -> Duration {
+        Instant::now().duration_since(*self)
+    }
+
+    /// The time elapsed from `earlier` to this instant, or zero if `earlier` is later
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        to_duration(self.millis - earlier.millis)
+    }
+
+    /// The time elapsed from `earlier` to this instant, or `None` if `earlier` is later
+    pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+        if earlier.millis <= self.millis {
+            Some(self.duration_since(earlier))
+        } else {
+            None
+        }
+    }
+
+    /// The instant `duration` after this one, or `None` if it can't be represented
+    pub fn checked_add(&self, duration: Duration) -> Option<Instant> {
+        let millis = self.millis + to_millis(duration);
+        if millis.is_finite() {
+            Some(Instant { millis })
+        } else {
+            None
+        }
+    }
+
+    /// The instant `duration` before this one, or `None` if it can't be represented
+    pub fn checked_sub(&self, duration: Duration) -> Option<Instant> {
+        let millis = self.millis - to_millis(duration);
+        if millis.is_finite() {
+            Some(Instant { millis })
+        } else {
+            None
+        }
+    }
+
+    /// The value returned by `performance.now()` for this instant, in milliseconds
+    pub fn as_millis_f64(&self) -> f64 {
+        self.millis
+    }
+}
+
+impl Eq for Instant {}
+
+impl PartialOrd for Instant {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Instant {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.millis.total_cmp(&other.millis)
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    /// # Panics
+    ///
+    /// This function panics if the resulting instant can't be represented, see
+    /// [`Instant::checked_add`].
+    fn add(self, duration: Duration) -> Instant {
+        self.checked_add(duration)
+            .expect_throw("overflow when adding duration to instant")
+    }
+}
+
+impl AddAssign<Duration> for Instant {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+
+    /// # Panics
+    ///
+    /// This function panics if the resulting instant can't be represented, see
+    /// [`Instant::checked_sub`].
+    fn sub(self, duration: Duration) -> Instant {
+        self.checked_sub(duration)
+            .expect_throw("overflow when subtracting duration from instant")
+    }
+}
+
+impl SubAssign<Duration> for Instant {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    /// The time elapsed from `earlier` to this instant, or zero if `earlier` is later, see
+    /// [`Instant::duration_since`].
+    fn sub(self, earlier: Instant) -> Duration {
+        self.duration_since(earlier)
+    }
+}
+
+/// Measures the time elapsed since it was started, and the laps in between.
+///
+/// # Example
+///
+/// ```no_run
+/// use gloo_timers::instant::Stopwatch;
+///
+/// let mut stopwatch = Stopwatch::start();
+/// // Load the data...
+/// let loading = stopwatch.lap();
+/// // Render it...
+/// let rendering = stopwatch.lap();
+/// assert_eq!(stopwatch.laps(), [loading, rendering]);
+/// assert!(stopwatch.elapsed() >= loading + rendering);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Stopwatch {
+    start: Instant,
+    last_lap: Instant,
+    laps: Vec<Duration>,
+}
+
+impl Stopwatch {
+    /// Start measuring from now
+    pub fn start() -> Stopwatch {
+        let now = Instant::now();
+        Stopwatch {
+            start: now,
+            last_lap: now,
+            laps: Vec::new(),
+        }
+    }
+
+    /// The instant the stopwatch was started or last restarted at
+    pub fn started_at(&self) -> Instant {
+        self.start
+    }
+
+    /// The time elapsed since the stopwatch was started
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// End the current lap and start a new one, returning the time elapsed since the previous
+    /// lap or, for the first one, since the stopwatch was started
+    pub fn lap(&mut self) -> Duration {
+        let now = Instant::now();
+        let lap = now - self.last_lap;
+        self.last_lap = now;
+        self.laps.push(lap);
+        lap
+    }
+
+    /// The laps completed so far, in order
+    pub fn laps(&self) -> &[Duration] {
+        &self.laps
+    }
+
+    /// Start measuring from now again, clearing the laps, and return the time elapsed until now
+    pub fn restart(&mut self) -> Duration {
+        let elapsed = self.elapsed();
+        *self = Stopwatch::start();
+        elapsed
+    }
+}
@@ -56,11 +56,54 @@ TODO
 
 TODO
 
+## Background Tabs
+
+Browsers throttle the timers of background tabs; the timers of the [`worker`] module run in a
+dedicated worker to keep their accuracy.
+
+## Animation Frames
+
+Visual polling loops can run on animation frames rather than timeouts, see the [`frame`]
+module.
+
+## Many Timeouts
+
+Thousands of timeouts can share a single JavaScript timer with a [`TimerWheel`](wheel::TimerWheel),
+see the [`wheel`] module.
+
+## Idle Callbacks
+
+Low-priority work can be scheduled for when the browser is idle with
+`requestIdleCallback`, see the [`idle`] module.
+
+## Measuring Time
+
+`std::time::Instant` panics on `wasm32-unknown-unknown`; use the [`Instant`](instant::Instant)
+and [`Stopwatch`](instant::Stopwatch) types of the [`instant`] module instead.
+
+## Retries
+
+With the `futures` feature, failing async operations can be retried with exponential backoff,
+see the `retry` module.
+
+## Schedules
+
+Callbacks can run at calendar times, like every day at 9am, described with cron
+expressions, see the [`schedule`] module.
+
  */
 
 #![deny(missing_docs, missing_debug_implementations)]
 
 pub mod callback;
+pub mod frame;
+pub mod idle;
+pub mod instant;
+pub mod schedule;
+pub mod wheel;
+pub mod worker;
 
 #[cfg(feature = "futures")]
 pub mod future;
+#[cfg(feature = "futures")]
+pub mod retry;
@@ -0,0 +1,208 @@
+//! Retrying fallible async operations with exponential backoff.
+//!
+//! ```no_run
+//! use gloo_timers::retry::{retry_with_backoff, Backoff};
+//! use wasm_bindgen_futures::spawn_local;
+//! # async fn fetch_config() -> Result<String, String> { Ok(String::new()) }
+//!
+//! spawn_local(async {
+//!     let config = retry_with_backoff(&Backoff::new().max_attempts(5), || fetch_config()).await;
+//! });
+//! ```
+
+use std::future::Future;
+use std::time::Duration;
+
+use crate::future::sleep;
+
+/// A policy for [`retry_with_backoff`]: how many times to try, and how long to wait in between.
+///
+/// The delay before the `n`th retry is `initial_delay * factor^(n - 1)`, capped at `max_delay`.
+/// With jitter, which is enabled by default, a random delay between zero and that is used
+/// instead, so that clients that failed together don't retry together.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use gloo_timers::retry::Backoff;
+///
+/// let backoff = Backoff::new()
+///     .initial_delay(Duration::from_millis(200))
+///     .factor(3.0)
+///     .max_delay(Duration::from_secs(10))
+///     .max_attempts(4)
+///     .jitter(false);
+/// assert_eq!(backoff.delay(1), Duration::from_millis(200));
+/// assert_eq!(backoff.delay(3), Duration::from_millis(1_800));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backoff {
+    initial_delay: Duration,
+    max_delay: Duration,
+    factor: f64,
+    max_attempts: Option<u32>,
+    jitter: bool,
+}
+
+impl Backoff {
+    /// A policy of at most 5 attempts, waiting 100 ms then twice as long after each attempt, up
+    /// to 30 seconds, with jitter
+    pub fn new() -> Self {
+        Backoff {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+            factor: 2.0,
+            max_attempts: Some(5),
+            jitter: true,
+        }
+    }
+
+    /// Set the delay before the first retry
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Set the longest delay between two attempts
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set by how much the delay is multiplied after each retry
+    pub fn factor(mut self, factor: f64) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    /// Set the number of attempts, including the first one, after which the last error is
+    /// returned
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = Some(attempts);
+        self
+    }
+
+    /// Retry until the operation succeeds
+    pub fn unlimited_attempts(mut self) -> Self {
+        self.max_attempts = None;
+        self
+    }
+
+    /// Set whether the delays are randomized
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// The delay before the `retry`th retry, starting at 1, without jitter
+    pub fn delay(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let millis = self.initial_delay.as_secs_f64() * 1_000.0 * self.factor.powi(exponent);
+        let max_millis = self.max_delay.as_secs_f64() * 1_000.0;
+        if millis.is_finite() && millis < max_millis {
+            Duration::from_secs_f64(millis.max(0.0) / 1_000.0)
+        } else {
+            self.max_delay
+        }
+    }
+
+    fn jittered_delay(&self, retry: u32) -> Duration {
+        let delay = self.delay(retry);
+        if self.jitter {
+            delay.mul_f64(random())
+        } else {
+            delay
+        }
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A random number in `[0, 1)`.
+#[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+fn random() -> f64 {
+    js_sys::Math::random()
+}
+
+/// A random number in `[0, 1)`.
+#[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+fn random() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    // Every `RandomState` is seeded differently.
+    let bits = RandomState::new().build_hasher().finish() >> 11;
+    bits as f64 / (1u64 << 53) as f64
+}
+
+/// Runs `operation` until it succeeds, waiting longer and longer between attempts according to
+/// `policy`.
+///
+/// Resolves to the output of the first successful attempt, or to the error of the last attempt
+/// once `policy` runs out of them.
+///
+/// # Example
+///
+/// ```no_run
+/// use gloo_timers::retry::{retry_with_backoff, Backoff};
+/// use wasm_bindgen_futures::spawn_local;
+/// # async fn save(_: &str) -> Result<(), String> { Ok(()) }
+///
+/// spawn_local(async {
+///     let draft = String::from("...");
+///     let result = retry_with_backoff(&Backoff::new(), || save(&draft)).await;
+/// });
+/// ```
+pub async fn retry_with_backoff<F, Fut, T, E>(policy: &Backoff, operation: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_with_backoff_if(policy, operation, |_| true).await
+}
+
+/// Like [`retry_with_backoff`], but only retries while `should_retry` returns `true` for the
+/// error, e.g. for network errors but not for invalid requests.
+///
+/// # Example
+///
+/// ```no_run
+/// use gloo_timers::retry::{retry_with_backoff_if, Backoff};
+/// use wasm_bindgen_futures::spawn_local;
+/// # async fn fetch_status() -> Result<u16, u16> { Ok(200) }
+///
+/// spawn_local(async {
+///     let status = retry_with_backoff_if(&Backoff::new(), fetch_status, |status| *status >= 500)
+///         .await;
+/// });
+/// ```
+pub async fn retry_with_backoff_if<F, Fut, T, E, P>(
+    policy: &Backoff,
+    mut operation: F,
+    mut should_retry: P,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: FnMut(&E) -> bool,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(output) => return Ok(output),
+            Err(error) => {
+                let exhausted = policy.max_attempts.map_or(false, |max| attempt >= max);
+                if exhausted || !should_retry(&error) {
+                    return Err(error);
+                }
+            }
+        }
+        sleep(policy.jittered_delay(attempt)).await;
+        attempt += 1;
+    }
+}
@@ -0,0 +1,664 @@
+//! Running callbacks at calendar times, e.g. every day at 9am.
+//!
+//! A [`Schedule`] is parsed from a cron expression, or built with [`Schedule::daily`] and
+//! [`Schedule::weekly`], and is evaluated in the local time zone of the browser. A [`Scheduler`]
+//! then invokes a callback at every time of the schedule.
+//!
+//! ```no_run
+//! use gloo_timers::schedule::{Schedule, Scheduler};
+//!
+//! // At 9:00 on weekdays.
+//! let schedule: Schedule = "0 9 * * MON-FRI".parse().unwrap();
+//! let scheduler = Scheduler::new(schedule, || {
+//!     // Show the daily reminder...
+//! });
+//! ```
+//!
+//! # Daylight saving time
+//!
+//! Times that don't exist because the clocks go forward run as soon as the clocks have moved,
+//! e.g. at 3:30 instead of 2:30, and times that happen twice because the clocks go back only
+//! run the first time.
+//!
+//! # Long gaps
+//!
+//! Browsers delay timers in background tabs and stop them while the computer sleeps, so the
+//! [`Scheduler`] checks the clock at least every minute instead of trusting a single long
+//! timeout. If several times were missed, e.g. while the computer was asleep, the callback runs
+//! only once as soon as possible, and the schedule resumes from the current time.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+use std::str::FromStr;
+
+use crate::callback::Timeout;
+
+/// How long the [`Scheduler`] waits at most before checking the clock again, in milliseconds.
+const MAX_DELAY_MILLIS: f64 = 60_000.0;
+
+/// How many days are searched for the next time of a schedule: enough to reach any
+/// February 29th.
+const MAX_SEARCH_DAYS: u32 = 366 * 8 + 1;
+
+/// A day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    /// Sunday, `0` or `7` in cron expressions
+    Sunday = 0,
+    /// Monday
+    Monday = 1,
+    /// Tuesday
+    Tuesday = 2,
+    /// Wednesday
+    Wednesday = 3,
+    /// Thursday
+    Thursday = 4,
+    /// Friday
+    Friday = 5,
+    /// Saturday
+    Saturday = 6,
+}
+
+/// The error returned when parsing an invalid cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+}
+
+impl ParseError {
+    fn new(message: impl Into<String>) -> Self {
+        ParseError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid cron expression: {}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A local date and time, to the minute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Civil {
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+}
+
+fn is_leap_year(year: u32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The day of the week, with Sunday as 0, using Sakamoto's method.
+fn weekday(year: u32, month: u32, day: u32) -> u32 {
+    const OFFSETS: [u32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let year = if month < 3 { year - 1 } else { year };
+    (year + year / 4 - year / 100 + year / 400 + OFFSETS[month as usize - 1] + day) % 7
+}
+
+impl Civil {
+    fn from_millis(millis: f64) -> Self {
+        let date = js_sys::Date::new(&millis.into());
+        Civil {
+            year: date.get_full_year(),
+            month: date.get_month() + 1,
+            day: date.get_date(),
+            hour: date.get_hours(),
+            minute: date.get_minutes(),
+        }
+    }
+
+    /// The first millisecond of this minute, moved forward if it does not exist.
+    fn to_millis(self) -> f64 {
+        js_sys::Date::new_with_year_month_day_hr_min(
+            self.year,
+            self.month as i32 - 1,
+            self.day as i32,
+            self.hour as i32,
+            self.minute as i32,
+        )
+        .get_time()
+    }
+
+    fn next_day(self) -> Self {
+        let (year, month, day) = if self.day < days_in_month(self.year, self.month) {
+            (self.year, self.month, self.day + 1)
+        } else if self.month < 12 {
+            (self.year, self.month + 1, 1)
+        } else {
+            (self.year + 1, 1, 1)
+        };
+        Civil {
+            year,
+            month,
+            day,
+            hour: 0,
+            minute: 0,
+        }
+    }
+}
+
+/// When a [`Scheduler`] runs, in local time.
+///
+/// Schedules are parsed from cron expressions of five fields: minute (0-59), hour (0-23), day of
+/// the month (1-31), month (1-12 or `JAN`-`DEC`) and day of the week (0-7 or `SUN`-`SAT`, where
+/// both 0 and 7 are Sunday). Each field is `*`, a value, a range like `1-5`, a step like `*/15`
+/// or `0-30/10`, or a comma-separated list of those. As in cron, when both the day of the month
+/// and the day of the week are restricted, a day matching either of them matches. The
+/// `@yearly`, `@monthly`, `@weekly`, `@daily` and `@hourly` shortcuts are also accepted.
+///
+/// ```rust
+/// use gloo_timers::schedule::{Schedule, Weekday};
+///
+/// let every_quarter_hour: Schedule = "*/15 * * * *".parse().unwrap();
+/// let daily = Schedule::daily(9, 0);
+/// assert_eq!(daily, "0 9 * * *".parse().unwrap());
+/// assert_eq!(
+///     Schedule::weekly(Weekday::Monday, 9, 30),
+///     "30 9 * * MON".parse().unwrap(),
+/// );
+/// assert!("60 * * * *".parse::<Schedule>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether the days of the month are restricted, i.e. the field is not `*`.
+    days_restricted: bool,
+    /// Whether the days of the week are restricted, i.e. the field is not `*`.
+    weekdays_restricted: bool,
+}
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+fn bits(range: std::ops::RangeInclusive<u32>) -> u64 {
+    range.fold(0, |bits, value| bits | 1 << value)
+}
+
+fn parse_value(text: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, ParseError> {
+    let value = match names
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(text))
+    {
+        Some(index) => index as u32 + min,
+        None => text
+            .parse()
+            .map_err(|_| ParseError::new(format!("`{}` is not a number", text)))?,
+    };
+    if value < min || value > max {
+        return Err(ParseError::new(format!(
+            "{} is not between {} and {}",
+            value, min, max
+        )));
+    }
+    Ok(value)
+}
+
+fn parse_field(text: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, ParseError> {
+    let mut field = 0;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| ParseError::new(format!("`{}` is not a step", step)))?;
+                if step == 0 {
+                    return Err(ParseError::new("steps must be positive"));
+                }
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (
+                parse_value(start, min, max, names)?,
+                parse_value(end, min, max, names)?,
+            ),
+            None => {
+                let start = parse_value(range, min, max, names)?;
+                // `5/10` means from 5 to the end, every 10.
+                (start, if step.is_some() { max } else { start })
+            }
+        };
+        if start > end {
+            return Err(ParseError::new(format!("{} is after {}", start, end)));
+        }
+        let step = step.unwrap_or(1) as usize;
+        field |= (start..=end)
+            .step_by(step)
+            .fold(0, |bits, value| bits | 1 << value);
+    }
+    Ok(field)
+}
+
+impl FromStr for Schedule {
+    type Err = ParseError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let expression = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expression => expression,
+        };
+        let fields: Vec<_> = expression.split_whitespace().collect();
+        let (minutes, hours, days, months, weekdays) = match fields[..] {
+            [minutes, hours, days, months, weekdays] => (minutes, hours, days, months, weekdays),
+            _ => {
+                return Err(ParseError::new(format!(
+                    "expected 5 fields, found {}",
+                    fields.len()
+                )))
+            }
+        };
+        let mut weekday_bits = parse_field(weekdays, 0, 7, &WEEKDAYS)?;
+        // 7 is another name for Sunday.
+        if weekday_bits & 1 << 7 != 0 {
+            weekday_bits = (weekday_bits | 1) & !(1 << 7);
+        }
+        Ok(Schedule {
+            minutes: parse_field(minutes, 0, 59, &[])?,
+            hours: parse_field(hours, 0, 23, &[])?,
+            days: parse_field(days, 1, 31, &[])?,
+            months: parse_field(months, 1, 12, &MONTHS)?,
+            weekdays: weekday_bits,
+            days_restricted: !days.starts_with('*'),
+            weekdays_restricted: !weekdays.starts_with('*'),
+        })
+    }
+}
+
+impl Schedule {
+    /// Every day at `hour:minute`
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `hour` is above 23 or `minute` is above 59.
+    pub fn daily(hour: u32, minute: u32) -> Self {
+        assert!(hour < 24, "hour must be between 0 and 23");
+        assert!(minute < 60, "minute must be between 0 and 59");
+        Schedule {
+            minutes: 1 << minute,
+            hours: 1 << hour,
+            days: bits(1..=31),
+            months: bits(1..=12),
+            weekdays: bits(0..=6),
+            days_restricted: false,
+            weekdays_restricted: false,
+        }
+    }
+
+    /// Every week on `weekday` at `hour:minute`
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `hour` is above 23 or `minute` is above 59.
+    pub fn weekly(weekday: Weekday, hour: u32, minute: u32) -> Self {
+        Schedule {
+            weekdays: 1 << weekday as u32,
+            weekdays_restricted: true,
+            ..Self::daily(hour, minute)
+        }
+    }
+
+    fn matches_day(&self, year: u32, month: u32, day: u32) -> bool {
+        let day_matches = self.days & 1 << day != 0;
+        let weekday_matches = self.weekdays & 1 << weekday(year, month, day) != 0;
+        self.months & 1 << month != 0
+            && if self.days_restricted && self.weekdays_restricted {
+                day_matches || weekday_matches
+            } else {
+                day_matches && weekday_matches
+            }
+    }
+
+    /// The first local time of the schedule after `time`, in local time.
+    fn next_civil(&self, time: Civil) -> Option<Civil> {
+        let first =
+            |bits: u64, from: u32, to: u32| (from..=to).find(|value| bits & 1 << value != 0);
+        let mut day = time;
+        for _ in 0..MAX_SEARCH_DAYS {
+            if self.matches_day(day.year, day.month, day.day) {
+                for hour in day.hour..24 {
+                    if self.hours & 1 << hour == 0 {
+                        continue;
+                    }
+                    let from = if hour == day.hour { day.minute } else { 0 };
+                    if let Some(minute) = first(self.minutes, from, 59) {
+                        return Some(Civil {
+                            hour,
+                            minute,
+                            ..day
+                        });
+                    }
+                }
+            }
+            day = day.next_day();
+        }
+        None
+    }
+
+    /// The first time of the schedule after `time`, or `None` if the schedule never runs, e.g.
+    /// on February 30th
+    ///
+    /// Times are in milliseconds since the Unix epoch, as returned by `Date.now()`.
+    pub fn next_after(&self, time: f64) -> Option<f64> {
+        let mut civil = Civil::from_millis(time);
+        loop {
+            // Start from the next minute, as the schedule has a precision of one minute.
+            civil = Civil {
+                minute: civil.minute + 1,
+                ..civil
+            };
+            if civil.minute == 60 {
+                civil = Civil {
+                    hour: civil.hour + 1,
+                    minute: 0,
+                    ..civil
+                };
+            }
+            if civil.hour == 24 {
+                civil = civil.next_day();
+            }
+            civil = self.next_civil(civil)?;
+            let millis = civil.to_millis();
+            // The local time happened earlier, before the clocks went back.
+            if millis > time {
+                return Some(millis);
+            }
+        }
+    }
+}
+
+struct Inner {
+    schedule: Schedule,
+    callback: Box<dyn FnMut()>,
+    next: Option<f64>,
+    timeout: Option<Timeout>,
+}
+
+/// Waits for the next time, checking the clock at least every minute.
+fn arm(inner: &Rc<RefCell<Inner>>) {
+    let next = match inner.borrow().next {
+        Some(next) => next,
+        None => return,
+    };
+    let delay = (next - js_sys::Date::now()).clamp(0.0, MAX_DELAY_MILLIS);
+    let weak = Rc::downgrade(inner);
+    let timeout = Timeout::new(delay.ceil() as u32, move || tick(&weak));
+    inner.borrow_mut().timeout = Some(timeout);
+}
+
+fn tick(inner: &Weak<RefCell<Inner>>) {
+    let inner = match inner.upgrade() {
+        Some(inner) => inner,
+        None => return,
+    };
+    let now = js_sys::Date::now();
+    let due = inner.borrow().next.map_or(false, |next| now >= next);
+    if due {
+        // Times missed while the timers were paused are skipped.
+        let next = inner.borrow().schedule.next_after(now);
+        inner.borrow_mut().next = next;
+        // Take the callback out, so that it can use the scheduler.
+        let mut callback = std::mem::replace(&mut inner.borrow_mut().callback, Box::new(|| {}));
+        callback();
+        inner.borrow_mut().callback = callback;
+    }
+    arm(&inner);
+}
+
+/// Invokes a callback at every time of a [`Schedule`].
+///
+/// Once scheduled, you can [`drop`] the [`Scheduler`] to stop it or [`forget`](Scheduler::forget)
+/// to leak it. Once forgotten, the scheduler will keep running forever.
+#[must_use = "schedulers stop on drop; either call `forget` or `drop` explicitly"]
+pub struct Scheduler {
+    inner: Rc<RefCell<Inner>>,
+}
+
+impl Scheduler {
+    /// Invoke `callback` at every time of `schedule`, starting from now.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::schedule::{Schedule, Scheduler};
+    ///
+    /// let scheduler = Scheduler::new(Schedule::daily(9, 0), move || {
+    ///     // Do something every day at 9am...
+    /// });
+    /// ```
+    pub fn new<F>(schedule: Schedule, callback: F) -> Scheduler
+    where
+        F: 'static + FnMut(),
+    {
+        let next = schedule.next_after(js_sys::Date::now());
+        let inner = Rc::new(RefCell::new(Inner {
+            schedule,
+            callback: Box::new(callback),
+            next,
+            timeout: None,
+        }));
+        arm(&inner);
+        Scheduler { inner }
+    }
+
+    /// The schedule the callback is invoked at
+    pub fn schedule(&self) -> Schedule {
+        self.inner.borrow().schedule.clone()
+    }
+
+    /// The next time the callback will be invoked, in milliseconds since the Unix epoch, or
+    /// `None` if the schedule never runs
+    pub fn next(&self) -> Option<f64> {
+        self.inner.borrow().next
+    }
+
+    /// Forget this resource without stopping the scheduler.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::schedule::{Schedule, Scheduler};
+    ///
+    /// // We want to do stuff every hour, indefinitely.
+    /// Scheduler::new("@hourly".parse().unwrap(), || {
+    ///     // Do stuff...
+    /// }).forget();
+    /// ```
+    pub fn forget(self) {
+        std::mem::forget(self.inner);
+    }
+}
+
+impl fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.borrow();
+        f.debug_struct("Scheduler")
+            .field("schedule", &inner.schedule)
+            .field("next", &inner.next)
+            .finish()
+    }
+}
+
+#[cfg(feature = "futures")]
+pub use self::stream::ScheduleStream;
+
+#[cfg(feature = "futures")]
+mod stream {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use futures_channel::mpsc;
+    use futures_core::stream::Stream;
+    use wasm_bindgen::UnwrapThrowExt;
+
+    use super::{Schedule, Scheduler};
+
+    /// The times of a [`Schedule`] as a `Stream`.
+    ///
+    /// Once scheduled, if you want to stop the schedule, you can `drop` the stream.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::stream::StreamExt;
+    /// use gloo_timers::schedule::{Schedule, ScheduleStream};
+    /// use wasm_bindgen_futures::spawn_local;
+    ///
+    /// spawn_local(async {
+    ///     ScheduleStream::new(Schedule::daily(0, 0)).for_each(|_| async {
+    ///         // Do stuff every midnight...
+    ///     }).await;
+    /// });
+    /// ```
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or spawned"]
+    pub struct ScheduleStream {
+        receiver: mpsc::UnboundedReceiver<()>,
+        _inner: Scheduler,
+    }
+
+    impl ScheduleStream {
+        /// Create a stream yielding at every time of `schedule`, starting from now.
+        pub fn new(schedule: Schedule) -> ScheduleStream {
+            let (sender, receiver) = mpsc::unbounded();
+            let inner = Scheduler::new(schedule, move || {
+                // if the receiver was dropped we do nothing.
+                sender.unbounded_send(()).unwrap_throw();
+            });
+            ScheduleStream {
+                receiver,
+                _inner: inner,
+            }
+        }
+    }
+
+    impl Stream for ScheduleStream {
+        type Item = ();
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            Stream::poll_next(Pin::new(&mut self.receiver), cx)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn civil(year: u32, month: u32, day: u32, hour: u32, minute: u32) -> Civil {
+        Civil {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let schedule: Schedule = "*/20 9-17 * JAN,jul 1-5".parse().unwrap();
+        assert_eq!(schedule.minutes, 1 | 1 << 20 | 1 << 40);
+        assert_eq!(schedule.hours, bits(9..=17));
+        assert_eq!(schedule.months, 1 << 1 | 1 << 7);
+        assert_eq!(schedule.weekdays, bits(1..=5));
+        assert!(!schedule.days_restricted);
+        assert!(schedule.weekdays_restricted);
+
+        assert_eq!("0 0 * * 7".parse::<Schedule>().unwrap().weekdays, 1);
+        assert_eq!(
+            "5/20 * * * *".parse::<Schedule>().unwrap().minutes,
+            bits(5..=5) | 1 << 25 | 1 << 45
+        );
+        assert_eq!("@daily".parse::<Schedule>(), Ok(Schedule::daily(0, 0)));
+
+        for invalid in [
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "* * * FOO *",
+        ] {
+            assert!(invalid.parse::<Schedule>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn weekdays() {
+        assert_eq!(weekday(1970, 1, 1), 4);
+        assert_eq!(weekday(2000, 2, 29), 2);
+        assert_eq!(weekday(2024, 12, 31), 2);
+    }
+
+    #[test]
+    fn next_civil() {
+        let daily = Schedule::daily(9, 0);
+        assert_eq!(
+            daily.next_civil(civil(2024, 1, 1, 9, 0)),
+            Some(civil(2024, 1, 1, 9, 0))
+        );
+        assert_eq!(
+            daily.next_civil(civil(2024, 1, 1, 9, 1)),
+            Some(civil(2024, 1, 2, 9, 0))
+        );
+        assert_eq!(
+            daily.next_civil(civil(2024, 12, 31, 10, 0)),
+            Some(civil(2025, 1, 1, 9, 0))
+        );
+
+        // 2024-01-01 is a Monday.
+        let weekly = Schedule::weekly(Weekday::Friday, 17, 30);
+        assert_eq!(
+            weekly.next_civil(civil(2024, 1, 1, 0, 0)),
+            Some(civil(2024, 1, 5, 17, 30))
+        );
+
+        let leap: Schedule = "0 0 29 2 *".parse().unwrap();
+        assert_eq!(
+            leap.next_civil(civil(2097, 3, 1, 0, 0)),
+            Some(civil(2104, 2, 29, 0, 0))
+        );
+        let never: Schedule = "0 0 30 2 *".parse().unwrap();
+        assert_eq!(never.next_civil(civil(2024, 1, 1, 0, 0)), None);
+
+        // Either the 13th or a Friday.
+        let either: Schedule = "0 0 13 * FRI".parse().unwrap();
+        assert_eq!(
+            either.next_civil(civil(2024, 1, 6, 0, 0)),
+            Some(civil(2024, 1, 12, 0, 0))
+        );
+        assert_eq!(
+            either.next_civil(civil(2024, 1, 12, 0, 1)),
+            Some(civil(2024, 1, 13, 0, 0))
+        );
+    }
+}
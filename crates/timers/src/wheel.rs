@@ -0,0 +1,452 @@
+//! Many timeouts multiplexed onto a single JavaScript timer with a hierarchical timer wheel.
+//!
+//! Each [`Timeout`](crate::callback::Timeout) costs a `setTimeout` call and a closure crossing
+//! the JavaScript boundary, which adds up for games, simulations or caches with a TTL per item
+//! that keep thousands of timeouts around. A [`TimerWheel`] keeps its timeouts on the Rust side,
+//! sorted into buckets by how far away they are, and only ever has one `setTimeout` pending, for
+//! the earliest bucket.
+//!
+//! ```no_run
+//! use gloo_timers::wheel::TimerWheel;
+//!
+//! let wheel = TimerWheel::new();
+//! for item in 0..10_000 {
+//!     wheel
+//!         .timeout(60_000 + item, move || {
+//!             // Evict the item from the cache...
+//!         })
+//!         .forget();
+//! }
+//! ```
+//!
+//! The timeouts fire in order of their deadlines, rounded up to the resolution of the wheel,
+//! and all the ones due when the JavaScript timer fires are run one after the other.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+use crate::callback;
+use crate::instant::Instant;
+
+/// Each level has 64 slots, i.e. 6 bits of the deadlines.
+const SLOT_BITS: u32 = 6;
+const SLOTS: usize = 1 << SLOT_BITS;
+/// Enough levels for any deadline of 64 bits.
+const LEVELS: usize = 11;
+
+/// The slots of the timeouts, in ticks of the wheel, without their callbacks.
+///
+/// A timeout is stored at the level of the most significant 6 bits in which its deadline differs
+/// from the current tick, in the slot of its deadline at that level. The slots of a level are
+/// processed when the current tick reaches them, firing the timeouts that are due and moving
+/// the other ones to lower levels.
+#[derive(Debug)]
+struct Slots {
+    elapsed: u64,
+    levels: Vec<Vec<Vec<(u64, u64)>>>,
+}
+
+impl Slots {
+    fn new() -> Self {
+        Slots {
+            elapsed: 0,
+            levels: (0..LEVELS).map(|_| vec![Vec::new(); SLOTS]).collect(),
+        }
+    }
+
+    fn level_for(&self, deadline: u64) -> usize {
+        let masked = (self.elapsed ^ deadline) | (SLOTS as u64 - 1);
+        ((63 - masked.leading_zeros()) / SLOT_BITS) as usize
+    }
+
+    fn slot_for(deadline: u64, level: usize) -> usize {
+        ((deadline >> (level as u32 * SLOT_BITS)) as usize) & (SLOTS - 1)
+    }
+
+    /// Adds the timeout `id`, due at the tick `deadline`, which must be after the current one.
+    fn insert(&mut self, id: u64, deadline: u64) {
+        let deadline = deadline.max(self.elapsed + 1);
+        let level = self.level_for(deadline);
+        self.levels[level][Self::slot_for(deadline, level)].push((id, deadline));
+    }
+
+    /// Moves the current tick to `now`, returning the timeouts that are due, in order.
+    fn advance(&mut self, now: u64) -> Vec<u64> {
+        if now <= self.elapsed {
+            return Vec::new();
+        }
+        let mut reached = Vec::new();
+        for level in (0..LEVELS).rev() {
+            let shift = level as u32 * SLOT_BITS;
+            let from = self.elapsed >> shift;
+            let to = now >> shift;
+            let count = (to - from).min(SLOTS as u64);
+            for index in (to - count + 1)..=to {
+                let slot = &mut self.levels[level][(index as usize) & (SLOTS - 1)];
+                reached.append(slot);
+            }
+        }
+
+        self.elapsed = now;
+        let mut due = Vec::new();
+        for (id, deadline) in reached {
+            if deadline <= now {
+                due.push((deadline, id));
+            } else {
+                self.insert(id, deadline);
+            }
+        }
+        due.sort_unstable();
+        due.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// The earliest tick at which a slot is reached, if any timeout is left.
+    ///
+    /// This is the exact deadline of the earliest timeout when it is in the lowest level, and a
+    /// lower bound otherwise.
+    fn next_expiration(&self) -> Option<u64> {
+        (0..LEVELS)
+            .filter_map(|level| {
+                let shift = level as u32 * SLOT_BITS;
+                let current = self.elapsed >> shift;
+                let base = current & !(SLOTS as u64 - 1);
+                let start = (current as usize & (SLOTS - 1)) + 1;
+                (start..SLOTS)
+                    .find(|&slot| !self.levels[level][slot].is_empty())
+                    .map(|slot| (base | slot as u64) << shift)
+            })
+            .min()
+    }
+}
+
+struct Wheel {
+    origin: Instant,
+    resolution: f64,
+    slots: Slots,
+    callbacks: HashMap<u64, Box<dyn FnOnce()>>,
+    next_id: u64,
+    /// The JavaScript timer, and the tick it is set for.
+    timer: Option<(callback::Timeout, u64)>,
+}
+
+impl Wheel {
+    fn now(&self) -> f64 {
+        Instant::now().as_millis_f64() - self.origin.as_millis_f64()
+    }
+
+    fn tick(&self) -> u64 {
+        (self.now() / self.resolution).floor() as u64
+    }
+
+    fn insert(&mut self, millis: u32, callback: Box<dyn FnOnce()>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        // Rounded up, so that timeouts never fire early.
+        let deadline = ((self.now() + f64::from(millis)) / self.resolution).ceil() as u64;
+        self.slots.insert(id, deadline);
+        self.callbacks.insert(id, callback);
+        id
+    }
+
+    fn run(inner: &Rc<RefCell<Wheel>>) {
+        let callbacks: Vec<_> = {
+            let mut wheel = inner.borrow_mut();
+            wheel.timer = None;
+            let now = wheel.tick();
+            let due = wheel.slots.advance(now);
+            due.into_iter()
+                .filter_map(|id| wheel.callbacks.remove(&id))
+                .collect()
+        };
+        // The wheel is not borrowed anymore, so the callbacks can add or cancel timeouts.
+        for callback in callbacks {
+            callback();
+        }
+        Wheel::arm(inner);
+    }
+
+    /// Sets the JavaScript timer for the next slot to process, if it isn't already.
+    fn arm(inner: &Rc<RefCell<Wheel>>) {
+        let mut wheel = inner.borrow_mut();
+        if wheel.callbacks.is_empty() {
+            wheel.timer = None;
+            return;
+        }
+        let next = match wheel.slots.next_expiration() {
+            Some(next) => next,
+            None => return,
+        };
+        if matches!(wheel.timer, Some((_, tick)) if tick <= next) {
+            return;
+        }
+        let delay = (next as f64 * wheel.resolution - wheel.now())
+            .max(0.0)
+            .ceil();
+        let weak = Rc::downgrade(inner);
+        let timeout = callback::Timeout::new(delay.min(f64::from(i32::MAX)) as u32, move || {
+            if let Some(inner) = weak.upgrade() {
+                Wheel::run(&inner);
+            }
+        });
+        wheel.timer = Some((timeout, next));
+    }
+}
+
+/// A hierarchical timer wheel, running many timeouts with a single JavaScript timer.
+///
+/// The wheel is shared by its clones. Once all of them are dropped, the pending timeouts are
+/// dropped too, without being run.
+#[derive(Clone)]
+pub struct TimerWheel {
+    inner: Rc<RefCell<Wheel>>,
+}
+
+impl TimerWheel {
+    /// Create a wheel with a resolution of one millisecond.
+    pub fn new() -> TimerWheel {
+        Self::with_resolution(1)
+    }
+
+    /// Create a wheel whose timeouts are rounded up to multiples of `millis` milliseconds.
+    ///
+    /// A coarser resolution batches the timeouts that are due around the same time, so that
+    /// they fire together with fewer wake-ups.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `millis` is zero.
+    pub fn with_resolution(millis: u32) -> TimerWheel {
+        assert!(
+            millis > 0,
+            "the resolution of a timer wheel must not be zero"
+        );
+        TimerWheel {
+            inner: Rc::new(RefCell::new(Wheel {
+                origin: Instant::now(),
+                resolution: f64::from(millis),
+                slots: Slots::new(),
+                callbacks: HashMap::new(),
+                next_id: 0,
+                timer: None,
+            })),
+        }
+    }
+
+    /// Schedule a timeout to invoke `callback` in `millis` milliseconds from now.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::wheel::TimerWheel;
+    ///
+    /// let wheel = TimerWheel::new();
+    /// let timeout = wheel.timeout(1_000, move || {
+    ///     // Do something...
+    /// });
+    /// ```
+    pub fn timeout<F>(&self, millis: u32, callback: F) -> WheelTimeout
+    where
+        F: 'static + FnOnce(),
+    {
+        let id = self.inner.borrow_mut().insert(millis, Box::new(callback));
+        Wheel::arm(&self.inner);
+        WheelTimeout {
+            wheel: Rc::downgrade(&self.inner),
+            id: Some(id),
+        }
+    }
+
+    /// The number of pending timeouts
+    pub fn len(&self) -> usize {
+        self.inner.borrow().callbacks.len()
+    }
+
+    /// Whether there is no pending timeout
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for TimerWheel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for TimerWheel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wheel = self.inner.borrow();
+        f.debug_struct("TimerWheel")
+            .field("resolution", &wheel.resolution)
+            .field("len", &wheel.callbacks.len())
+            .finish()
+    }
+}
+
+/// A timeout scheduled on a [`TimerWheel`].
+///
+/// See `TimerWheel::timeout` for scheduling new timeouts.
+///
+/// Once scheduled, you can [`drop`] the [`WheelTimeout`] to cancel it or
+/// [`forget`](WheelTimeout::forget) to leak it.
+#[derive(Debug)]
+#[must_use = "timeouts cancel on drop; either call `forget` or `drop` explicitly"]
+pub struct WheelTimeout {
+    wheel: Weak<RefCell<Wheel>>,
+    id: Option<u64>,
+}
+
+impl Drop for WheelTimeout {
+    /// Disposes of the timeout, removing it from the wheel.
+    fn drop(&mut self) {
+        if let (Some(id), Some(wheel)) = (self.id.take(), self.wheel.upgrade()) {
+            // The slot is cleaned up when the wheel reaches it.
+            let callback = wheel.borrow_mut().callbacks.remove(&id);
+            drop(callback);
+        }
+    }
+}
+
+impl WheelTimeout {
+    /// Whether the timeout is still waiting to fire
+    pub fn is_pending(&self) -> bool {
+        match (self.id, self.wheel.upgrade()) {
+            (Some(id), Some(wheel)) => wheel.borrow().callbacks.contains_key(&id),
+            _ => false,
+        }
+    }
+
+    /// Forgets this resource without cancelling the timeout.
+    pub fn forget(mut self) {
+        self.id = None;
+    }
+
+    /// Cancel this timeout so that the callback is not invoked after the time is up.
+    pub fn cancel(self) {
+        drop(self);
+    }
+}
+
+#[cfg(feature = "futures")]
+pub use self::future::WheelSleep;
+
+#[cfg(feature = "futures")]
+mod future {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use futures_channel::oneshot;
+    use wasm_bindgen::UnwrapThrowExt;
+
+    use super::{TimerWheel, WheelTimeout};
+
+    /// A timeout on a [`TimerWheel`] as a `Future`.
+    ///
+    /// Once scheduled, if you change your mind and don't want the timeout to fire, you can
+    /// `drop` the future.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless polled or spawned"]
+    pub struct WheelSleep {
+        _inner: WheelTimeout,
+        rx: oneshot::Receiver<()>,
+    }
+
+    impl TimerWheel {
+        /// Create a future resolving in `millis` milliseconds from now.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use gloo_timers::wheel::TimerWheel;
+        /// use wasm_bindgen_futures::spawn_local;
+        ///
+        /// let wheel = TimerWheel::new();
+        /// spawn_local(async move {
+        ///     wheel.sleep(1_000).await;
+        ///     // Do stuff after one second...
+        /// });
+        /// ```
+        pub fn sleep(&self, millis: u32) -> WheelSleep {
+            let (tx, rx) = oneshot::channel();
+            let inner = self.timeout(millis, move || {
+                // if the receiver was dropped we do nothing.
+                let _ = tx.send(());
+            });
+            WheelSleep { _inner: inner, rx }
+        }
+    }
+
+    impl Future for WheelSleep {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            Future::poll(Pin::new(&mut self.rx), cx).map(|t| t.unwrap_throw())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fires_in_order() {
+        let mut slots = Slots::new();
+        for (id, deadline) in [(0, 5), (1, 3), (2, 70), (3, 4_100), (4, 3)] {
+            slots.insert(id, deadline);
+        }
+        assert_eq!(slots.next_expiration(), Some(3));
+        assert_eq!(slots.advance(2), Vec::<u64>::new());
+        assert_eq!(slots.advance(5), vec![1, 4, 0]);
+        assert_eq!(slots.advance(69), Vec::<u64>::new());
+        assert_eq!(slots.advance(70), vec![2]);
+        assert_eq!(slots.advance(4_099), Vec::<u64>::new());
+        assert_eq!(slots.advance(4_100), vec![3]);
+        assert_eq!(slots.next_expiration(), None);
+    }
+
+    #[test]
+    fn cascades_step_by_step() {
+        let mut slots = Slots::new();
+        let deadlines = [1, 63, 64, 65, 200, 4_095, 4_096, 262_145, 10_000_000];
+        for (id, &deadline) in deadlines.iter().enumerate() {
+            slots.insert(id as u64, deadline);
+        }
+        let mut fired = Vec::new();
+        while let Some(next) = slots.next_expiration() {
+            assert!(next > slots.elapsed);
+            for id in slots.advance(next) {
+                assert_eq!(deadlines[id as usize], next);
+                fired.push(id);
+            }
+        }
+        assert_eq!(fired, (0..deadlines.len() as u64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn large_jumps() {
+        let mut slots = Slots::new();
+        slots.insert(0, 100);
+        slots.insert(1, 5_000_000);
+        slots.insert(2, 5_000_001);
+        slots.insert(3, u64::MAX - 1);
+        slots.advance(10);
+        assert_eq!(slots.advance(4_999_999), vec![0]);
+        assert_eq!(slots.advance(5_000_000), vec![1]);
+        assert_eq!(slots.next_expiration(), Some(5_000_001));
+        assert_eq!(slots.advance(u64::MAX - 2), vec![2]);
+        assert_eq!(slots.advance(u64::MAX - 1), vec![3]);
+    }
+
+    #[test]
+    fn past_deadlines_fire_on_next_tick() {
+        let mut slots = Slots::new();
+        slots.advance(100);
+        slots.insert(0, 50);
+        assert_eq!(slots.next_expiration(), Some(101));
+        assert_eq!(slots.advance(101), vec![0]);
+    }
+}
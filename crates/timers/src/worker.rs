@@ -0,0 +1,438 @@
+//! Timers running in a dedicated worker, to keep their accuracy in background tabs.
+//!
+//! Browsers throttle the timers of background tabs, down to once per second or even once per
+//! minute, which breaks music players, pollers and countdowns. Workers are throttled much less,
+//! so the [`Timeout`] and [`Interval`] of this module are scheduled by a tiny worker shared by
+//! all of them, which posts a message back when they fire.
+//!
+//! The callbacks still run on the current thread, and when workers are not available (e.g. in
+//! Node.js, or when a Content Security Policy forbids `blob:` workers), the timers fall back
+//! to the ones of the [`callback`] module.
+//!
+//! ```no_run
+//! use gloo_timers::worker::Interval;
+//!
+//! let interval = Interval::new(1_000, move || {
+//!     // Update the countdown, even while the tab is in the background...
+//! });
+//! interval.forget();
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use js_sys::{Array, Function, Object, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::callback;
+
+/// The script of the worker, receiving `[op, id, millis]` messages and posting back the `id` of
+/// the timers as they fire.
+const WORKER_SCRIPT: &str = "\
+const timers = new Map();
+onmessage = ({ data: [op, id, millis] }) => {
+    if (op === 2) {
+        clearTimeout(timers.get(id));
+        timers.delete(id);
+    } else if (op === 1) {
+        timers.set(id, setInterval(() => postMessage(id), millis));
+    } else {
+        timers.set(id, setTimeout(() => { timers.delete(id); postMessage(id); }, millis));
+    }
+};
+";
+
+const OP_TIMEOUT: u32 = 0;
+const OP_INTERVAL: u32 = 1;
+const OP_CLEAR: u32 = 2;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = "Worker")]
+    type RawWorker;
+
+    #[wasm_bindgen(constructor, js_class = "Worker", catch)]
+    fn new(url: &str) -> Result<RawWorker, JsValue>;
+
+    #[wasm_bindgen(method, js_class = "Worker", js_name = "postMessage")]
+    fn post_message(this: &RawWorker, message: &JsValue);
+
+    #[wasm_bindgen(method, setter, js_class = "Worker")]
+    fn set_onmessage(this: &RawWorker, handler: &Function);
+
+    type MessageEvent;
+
+    #[wasm_bindgen(method, getter)]
+    fn data(this: &MessageEvent) -> JsValue;
+
+    #[wasm_bindgen(js_name = "Blob")]
+    type RawBlob;
+
+    #[wasm_bindgen(constructor, js_class = "Blob", catch)]
+    fn new(parts: &Array, options: &JsValue) -> Result<RawBlob, JsValue>;
+
+    #[wasm_bindgen(js_namespace = URL, js_name = "createObjectURL", catch)]
+    fn create_object_url(blob: &RawBlob) -> Result<String, JsValue>;
+}
+
+thread_local! {
+    /// The worker of the current thread, or `None` if workers are not available.
+    static WORKER: Option<TimerWorker> = TimerWorker::spawn();
+}
+
+/// A callback waiting for its timer to fire.
+struct Entry {
+    callback: Rc<RefCell<dyn FnMut()>>,
+    once: bool,
+}
+
+struct TimerWorker {
+    worker: RawWorker,
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+    entries: RefCell<HashMap<u32, Entry>>,
+    next_id: Cell<u32>,
+}
+
+impl TimerWorker {
+    fn spawn() -> Option<TimerWorker> {
+        let global = js_sys::global();
+        let supported = ["Worker", "Blob", "URL"]
+            .iter()
+            .all(|name| Reflect::has(&global, &JsValue::from_str(name)).unwrap_or(false));
+        if !supported {
+            return None;
+        }
+
+        let options = Object::new();
+        Reflect::set(&options, &"type".into(), &"text/javascript".into()).ok()?;
+        let blob = RawBlob::new(&Array::of1(&WORKER_SCRIPT.into()), &options).ok()?;
+        let url = create_object_url(&blob).ok()?;
+        let worker = RawWorker::new(&url).ok()?;
+
+        let onmessage = Closure::wrap(Box::new(|event: MessageEvent| {
+            if let Some(id) = event.data().as_f64() {
+                fire(id as u32);
+            }
+        }) as Box<dyn FnMut(MessageEvent)>);
+        worker.set_onmessage(onmessage.as_ref().unchecked_ref());
+
+        Some(TimerWorker {
+            worker,
+            _onmessage: onmessage,
+            entries: RefCell::new(HashMap::new()),
+            next_id: Cell::new(0),
+        })
+    }
+
+    fn schedule(&self, op: u32, millis: u32, entry: Entry) -> u32 {
+        let id = self.next_id.get();
+        self.next_id.set(id.wrapping_add(1));
+        self.entries.borrow_mut().insert(id, entry);
+        self.worker
+            .post_message(&Array::of3(&op.into(), &id.into(), &millis.into()));
+        id
+    }
+
+    fn clear(&self, id: u32) {
+        if self.entries.borrow_mut().remove(&id).is_some() {
+            self.worker
+                .post_message(&Array::of3(&OP_CLEAR.into(), &id.into(), &0.into()));
+        }
+    }
+}
+
+fn fire(id: u32) {
+    let callback = WORKER.with(|worker| {
+        let mut entries = worker.as_ref()?.entries.borrow_mut();
+        let entry = entries.get(&id)?;
+        let callback = entry.callback.clone();
+        if entry.once {
+            entries.remove(&id);
+        }
+        Some(callback)
+    });
+    // The entries are not borrowed anymore, so the callback can schedule or clear timers.
+    if let Some(callback) = callback {
+        (callback.borrow_mut())();
+    }
+}
+
+/// Schedules `callback` in the worker, or returns it back if workers are not available.
+fn schedule<F>(op: u32, millis: u32, once: bool, callback: F) -> Result<u32, F>
+where
+    F: 'static + FnMut(),
+{
+    let mut callback = Some(callback);
+    let id = WORKER.with(|worker| {
+        let worker = worker.as_ref()?;
+        let entry = Entry {
+            callback: Rc::new(RefCell::new(callback.take().unwrap_throw())),
+            once,
+        };
+        Some(worker.schedule(op, millis, entry))
+    });
+    id.ok_or_else(|| callback.take().unwrap_throw())
+}
+
+fn clear(id: u32) {
+    WORKER.with(|worker| {
+        if let Some(worker) = worker {
+            worker.clear(id);
+        }
+    });
+}
+
+#[derive(Debug)]
+enum Inner<T> {
+    Worker(u32),
+    Fallback(T),
+}
+
+/// A scheduled timeout, run by the worker.
+///
+/// See `Timeout::new` for scheduling new timeouts.
+///
+/// Once scheduled, you can [`drop`] the [`Timeout`] to clear it or
+/// [`forget`](Timeout::forget) to leak it.
+#[derive(Debug)]
+#[must_use = "timeouts cancel on drop; either call `forget` or `drop` explicitly"]
+pub struct Timeout {
+    inner: Option<Inner<callback::Timeout>>,
+}
+
+impl Drop for Timeout {
+    /// Disposes of the timeout, dually cancelling this timeout in the worker.
+    fn drop(&mut self) {
+        if let Some(Inner::Worker(id)) = self.inner.take() {
+            clear(id);
+        }
+    }
+}
+
+impl Timeout {
+    /// Schedule a timeout to invoke `callback` in `millis` milliseconds from now.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::worker::Timeout;
+    ///
+    /// let timeout = Timeout::new(1_000, move || {
+    ///     // Do something...
+    /// });
+    /// ```
+    pub fn new<F>(millis: u32, callback: F) -> Timeout
+    where
+        F: 'static + FnOnce(),
+    {
+        let mut callback = Some(callback);
+        let once = move || {
+            if let Some(callback) = callback.take() {
+                callback();
+            }
+        };
+        let inner = match schedule(OP_TIMEOUT, millis, true, once) {
+            Ok(id) => Inner::Worker(id),
+            Err(once) => Inner::Fallback(callback::Timeout::new(millis, once)),
+        };
+        Timeout { inner: Some(inner) }
+    }
+
+    /// Whether this timeout is run by the worker, rather than falling back to `setTimeout` on
+    /// the current thread.
+    pub fn is_in_worker(&self) -> bool {
+        matches!(self.inner, Some(Inner::Worker(_)))
+    }
+
+    /// Forgets this resource without clearing the timeout.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::worker::Timeout;
+    ///
+    /// Timeout::new(1_000, || {
+    ///     // Do stuff...
+    /// }).forget();
+    /// ```
+    pub fn forget(mut self) {
+        if let Some(Inner::Fallback(timeout)) = self.inner.take() {
+            timeout.forget();
+        }
+    }
+
+    /// Cancel this timeout so that the callback is not invoked after the time is up.
+    pub fn cancel(self) {
+        drop(self);
+    }
+}
+
+/// A scheduled interval, run by the worker.
+///
+/// See `Interval::new` for scheduling new intervals.
+///
+/// Once scheduled, you can [`drop`] the [`Interval`] to clear it or
+/// [`forget`](Interval::forget) to leak it. Once forgotten, the interval will keep running
+/// forever.
+#[derive(Debug)]
+#[must_use = "intervals cancel on drop; either call `forget` or `drop` explicitly"]
+pub struct Interval {
+    inner: Option<Inner<callback::Interval>>,
+}
+
+impl Drop for Interval {
+    /// Disposes of the interval, dually cancelling this interval in the worker.
+    fn drop(&mut self) {
+        if let Some(Inner::Worker(id)) = self.inner.take() {
+            clear(id);
+        }
+    }
+}
+
+impl Interval {
+    /// Schedule an interval to invoke `callback` every `millis` milliseconds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::worker::Interval;
+    ///
+    /// let interval = Interval::new(1_000, move || {
+    ///     // Do something...
+    /// });
+    /// ```
+    pub fn new<F>(millis: u32, callback: F) -> Interval
+    where
+        F: 'static + FnMut(),
+    {
+        let inner = match schedule(OP_INTERVAL, millis, false, callback) {
+            Ok(id) => Inner::Worker(id),
+            Err(callback) => Inner::Fallback(callback::Interval::new(millis, callback)),
+        };
+        Interval { inner: Some(inner) }
+    }
+
+    /// Whether this interval is run by the worker, rather than falling back to `setInterval` on
+    /// the current thread.
+    pub fn is_in_worker(&self) -> bool {
+        matches!(self.inner, Some(Inner::Worker(_)))
+    }
+
+    /// Forget this resource without clearing the interval.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::worker::Interval;
+    ///
+    /// Interval::new(1_000, || {
+    ///     // Do stuff...
+    /// }).forget();
+    /// ```
+    pub fn forget(mut self) {
+        if let Some(Inner::Fallback(interval)) = self.inner.take() {
+            interval.forget();
+        }
+    }
+
+    /// Cancel this interval so that the callback is no longer periodically invoked.
+    pub fn cancel(self) {
+        drop(self);
+    }
+}
+
+#[cfg(feature = "futures")]
+pub use self::future::{IntervalStream, TimeoutFuture};
+
+#[cfg(feature = "futures")]
+mod future {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use futures_channel::{mpsc, oneshot};
+    use futures_core::stream::Stream;
+    use wasm_bindgen::UnwrapThrowExt;
+
+    use super::{Interval, Timeout};
+
+    /// A scheduled timeout as a `Future`, run by the worker.
+    ///
+    /// Once scheduled, if you change your mind and don't want the timeout to fire, you can
+    /// `drop` the future.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gloo_timers::worker::TimeoutFuture;
+    /// use wasm_bindgen_futures::spawn_local;
+    ///
+    /// spawn_local(async {
+    ///     TimeoutFuture::new(1_000).await;
+    ///     // Do stuff after one second, even in a background tab...
+    /// });
+    /// ```
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless polled or spawned"]
+    pub struct TimeoutFuture {
+        _inner: Timeout,
+        rx: oneshot::Receiver<()>,
+    }
+
+    impl TimeoutFuture {
+        /// Create a new timeout future.
+        pub fn new(millis: u32) -> TimeoutFuture {
+            let (tx, rx) = oneshot::channel();
+            let inner = Timeout::new(millis, move || {
+                // if the receiver was dropped we do nothing.
+                let _ = tx.send(());
+            });
+            TimeoutFuture { _inner: inner, rx }
+        }
+    }
+
+    impl Future for TimeoutFuture {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            Future::poll(Pin::new(&mut self.rx), cx).map(|t| t.unwrap_throw())
+        }
+    }
+
+    /// A scheduled interval as a `Stream`, run by the worker.
+    ///
+    /// Once scheduled, if you want to stop the interval from continuing to fire, you can `drop`
+    /// the stream.
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or spawned"]
+    pub struct IntervalStream {
+        receiver: mpsc::UnboundedReceiver<()>,
+        _inner: Interval,
+    }
+
+    impl IntervalStream {
+        /// Create a new interval stream.
+        pub fn new(millis: u32) -> IntervalStream {
+            let (sender, receiver) = mpsc::unbounded();
+            let inner = Interval::new(millis, move || {
+                // if the receiver was dropped we do nothing.
+                let _ = sender.unbounded_send(());
+            });
+
+            IntervalStream {
+                receiver,
+                _inner: inner,
+            }
+        }
+    }
+
+    impl Stream for IntervalStream {
+        type Item = ();
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            Stream::poll_next(Pin::new(&mut self.receiver), cx)
+        }
+    }
+}
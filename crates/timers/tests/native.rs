@@ -0,0 +1,91 @@
+#![cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+
+use futures_util::stream::StreamExt;
+use gloo_timers::future::{
+    sleep, sleep_until, Elapsed, IntervalStream, MissedTicks, TimeoutExt, TimeoutFuture,
+};
+use gloo_timers::instant::Instant;
+use gloo_timers::retry::{retry_with_backoff, retry_with_backoff_if, Backoff};
+use std::cell::Cell;
+use std::time::Duration;
+
+#[tokio::test(start_paused = true)]
+async fn timeout() {
+    let start = Instant::now();
+    TimeoutFuture::new(1_000).await;
+    assert!(start.elapsed() >= Duration::from_secs(1));
+
+    sleep(Duration::from_secs(60)).await;
+    assert!(start.elapsed() >= Duration::from_secs(61));
+}
+
+#[tokio::test(start_paused = true)]
+async fn deadlines() {
+    let start = Instant::now();
+    sleep_until(start + Duration::from_millis(500)).await;
+    assert!(start.elapsed() >= Duration::from_millis(500));
+
+    let result = sleep(Duration::from_secs(2))
+        .timeout(Duration::from_secs(1))
+        .await;
+    assert_eq!(result, Err(Elapsed));
+    assert_eq!(
+        TimeoutFuture::new(10).timeout(Duration::from_secs(1)).await,
+        Ok(())
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn interval_stream() {
+    let start = Instant::now();
+    let ticks: Vec<_> = IntervalStream::new(100).take(5).collect().await;
+    assert_eq!(ticks.len(), 5);
+    assert!(start.elapsed() >= Duration::from_millis(500));
+
+    let mut coalesced = IntervalStream::with_missed_ticks(100, MissedTicks::Coalesce);
+    sleep(Duration::from_millis(450)).await;
+    coalesced.next().await.unwrap();
+    assert_eq!(coalesced.missed(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn retry_backoff() {
+    let backoff = Backoff::new()
+        .initial_delay(Duration::from_millis(5))
+        .max_attempts(3);
+    let attempts = Cell::new(0);
+    let result: Result<(), u32> = retry_with_backoff(&backoff, || {
+        attempts.set(attempts.get() + 1);
+        async { Err(attempts.get()) }
+    })
+    .await;
+    assert_eq!(result, Err(3));
+
+    attempts.set(0);
+    let result = retry_with_backoff(&backoff, || {
+        attempts.set(attempts.get() + 1);
+        let attempt = attempts.get();
+        async move {
+            if attempt < 2 {
+                Err(())
+            } else {
+                Ok(attempt)
+            }
+        }
+    })
+    .await;
+    assert_eq!(result, Ok(2));
+
+    attempts.set(0);
+    let result: Result<(), &str> = retry_with_backoff_if(
+        &backoff,
+        || {
+            attempts.set(attempts.get() + 1);
+            async { Err("invalid") }
+        },
+        |error| *error != "invalid",
+    )
+    .await;
+    assert_eq!(result, Err("invalid"));
+    assert_eq!(attempts.get(), 1);
+}
@@ -7,7 +7,17 @@ use futures_util::{
 };
 use gloo_timers::{
     callback::{Interval, Timeout},
-    future::{sleep, IntervalStream, TimeoutFuture},
+    frame::{FrameInterval, FrameStream},
+    future::{
+        sleep, sleep_until, sleep_with_handle, Cancelled, Deadline, Elapsed, IntervalStream,
+        MissedTicks, TimeoutExt, TimeoutFuture,
+    },
+    idle::{IdleCallback, IdleFuture, IdleStream},
+    instant::{Instant, Stopwatch},
+    retry::{retry_with_backoff, retry_with_backoff_if, Backoff},
+    schedule::{Schedule, ScheduleStream, Scheduler},
+    wheel::TimerWheel,
+    worker,
 };
 use std::cell::Cell;
 use std::rc::Rc;
@@ -82,6 +92,43 @@ async fn timeout_future_cancel() {
     assert_eq!(cell.get(), false);
 }
 
+#[wasm_bindgen_test]
+async fn sleep_handle_cancel() {
+    let (future, handle) = sleep_with_handle(Duration::from_secs(60));
+    assert!(handle.is_pending());
+    handle.cancel();
+    assert_eq!(future.await, Err(Cancelled));
+    assert!(!handle.is_pending());
+}
+
+#[wasm_bindgen_test]
+async fn sleep_handle_reset() {
+    let (future, handle) = sleep_with_handle(Duration::from_millis(10));
+    let resetter = async {
+        sleep(Duration::from_millis(5)).await;
+        handle.reset(Duration::from_millis(50));
+    };
+    let start = js_sys::Date::now();
+    let (result, ()) = futures_util::future::join(future, resetter).await;
+    assert_eq!(result, Ok(()));
+    assert!(js_sys::Date::now() - start >= 50.0);
+}
+
+#[wasm_bindgen_test]
+async fn timeout_combinator() {
+    let fast = async {
+        TimeoutFuture::new(1).await;
+        1
+    };
+    assert_eq!(
+        gloo_timers::future::timeout(Duration::from_millis(50), fast).await,
+        Ok(1)
+    );
+
+    let slow = TimeoutFuture::new(50).timeout(Duration::from_millis(1));
+    assert_eq!(slow.await, Err(Elapsed));
+}
+
 #[wasm_bindgen_test]
 async fn interval() {
     let (mut sender, receiver) = mpsc::channel(1);
@@ -112,3 +159,265 @@ async fn interval_stream() {
     let results: Vec<_> = IntervalStream::new(1).take(5).collect().await;
     assert_eq!(results.len(), 5);
 }
+
+#[wasm_bindgen_test]
+async fn idle_callback() {
+    let (sender, receiver) = oneshot::channel();
+    IdleCallback::new(|deadline| sender.send(deadline.time_remaining()).unwrap()).forget();
+    assert!(receiver.await.unwrap() <= Duration::from_millis(50));
+}
+
+#[wasm_bindgen_test]
+async fn idle_callback_cancel() {
+    let idle = IdleCallback::new(|_| panic!("should have been cancelled"));
+    idle.cancel();
+    IdleFuture::new().await;
+}
+
+#[wasm_bindgen_test]
+async fn idle_stream() {
+    let results: Vec<_> = IdleStream::with_timeout(100).take(3).collect().await;
+    assert_eq!(results.len(), 3);
+}
+
+#[wasm_bindgen_test]
+fn schedule_next_after() {
+    let schedule = Schedule::daily(9, 0);
+    let now = js_sys::Date::now();
+    let next = schedule.next_after(now).unwrap();
+    assert!(next > now && next - now <= 25.0 * 60.0 * 60.0 * 1_000.0);
+    let date = js_sys::Date::new(&next.into());
+    assert_eq!((date.get_hours(), date.get_minutes()), (9, 0));
+    assert_eq!(schedule.next_after(next - 1.0), Some(next));
+
+    let scheduler = Scheduler::new(schedule, || panic!("should not run yet"));
+    assert_eq!(scheduler.next(), Some(next));
+    drop(scheduler);
+
+    let never: Schedule = "0 0 30 2 *".parse().unwrap();
+    assert_eq!(never.next_after(now), None);
+    let _stream = ScheduleStream::new(never);
+}
+
+#[wasm_bindgen_test]
+async fn instant() {
+    let start = Instant::now();
+    sleep(Duration::from_millis(10)).await;
+    let end = Instant::now();
+    assert!(end > start);
+    assert!(end - start >= Duration::from_millis(9));
+    assert_eq!(start - end, Duration::ZERO);
+    assert_eq!(start.checked_duration_since(end), None);
+    assert_eq!(start + (end - start), end);
+
+    let mut stopwatch = Stopwatch::start();
+    sleep(Duration::from_millis(5)).await;
+    let lap = stopwatch.lap();
+    assert!(lap >= Duration::from_millis(4));
+    stopwatch.lap();
+    assert_eq!(stopwatch.laps().len(), 2);
+    assert!(stopwatch.restart() >= lap);
+    assert!(stopwatch.laps().is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn interval_stream_missed_ticks() {
+    let mut queued = IntervalStream::with_missed_ticks(1, MissedTicks::Queue);
+    let mut skipped = IntervalStream::with_missed_ticks(1, MissedTicks::Skip);
+    let mut coalesced = IntervalStream::with_missed_ticks(1, MissedTicks::Coalesce);
+    sleep(Duration::from_millis(50)).await;
+
+    // The queued ticks are all yielded right away.
+    queued.next().await.unwrap();
+    assert_eq!(queued.next().now_or_never(), Some(Some(())));
+
+    skipped.next().await.unwrap();
+    assert_eq!(skipped.next().now_or_never(), None);
+    assert_eq!(skipped.missed(), 0);
+
+    coalesced.next().await.unwrap();
+    assert_eq!(coalesced.next().now_or_never(), None);
+    assert!(coalesced.missed() > 0);
+}
+
+#[wasm_bindgen_test]
+async fn worker_timeout() {
+    let (sender, receiver) = oneshot::channel();
+    worker::Timeout::new(1, move || sender.send(()).unwrap()).forget();
+    receiver.await.unwrap();
+
+    let cancelled = Rc::new(Cell::new(false));
+    let timeout = worker::Timeout::new(1, {
+        let cancelled = cancelled.clone();
+        move || cancelled.set(true)
+    });
+    timeout.cancel();
+    worker::TimeoutFuture::new(10).await;
+    assert!(!cancelled.get());
+}
+
+#[wasm_bindgen_test]
+async fn worker_interval() {
+    let counter = Rc::new(Cell::new(0));
+    let interval = worker::Interval::new(1, {
+        let counter = counter.clone();
+        move || counter.set(counter.get() + 1)
+    });
+    let ticks = worker::IntervalStream::new(1)
+        .take(5)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(ticks.len(), 5);
+    drop(interval);
+    let count = counter.get();
+    assert!(count > 0);
+    worker::TimeoutFuture::new(10).await;
+    assert_eq!(counter.get(), count);
+}
+
+#[wasm_bindgen_test]
+async fn frame_interval() {
+    let frames = Rc::new(Cell::new(0));
+    let interval = FrameInterval::new({
+        let frames = frames.clone();
+        move |frame| {
+            assert!(frame.frames() >= 1);
+            frames.set(frames.get() + 1);
+        }
+    });
+    let spaced: Vec<_> = FrameStream::with_min_spacing(40).take(3).collect().await;
+    assert!(spaced[0].elapsed().is_zero());
+    assert!(spaced[1].elapsed() >= Duration::from_millis(39));
+    assert!(spaced[2].timestamp() > spaced[1].timestamp());
+    assert!(spaced[2].frames() > 1);
+    drop(interval);
+    let count = frames.get();
+    assert!(count >= 4);
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(frames.get(), count);
+}
+
+#[wasm_bindgen_test]
+async fn timeout_pause_resume() {
+    let (sender, receiver) = oneshot::channel();
+    let mut timeout = Timeout::new(20, move || sender.send(()).unwrap());
+    timeout.pause();
+    assert!(timeout.is_paused());
+    sleep(Duration::from_millis(40)).await;
+
+    let resumed = Instant::now();
+    timeout.resume();
+    assert!(!timeout.is_paused());
+    receiver.await.unwrap();
+    assert!(resumed.elapsed() >= Duration::from_millis(10));
+    timeout.forget();
+}
+
+#[wasm_bindgen_test]
+async fn interval_pause_resume() {
+    let counter = Rc::new(Cell::new(0));
+    let mut interval = Interval::new(10, {
+        let counter = counter.clone();
+        move || counter.set(counter.get() + 1)
+    });
+    sleep(Duration::from_millis(35)).await;
+    interval.pause();
+    let count = counter.get();
+    assert!(count > 0);
+    sleep(Duration::from_millis(30)).await;
+    assert_eq!(counter.get(), count);
+
+    interval.resume();
+    interval.pause();
+    interval.resume();
+    sleep(Duration::from_millis(35)).await;
+    assert!(counter.get() > count);
+}
+
+#[wasm_bindgen_test]
+async fn sleep_until_deadline() {
+    let start = Instant::now();
+    sleep_until(start + Duration::from_millis(20)).await;
+    assert!(start.elapsed() >= Duration::from_millis(19));
+
+    let date = js_sys::Date::new(&(js_sys::Date::now() + 20.0).into());
+    let deadline = Deadline::from(&date);
+    assert!(deadline.remaining() > Duration::ZERO);
+    sleep_until(deadline).await;
+    assert_eq!(deadline.remaining(), Duration::ZERO);
+
+    // Deadlines in the past resolve right away.
+    assert_eq!(sleep_until(start).now_or_never(), Some(()));
+
+    let result = TimeoutFuture::new(100)
+        .timeout_at(Instant::now() + Duration::from_millis(10))
+        .await;
+    assert_eq!(result, Err(Elapsed));
+}
+
+#[wasm_bindgen_test]
+async fn timer_wheel() {
+    let wheel = TimerWheel::new();
+    let fired = Rc::new(std::cell::RefCell::new(Vec::new()));
+    for millis in [30, 10, 20, 70] {
+        let fired = fired.clone();
+        wheel
+            .timeout(millis, move || fired.borrow_mut().push(millis))
+            .forget();
+    }
+    let cancelled = wheel.timeout(15, {
+        let fired = fired.clone();
+        move || fired.borrow_mut().push(15)
+    });
+    assert!(cancelled.is_pending());
+    assert_eq!(wheel.len(), 5);
+    drop(cancelled);
+
+    wheel.sleep(50).await;
+    assert_eq!(*fired.borrow(), [10, 20, 30]);
+    wheel.sleep(30).await;
+    assert_eq!(*fired.borrow(), [10, 20, 30, 70]);
+    assert!(wheel.is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn retry_backoff() {
+    let backoff = Backoff::new()
+        .initial_delay(Duration::from_millis(5))
+        .max_attempts(3);
+    let attempts = Cell::new(0);
+    let result: Result<(), u32> = retry_with_backoff(&backoff, || {
+        attempts.set(attempts.get() + 1);
+        async { Err(attempts.get()) }
+    })
+    .await;
+    assert_eq!(result, Err(3));
+
+    attempts.set(0);
+    let result = retry_with_backoff(&backoff, || {
+        attempts.set(attempts.get() + 1);
+        let attempt = attempts.get();
+        async move {
+            if attempt < 2 {
+                Err(())
+            } else {
+                Ok(attempt)
+            }
+        }
+    })
+    .await;
+    assert_eq!(result, Ok(2));
+
+    attempts.set(0);
+    let result: Result<(), &str> = retry_with_backoff_if(
+        &backoff,
+        || {
+            attempts.set(attempts.get() + 1);
+            async { Err("invalid") }
+        },
+        |error| *error != "invalid",
+    )
+    .await;
+    assert_eq!(result, Err("invalid"));
+    assert_eq!(attempts.get(), 1);
+}
@@ -0,0 +1,285 @@
+//! Typed DOM queries, and building DOM trees.
+
+use crate::document;
+use crate::errors::QueryError;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use web_sys::{Document, DocumentFragment, Element, Node, NodeList};
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for web_sys::Document {}
+    impl Sealed for web_sys::DocumentFragment {}
+    impl Sealed for web_sys::Element {}
+}
+
+/// Extension trait to query the descendants of a [`Document`], [`DocumentFragment`] or
+/// [`Element`] as a specific type of element.
+///
+/// # Example
+///
+/// ```rust
+/// use gloo_utils::document;
+/// use gloo_utils::dom::QueryExt;
+/// use web_sys::HtmlElement;
+///
+/// # fn no_run() {
+/// let body = document().query::<HtmlElement>("body").unwrap();
+/// let paragraphs = body.query_all::<HtmlElement>("p").unwrap();
+/// # }
+/// ```
+pub trait QueryExt: private::Sealed {
+    /// Returns the first descendant matching `selector`, with `querySelector`, cast to `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `selector` is not valid, if no descendant matches it, or if the
+    /// first one is not a `T`.
+    fn query<T>(&self, selector: &str) -> Result<T, QueryError>
+    where
+        T: JsCast;
+
+    /// Returns all the descendants matching `selector`, with `querySelectorAll`, cast to `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `selector` is not valid, or if one of the descendants matching it
+    /// is not a `T`.
+    fn query_all<T>(&self, selector: &str) -> Result<Vec<T>, QueryError>
+    where
+        T: JsCast;
+}
+
+macro_rules! impl_query_ext {
+    ($($ty:ty),*) => {
+        $(
+            impl QueryExt for $ty {
+                fn query<T>(&self, selector: &str) -> Result<T, QueryError>
+                where
+                    T: JsCast,
+                {
+                    let element = self
+                        .query_selector(selector)
+                        .map_err(|error| QueryError::invalid_selector(selector, error))?
+                        .ok_or_else(|| QueryError::NotFound {
+                            selector: selector.to_owned(),
+                        })?;
+                    cast(selector, element.into())
+                }
+
+                fn query_all<T>(&self, selector: &str) -> Result<Vec<T>, QueryError>
+                where
+                    T: JsCast,
+                {
+                    let nodes = self
+                        .query_selector_all(selector)
+                        .map_err(|error| QueryError::invalid_selector(selector, error))?;
+                    cast_all(selector, &nodes)
+                }
+            }
+        )*
+    };
+}
+
+impl_query_ext!(Document, DocumentFragment, Element);
+
+fn cast<T>(selector: &str, value: JsValue) -> Result<T, QueryError>
+where
+    T: JsCast,
+{
+    value.dyn_into().map_err(|_| QueryError::WrongType {
+        selector: selector.to_owned(),
+        expected: type_name::<T>(),
+    })
+}
+
+fn cast_all<T>(selector: &str, nodes: &NodeList) -> Result<Vec<T>, QueryError>
+where
+    T: JsCast,
+{
+    (0..nodes.length())
+        .filter_map(|index| nodes.get(index))
+        .map(|node| cast(selector, node.into()))
+        .collect()
+}
+
+/// Returns the name of `T` without its path, e.g. `HtmlInputElement`.
+pub(crate) fn type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(feature = "events")]
+type Listeners = Vec<gloo_events::EventListener>;
+#[cfg(not(feature = "events"))]
+type Listeners = Vec<std::convert::Infallible>;
+
+/// Starts building an element with the given tag name, e.g. `div`.
+///
+/// # Panics
+///
+/// Panics if `tag` is not a valid tag name.
+///
+/// # Example
+///
+/// ```rust
+/// use gloo_utils::dom::el;
+///
+/// # fn no_run() {
+/// let card = el("div")
+///     .class("card")
+///     .attr("data-id", "42")
+///     .child(el("h2").text("Title"))
+///     .child(el("p").text("Some text"))
+///     .into_element();
+/// gloo_utils::body().append_child(&card).unwrap();
+/// # }
+/// ```
+pub fn el(tag: &str) -> ElementBuilder {
+    ElementBuilder {
+        element: document().create_element(tag).unwrap_throw(),
+        listeners: Vec::new(),
+    }
+}
+
+/// A builder of DOM elements, returned by [`el`].
+#[derive(Debug)]
+#[must_use = "the element is only attached to the DOM once it is built and appended"]
+pub struct ElementBuilder {
+    element: Element,
+    listeners: Listeners,
+}
+
+impl ElementBuilder {
+    /// Adds a class.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `class` is empty or contains whitespace.
+    pub fn class(self, class: &str) -> Self {
+        self.element.class_list().add_1(class).unwrap_throw();
+        self
+    }
+
+    /// Sets the `id` attribute.
+    pub fn id(self, id: &str) -> Self {
+        self.element.set_id(id);
+        self
+    }
+
+    /// Sets an attribute.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a valid attribute name.
+    pub fn attr(self, name: &str, value: &str) -> Self {
+        self.element.set_attribute(name, value).unwrap_throw();
+        self
+    }
+
+    /// Appends a text node.
+    pub fn text(self, text: &str) -> Self {
+        let node = document().create_text_node(text);
+        self.element.append_child(&node).unwrap_throw();
+        self
+    }
+
+    /// Appends a child element. Its event listeners are moved to this builder.
+    pub fn child(mut self, child: ElementBuilder) -> Self {
+        self.element.append_child(&child.element).unwrap_throw();
+        self.listeners.extend(child.listeners);
+        self
+    }
+
+    /// Appends child elements, like [`child`](Self::child).
+    pub fn children<I>(self, children: I) -> Self
+    where
+        I: IntoIterator<Item = ElementBuilder>,
+    {
+        children
+            .into_iter()
+            .fold(self, |builder, child| builder.child(child))
+    }
+
+    /// Appends an existing node, e.g. an element created by other means.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node` can't be a child of an element, e.g. if it is a document, or contains
+    /// the element being built.
+    pub fn node(self, node: &Node) -> Self {
+        self.element.append_child(node).unwrap_throw();
+        self
+    }
+
+    /// Adds an event listener of type `E`, e.g. [`Click`](gloo_events::events::Click), with
+    /// [`gloo_events::on`].
+    ///
+    /// Usage of this API requires activating the `events` feature of the `gloo-utils` crate.
+    #[cfg(feature = "events")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "events")))]
+    pub fn on<E>(mut self, callback: impl FnMut(E::Event) + 'static) -> Self
+    where
+        E: gloo_events::events::StaticEvent,
+    {
+        let listener = gloo_events::on::<E>(&self.element, callback);
+        self.listeners.push(listener);
+        self
+    }
+
+    /// Adds an event listener for `event_type`, with
+    /// [`EventListener::new`](gloo_events::EventListener::new).
+    ///
+    /// Usage of this API requires activating the `events` feature of the `gloo-utils` crate.
+    #[cfg(feature = "events")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "events")))]
+    pub fn listener<F>(mut self, event_type: &'static str, callback: F) -> Self
+    where
+        F: FnMut(&web_sys::Event) + 'static,
+    {
+        let listener = gloo_events::EventListener::new(&self.element, event_type, callback);
+        self.listeners.push(listener);
+        self
+    }
+
+    /// Returns the built element, keeping its event listeners, and those of its children,
+    /// forever.
+    ///
+    /// Use [`build`](Self::build) to remove them when they're not needed anymore.
+    pub fn into_element(self) -> Element {
+        self.build().forget()
+    }
+
+    /// Returns the built element, along with its event listeners and those of its children.
+    pub fn build(self) -> BuiltElement {
+        BuiltElement {
+            element: self.element,
+            listeners: self.listeners,
+        }
+    }
+}
+
+/// An element built with [`ElementBuilder::build`], whose event listeners are removed when
+/// dropped.
+#[derive(Debug)]
+#[must_use = "the event listeners are removed when dropped"]
+pub struct BuiltElement {
+    element: Element,
+    #[cfg_attr(not(feature = "events"), allow(dead_code))]
+    listeners: Listeners,
+}
+
+impl BuiltElement {
+    /// Returns the element.
+    pub fn element(&self) -> &Element {
+        &self.element
+    }
+
+    /// Returns the element, keeping its event listeners forever.
+    pub fn forget(self) -> Element {
+        #[cfg(feature = "events")]
+        for listener in self.listeners {
+            listener.forget();
+        }
+        self.element
+    }
+}
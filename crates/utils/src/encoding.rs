@@ -0,0 +1,245 @@
+//! Base64 and hex encoding of bytes, e.g. for data URLs, tokens, or storing binary values as
+//! strings.
+//!
+//! The encoding is implemented in Rust, which is faster than `btoa` and `atob` as it doesn't
+//! need to go through "binary strings" to cross the JavaScript boundary.
+//!
+//! ```rust
+//! use gloo_utils::encoding;
+//!
+//! let encoded = encoding::encode_base64(b"gloo");
+//! assert_eq!(encoded, "Z2xvbw==");
+//! assert_eq!(encoding::decode_base64(&encoded).unwrap(), b"gloo");
+//!
+//! assert_eq!(encoding::encode_hex(&[0xca, 0xfe]), "cafe");
+//! ```
+
+use crate::errors::DecodeError;
+
+const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+const STANDARD_DECODE: &[u8; 256] = &reverse(STANDARD);
+const URL_SAFE_DECODE: &[u8; 256] = &reverse(URL_SAFE);
+/// Marks the bytes which are not part of an alphabet in its reverse table.
+const INVALID: u8 = 0xff;
+
+/// Maps each byte to its value in `alphabet`, or [`INVALID`].
+const fn reverse(alphabet: &[u8; 64]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < 64 {
+        table[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// Encodes `bytes` with the standard base64 alphabet, with padding, like `btoa`.
+pub fn encode_base64(bytes: &[u8]) -> String {
+    encode(bytes, STANDARD, true)
+}
+
+/// Decodes `input`, encoded with the standard base64 alphabet, like `atob`. Padding is optional.
+///
+/// # Errors
+///
+/// Returns an error if `input` is not valid base64.
+pub fn decode_base64(input: &str) -> Result<Vec<u8>, DecodeError> {
+    decode(input, STANDARD_DECODE)
+}
+
+/// Encodes `bytes` with the URL and filename safe base64 alphabet, without padding, e.g. for
+/// URLs or JSON Web Tokens.
+pub fn encode_base64url(bytes: &[u8]) -> String {
+    encode(bytes, URL_SAFE, false)
+}
+
+/// Decodes `input`, encoded with the URL and filename safe base64 alphabet. Padding is optional.
+///
+/// # Errors
+///
+/// Returns an error if `input` is not valid base64url.
+pub fn decode_base64url(input: &str) -> Result<Vec<u8>, DecodeError> {
+    decode(input, URL_SAFE_DECODE)
+}
+
+/// Encodes `bytes` as lowercase hexadecimal.
+pub fn encode_hex(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        output.push(HEX[(byte >> 4) as usize] as char);
+        output.push(HEX[(byte & 0x0f) as usize] as char);
+    }
+    output
+}
+
+/// Decodes `input`, encoded as hexadecimal, in either case.
+///
+/// # Errors
+///
+/// Returns an error if `input` is not valid hexadecimal.
+pub fn decode_hex(input: &str) -> Result<Vec<u8>, DecodeError> {
+    if input.len() % 2 != 0 {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let digit = |index: usize| {
+        let byte = input.as_bytes()[index];
+        match byte {
+            b'0'..=b'9' => Ok(byte - b'0'),
+            b'a'..=b'f' => Ok(byte - b'a' + 10),
+            b'A'..=b'F' => Ok(byte - b'A' + 10),
+            _ => Err(invalid_character(input, index)),
+        }
+    };
+    (0..input.len())
+        .step_by(2)
+        .map(|index| Ok(digit(index)? << 4 | digit(index + 1)?))
+        .collect()
+}
+
+fn encode(bytes: &[u8], alphabet: &[u8; 64], pad: bool) -> String {
+    let mut output = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let n = match *chunk {
+            [a, b, c] => u32::from(a) << 16 | u32::from(b) << 8 | u32::from(c),
+            [a, b] => u32::from(a) << 16 | u32::from(b) << 8,
+            [a] => u32::from(a) << 16,
+            _ => unreachable!(),
+        };
+        // Each byte adds 8 bits, i.e. one sextet plus a partial one.
+        for i in 0..=chunk.len() {
+            output.push(alphabet[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+        if pad {
+            for _ in chunk.len()..3 {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+fn decode(input: &str, table: &[u8; 256]) -> Result<Vec<u8>, DecodeError> {
+    let data = input.trim_end_matches('=');
+    let padding = input.len() - data.len();
+    if padding > 0 && (padding > 2 || input.len() % 4 != 0) {
+        return Err(DecodeError::InvalidPadding);
+    }
+    if data.len() % 4 == 1 {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let mut output = Vec::with_capacity(data.len() * 3 / 4);
+    for (chunk_index, chunk) in data.as_bytes().chunks(4).enumerate() {
+        let mut n = 0;
+        for (i, byte) in chunk.iter().enumerate() {
+            let value = table[*byte as usize];
+            if value == INVALID {
+                return Err(invalid_character(input, chunk_index * 4 + i));
+            }
+            n |= u32::from(value) << (18 - 6 * i);
+        }
+        // A chunk of `k` sextets holds `k - 1` bytes, and the bits left over must be zero, so
+        // that each byte sequence has a single encoding.
+        let bytes = chunk.len() - 1;
+        if n << (8 * bytes) & 0xff_ffff != 0 {
+            return Err(invalid_character(input, chunk_index * 4 + bytes));
+        }
+        for i in 0..bytes {
+            output.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(output)
+}
+
+fn invalid_character(input: &str, index: usize) -> DecodeError {
+    // `index` may be in the middle of a multi-byte character.
+    let start = (0..=index)
+        .rev()
+        .find(|m| input.is_char_boundary(*m))
+        .unwrap_or(0);
+    DecodeError::InvalidCharacter {
+        index: start,
+        character: input[start..].chars().next().unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64() {
+        let cases: &[(&[u8], &str)] = &[
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+        ];
+        for (bytes, encoded) in cases {
+            assert_eq!(encode_base64(bytes), *encoded);
+            assert_eq!(decode_base64(encoded).unwrap(), *bytes);
+            assert_eq!(
+                decode_base64(encoded.trim_end_matches('=')).unwrap(),
+                *bytes
+            );
+        }
+
+        assert_eq!(encode_base64(&[0xfb, 0xff]), "+/8=");
+        assert_eq!(encode_base64url(&[0xfb, 0xff]), "-_8");
+        assert_eq!(decode_base64url("-_8=").unwrap(), [0xfb, 0xff]);
+
+        assert_eq!(decode_base64("Zg="), Err(DecodeError::InvalidPadding));
+        assert_eq!(decode_base64("Zg==="), Err(DecodeError::InvalidPadding));
+        assert_eq!(decode_base64("Zm9vY"), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            decode_base64("Zh=="),
+            Err(DecodeError::InvalidCharacter {
+                index: 1,
+                character: 'h'
+            })
+        );
+        assert_eq!(
+            decode_base64("Zm9="),
+            Err(DecodeError::InvalidCharacter {
+                index: 2,
+                character: '9'
+            })
+        );
+        assert_eq!(
+            decode_base64("Zm-v"),
+            Err(DecodeError::InvalidCharacter {
+                index: 2,
+                character: '-'
+            })
+        );
+        assert_eq!(
+            decode_base64url("Zmé"),
+            Err(DecodeError::InvalidCharacter {
+                index: 2,
+                character: 'é'
+            })
+        );
+    }
+
+    #[test]
+    fn hex() {
+        assert_eq!(encode_hex(&[]), "");
+        assert_eq!(encode_hex(&[0x00, 0x7f, 0xca, 0xfe]), "007fcafe");
+        assert_eq!(decode_hex("007fCAFE").unwrap(), [0x00, 0x7f, 0xca, 0xfe]);
+        assert_eq!(decode_hex("abc"), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            decode_hex("0g"),
+            Err(DecodeError::InvalidCharacter {
+                index: 1,
+                character: 'g'
+            })
+        );
+    }
+}
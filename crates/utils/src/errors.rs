@@ -78,3 +78,165 @@ impl fmt::Debug for JsError {
 }
 
 impl std::error::Error for JsError {}
+
+/// An error returned by the typed queries of [`QueryExt`](crate::dom::QueryExt).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// The selector is not a valid CSS selector.
+    InvalidSelector {
+        /// The invalid selector.
+        selector: String,
+        /// The message of the error thrown by the browser.
+        message: String,
+    },
+    /// No element matches the selector.
+    NotFound {
+        /// The selector which matches no element.
+        selector: String,
+    },
+    /// An element matching the selector is not of the expected type.
+    WrongType {
+        /// The selector which matches the element.
+        selector: String,
+        /// The name of the expected type, e.g. `HtmlInputElement`.
+        expected: &'static str,
+    },
+}
+
+impl QueryError {
+    pub(crate) fn invalid_selector(selector: &str, error: JsValue) -> Self {
+        let message = match JsError::try_from(error) {
+            Ok(error) => error.to_string(),
+            Err(error) => error.to_string(),
+        };
+        QueryError::InvalidSelector {
+            selector: selector.to_owned(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::InvalidSelector { selector, message } => {
+                write!(f, "`{}` is not a valid selector: {}", selector, message)
+            }
+            QueryError::NotFound { selector } => write!(f, "no element matches `{}`", selector),
+            QueryError::WrongType { selector, expected } => write!(
+                f,
+                "an element matching `{}` is not a `{}`",
+                selector, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// An error returned when decoding invalid input with [`encoding`](crate::encoding).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input contains a character which is not part of the alphabet, or a last character
+    /// whose unused bits are not zero, e.g. `Zh==` instead of `Zg==`.
+    InvalidCharacter {
+        /// The byte offset of the character in the input.
+        index: usize,
+        /// The invalid character.
+        character: char,
+    },
+    /// The length of the input is not valid for the encoding.
+    InvalidLength,
+    /// The input is padded incorrectly.
+    InvalidPadding,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidCharacter { index, character } => {
+                write!(f, "invalid character {:?} at offset {}", character, index)
+            }
+            DecodeError::InvalidLength => f.write_str("invalid input length"),
+            DecodeError::InvalidPadding => f.write_str("invalid padding"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// An error returned by [`structured_clone`](crate::structured_clone).
+#[derive(Debug)]
+pub enum CloneError {
+    /// The value, or a value it contains, can't be cloned or transferred, i.e. `structuredClone()`
+    /// threw a `DataCloneError`.
+    NotCloneable(JsError),
+    /// `structuredClone()` threw another error, e.g. a `TypeError` for an invalid transfer list.
+    Other(JsError),
+    /// `structuredClone()` is not available in this environment.
+    Unsupported,
+}
+
+impl CloneError {
+    pub(crate) fn from_js(error: JsValue) -> Self {
+        // `DOMException`s are errors in all the browsers supporting `structuredClone()`.
+        let error = JsError::from(error.unchecked_into::<js_sys::Error>());
+        if error.name == "DataCloneError" {
+            CloneError::NotCloneable(error)
+        } else {
+            CloneError::Other(error)
+        }
+    }
+}
+
+impl fmt::Display for CloneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CloneError::NotCloneable(error) => write!(f, "value can't be cloned: {}", error),
+            CloneError::Other(error) => fmt::Display::fmt(error, f),
+            CloneError::Unsupported => f.write_str("`structuredClone()` is not supported"),
+        }
+    }
+}
+
+impl std::error::Error for CloneError {}
+
+/// An error returned by [`UrlBuilder`](crate::url::UrlBuilder).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    /// The URL is not valid, e.g. a relative URL without a base.
+    InvalidUrl {
+        /// The invalid URL.
+        url: String,
+        /// The message of the error thrown by the browser.
+        message: String,
+    },
+    /// The query parameters can't be serialized.
+    InvalidQuery(String),
+}
+
+impl UrlError {
+    pub(crate) fn invalid_url(url: &str, error: JsValue) -> Self {
+        let message = match JsError::try_from(error) {
+            Ok(error) => error.to_string(),
+            Err(error) => error.to_string(),
+        };
+        UrlError::InvalidUrl {
+            url: url.to_owned(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlError::InvalidUrl { url, message } => {
+                write!(f, "`{}` is not a valid URL: {}", url, message)
+            }
+            UrlError::InvalidQuery(message) => write!(f, "invalid query parameters: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for UrlError {}
@@ -0,0 +1,147 @@
+#![cfg(feature = "serde")]
+
+use std::fmt;
+use wasm_bindgen::JsValue;
+
+/// A policy for converting Rust values to and from [`JsValue`]s with [`serde`], without going
+/// through JSON.
+///
+/// Unlike [`JsValueSerdeExt`](crate::format::JsValueSerdeExt), values are converted directly to
+/// JavaScript values, so they can contain `Map`s, `BigInt`s and `Uint8Array`s. Crates which
+/// convert values on behalf of their users can take a `Conversion`, so the same policy is used
+/// everywhere.
+///
+/// The default policy converts:
+/// - maps to `Map`s,
+/// - 64-bit integers to numbers, failing if they can't be represented exactly,
+/// - bytes to `Uint8Array`s,
+/// - `()`, unit structs and `None` to `undefined`.
+///
+/// Usage of this API requires activating the `serde` feature of the `gloo-utils` crate.
+///
+/// # Example
+///
+/// ```rust
+/// use gloo_utils::format::Conversion;
+/// use std::collections::HashMap;
+///
+/// # fn no_run() {
+/// const CONVERSION: Conversion = Conversion::new().maps_as_objects(true);
+///
+/// let map = HashMap::from([("a", 1), ("b", 2)]);
+/// let object = CONVERSION.to_js(&map).unwrap();
+/// let map: HashMap<String, u32> = CONVERSION.from_js(object).unwrap();
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Conversion {
+    maps_as_objects: bool,
+    large_numbers_as_bigints: bool,
+    bytes_as_arrays: bool,
+    missing_as_null: bool,
+}
+
+impl Conversion {
+    /// Creates the default policy.
+    pub const fn new() -> Self {
+        Self {
+            maps_as_objects: false,
+            large_numbers_as_bigints: false,
+            bytes_as_arrays: false,
+            missing_as_null: false,
+        }
+    }
+
+    /// Creates a policy which only produces values which can be stringified to JSON, like
+    /// [`JsValueSerdeExt::from_serde`](crate::format::JsValueSerdeExt::from_serde), i.e. with
+    /// maps converted to plain objects, bytes to arrays, and `None` to `null`.
+    pub const fn json_compatible() -> Self {
+        Self {
+            maps_as_objects: true,
+            large_numbers_as_bigints: false,
+            bytes_as_arrays: true,
+            missing_as_null: true,
+        }
+    }
+
+    /// Sets whether maps are converted to plain objects instead of `Map`s.
+    ///
+    /// The keys of maps converted to objects must be strings or numbers.
+    pub const fn maps_as_objects(mut self, value: bool) -> Self {
+        self.maps_as_objects = value;
+        self
+    }
+
+    /// Sets whether `i64`, `u64`, `i128` and `u128` values are converted to `BigInt`s instead of
+    /// numbers.
+    pub const fn large_numbers_as_bigints(mut self, value: bool) -> Self {
+        self.large_numbers_as_bigints = value;
+        self
+    }
+
+    /// Sets whether bytes are converted to arrays of numbers instead of `Uint8Array`s.
+    pub const fn bytes_as_arrays(mut self, value: bool) -> Self {
+        self.bytes_as_arrays = value;
+        self
+    }
+
+    /// Sets whether `()`, unit structs and `None` are converted to `null` instead of `undefined`.
+    pub const fn missing_as_null(mut self, value: bool) -> Self {
+        self.missing_as_null = value;
+        self
+    }
+
+    /// Converts `value` to a `JsValue`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered when serializing `T`, e.g. if a 64-bit integer can't be
+    /// represented exactly by a number.
+    pub fn to_js<T>(&self, value: &T) -> Result<JsValue, ConversionError>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        let serializer = serde_wasm_bindgen::Serializer::new()
+            .serialize_maps_as_objects(self.maps_as_objects)
+            .serialize_large_number_types_as_bigints(self.large_numbers_as_bigints)
+            .serialize_bytes_as_arrays(self.bytes_as_arrays)
+            .serialize_missing_as_null(self.missing_as_null);
+        value.serialize(&serializer).map_err(ConversionError)
+    }
+
+    /// Converts `value` to a `T`.
+    ///
+    /// Both forms of each option are accepted, e.g. maps can be converted from either `Map`s or
+    /// plain objects.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered when deserializing `T`.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_js<T>(&self, value: JsValue) -> Result<T, ConversionError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        serde_wasm_bindgen::from_value(value).map_err(ConversionError)
+    }
+}
+
+/// An error encountered when converting a value with a [`Conversion`].
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Debug)]
+pub struct ConversionError(serde_wasm_bindgen::Error);
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+impl From<ConversionError> for JsValue {
+    fn from(error: ConversionError) -> Self {
+        error.0.into()
+    }
+}
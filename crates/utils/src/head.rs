@@ -0,0 +1,198 @@
+//! Management of the title and the `<meta>`, `<link>` and JSON-LD tags of the document's head,
+//! e.g. to update the SEO and social tags of a single-page application when navigating.
+//!
+//! Each change returns a [`HeadChange`], which restores the previous value when dropped, so the
+//! tags of a page can be tied to its lifetime. Call [`HeadChange::forget`] to keep a change.
+//!
+//! ```no_run
+//! use gloo_utils::head;
+//!
+//! let _changes = [
+//!     head::set_title("Gloo - Items"),
+//!     head::set_meta("description", "The list of items"),
+//!     head::set_meta_property("og:title", "Items"),
+//!     head::set_link("canonical", "https://example.com/items"),
+//!     head::set_json_ld("breadcrumbs", r#"{"@context": "https://schema.org"}"#),
+//! ];
+//! // The previous title and tags are restored when `_changes` is dropped.
+//! ```
+//!
+//! Changes are expected to be dropped in the reverse order they were made in, like nested scopes.
+
+use crate::dom::QueryExt;
+use crate::{document, head};
+use wasm_bindgen::UnwrapThrowExt;
+use web_sys::Element;
+
+const JSON_LD_TYPE: &str = "application/ld+json";
+
+/// A change to the document's head, which is reverted when dropped.
+#[must_use = "the change is reverted when dropped, call `forget` to keep it"]
+#[derive(Debug)]
+pub struct HeadChange {
+    revert: Option<Revert>,
+}
+
+#[derive(Debug)]
+enum Revert {
+    Title(String),
+    Element {
+        current: Option<Element>,
+        previous: Option<Element>,
+    },
+}
+
+impl HeadChange {
+    /// Keeps the change, i.e. drops this handle without reverting it.
+    pub fn forget(mut self) {
+        self.revert = None;
+    }
+}
+
+impl Drop for HeadChange {
+    fn drop(&mut self) {
+        match self.revert.take() {
+            Some(Revert::Title(title)) => document().set_title(&title),
+            Some(Revert::Element { current, previous }) => match (current, previous) {
+                (Some(current), Some(previous)) => {
+                    let _ = current.replace_with_with_node_1(&previous);
+                }
+                (Some(current), None) => current.remove(),
+                (None, Some(previous)) => {
+                    let _ = head().append_child(&previous);
+                }
+                (None, None) => {}
+            },
+            None => {}
+        }
+    }
+}
+
+/// Sets the title of the document.
+pub fn set_title(title: &str) -> HeadChange {
+    let document = document();
+    let previous = document.title();
+    document.set_title(title);
+    HeadChange {
+        revert: Some(Revert::Title(previous)),
+    }
+}
+
+/// Sets the `<meta>` tag with the given `name`, e.g. `description`, to `content`.
+pub fn set_meta(name: &str, content: &str) -> HeadChange {
+    upsert(
+        "meta",
+        |m| has_attribute(m, "name", name),
+        &[("name", name), ("content", content)],
+        None,
+    )
+}
+
+/// Removes the `<meta>` tag with the given `name`, if any.
+pub fn remove_meta(name: &str) -> HeadChange {
+    remove("meta", |m| has_attribute(m, "name", name))
+}
+
+/// Sets the `<meta>` tag with the given `property`, e.g. `og:title` for Open Graph, to `content`.
+pub fn set_meta_property(property: &str, content: &str) -> HeadChange {
+    upsert(
+        "meta",
+        |m| has_attribute(m, "property", property),
+        &[("property", property), ("content", content)],
+        None,
+    )
+}
+
+/// Removes the `<meta>` tag with the given `property`, if any.
+pub fn remove_meta_property(property: &str) -> HeadChange {
+    remove("meta", |m| has_attribute(m, "property", property))
+}
+
+/// Sets the `<link>` tag with the given `rel`, e.g. `canonical`, to `href`.
+pub fn set_link(rel: &str, href: &str) -> HeadChange {
+    upsert(
+        "link",
+        |m| has_attribute(m, "rel", rel),
+        &[("rel", rel), ("href", href)],
+        None,
+    )
+}
+
+/// Removes the `<link>` tag with the given `rel`, if any.
+pub fn remove_link(rel: &str) -> HeadChange {
+    remove("link", |m| has_attribute(m, "rel", rel))
+}
+
+/// Sets the JSON-LD script tag with the given `id` to `json`, i.e. a
+/// `<script type="application/ld+json">` containing structured data.
+///
+/// `json` is not validated.
+pub fn set_json_ld(id: &str, json: &str) -> HeadChange {
+    upsert(
+        "script",
+        |m| is_json_ld(m, id),
+        &[("type", JSON_LD_TYPE), ("id", id)],
+        Some(json),
+    )
+}
+
+/// Removes the JSON-LD script tag with the given `id`, if any.
+pub fn remove_json_ld(id: &str) -> HeadChange {
+    remove("script", |m| is_json_ld(m, id))
+}
+
+fn has_attribute(element: &Element, name: &str, value: &str) -> bool {
+    element.get_attribute(name).as_deref() == Some(value)
+}
+
+fn is_json_ld(element: &Element, id: &str) -> bool {
+    has_attribute(element, "type", JSON_LD_TYPE) && element.id() == id
+}
+
+fn find(tag: &str, matches: impl Fn(&Element) -> bool) -> Option<Element> {
+    head()
+        .query_all::<Element>(tag)
+        .unwrap_throw()
+        .into_iter()
+        .find(|m| matches(m))
+}
+
+fn upsert(
+    tag: &str,
+    matches: impl Fn(&Element) -> bool,
+    attributes: &[(&str, &str)],
+    text: Option<&str>,
+) -> HeadChange {
+    let element = document().create_element(tag).unwrap_throw();
+    for (name, value) in attributes {
+        element.set_attribute(name, value).unwrap_throw();
+    }
+    element.set_text_content(text);
+
+    let previous = find(tag, matches);
+    match &previous {
+        Some(previous) => previous.replace_with_with_node_1(&element).unwrap_throw(),
+        None => {
+            head().append_child(&element).unwrap_throw();
+        }
+    }
+    HeadChange {
+        revert: Some(Revert::Element {
+            current: Some(element),
+            previous,
+        }),
+    }
+}
+
+fn remove(tag: &str, matches: impl Fn(&Element) -> bool) -> HeadChange {
+    let previous = find(tag, matches);
+    if let Some(previous) = &previous {
+        previous.remove();
+    }
+    HeadChange {
+        revert: Some(Revert::Element {
+            current: None,
+            previous,
+        }),
+    }
+}
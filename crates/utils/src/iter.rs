@@ -1,4 +1,9 @@
-use wasm_bindgen::{JsValue, UnwrapThrowExt};
+use crate::dom::type_name;
+use js_sys::{Function, Reflect, Symbol};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use web_sys::{HtmlCollection, NodeList};
 
 /// A wrapper around JS Iterator so it can be consumed from Rust.
 ///
@@ -60,6 +65,318 @@ impl Iterator for UncheckedIter {
     }
 }
 
+fn cast<T>(value: JsValue) -> T
+where
+    T: JsCast,
+{
+    match value.dyn_into() {
+        Ok(value) => value,
+        Err(value) => panic!("expected a `{}`, got {:?}", type_name::<T>(), value),
+    }
+}
+
+/// A wrapper around JS Iterator which casts the values it yields to `T`.
+///
+/// # Panics
+///
+/// Iterating panics if a value is not a `T`. Use [`JsValue`] as `T` to get any value.
+///
+/// # Example
+///
+/// ```rust
+/// use gloo_utils::iter::set_values;
+/// use js_sys::JsString;
+///
+/// # fn no_run() {
+/// let set = js_sys::Set::new(&js_sys::Array::of2(&"a".into(), &"b".into()));
+/// let values: Vec<JsString> = set_values(&set).collect();
+/// assert_eq!(values, ["a", "b"]);
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Iter<T> {
+    inner: js_sys::Iterator,
+    done: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Iter<T> {
+    /// Obtain the raw [`js_sys::Iterator`]
+    pub fn into_raw(self) -> js_sys::Iterator {
+        self.inner
+    }
+}
+
+impl<T> From<js_sys::Iterator> for Iter<T> {
+    fn from(iter: js_sys::Iterator) -> Self {
+        Self {
+            inner: iter,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Iterator for Iter<T>
+where
+    T: JsCast,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = self.inner.next().unwrap_throw();
+        if next.done() {
+            self.done = true;
+            None
+        } else {
+            Some(cast(next.value()))
+        }
+    }
+}
+
+impl<T> FusedIterator for Iter<T> where T: JsCast {}
+
+/// Returns an iterator over the values of `iterable`, i.e. of the iterator returned by its
+/// `[Symbol.iterator]()` method, or `None` if `iterable` is not iterable.
+///
+/// This supports any object implementing the iteration protocol, e.g. arrays, typed arrays,
+/// generators, `FormData` or `Headers`.
+pub fn iterate<T>(iterable: &JsValue) -> Option<Iter<T>>
+where
+    T: JsCast,
+{
+    if iterable.is_undefined() || iterable.is_null() {
+        return None;
+    }
+    let method: Function = Reflect::get(iterable, &Symbol::iterator())
+        .ok()?
+        .dyn_into()
+        .ok()?;
+    let iterator = method.call0(iterable).ok()?;
+    let next = Reflect::get(&iterator, &JsValue::from_str("next")).ok()?;
+    if next.is_function() {
+        Some(Iter::from(iterator.unchecked_into::<js_sys::Iterator>()))
+    } else {
+        None
+    }
+}
+
+/// Returns an iterator over the values of `set`, in insertion order.
+///
+/// # Panics
+///
+/// Iterating panics if a value is not a `T`.
+pub fn set_values<T>(set: &js_sys::Set) -> Iter<T>
+where
+    T: JsCast,
+{
+    Iter::from(set.values())
+}
+
+/// An iterator over the entries of a [`js_sys::Map`], returned by [`map_entries`].
+#[derive(Debug)]
+pub struct MapEntries<K, V> {
+    inner: Iter<js_sys::Array>,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> Iterator for MapEntries<K, V>
+where
+    K: JsCast,
+    V: JsCast,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.inner.next()?;
+        Some((cast(entry.get(0)), cast(entry.get(1))))
+    }
+}
+
+impl<K, V> FusedIterator for MapEntries<K, V>
+where
+    K: JsCast,
+    V: JsCast,
+{
+}
+
+/// Returns an iterator over the entries of `map`, in insertion order.
+///
+/// # Panics
+///
+/// Iterating panics if a key is not a `K` or a value is not a `V`.
+///
+/// # Example
+///
+/// ```rust
+/// use gloo_utils::iter::map_entries;
+/// use js_sys::JsString;
+/// use wasm_bindgen::JsValue;
+///
+/// # fn no_run() {
+/// let map = js_sys::Map::new();
+/// map.set(&"one".into(), &1.into());
+///
+/// for (key, value) in map_entries::<JsString, JsValue>(&map) {
+///     assert_eq!(key, "one");
+///     assert_eq!(value.as_f64(), Some(1.0));
+/// }
+/// # }
+/// ```
+pub fn map_entries<K, V>(map: &js_sys::Map) -> MapEntries<K, V>
+where
+    K: JsCast,
+    V: JsCast,
+{
+    MapEntries {
+        inner: Iter::from(map.entries()),
+        _marker: PhantomData,
+    }
+}
+
+/// Returns an iterator over the keys of `map`, in insertion order.
+///
+/// # Panics
+///
+/// Iterating panics if a key is not a `K`.
+pub fn map_keys<K>(map: &js_sys::Map) -> Iter<K>
+where
+    K: JsCast,
+{
+    Iter::from(map.keys())
+}
+
+/// Returns an iterator over the values of `map`, in insertion order.
+///
+/// # Panics
+///
+/// Iterating panics if a value is not a `V`.
+pub fn map_values<V>(map: &js_sys::Map) -> Iter<V>
+where
+    V: JsCast,
+{
+    Iter::from(map.values())
+}
+
+#[derive(Debug)]
+enum List {
+    Nodes(NodeList),
+    Elements(HtmlCollection),
+}
+
+impl List {
+    fn get(&self, index: u32) -> Option<JsValue> {
+        match self {
+            List::Nodes(list) => list.get(index).map(Into::into),
+            List::Elements(list) => list.get_with_index(index).map(Into::into),
+        }
+    }
+}
+
+/// An iterator over a [`NodeList`] or an [`HtmlCollection`], returned by [`node_list`] and
+/// [`html_collection`].
+///
+/// The length of the list is read when the iterator is created. Items removed from a live list
+/// in the meantime are skipped.
+#[derive(Debug)]
+pub struct ListIter<T> {
+    list: List,
+    front: u32,
+    back: u32,
+    _marker: PhantomData<T>,
+}
+
+impl<T> ListIter<T> {
+    fn new(list: List, length: u32) -> Self {
+        Self {
+            list,
+            front: 0,
+            back: length,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Iterator for ListIter<T>
+where
+    T: JsCast,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            let item = self.list.get(self.front);
+            self.front += 1;
+            if let Some(item) = item {
+                return Some(cast(item));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some((self.back - self.front) as usize))
+    }
+}
+
+impl<T> DoubleEndedIterator for ListIter<T>
+where
+    T: JsCast,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            self.back -= 1;
+            if let Some(item) = self.list.get(self.back) {
+                return Some(cast(item));
+            }
+        }
+        None
+    }
+}
+
+impl<T> FusedIterator for ListIter<T> where T: JsCast {}
+
+/// Returns an iterator over the nodes of `list`.
+///
+/// # Panics
+///
+/// Iterating panics if a node is not a `T`.
+///
+/// # Example
+///
+/// ```rust
+/// use gloo_utils::iter::node_list;
+/// use web_sys::Element;
+///
+/// # fn no_run() {
+/// let list = gloo_utils::document().query_selector_all("p").unwrap();
+/// for paragraph in node_list::<Element>(&list) {
+///     paragraph.set_class_name("text");
+/// }
+/// # }
+/// ```
+pub fn node_list<T>(list: &NodeList) -> ListIter<T>
+where
+    T: JsCast,
+{
+    ListIter::new(List::Nodes(list.clone()), list.length())
+}
+
+/// Returns an iterator over the elements of `collection`, e.g. the children of an element.
+///
+/// # Panics
+///
+/// Iterating panics if an element is not a `T`.
+pub fn html_collection<T>(collection: &HtmlCollection) -> ListIter<T>
+where
+    T: JsCast,
+{
+    ListIter::new(List::Elements(collection.clone()), collection.length())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,4 +411,49 @@ mod tests {
         assert_eq!(iter.next(), Some((String::from("three"), 3_f64)));
         assert_eq!(iter.next(), None);
     }
+
+    #[wasm_bindgen_test]
+    fn typed_iterators() {
+        use js_sys::JsString;
+
+        let map = js_sys::Map::new();
+        map.set(&JsValue::from("one"), &JsValue::from(1_f64));
+        map.set(&JsValue::from("two"), &JsValue::from(2_f64));
+        let entries = map_entries::<JsString, JsValue>(&map)
+            .map(|(key, value)| (String::from(key), value.as_f64().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(entries, [("one".to_owned(), 1.0), ("two".to_owned(), 2.0)]);
+        assert_eq!(
+            map_keys::<JsString>(&map).collect::<Vec<_>>(),
+            ["one", "two"]
+        );
+        assert_eq!(map_values::<JsValue>(&map).count(), 2);
+
+        let array = js_sys::Array::of2(&"a".into(), &"b".into());
+        let set = js_sys::Set::new(&array);
+        assert_eq!(set_values::<JsString>(&set).collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(
+            iterate::<JsString>(&array).unwrap().collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+        assert!(iterate::<JsValue>(&JsValue::from(1)).is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn lists() {
+        let element = crate::document().create_element("div").unwrap();
+        element.set_inner_html("<p>1</p><p>2</p><p>3</p>");
+
+        let texts = html_collection::<web_sys::Element>(&element.children())
+            .map(|m| m.text_content().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, ["1", "2", "3"]);
+
+        let list = element.query_selector_all("p").unwrap();
+        let texts = node_list::<web_sys::Element>(&list)
+            .rev()
+            .map(|m| m.text_content().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, ["3", "2", "1"]);
+    }
 }
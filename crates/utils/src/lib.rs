@@ -1,19 +1,106 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+pub mod dom;
+pub mod encoding;
 pub mod errors;
+pub mod head;
 pub mod iter;
+pub mod text;
+pub mod url;
 pub mod format {
+    mod convert;
     mod json;
     #[cfg(feature = "serde")]
+    pub use convert::{Conversion, ConversionError};
+    #[cfg(feature = "serde")]
     pub use json::JsValueSerdeExt;
 }
-use wasm_bindgen::UnwrapThrowExt;
+use errors::CloneError;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+
+/// The global scope the code runs in, returned by [`global_scope`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GlobalScope {
+    /// The main thread of a page.
+    Window(web_sys::Window),
+    /// A dedicated worker, i.e. created with `new Worker()`.
+    DedicatedWorker(web_sys::DedicatedWorkerGlobalScope),
+    /// A shared worker, i.e. created with `new SharedWorker()`.
+    SharedWorker(web_sys::SharedWorkerGlobalScope),
+    /// A service worker.
+    ServiceWorker(web_sys::ServiceWorkerGlobalScope),
+    /// Another global scope, e.g. a worklet or a non-browser runtime.
+    Other(js_sys::Object),
+}
+
+impl GlobalScope {
+    /// Returns whether the global scope is a worker of any kind.
+    pub fn is_worker(&self) -> bool {
+        matches!(
+            self,
+            GlobalScope::DedicatedWorker(_)
+                | GlobalScope::SharedWorker(_)
+                | GlobalScope::ServiceWorker(_)
+        )
+    }
+}
+
+/// Returns the global scope the code runs in, so code shared between pages and workers can
+/// check what is available instead of panicking in [`window`].
+///
+/// # Example
+///
+/// ```rust
+/// use gloo_utils::{global_scope, GlobalScope};
+///
+/// # fn no_run() {
+/// match global_scope() {
+///     GlobalScope::Window(window) => { /* Use the DOM... */ }
+///     scope if scope.is_worker() => { /* Post a message to the page... */ }
+///     _ => {}
+/// }
+/// # }
+/// ```
+pub fn global_scope() -> GlobalScope {
+    let global = js_sys::global();
+    // `instanceof` checks are safe even if the class is not defined in this scope.
+    let global = match global.dyn_into() {
+        Ok(window) => return GlobalScope::Window(window),
+        Err(global) => global,
+    };
+    let global = match global.dyn_into() {
+        Ok(worker) => return GlobalScope::DedicatedWorker(worker),
+        Err(global) => global,
+    };
+    let global = match global.dyn_into() {
+        Ok(worker) => return GlobalScope::SharedWorker(worker),
+        Err(global) => global,
+    };
+    match global.dyn_into() {
+        Ok(worker) => GlobalScope::ServiceWorker(worker),
+        Err(global) => GlobalScope::Other(global),
+    }
+}
 
 /// Convenience function to avoid repeating expect logic.
+///
+/// Use [`try_window`] in code which may run in a worker.
 pub fn window() -> web_sys::Window {
     web_sys::window().expect_throw("Can't find the global Window")
 }
 
+/// Returns the global Window, or `None` if the code doesn't run in a window, e.g. in a worker.
+pub fn try_window() -> Option<web_sys::Window> {
+    web_sys::window()
+}
+
+/// Returns the web_sys DOM document, or `None` if the code doesn't run in a window, e.g. in a
+/// worker.
+pub fn try_document() -> Option<web_sys::Document> {
+    try_window()?.document()
+}
+
 /// Convenience function to access the head element.
 pub fn head() -> web_sys::HtmlHeadElement {
     document()
@@ -22,6 +109,8 @@ pub fn head() -> web_sys::HtmlHeadElement {
 }
 
 /// Convenience function to access the web_sys DOM document.
+///
+/// Use [`try_document`] in code which may run in a worker.
 pub fn document() -> web_sys::Document {
     window().document().expect_throw("Can't find document")
 }
@@ -42,3 +131,62 @@ pub fn document_element() -> web_sys::Element {
 pub fn history() -> web_sys::History {
     window().history().expect_throw("Can't find history")
 }
+
+/// Returns a deep copy of `value`, made with the global `structuredClone()`, e.g. to snapshot
+/// JavaScript state.
+///
+/// Objects, arrays, `Map`s, `Set`s, `Date`s, typed arrays, `Blob`s and other
+/// [serializable objects](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API/Structured_clone_algorithm)
+/// are cloned, including cycles. Class instances are cloned as plain objects.
+///
+/// # Errors
+///
+/// Returns [`CloneError::NotCloneable`] if `value` contains a value which can't be cloned,
+/// e.g. a function or a DOM node, or [`CloneError::Unsupported`] if `structuredClone()` is not
+/// available.
+pub fn structured_clone(value: &JsValue) -> Result<JsValue, CloneError> {
+    structured_clone_with_transfer(value, &js_sys::Array::new())
+}
+
+/// Returns a deep copy of `value`, made with the global `structuredClone()`, moving the objects
+/// of `transfer`, e.g. `ArrayBuffer`s or `MessagePort`s, to the copy instead of cloning them,
+/// like when posting a message to a worker.
+///
+/// The transferred objects are unusable afterwards, e.g. transferred `ArrayBuffer`s are
+/// detached.
+///
+/// # Errors
+///
+/// Returns [`CloneError::NotCloneable`] if `value` contains a value which can't be cloned, or
+/// if an object of `transfer` can't be transferred, e.g. because it was already transferred.
+/// Returns [`CloneError::Unsupported`] if `structuredClone()` is not available.
+///
+/// # Example
+///
+/// ```rust
+/// use gloo_utils::structured_clone_with_transfer;
+/// use js_sys::{Array, ArrayBuffer};
+///
+/// # fn no_run() {
+/// let buffer = ArrayBuffer::new(8);
+/// let copy = structured_clone_with_transfer(&buffer, &Array::of1(&buffer)).unwrap();
+/// assert_eq!(buffer.byte_length(), 0);
+/// # }
+/// ```
+pub fn structured_clone_with_transfer(
+    value: &JsValue,
+    transfer: &js_sys::Array,
+) -> Result<JsValue, CloneError> {
+    let function: js_sys::Function =
+        js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("structuredClone"))
+            .ok()
+            .and_then(|m| m.dyn_into().ok())
+            .ok_or(CloneError::Unsupported)?;
+
+    let options = js_sys::Object::new();
+    // Setting a property on a plain object can't fail.
+    let _ = js_sys::Reflect::set(&options, &JsValue::from_str("transfer"), transfer);
+    function
+        .call2(&JsValue::UNDEFINED, value, &options)
+        .map_err(CloneError::from_js)
+}
@@ -0,0 +1,153 @@
+//! Wrappers around the browser's `TextEncoder` and `TextDecoder`, which convert between bytes and
+//! text.
+//!
+//! [`TextDecoder`] supports every encoding of the
+//! [Encoding Standard](https://encoding.spec.whatwg.org/#names-and-labels), e.g. `windows-1252`
+//! or `shift_jis`, and can decode text arriving in chunks, e.g. from a network stream, where a
+//! character may be split between two chunks.
+//!
+//! ```rust
+//! use gloo_utils::text::TextDecoder;
+//!
+//! # fn no_run() {
+//! let decoder = TextDecoder::new();
+//! let mut text = String::new();
+//! // "é" is encoded as [0xc3, 0xa9].
+//! for chunk in [&b"caf\xc3"[..], &b"\xa9!"[..]] {
+//!     text.push_str(&decoder.decode_stream(chunk).unwrap());
+//! }
+//! text.push_str(&decoder.finish().unwrap());
+//! assert_eq!(text, "café!");
+//! # }
+//! ```
+
+use crate::errors::JsError;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use web_sys::{TextDecodeOptions, TextDecoderOptions};
+
+fn js_error(error: JsValue) -> JsError {
+    error.unchecked_into::<js_sys::Error>().into()
+}
+
+/// A wrapper around `TextEncoder`, which encodes text as UTF-8.
+#[derive(Debug, Clone)]
+pub struct TextEncoder(web_sys::TextEncoder);
+
+impl TextEncoder {
+    /// Creates an encoder.
+    pub fn new() -> Self {
+        Self(web_sys::TextEncoder::new().unwrap_throw())
+    }
+
+    /// Encodes `text` as UTF-8.
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        self.0.encode_with_input(text)
+    }
+
+    /// Obtain the raw [`web_sys::TextEncoder`]
+    pub fn into_raw(self) -> web_sys::TextEncoder {
+        self.0
+    }
+}
+
+impl Default for TextEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A wrapper around `TextDecoder`, which decodes bytes in a given encoding to text.
+///
+/// By default, invalid data is replaced with `U+FFFD REPLACEMENT CHARACTER`. Decoders created
+/// with `fatal` set return an error instead.
+#[derive(Debug, Clone)]
+pub struct TextDecoder(web_sys::TextDecoder);
+
+impl TextDecoder {
+    /// Creates a UTF-8 decoder.
+    pub fn new() -> Self {
+        Self(web_sys::TextDecoder::new().unwrap_throw())
+    }
+
+    /// Creates a decoder for the encoding with the given `label`, e.g. `latin1` or `utf-16le`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `RangeError` if the label is not supported.
+    pub fn with_label(label: &str) -> Result<Self, JsError> {
+        Self::with_options(label, false)
+    }
+
+    /// Creates a decoder for the encoding with the given `label`, which returns an error when
+    /// decoding invalid data if `fatal` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `RangeError` if the label is not supported.
+    pub fn with_options(label: &str, fatal: bool) -> Result<Self, JsError> {
+        let mut options = TextDecoderOptions::new();
+        options.fatal(fatal);
+        web_sys::TextDecoder::new_with_label_and_options(label, &options)
+            .map(Self)
+            .map_err(js_error)
+    }
+
+    /// Returns the name of the encoding, e.g. `windows-1252` for the `latin1` label.
+    pub fn encoding(&self) -> String {
+        self.0.encoding()
+    }
+
+    /// Decodes `bytes`, which must be a complete input.
+    ///
+    /// If a streaming decode was in progress, the bytes are decoded as its end.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TypeError` if the decoder is `fatal` and `bytes` are invalid.
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, JsError> {
+        self.decode_with(bytes, false)
+    }
+
+    /// Decodes a chunk of a streamed input. Bytes of a character split between two chunks are
+    /// kept until the next call, so the returned text may be shorter than expected.
+    ///
+    /// Call [`finish`](Self::finish) after the last chunk, so incomplete characters at the end
+    /// of the input are handled.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TypeError` if the decoder is `fatal` and `chunk` is invalid.
+    pub fn decode_stream(&self, chunk: &[u8]) -> Result<String, JsError> {
+        self.decode_with(chunk, true)
+    }
+
+    /// Ends a streamed input, returning the text of the bytes kept from the last chunk, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TypeError` if the decoder is `fatal` and the input ends with an incomplete
+    /// character.
+    pub fn finish(&self) -> Result<String, JsError> {
+        self.0.decode().map_err(js_error)
+    }
+
+    fn decode_with(&self, bytes: &[u8], stream: bool) -> Result<String, JsError> {
+        let mut options = TextDecodeOptions::new();
+        options.stream(stream);
+        let array = js_sys::Uint8Array::from(bytes);
+        self.0
+            .decode_with_buffer_source_and_options(&array, &options)
+            .map_err(js_error)
+    }
+
+    /// Obtain the raw [`web_sys::TextDecoder`]
+    pub fn into_raw(self) -> web_sys::TextDecoder {
+        self.0
+    }
+}
+
+impl Default for TextDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
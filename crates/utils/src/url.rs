@@ -0,0 +1,184 @@
+//! Building URLs without concatenating strings.
+
+use crate::errors::UrlError;
+use wasm_bindgen::UnwrapThrowExt;
+use web_sys::Url;
+
+/// A builder of URLs, wrapping [`web_sys::Url`], which encodes path segments and query
+/// parameters.
+///
+/// # Example
+///
+/// ```rust
+/// use gloo_utils::url::UrlBuilder;
+///
+/// # fn no_run() {
+/// let url = UrlBuilder::new("https://example.com/api/")
+///     .unwrap()
+///     .segment("items")
+///     .segment("a/b c")
+///     .query("page", "2")
+///     .fragment("top")
+///     .into_string();
+/// assert_eq!(url, "https://example.com/api/items/a%2Fb%20c?page=2#top");
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct UrlBuilder {
+    url: Url,
+}
+
+impl UrlBuilder {
+    /// Starts building from an absolute URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `url` is not a valid absolute URL.
+    pub fn new(url: &str) -> Result<Self, UrlError> {
+        Url::new(url)
+            .map(|url| Self { url })
+            .map_err(|error| UrlError::invalid_url(url, error))
+    }
+
+    /// Starts building from `url` resolved against `base`, e.g. `/items` against the location
+    /// of the page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `base` is not a valid absolute URL, or if `url` is not valid.
+    pub fn with_base(url: &str, base: &str) -> Result<Self, UrlError> {
+        Url::new_with_base(url, base)
+            .map(|url| Self { url })
+            .map_err(|error| UrlError::invalid_url(url, error))
+    }
+
+    /// Resolves `url` against the URL built so far, like a link on a page at this URL, e.g.
+    /// `../other` or `?page=2`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `url` is not valid.
+    pub fn join(self, url: &str) -> Result<Self, UrlError> {
+        Self::with_base(url, &self.url.href())
+    }
+
+    /// Appends a path segment, with its reserved characters percent-encoded, e.g. `/`, `?`, `#`
+    /// or spaces.
+    ///
+    /// `.` and `..` are handled by the URL parser, i.e. `..` removes the previous segment.
+    pub fn segment(self, segment: &str) -> Self {
+        let mut path = self.url.pathname();
+        if !path.ends_with('/') {
+            path.push('/');
+        }
+        path.push_str(&String::from(js_sys::encode_uri_component(segment)));
+        self.url.set_pathname(&path);
+        self
+    }
+
+    /// Appends the path segments of `segments`, like [`segment`](Self::segment).
+    pub fn segments<'a, I>(self, segments: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        segments
+            .into_iter()
+            .fold(self, |builder, segment| builder.segment(segment))
+    }
+
+    /// Appends a query parameter.
+    pub fn query(self, key: &str, value: &str) -> Self {
+        self.url.search_params().append(key, value);
+        self
+    }
+
+    /// Appends the query parameters of `params`, serialized with [`serde`], e.g. a struct or a
+    /// map.
+    ///
+    /// `None` values are skipped, and sequences are appended as repeated parameters.
+    ///
+    /// Usage of this API requires activating the `serde` feature of the `gloo-utils` crate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `params` doesn't serialize to a map of strings, numbers, booleans
+    /// or sequences of them.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn query_params<T>(self, params: &T) -> Result<Self, UrlError>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        use crate::format::Conversion;
+        use js_sys::{Array, Object};
+        use wasm_bindgen::{JsCast, JsValue};
+
+        fn to_string(key: &str, value: &JsValue) -> Result<String, UrlError> {
+            if let Some(value) = value.as_string() {
+                Ok(value)
+            } else if let Some(value) = value.as_f64() {
+                Ok(value.to_string())
+            } else if let Some(value) = value.as_bool() {
+                Ok(value.to_string())
+            } else {
+                Err(UrlError::InvalidQuery(format!(
+                    "the value of `{}` is not a string, a number or a boolean",
+                    key
+                )))
+            }
+        }
+
+        // Unlike `serde_json`'s maps, objects keep the order of the fields.
+        let params = Conversion::json_compatible()
+            .to_js(params)
+            .map_err(|error| UrlError::InvalidQuery(error.to_string()))?;
+        if !params.is_object() || Array::is_array(&params) {
+            return Err(UrlError::InvalidQuery(
+                "query parameters must be a map or a struct".to_owned(),
+            ));
+        }
+
+        let search_params = self.url.search_params();
+        for entry in Object::entries(params.unchecked_ref()) {
+            let entry: Array = entry.unchecked_into();
+            let key = entry.get(0).as_string().unwrap_throw();
+            let value = entry.get(1);
+            if value.is_null() {
+                continue;
+            }
+            if Array::is_array(&value) {
+                for value in Array::from(&value) {
+                    search_params.append(&key, &to_string(&key, &value)?);
+                }
+            } else {
+                search_params.append(&key, &to_string(&key, &value)?);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Sets the fragment, i.e. the part after `#`, percent-encoding it as needed.
+    pub fn fragment(self, fragment: &str) -> Self {
+        self.url.set_hash(fragment);
+        self
+    }
+
+    /// Returns the built URL.
+    pub fn build(self) -> Url {
+        self.url
+    }
+
+    /// Returns the built URL as a string.
+    pub fn into_string(self) -> String {
+        self.url.href()
+    }
+}
+
+impl From<Url> for UrlBuilder {
+    fn from(url: Url) -> Self {
+        // Build from a copy, so the original isn't modified.
+        Self {
+            url: Url::new(&url.href()).unwrap_throw(),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+#![cfg(target_arch = "wasm32")]
+
+use gloo_utils::dom::QueryExt;
+use gloo_utils::errors::QueryError;
+use gloo_utils::{body, document};
+use wasm_bindgen_test::*;
+use web_sys::{HtmlElement, HtmlHeadElement};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn query() {
+    let element = document().create_element("div").unwrap();
+    element.set_inner_html("<p class='a'>1</p><p>2</p><span class='a'>3</span>");
+    body().append_child(&element).unwrap();
+
+    let found = element.query::<HtmlElement>(".a").unwrap();
+    assert_eq!(found.inner_text(), "1");
+    assert_eq!(
+        document().query::<HtmlHeadElement>("head"),
+        Ok(gloo_utils::head())
+    );
+
+    let all = element.query_all::<HtmlElement>("p").unwrap();
+    assert_eq!(
+        all.iter().map(|m| m.inner_text()).collect::<Vec<_>>(),
+        ["1", "2"]
+    );
+    assert_eq!(element.query_all::<HtmlElement>("table").unwrap(), []);
+
+    assert_eq!(
+        element.query::<HtmlElement>("table"),
+        Err(QueryError::NotFound {
+            selector: "table".to_owned()
+        })
+    );
+    assert_eq!(
+        element.query_all::<HtmlHeadElement>(".a"),
+        Err(QueryError::WrongType {
+            selector: ".a".to_owned(),
+            expected: "HtmlHeadElement",
+        })
+    );
+    assert!(matches!(
+        element.query::<HtmlElement>("p["),
+        Err(QueryError::InvalidSelector { .. })
+    ));
+
+    body().remove_child(&element).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn build_elements() {
+    use gloo_utils::dom::el;
+
+    let element = el("div")
+        .class("card")
+        .class("big")
+        .id("card")
+        .attr("data-id", "42")
+        .child(el("h2").text("Title"))
+        .children(vec![el("p").text("1"), el("p").text("2")])
+        .into_element();
+
+    assert_eq!(element.tag_name(), "DIV");
+    assert_eq!(element.class_name(), "card big");
+    assert_eq!(element.id(), "card");
+    assert_eq!(element.get_attribute("data-id").as_deref(), Some("42"));
+    assert_eq!(element.inner_html(), "<h2>Title</h2><p>1</p><p>2</p>");
+}
+
+#[cfg(feature = "events")]
+#[wasm_bindgen_test]
+fn build_elements_with_listeners() {
+    use gloo_utils::dom::el;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let clicks = Rc::new(Cell::new(0));
+    let built = el("div")
+        .child(el("button").listener("click", {
+            let clicks = clicks.clone();
+            move |_| clicks.set(clicks.get() + 1)
+        }))
+        .build();
+
+    let button = built.element().query::<HtmlElement>("button").unwrap();
+    button.click();
+    assert_eq!(clicks.get(), 1);
+
+    drop(built);
+    button.click();
+    assert_eq!(clicks.get(), 1);
+}
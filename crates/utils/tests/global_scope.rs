@@ -0,0 +1,16 @@
+#![cfg(target_arch = "wasm32")]
+
+use gloo_utils::{global_scope, try_document, try_window, GlobalScope};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn global_scope_is_window() {
+    let scope = global_scope();
+    assert_eq!(scope, GlobalScope::Window(gloo_utils::window()));
+    assert!(!scope.is_worker());
+
+    assert_eq!(try_window(), Some(gloo_utils::window()));
+    assert_eq!(try_document(), Some(gloo_utils::document()));
+}
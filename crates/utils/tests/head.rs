@@ -0,0 +1,72 @@
+#![cfg(target_arch = "wasm32")]
+
+use gloo_utils::dom::QueryExt;
+use gloo_utils::{document, head};
+use wasm_bindgen_test::*;
+use web_sys::Element;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn meta(name: &str) -> Option<String> {
+    head()
+        .query_all::<Element>("meta")
+        .unwrap()
+        .into_iter()
+        .find(|m| m.get_attribute("name").as_deref() == Some(name))
+        .and_then(|m| m.get_attribute("content"))
+}
+
+#[wasm_bindgen_test]
+fn title() {
+    document().set_title("before");
+    let change = head::set_title("after");
+    assert_eq!(document().title(), "after");
+    drop(change);
+    assert_eq!(document().title(), "before");
+
+    head::set_title("kept").forget();
+    assert_eq!(document().title(), "kept");
+}
+
+#[wasm_bindgen_test]
+fn meta_tags() {
+    let outer = head::set_meta("description", "outer");
+    assert_eq!(meta("description").as_deref(), Some("outer"));
+
+    let inner = head::set_meta("description", "inner");
+    assert_eq!(meta("description").as_deref(), Some("inner"));
+
+    let removed = head::remove_meta("description");
+    assert_eq!(meta("description"), None);
+
+    drop(removed);
+    assert_eq!(meta("description").as_deref(), Some("inner"));
+    drop(inner);
+    assert_eq!(meta("description").as_deref(), Some("outer"));
+    drop(outer);
+    assert_eq!(meta("description"), None);
+}
+
+#[wasm_bindgen_test]
+fn links_and_json_ld() {
+    let _link = head::set_link("canonical", "https://example.com/");
+    let link = head().query::<Element>("link[rel=canonical]").unwrap();
+    assert_eq!(
+        link.get_attribute("href").as_deref(),
+        Some("https://example.com/")
+    );
+
+    let json_ld = head::set_json_ld("data", r#"{"@type":"Thing"}"#);
+    let script = head().query::<Element>("script#data").unwrap();
+    assert_eq!(
+        script.get_attribute("type").as_deref(),
+        Some("application/ld+json")
+    );
+    assert_eq!(
+        script.text_content().as_deref(),
+        Some(r#"{"@type":"Thing"}"#)
+    );
+
+    drop(json_ld);
+    assert!(head().query::<Element>("script#data").is_err());
+}
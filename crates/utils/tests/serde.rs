@@ -6,9 +6,10 @@ extern crate wasm_bindgen_test;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
 
-use gloo_utils::format::JsValueSerdeExt;
+use gloo_utils::format::{Conversion, JsValueSerdeExt};
 
 use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -66,3 +67,36 @@ fn into_serde() {
     assert_eq!(JsValue::undefined().into_serde::<i32>().ok(), None);
     assert_eq!(JsValue::null().into_serde::<i32>().ok(), None);
 }
+
+#[wasm_bindgen_test]
+fn conversion() {
+    let map = BTreeMap::from([("a".to_owned(), 1u64)]);
+
+    let js = Conversion::new().to_js(&map).unwrap();
+    assert!(js.is_instance_of::<js_sys::Map>());
+    let js = Conversion::json_compatible().to_js(&map).unwrap();
+    assert!(!js.is_instance_of::<js_sys::Map>());
+    assert_eq!(
+        Conversion::new()
+            .from_js::<BTreeMap<String, u64>>(js)
+            .unwrap(),
+        map
+    );
+
+    let js = Conversion::new()
+        .large_numbers_as_bigints(true)
+        .to_js(&u64::MAX)
+        .unwrap();
+    assert!(js.is_bigint());
+    assert!(Conversion::new().to_js(&u64::MAX).is_err());
+
+    assert!(Conversion::new()
+        .to_js(&None::<u32>)
+        .unwrap()
+        .is_undefined());
+    assert!(Conversion::new()
+        .missing_as_null(true)
+        .to_js(&None::<u32>)
+        .unwrap()
+        .is_null());
+}
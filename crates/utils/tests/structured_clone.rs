@@ -0,0 +1,42 @@
+#![cfg(target_arch = "wasm32")]
+
+use gloo_utils::errors::CloneError;
+use gloo_utils::{structured_clone, structured_clone_with_transfer};
+use js_sys::{Array, ArrayBuffer, Function, Map, Object, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn clone() {
+    let map = Map::new();
+    map.set(&"key".into(), &Array::of1(&1.into()));
+    let copy: Map = structured_clone(&map).unwrap().dyn_into().unwrap();
+    assert_ne!(JsValue::from(&copy), JsValue::from(&map));
+    assert_eq!(copy.size(), 1);
+    assert!(Array::is_array(&copy.get(&"key".into())));
+
+    let object = Object::new();
+    Reflect::set(&object, &"f".into(), &Function::new_no_args("")).unwrap();
+    assert!(matches!(
+        structured_clone(&object),
+        Err(CloneError::NotCloneable(_))
+    ));
+}
+
+#[wasm_bindgen_test]
+fn transfer() {
+    let buffer = ArrayBuffer::new(8);
+    let copy: ArrayBuffer = structured_clone_with_transfer(&buffer, &Array::of1(&buffer))
+        .unwrap()
+        .dyn_into()
+        .unwrap();
+    assert_eq!(copy.byte_length(), 8);
+    assert_eq!(buffer.byte_length(), 0);
+
+    assert!(matches!(
+        structured_clone_with_transfer(&buffer, &Array::of1(&buffer)),
+        Err(CloneError::NotCloneable(_))
+    ));
+}
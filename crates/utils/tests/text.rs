@@ -0,0 +1,41 @@
+#![cfg(target_arch = "wasm32")]
+
+use gloo_utils::text::{TextDecoder, TextEncoder};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn encode_and_decode() {
+    let bytes = TextEncoder::new().encode("café");
+    assert_eq!(bytes, "café".as_bytes());
+
+    let decoder = TextDecoder::new();
+    assert_eq!(decoder.encoding(), "utf-8");
+    assert_eq!(decoder.decode(&bytes).unwrap(), "café");
+    assert_eq!(decoder.decode(b"\xff").unwrap(), "\u{fffd}");
+}
+
+#[wasm_bindgen_test]
+fn labels() {
+    let decoder = TextDecoder::with_label("latin1").unwrap();
+    assert_eq!(decoder.encoding(), "windows-1252");
+    assert_eq!(decoder.decode(b"caf\xe9").unwrap(), "café");
+
+    let error = TextDecoder::with_label("not an encoding").unwrap_err();
+    assert_eq!(error.name, "RangeError");
+
+    let decoder = TextDecoder::with_options("utf-8", true).unwrap();
+    assert_eq!(decoder.decode(b"\xff").unwrap_err().name, "TypeError");
+}
+
+#[wasm_bindgen_test]
+fn streaming() {
+    let decoder = TextDecoder::new();
+    assert_eq!(decoder.decode_stream(b"caf\xc3").unwrap(), "caf");
+    assert_eq!(decoder.decode_stream(b"\xa9!").unwrap(), "é!");
+    assert_eq!(decoder.finish().unwrap(), "");
+
+    assert_eq!(decoder.decode_stream(b"\xc3").unwrap(), "");
+    assert_eq!(decoder.finish().unwrap(), "\u{fffd}");
+}
@@ -0,0 +1,70 @@
+#![cfg(target_arch = "wasm32")]
+
+use gloo_utils::errors::UrlError;
+use gloo_utils::url::UrlBuilder;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn build() {
+    let url = UrlBuilder::new("https://example.com/api")
+        .unwrap()
+        .segments(["items", "a/b c?"])
+        .query("q", "x&y")
+        .query("q", "z")
+        .fragment("top")
+        .into_string();
+    assert_eq!(
+        url,
+        "https://example.com/api/items/a%2Fb%20c%3F?q=x%26y&q=z#top"
+    );
+
+    let url = UrlBuilder::with_base("../other?page=2", "https://example.com/a/b/c")
+        .unwrap()
+        .into_string();
+    assert_eq!(url, "https://example.com/a/other?page=2");
+
+    let url = UrlBuilder::new("https://example.com/a/b")
+        .unwrap()
+        .join("c")
+        .unwrap()
+        .build();
+    assert_eq!(url.pathname(), "/a/c");
+
+    assert!(matches!(
+        UrlBuilder::new("/relative"),
+        Err(UrlError::InvalidUrl { .. })
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test]
+fn query_params() {
+    #[derive(serde_derive::Serialize)]
+    struct Params {
+        page: u32,
+        tags: Vec<&'static str>,
+        search: Option<String>,
+        exact: bool,
+    }
+
+    let url = UrlBuilder::new("https://example.com/")
+        .unwrap()
+        .query_params(&Params {
+            page: 2,
+            tags: vec!["a", "b"],
+            search: None,
+            exact: true,
+        })
+        .unwrap()
+        .into_string();
+    assert_eq!(url, "https://example.com/?page=2&tags=a&tags=b&exact=true");
+
+    assert!(matches!(
+        UrlBuilder::new("https://example.com/")
+            .unwrap()
+            .query_params(&[1, 2]),
+        Err(UrlError::InvalidQuery(_))
+    ));
+}